@@ -1,78 +1,462 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
 use bigsmiles::BigSmilesSegment;
 use colored::Colorize;
 use polysim_core::{
-    builder::linear::LinearBuilder,
-    parse,
+    builder::{identify::closest_known, linear::LinearBuilder},
+    parse, parse_strict,
     properties::{
-        formula::{molecular_formula, total_atom_count},
-        molecular_weight::monoisotopic_mass,
+        electrical::refractive_index,
+        ensemble::EnsembleStats,
+        formula::{molecular_formula, molecular_formula_of_smiles, total_atom_count},
+        groups::decompose,
+        molecular_weight::{average_mass_of_smiles, monoisotopic_mass},
+        reference::reference_properties,
+        thermal::{tg_fox_flory, tg_infinite_chain, DEFAULT_FOX_FLORY_K},
+        volume::density,
     },
+    BigSmiles, BuildStrategy, PolySimError, PolymerChain,
 };
 
+use crate::commands::generate::{build_ensemble, Sampling};
 use crate::display;
-use crate::report::AnalysisResult;
-use crate::{Architecture, ArchitectureArgs, StrategyArgs};
+use crate::report::{AnalysisResult, Comparison, DatabaseComparison, Explanation, TerminalBlock};
+use crate::{Architecture, ArchitectureArgs, DistributionKind, MassUnit, StrategyArgs};
+
+/// Boolean switches of the `analyze` subcommand.
+#[derive(Debug, Clone, Copy)]
+pub struct Flags {
+    /// Parse with [`parse_strict`] instead of [`parse`].
+    pub strict: bool,
+    /// Append the calculation steps to the report.
+    pub explain: bool,
+    /// Print step timings to stderr.
+    pub verbose: bool,
+    /// Print the [`AnalysisResult`] as JSON instead of the report.
+    pub emit_debug_json: bool,
+    /// Print the formula without Unicode subscripts.
+    pub ascii: bool,
+    /// Append the structural-group decomposition to the report.
+    pub groups: bool,
+    /// Append the comparison with the literature values to the report.
+    pub compare_to_database: bool,
+    /// Neutralise charged chains with counter-ions.
+    pub counterions: bool,
+}
 
 /// Entry point for the `analyze` subcommand.
 pub fn run(
     bigsmiles_str: &str,
     args: &StrategyArgs,
+    dispersity: f64,
+    num_chains: usize,
+    mass_unit: MassUnit,
+    flags: Flags,
     arch_args: &ArchitectureArgs,
 ) -> Result<(), i32> {
-    let bigsmiles = parse(bigsmiles_str).map_err(report_err)?;
-
-    let mut builder = LinearBuilder::new(bigsmiles.clone(), args.build_strategy());
-    if let Some(seed) = arch_args.copolymer_seed {
-        builder = builder.seed(seed);
+    if let Some(ref path) = args.mn_file {
+        let targets = read_mn_file(path).map_err(report_err)?;
+        for mn in targets {
+            let args = StrategyArgs::by_target_mn(mn);
+            run(
+                bigsmiles_str,
+                &args,
+                dispersity,
+                num_chains,
+                mass_unit,
+                flags,
+                arch_args,
+            )?;
+        }
+        return Ok(());
     }
 
-    let chain = match arch_args.arch {
-        Architecture::Homo => builder.homopolymer(),
-        Architecture::Random => {
-            let fractions = arch_args.fractions.as_deref().unwrap_or(&[]);
-            builder.random_copolymer(fractions)
-        }
-        Architecture::Alternating => builder.alternating_copolymer(),
-        Architecture::Block => {
-            let lengths = arch_args.block_lengths.as_deref().unwrap_or(&[]);
-            builder.block_copolymer(lengths)
-        }
-        Architecture::Gradient => {
-            let profile = arch_args.gradient_profile();
-            builder.gradient_copolymer(&profile)
+    let mut timings = Timings::new(flags.verbose);
+
+    let bigsmiles = timings.time("parse", || {
+        if flags.strict {
+            parse_strict(bigsmiles_str).map_err(|e| e.to_string())
+        } else {
+            parse(bigsmiles_str).map_err(|e| e.to_string())
         }
+    });
+    let bigsmiles = bigsmiles.map_err(report_err)?;
+
+    let arch = arch_args.resolve(&bigsmiles);
+    if let Some(ref repeat_counts) = args.sweep_repeat {
+        return run_sweep(
+            bigsmiles_str,
+            &bigsmiles,
+            repeat_counts,
+            &arch,
+            arch_args,
+            mass_unit,
+            flags,
+        );
     }
-    .map_err(report_err)?;
 
-    let mono_mass = monoisotopic_mass(&chain);
+    // --by-mn on a homopolymer: the library returns the Mn error with the chain
+    let built = timings.time("build", || match (&arch, args.by_mn) {
+        (Architecture::Homo, Some(target)) => linear_builder(
+            &bigsmiles,
+            args.build_strategy(),
+            arch_args,
+            flags.counterions,
+        )
+        .build_closest_to_mn(target)
+        .map(|(chain, delta)| (chain, Some(delta))),
+        _ => build_chain(
+            &bigsmiles,
+            args.build_strategy(),
+            &arch,
+            arch_args,
+            flags.counterions,
+        )
+        .map(|chain| (chain, None)),
+    });
+    let (chain, chain_delta_mn) = built.map_err(report_err)?;
+    if chain.has_wildcards() {
+        eprintln!(
+            "  {} The chain contains unresolved wildcard atoms (*); \
+             they are not counted in the mass or the formula.",
+            "warning:".yellow().bold()
+        );
+        eprintln!();
+    }
+
+    // Đ > 1: simulate a Schulz-Zimm distribution centred on the target Mn
+    // (--by-mn), else on the resolved chain.
+    let (mn, mw, pdi, ensemble_chains) = if dispersity > 1.0 {
+        let ensemble = timings.time("ensemble", || {
+            build_ensemble(
+                &DistributionKind::SchulzZimm,
+                bigsmiles.clone(),
+                args.by_mn.unwrap_or(chain.mn),
+                dispersity,
+                &Sampling {
+                    num_chains,
+                    seed: arch_args.copolymer_seed,
+                    exact_mn: args.by_mn.is_some(),
+                    show_progress: false,
+                },
+                arch_args,
+            )
+        });
+        let stats = EnsembleStats::from_ensemble(&ensemble.map_err(report_err)?);
+        (stats.mn, stats.mw, stats.pdi, Some(stats.num_chains))
+    } else {
+        (chain.mn, chain.mn, 1.0, None)
+    };
+
+    let explanation = if flags.explain {
+        let calibration = match arch {
+            Architecture::Homo => Some(
+                linear_builder(
+                    &bigsmiles,
+                    args.build_strategy(),
+                    arch_args,
+                    flags.counterions,
+                )
+                .repeat_unit_mass()
+                .map_err(report_err)?,
+            ),
+            _ => None,
+        };
+        Some(Explanation {
+            strategy: args.build_strategy(),
+            calibration,
+            chain_mass: chain.mn,
+        })
+    } else {
+        None
+    };
+
+    let mono_mass = timings.time("monoisotopic mass", || monoisotopic_mass(&chain));
+    let formula_raw = timings.time("formula", || molecular_formula(&chain));
+    let n_atoms = timings.time("atom count", || total_atom_count(&chain));
+    let tg_infinity = timings.time("tg", || tg_infinite_chain(&chain));
+    let known_polymer = timings.time("identify", || closest_known(&chain));
+    let groups = flags.groups.then(|| {
+        timings.time("groups", || {
+            decompose(&chain)
+                .into_iter()
+                .map(|(group, count)| (group.label(), count))
+                .collect()
+        })
+    });
+    let comparison = flags.compare_to_database.then(|| {
+        timings.time("comparison", || {
+            compare_to_database(&chain, tg_infinity, known_polymer)
+        })
+    });
 
     let result = AnalysisResult {
         bigsmiles_str: bigsmiles_str.to_owned(),
-        strategy_label: args.label(),
-        architecture_label: arch_args.arch.label().to_owned(),
-        begin_block: segments_to_smiles(bigsmiles.prefix_segments()),
-        end_block: segments_to_smiles(bigsmiles.suffix_segments()),
+        strategy_label: args.label(mass_unit),
+        architecture_label: arch.label().to_owned(),
+        begin_block: terminal_block(bigsmiles.prefix_segments()),
+        end_block: terminal_block(bigsmiles.suffix_segments()),
         smiles: chain.smiles.clone(),
+        composition: chain
+            .composition
+            .iter()
+            .map(|unit| (unit.smiles.clone(), unit.fraction))
+            .collect(),
+        known_polymer,
         repeat_count: chain.repeat_count,
-        mn: chain.mn,
+        counterions: flags.counterions.then_some(chain.counterion_count),
+        mn,
+        mw,
+        dispersity: pdi,
+        ensemble_chains,
         mono_mass,
-        formula_raw: molecular_formula(&chain),
-        n_atoms: total_atom_count(&chain),
-        delta_mn: args.by_mn.map(|t| chain.mn - t),
+        formula_raw,
+        n_atoms,
+        tg_infinity,
+        tg: tg_fox_flory(tg_infinity, mn, DEFAULT_FOX_FLORY_K),
+        // Đ > 1: error of the ensemble Mn, else of the single chain
+        delta_mn: match ensemble_chains {
+            Some(_) => args.by_mn.map(|t| mn - t),
+            None => chain_delta_mn.or(args.by_mn.map(|t| chain.mn - t)),
+        },
         delta_mass: args.by_mass.map(|t| mono_mass - t),
+        mass_unit,
+        ascii: flags.ascii,
+        explanation,
+        groups,
+        comparison,
     };
 
-    display::print_report(&result);
+    if flags.emit_debug_json {
+        let json = serde_json::to_string_pretty(&result).map_err(report_err)?;
+        println!("{json}");
+    } else {
+        display::print_report(&result);
+    }
+    timings.report();
     Ok(())
 }
 
+// ─── Literature values (--compare-to-database) ───────────────────────────────
+
+/// Temperature (K) of the tabulated densities and refractive indices.
+const REFERENCE_TEMPERATURE: f64 = 298.15;
+
+/// Pairs the estimates for `chain` with the literature values of the known
+/// polymer it matches exactly; no rows when the match is only approximate.
+fn compare_to_database(
+    chain: &PolymerChain,
+    tg_infinity: f64,
+    known_polymer: Option<(&'static str, f64)>,
+) -> DatabaseComparison {
+    let polymer = known_polymer
+        .filter(|&(_, score)| score >= 1.0)
+        .map(|(name, _)| name);
+    let mut rows = Vec::new();
+    if let Some(reference) = polymer.and_then(reference_properties) {
+        if let Some(tg) = reference.tg {
+            rows.push(Comparison {
+                property: "Tg∞",
+                unit: "K",
+                predicted: tg_infinity,
+                reference: tg,
+            });
+        }
+        rows.push(Comparison {
+            property: "Density",
+            unit: "g/cm³",
+            predicted: density(chain, REFERENCE_TEMPERATURE),
+            reference: reference.density,
+        });
+        rows.push(Comparison {
+            property: "Refractive index",
+            unit: "",
+            predicted: refractive_index(chain),
+            reference: reference.refractive_index,
+        });
+    }
+    DatabaseComparison { polymer, rows }
+}
+
+// ─── Sweep (--sweep-repeat) ──────────────────────────────────────────────────
+
+/// Builds one chain per repeat count and prints Mn and the formula of each.
+fn run_sweep(
+    bigsmiles_str: &str,
+    bigsmiles: &BigSmiles,
+    repeat_counts: &[usize],
+    arch: &Architecture,
+    arch_args: &ArchitectureArgs,
+    mass_unit: MassUnit,
+    flags: Flags,
+) -> Result<(), i32> {
+    let rows = repeat_counts
+        .iter()
+        .map(|&n| {
+            let strategy = BuildStrategy::ByRepeatCount(n);
+            let chain = build_chain(bigsmiles, strategy, arch, arch_args, flags.counterions)?;
+            Ok((n, chain.mn, molecular_formula(&chain)))
+        })
+        .collect::<Result<Vec<_>, PolySimError>>()
+        .map_err(report_err)?;
+    display::print_sweep_report(bigsmiles_str, arch.label(), &rows, mass_unit, flags.ascii);
+    Ok(())
+}
+
+// ─── Batch of targets (--mn-file) ────────────────────────────────────────────
+
+/// Reads one target Mn per line, skipping blank lines.
+fn read_mn_file(path: &Path) -> Result<Vec<f64>, String> {
+    let text =
+        fs::read_to_string(path).map_err(|e| format!("cannot read '{}': {e}", path.display()))?;
+    let targets = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.trim().parse::<f64>().map_err(|_| {
+                format!(
+                    "invalid Mn '{}' on line {} of '{}'",
+                    line.trim(),
+                    i + 1,
+                    path.display()
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if targets.is_empty() {
+        return Err(format!("no target Mn in '{}'", path.display()));
+    }
+    Ok(targets)
+}
+
+// ─── BigSMILES from stdin (-) ────────────────────────────────────────────────
+
+/// Returns `arg`, or the BigSMILES read from stdin when `arg` is `-`.
+///
+/// Surrounding whitespace (e.g. the newline left by `echo`) is trimmed.
+pub fn bigsmiles_arg(arg: String) -> Result<String, i32> {
+    if arg != "-" {
+        return Ok(arg);
+    }
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| report_err(format!("cannot read BigSMILES from stdin: {e}")))?;
+    let bigsmiles = input.trim();
+    if bigsmiles.is_empty() {
+        return Err(report_err("no BigSMILES on stdin"));
+    }
+    Ok(bigsmiles.to_owned())
+}
+
+// ─── Timings (--verbose) ─────────────────────────────────────────────────────
+
+/// Wall-clock durations of the analysis steps, recorded only in verbose mode.
+struct Timings {
+    enabled: bool,
+    steps: Vec<(&'static str, Duration)>,
+}
+
+impl Timings {
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Runs `f`, recording its duration under `label` when enabled.
+    fn time<T>(&mut self, label: &'static str, f: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return f();
+        }
+        let start = Instant::now();
+        let value = f();
+        self.steps.push((label, start.elapsed()));
+        value
+    }
+
+    /// Prints one line per step to stderr, so the stdout report is unchanged.
+    fn report(&self) {
+        if !self.enabled {
+            return;
+        }
+        for (label, elapsed) in &self.steps {
+            eprintln!(
+                "{} {label:<18} {:>10.3} ms",
+                "timing:".dimmed(),
+                elapsed.as_secs_f64() * 1e3
+            );
+        }
+        let total: Duration = self.steps.iter().map(|(_, d)| *d).sum();
+        eprintln!(
+            "{} {:<18} {:>10.3} ms",
+            "timing:".dimmed(),
+            "total",
+            total.as_secs_f64() * 1e3
+        );
+    }
+}
+
 // ─── Helpers ─────────────────────────────────────────────────────────────────
 
+/// Linear builder configured from the command-line options.
+fn linear_builder(
+    bigsmiles: &BigSmiles,
+    strategy: BuildStrategy,
+    arch_args: &ArchitectureArgs,
+    counterions: bool,
+) -> LinearBuilder {
+    let builder = LinearBuilder::new(bigsmiles.clone(), strategy).counterions(counterions);
+    match arch_args.copolymer_seed {
+        Some(seed) => builder.seed(seed),
+        None => builder,
+    }
+}
+
+/// Builds a single chain of the selected architecture.
+fn build_chain(
+    bigsmiles: &BigSmiles,
+    strategy: BuildStrategy,
+    arch: &Architecture,
+    arch_args: &ArchitectureArgs,
+    counterions: bool,
+) -> Result<PolymerChain, PolySimError> {
+    let builder = linear_builder(bigsmiles, strategy, arch_args, counterions);
+    match arch {
+        Architecture::Homo => builder.homopolymer(),
+        Architecture::Random => builder.random_copolymer(&arch_args.random_fractions(bigsmiles)),
+        Architecture::Alternating => builder.alternating_copolymer(),
+        Architecture::Block => {
+            let lengths = arch_args.block_lengths.as_deref().unwrap_or(&[]);
+            builder.block_copolymer(lengths)
+        }
+        Architecture::Gradient => {
+            let profile = arch_args.gradient_profile();
+            builder.gradient_copolymer(&profile)
+        }
+    }
+}
+
 fn report_err(e: impl std::fmt::Display) -> i32 {
     eprintln!("{} {e}", "error:".red().bold());
     1
 }
 
+/// Begin or end block made of the SMILES segments `segs`, with its formula and mass.
+fn terminal_block(segs: &[BigSmilesSegment]) -> Option<TerminalBlock> {
+    let smiles = segments_to_smiles(segs)?;
+    Some(TerminalBlock {
+        formula_raw: molecular_formula_of_smiles(&smiles).ok(),
+        mass: average_mass_of_smiles(&smiles).ok(),
+        smiles,
+    })
+}
+
 fn segments_to_smiles(segs: &[BigSmilesSegment]) -> Option<String> {
     let s: String = segs
         .iter()
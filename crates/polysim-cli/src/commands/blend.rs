@@ -0,0 +1,110 @@
+use colored::Colorize;
+use polysim_core::{
+    builder::{identify::closest_known, linear::LinearBuilder},
+    parse, parse_strict,
+    properties::{
+        solubility::{critical_chi, flory_huggins_chi},
+        thermal::{homopolymer_tg, tg_fox, tg_fox_flory, tg_infinite_chain, DEFAULT_FOX_FLORY_K},
+        volume::density,
+    },
+    BuildStrategy, PolySimError, PolymerChain,
+};
+
+use crate::display;
+
+/// One polymer of the blend and its computed properties.
+pub struct BlendComponent {
+    pub bigsmiles: String,
+    /// Weight fraction in the blend.
+    pub fraction: f64,
+    /// Polymer whose tabulated Tg was used, if the component was recognised.
+    pub known_polymer: Option<&'static str>,
+    /// Fox-Flory Tg of the component chain (K).
+    pub tg: f64,
+    /// Amorphous density at the blend temperature (g/cm³).
+    pub density: f64,
+}
+
+/// Properties of a binary blend.
+pub struct BlendReport {
+    pub components: [BlendComponent; 2],
+    pub repeat_count: usize,
+    pub temperature_k: f64,
+    /// Fox-equation Tg of the blend (K), assuming a single mixed phase.
+    pub tg: f64,
+    /// Density assuming additive volumes (g/cm³).
+    pub density: f64,
+    /// Flory-Huggins interaction parameter.
+    pub chi: f64,
+    /// Critical χ above which the blend phase-separates.
+    pub chi_critical: f64,
+}
+
+/// Entry point for the `blend` subcommand.
+pub fn run(
+    components: [(&str, f64); 2],
+    repeat_count: usize,
+    temperature_k: f64,
+    strict: bool,
+) -> Result<(), i32> {
+    let sum = components[0].1 + components[1].1;
+    if components.iter().any(|&(_, w)| !(0.0..=1.0).contains(&w)) || (sum - 1.0).abs() > 1e-6 {
+        return Err(report_err(PolySimError::InvalidFractions { sum }));
+    }
+
+    let chains = [
+        build_chain(components[0].0, repeat_count, strict)?,
+        build_chain(components[1].0, repeat_count, strict)?,
+    ];
+    let [a, b] = [0, 1].map(|i| {
+        let chain = &chains[i];
+        let (known_polymer, tg_infinity) = tg_infinity(chain);
+        BlendComponent {
+            bigsmiles: components[i].0.to_owned(),
+            fraction: components[i].1,
+            known_polymer,
+            tg: tg_fox_flory(tg_infinity, chain.mn, DEFAULT_FOX_FLORY_K),
+            density: density(chain, temperature_k),
+        }
+    });
+
+    let report = BlendReport {
+        tg: tg_fox(&[(a.fraction, a.tg), (b.fraction, b.tg)]),
+        density: 1.0 / (a.fraction / a.density + b.fraction / b.density),
+        chi: flory_huggins_chi(&chains[0], &chains[1], temperature_k),
+        chi_critical: critical_chi(&chains[0], &chains[1], temperature_k),
+        components: [a, b],
+        repeat_count,
+        temperature_k,
+    };
+    display::print_blend_report(&report);
+    Ok(())
+}
+
+/// Tg at infinite molar mass (K): the tabulated value when the chain is
+/// identified exactly as a common polymer, the Van Krevelen estimate otherwise.
+fn tg_infinity(chain: &PolymerChain) -> (Option<&'static str>, f64) {
+    let known = closest_known(chain)
+        .filter(|&(_, score)| score == 1.0)
+        .and_then(|(name, _)| homopolymer_tg(name).map(|tg| (name, tg)));
+    match known {
+        Some((name, tg)) => (Some(name), tg),
+        None => (None, tg_infinite_chain(chain)),
+    }
+}
+
+fn build_chain(bigsmiles_str: &str, n: usize, strict: bool) -> Result<PolymerChain, i32> {
+    let bs = if strict {
+        parse_strict(bigsmiles_str).map_err(report_err)?
+    } else {
+        parse(bigsmiles_str).map_err(report_err)?
+    };
+    LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(n))
+        .homopolymer()
+        .map_err(report_err)
+}
+
+fn report_err(e: impl std::fmt::Display) -> i32 {
+    eprintln!("{} {e}", "error:".red().bold());
+    1
+}
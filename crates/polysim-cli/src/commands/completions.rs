@@ -0,0 +1,33 @@
+use clap::{Command, ValueEnum};
+
+/// Shell targeted by `polysim completions`.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl From<Shell> for clap_complete::Shell {
+    fn from(shell: Shell) -> Self {
+        match shell {
+            Shell::Bash => Self::Bash,
+            Shell::Zsh => Self::Zsh,
+            Shell::Fish => Self::Fish,
+        }
+    }
+}
+
+/// Entry point for the `completions` subcommand.
+///
+/// Prints a completion script for `shell`, generated by `clap_complete` from
+/// the clap definition of the command line.
+pub fn run(shell: Shell, mut cli: Command) {
+    let name = cli.get_name().to_string();
+    clap_complete::generate(
+        clap_complete::Shell::from(shell),
+        &mut cli,
+        name,
+        &mut std::io::stdout(),
+    );
+}
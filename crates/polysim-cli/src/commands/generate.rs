@@ -2,26 +2,41 @@ use colored::Colorize;
 use polysim_core::{
     builder::EnsembleBuilder,
     distribution::{ChainLengthDistribution, Flory, LogNormal, SchulzZimm},
-    parse,
+    parse, parse_strict,
     polymer::PolymerEnsemble,
     properties::ensemble::EnsembleStats,
     PolySimError,
 };
 
 use crate::display;
+use crate::utils::progress::chain_progress;
 use crate::{Architecture, ArchitectureArgs, DistributionKind};
 
+/// Sampling settings shared by every ensemble build.
+pub(crate) struct Sampling {
+    pub(crate) num_chains: usize,
+    pub(crate) seed: Option<u64>,
+    /// Rescale the sampled lengths so that the ensemble Mn hits the target.
+    pub(crate) exact_mn: bool,
+    /// Show a progress bar while the chains are built (only on a terminal).
+    pub(crate) show_progress: bool,
+}
+
 /// Entry point for the `generate` subcommand.
 pub fn run(
     bigsmiles_str: &str,
     mn: f64,
     pdi: f64,
     distribution: &DistributionKind,
-    num_chains: usize,
-    seed: Option<u64>,
+    sampling: &Sampling,
+    strict: bool,
     arch_args: &ArchitectureArgs,
 ) -> Result<(), i32> {
-    let bs = parse(bigsmiles_str).map_err(report_err)?;
+    let bs = if strict {
+        parse_strict(bigsmiles_str).map_err(report_err)?
+    } else {
+        parse(bigsmiles_str).map_err(report_err)?
+    };
 
     if matches!(distribution, DistributionKind::Flory) && (pdi - 2.0).abs() > 0.01 {
         eprintln!(
@@ -37,34 +52,28 @@ pub fn run(
         eprintln!();
     }
 
-    let ensemble = build_ensemble(distribution, bs, mn, pdi, num_chains, seed, arch_args)
-        .map_err(report_err)?;
+    let arch = arch_args.resolve(&bs);
+    let ensemble =
+        build_ensemble(distribution, bs, mn, pdi, sampling, arch_args).map_err(report_err)?;
 
     let stats = EnsembleStats::from_ensemble(&ensemble);
-    display::print_ensemble_report(
-        bigsmiles_str,
-        distribution,
-        &arch_args.arch,
-        mn,
-        pdi,
-        &stats,
-    );
+    display::print_ensemble_report(bigsmiles_str, distribution, &arch, mn, pdi, &stats);
     Ok(())
 }
 
-fn build_ensemble(
+/// Builds an ensemble for the selected distribution model and architecture.
+pub(crate) fn build_ensemble(
     distribution: &DistributionKind,
     bs: polysim_core::BigSmiles,
     mn: f64,
     pdi: f64,
-    num_chains: usize,
-    seed: Option<u64>,
+    sampling: &Sampling,
     arch_args: &ArchitectureArgs,
 ) -> Result<PolymerEnsemble, PolySimError> {
     match distribution {
-        DistributionKind::Flory => build(Flory, bs, mn, pdi, num_chains, seed, arch_args),
-        DistributionKind::LogNormal => build(LogNormal, bs, mn, pdi, num_chains, seed, arch_args),
-        DistributionKind::SchulzZimm => build(SchulzZimm, bs, mn, pdi, num_chains, seed, arch_args),
+        DistributionKind::Flory => build(Flory, bs, mn, pdi, sampling, arch_args),
+        DistributionKind::LogNormal => build(LogNormal, bs, mn, pdi, sampling, arch_args),
+        DistributionKind::SchulzZimm => build(SchulzZimm, bs, mn, pdi, sampling, arch_args),
     }
 }
 
@@ -73,21 +82,26 @@ fn build<D: ChainLengthDistribution>(
     bs: polysim_core::BigSmiles,
     mn: f64,
     pdi: f64,
-    num_chains: usize,
-    seed: Option<u64>,
+    sampling: &Sampling,
     arch_args: &ArchitectureArgs,
 ) -> Result<PolymerEnsemble, PolySimError> {
-    let mut builder = EnsembleBuilder::new(bs, dist, mn, pdi).num_chains(num_chains);
-    if let Some(s) = seed {
+    let arch = arch_args.resolve(&bs);
+    let fractions = arch_args.random_fractions(&bs);
+    let mut builder = EnsembleBuilder::new(bs, dist, mn, pdi)
+        .num_chains(sampling.num_chains)
+        .exact_mn(sampling.exact_mn);
+    if let Some(s) = sampling.seed {
         builder = builder.seed(s);
     }
+    if sampling.show_progress {
+        if let Some(progress) = chain_progress(sampling.num_chains) {
+            builder = builder.on_progress(progress);
+        }
+    }
 
-    match arch_args.arch {
+    match arch {
         Architecture::Homo => builder.homopolymer_ensemble(),
-        Architecture::Random => {
-            let fractions = arch_args.fractions.as_deref().unwrap_or(&[]);
-            builder.random_copolymer_ensemble(fractions)
-        }
+        Architecture::Random => builder.random_copolymer_ensemble(&fractions),
         Architecture::Alternating => builder.alternating_copolymer_ensemble(),
         Architecture::Block => {
             let ratios = arch_args.block_ratios.as_deref().unwrap_or(&[]);
@@ -0,0 +1,116 @@
+use bigsmiles::{BigSmilesSegment, BondingDescriptor, BondingDescriptorKind};
+use colored::Colorize;
+use polysim_core::{parse, parse_strict};
+
+/// Entry point for the `inspect` subcommand.
+pub fn run(bigsmiles_str: &str, strict: bool) -> Result<(), i32> {
+    let bs = if strict {
+        parse_strict(bigsmiles_str).map_err(report_err)?
+    } else {
+        parse(bigsmiles_str).map_err(report_err)?
+    };
+
+    let is_stochastic = |seg: &BigSmilesSegment| matches!(seg, BigSmilesSegment::Stochastic(_));
+    let first = bs.segments.iter().position(is_stochastic);
+    let last = bs.segments.iter().rposition(is_stochastic);
+
+    println!();
+    println!("  {:<11}{}", "BigSMILES".bold(), bigsmiles_str.yellow());
+    println!(
+        "  {:<11}{} segment(s), {} stochastic object(s)",
+        "Segments".bold(),
+        bs.segments.len(),
+        bs.segments.iter().filter(|seg| is_stochastic(seg)).count()
+    );
+    println!();
+
+    let mut object = 0;
+    for (i, segment) in bs.segments.iter().enumerate() {
+        match segment {
+            BigSmilesSegment::Smiles(mol) => {
+                let label = match (first, last) {
+                    (Some(first), _) if i < first => "Begin",
+                    (_, Some(last)) if i > last => "End",
+                    (None, None) => "SMILES",
+                    _ => "Linker",
+                };
+                println!("  {:<11}{}", label.bold(), format!("{mol}").yellow());
+            }
+            BigSmilesSegment::Stochastic(stoch) => {
+                object += 1;
+                println!(
+                    "  {}  {}",
+                    format!("Stochastic object {object}").bold(),
+                    format!(
+                        "terminals: {} {}",
+                        descriptor_notation(&stoch.left_terminal),
+                        descriptor_notation(&stoch.right_terminal)
+                    )
+                    .dimmed()
+                );
+                let fragments = stoch
+                    .repeat_units
+                    .iter()
+                    .enumerate()
+                    .map(|(k, unit)| (format!("unit {}", k + 1), unit))
+                    .chain(
+                        stoch
+                            .end_groups
+                            .iter()
+                            .enumerate()
+                            .map(|(k, end)| (format!("end {}", k + 1), end)),
+                    );
+                for (label, fragment) in fragments {
+                    let descriptors = bonding_descriptors(&fragment.smiles_raw);
+                    let descriptors = if descriptors.is_empty() {
+                        "none".to_owned()
+                    } else {
+                        descriptors.join(" ")
+                    };
+                    println!(
+                        "    {label:<9}{}  {}",
+                        fragment.smiles_raw.cyan(),
+                        format!("descriptors: {descriptors}").dimmed()
+                    );
+                }
+            }
+        }
+    }
+    println!();
+    Ok(())
+}
+
+/// BigSMILES notation of a terminal bonding descriptor, e.g. `[$]` or `[<1]`.
+fn descriptor_notation(descriptor: &BondingDescriptor) -> String {
+    let symbol = match descriptor.kind {
+        BondingDescriptorKind::NoBond => "",
+        BondingDescriptorKind::NonDirectional => "$",
+        BondingDescriptorKind::Head => "<",
+        BondingDescriptorKind::Tail => ">",
+    };
+    let index = descriptor.index.map(|i| i.to_string()).unwrap_or_default();
+    format!("[{symbol}{index}]")
+}
+
+/// Bonding descriptors (`[$]`, `[<1]`, `[]`…) of a repeat unit, in order.
+fn bonding_descriptors(smiles_raw: &str) -> Vec<&str> {
+    let mut descriptors = Vec::new();
+    let mut rest = smiles_raw;
+    while let Some(start) = rest.find('[') {
+        let Some(len) = rest[start..].find(']') else {
+            break;
+        };
+        let token = &rest[start..=start + len];
+        let inner = &token[1..token.len() - 1];
+        if inner.is_empty() || inner.starts_with(['$', '<', '>']) {
+            descriptors.push(token);
+        }
+        rest = &rest[start + len + 1..];
+    }
+    descriptors
+}
+
+fn report_err(e: impl std::fmt::Display) -> i32 {
+    eprintln!("{} {e}", "error:".red().bold());
+    1
+}
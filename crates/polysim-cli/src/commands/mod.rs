@@ -1,2 +1,6 @@
 pub mod analyze;
+pub mod blend;
+pub mod completions;
 pub mod generate;
+pub mod inspect;
+pub mod schema;
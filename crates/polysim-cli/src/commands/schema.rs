@@ -0,0 +1,30 @@
+use polysim_core::PropertySummary;
+use serde_json::{json, Map, Value};
+
+/// Entry point for the `schema` subcommand.
+pub fn run() {
+    println!("{}", render());
+}
+
+/// JSON Schema of the serialized [`PropertySummary`], with a non-standard
+/// `unit` keyword on each field that has one.
+fn render() -> String {
+    let mut properties = Map::new();
+    for field in PropertySummary::FIELDS {
+        let mut entry = json!({ "type": field.json_type });
+        if let Some(unit) = field.unit {
+            entry["unit"] = Value::from(unit);
+        }
+        properties.insert(field.name.to_owned(), entry);
+    }
+    let required: Vec<&str> = PropertySummary::FIELDS.iter().map(|f| f.name).collect();
+
+    let schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "PropertySummary",
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    });
+    serde_json::to_string_pretty(&schema).expect("a JSON value always serializes")
+}
@@ -1,17 +1,31 @@
 use colored::Colorize;
 use comfy_table::{Attribute, Cell, Color as TableColor, ContentArrangement, Table};
 
-use crate::report::AnalysisResult;
-use crate::utils::format::{delta_style, subscript_digits, truncate};
+use crate::report::{AnalysisResult, DatabaseComparison, Explanation, TerminalBlock};
+use crate::utils::format::{delta_style, format_mass, subscript_digits, truncate};
+use crate::MassUnit;
+use polysim_core::BuildStrategy;
 
 /// Prints the full analysis report to stdout.
 pub fn print_report(r: &AnalysisResult) {
     print_banner();
     print_summary(r);
     print_table(r);
-    print_footnote();
+    print_footnote(r);
+    if let Some(ref explanation) = r.explanation {
+        print_explanation(r, explanation);
+    }
+    if let Some(ref groups) = r.groups {
+        print_groups(r, groups);
+    }
+    if let Some(ref comparison) = r.comparison {
+        print_comparison(comparison);
+    }
 }
 
+/// Relative Mn error above which `--by-mn` results are flagged.
+const MN_TOLERANCE: f64 = 0.05;
+
 // ─── Sections ────────────────────────────────────────────────────────────────
 
 fn print_banner() {
@@ -28,17 +42,42 @@ fn print_summary(r: &AnalysisResult) {
     println!("  {:<11}{}", "BigSMILES".bold(), r.bigsmiles_str.yellow());
     println!("  {:<11}{}", "Arch".bold(), r.architecture_label.cyan());
     println!("  {:<11}{}", "Strategy".bold(), r.strategy_label);
-    if let Some(ref bb) = r.begin_block {
-        println!("  {:<11}{}", "Begin".bold(), bb.yellow());
-    }
-    if let Some(ref eb) = r.end_block {
-        println!("  {:<11}{}", "End".bold(), eb.yellow());
+    for (label, block) in [("Begin", &r.begin_block), ("End", &r.end_block)] {
+        if let Some(block) = block {
+            println!(
+                "  {:<11}{}{}",
+                label.bold(),
+                block.smiles.yellow(),
+                terminal_block_details(r, block).dimmed()
+            );
+        }
     }
     println!(
         "  {:<11}{}",
         "SMILES".bold(),
         truncate(&r.smiles, 60).dimmed()
     );
+    if r.composition.len() > 1 {
+        let units: Vec<String> = r
+            .composition
+            .iter()
+            .map(|(smiles, fraction)| format!("{smiles} {:.1} %", fraction * 100.0))
+            .collect();
+        println!("  {:<11}{}", "Units".bold(), units.join("  ·  "));
+    }
+    if let Some((name, score)) = r.known_polymer {
+        let similarity = if score < 1.0 {
+            format!(" (similarity {score:.2})")
+        } else {
+            String::new()
+        };
+        println!(
+            "  {:<11}{}{}",
+            "Looks like".bold(),
+            name.green(),
+            similarity.dimmed()
+        );
+    }
     println!();
 }
 
@@ -47,16 +86,174 @@ fn print_table(r: &AnalysisResult) {
     for line in table.to_string().lines() {
         println!("  {line}");
     }
+    if let Some(relative) = mn_relative_error(r).filter(|e| e.abs() > MN_TOLERANCE) {
+        println!();
+        println!(
+            "  {} The nearest achievable Mn is {:.1} % off the target; \
+             consider adjusting --by-mn.",
+            "warning:".yellow().bold(),
+            relative.abs() * 100.0
+        );
+    }
 }
 
-fn print_footnote() {
+fn print_footnote(r: &AnalysisResult) {
     println!();
-    println!("  {} Single ideal chain — Mw = Mn, Đ = 1.000", "¹".dimmed());
+    match r.ensemble_chains {
+        Some(n) => {
+            println!(
+                "  {} Schulz-Zimm distribution of {n} chains simulated — Mn, Mw, Đ are ensemble averages",
+                "¹".dimmed()
+            );
+            println!(
+                "    {}",
+                "Structure rows (n, formula, masses) describe a single chain at the target Mn."
+                    .dimmed()
+                    .italic()
+            );
+        }
+        None => {
+            println!("  {} Single ideal chain — Mw = Mn, Đ = 1.000", "¹".dimmed());
+            println!(
+                "    {}",
+                "Use --dispersity to simulate a chain-length distribution."
+                    .dimmed()
+                    .italic()
+            );
+        }
+    }
+    println!();
+}
+
+fn print_explanation(r: &AnalysisResult, e: &Explanation) {
+    println!("  {}", "How Mn was derived".bold());
+    match e.calibration {
+        Some((m0, m_end)) => {
+            println!(
+                "    repeat-unit mass   M₀ = {}   {}",
+                mass_in_unit(r, m0).green(),
+                "(MW(n=2) − MW(n=1))".dimmed()
+            );
+            println!(
+                "    end groups         M_end = {}   {}",
+                mass_in_unit(r, m_end).green(),
+                "(MW(n=1) − M₀)".dimmed()
+            );
+        }
+        None => println!(
+            "    {}",
+            "Per-unit breakdown is only available for homopolymers.".dimmed()
+        ),
+    }
+
+    let n = r.repeat_count;
+    let resolution = match (&e.strategy, e.calibration) {
+        (BuildStrategy::ByRepeatCount(_), _) => "given with --by-repeat".to_owned(),
+        (BuildStrategy::ByTargetMn(target), Some((m0, m_end))) => format!(
+            "round((Mn_target − M_end) / M₀) = round(({target:.3} − {m_end:.3}) / {m0:.3}) = round({:.3})",
+            (target - m_end) / m0
+        ),
+        (BuildStrategy::ByTargetMn(_), None) => {
+            "chosen so that the average mass is closest to the target Mn".to_owned()
+        }
+        (BuildStrategy::ByExactMass(_), _) => {
+            "chosen so that the monoisotopic mass is closest to the target".to_owned()
+        }
+        (BuildStrategy::ByAtomCount(_), _) => {
+            "chosen so that the total atom count is closest to the target".to_owned()
+        }
+        _ => "chosen from the build strategy".to_owned(),
+    };
     println!(
-        "    {}",
-        "Material simulation (real distributions) will be available in a future release."
-            .dimmed()
-            .italic()
+        "    repeat units       n = {}   {}",
+        n.to_string().cyan(),
+        resolution.dimmed()
+    );
+
+    match e.calibration {
+        Some((m0, m_end)) => println!(
+            "    Mn = n × M₀ + M_end = {n} × {} + {} = {}",
+            mass_in_unit(r, m0),
+            mass_in_unit(r, m_end),
+            mass_in_unit(r, e.chain_mass).green().bold()
+        ),
+        None => println!(
+            "    Mn = Σ atomic masses of the chain = {}",
+            mass_in_unit(r, e.chain_mass).green().bold()
+        ),
+    }
+    println!();
+}
+
+fn print_groups(r: &AnalysisResult, groups: &[(&str, usize)]) {
+    println!("  {}", "Structural groups".bold());
+    let mut table = Table::new();
+    table.load_preset(comfy_table::presets::UTF8_FULL);
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec![
+        Cell::new("Group").add_attribute(Attribute::Bold),
+        Cell::new("Count").add_attribute(Attribute::Bold),
+        Cell::new("Per unit").add_attribute(Attribute::Bold),
+    ]);
+    for &(label, count) in groups {
+        table.add_row(vec![
+            Cell::new(label),
+            Cell::new(count).fg(TableColor::Cyan),
+            Cell::new(format!(
+                "{:.2}",
+                count as f64 / r.repeat_count.max(1) as f64
+            )),
+        ]);
+    }
+    for line in table.to_string().lines() {
+        println!("  {line}");
+    }
+    println!();
+}
+
+fn print_comparison(c: &DatabaseComparison) {
+    println!("  {}", "Literature comparison".bold());
+    let Some(polymer) = c.polymer else {
+        println!(
+            "    {}",
+            "No exact match in the known-polymer table; nothing to compare.".dimmed()
+        );
+        println!();
+        return;
+    };
+    println!("    {}", polymer.green());
+    let mut table = Table::new();
+    table.load_preset(comfy_table::presets::UTF8_FULL);
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec![
+        Cell::new("Property").add_attribute(Attribute::Bold),
+        Cell::new("Predicted").add_attribute(Attribute::Bold),
+        Cell::new("Reference").add_attribute(Attribute::Bold),
+        Cell::new("Δ (predicted − reference)").add_attribute(Attribute::Bold),
+    ]);
+    for row in &c.rows {
+        // One decimal for temperatures, three for densities and indices.
+        let decimals = if row.unit == "K" { 1 } else { 3 };
+        let format = |value: f64| {
+            format!("{value:.decimals$} {}", row.unit)
+                .trim_end()
+                .to_owned()
+        };
+        let delta = row.predicted - row.reference;
+        let (sign, color) = delta_style(delta, row.reference);
+        table.add_row(vec![
+            Cell::new(row.property),
+            Cell::new(format(row.predicted)),
+            Cell::new(format(row.reference)),
+            Cell::new(format!("{sign}{}", format(delta))).fg(color),
+        ]);
+    }
+    for line in table.to_string().lines() {
+        println!("  {line}");
+    }
+    println!(
+        "  {}",
+        "Reference values: Polymer Handbook, amorphous polymer at 25 °C.".dimmed()
     );
     println!();
 }
@@ -76,58 +273,157 @@ fn build_table(r: &AnalysisResult) -> Table {
         Cell::new("Repeat units (n)"),
         Cell::new(r.repeat_count.to_string()).fg(TableColor::Cyan),
     ]);
+    if let Some(count) = r.counterions {
+        table.add_row(vec![
+            Cell::new("Counter-ions"),
+            Cell::new(count.to_string()).fg(TableColor::Cyan),
+        ]);
+    }
 
     add_mn_rows(&mut table, r);
 
     table.add_row(vec![
         Cell::new("Mw¹"),
-        Cell::new(format!("{:.3} g/mol", r.mn)).fg(TableColor::Green),
+        Cell::new(mass_in_unit(r, r.mw)).fg(TableColor::Green),
     ]);
     table.add_row(vec![
         Cell::new("Dispersity Đ¹"),
-        Cell::new("1.000").fg(TableColor::Green),
+        Cell::new(format!("{:.3}", r.dispersity)).fg(TableColor::Green),
     ]);
 
     add_mono_rows(&mut table, r);
 
+    let formula = if r.ascii {
+        r.formula_raw.clone()
+    } else {
+        subscript_digits(&r.formula_raw)
+    };
     table.add_row(vec![
         Cell::new("Molecular formula"),
-        Cell::new(subscript_digits(&r.formula_raw)).fg(TableColor::Magenta),
+        Cell::new(formula).fg(TableColor::Magenta),
     ]);
     table.add_row(vec![
         Cell::new("Total atoms"),
         Cell::new(r.n_atoms.to_string()).fg(TableColor::Cyan),
     ]);
 
+    table.add_row(vec![
+        Cell::new("Tg∞ (Van Krevelen)"),
+        Cell::new(format!("{:.1} K", r.tg_infinity)).fg(TableColor::Blue),
+    ]);
+    table.add_row(vec![
+        Cell::new("Tg (Fox-Flory, Mn)"),
+        Cell::new(format!("{:.1} K", r.tg)).fg(TableColor::Blue),
+    ]);
+
     table
 }
 
 fn add_mn_rows(table: &mut Table, r: &AnalysisResult) {
     table.add_row(vec![
         Cell::new("Mn (number-average)"),
-        Cell::new(format!("{:.3} g/mol", r.mn)).fg(TableColor::Green),
+        Cell::new(mass_in_unit(r, r.mn)).fg(TableColor::Green),
     ]);
     if let Some(d) = r.delta_mn {
         let (sign, color) = delta_style(d, r.mn);
         table.add_row(vec![
             Cell::new("Δ Mn (achieved − target)").fg(TableColor::DarkGrey),
-            Cell::new(format!("{sign}{d:.3} g/mol")).fg(color),
+            Cell::new(format!("{sign}{}", mass_in_unit(r, d))).fg(color),
         ]);
+        if let Some(relative) = mn_relative_error(r) {
+            let color = if relative.abs() > MN_TOLERANCE {
+                TableColor::Red
+            } else {
+                color
+            };
+            table.add_row(vec![
+                Cell::new("Δ Mn relative").fg(TableColor::DarkGrey),
+                Cell::new(format!("{sign}{:.3} %", relative * 100.0)).fg(color),
+            ]);
+        }
     }
 }
 
+/// (achieved − target) / target for `--by-mn`, `None` otherwise.
+fn mn_relative_error(r: &AnalysisResult) -> Option<f64> {
+    let d = r.delta_mn?;
+    let target = r.mn - d;
+    (target.abs() > 1e-9).then(|| d / target)
+}
+
 fn add_mono_rows(table: &mut Table, r: &AnalysisResult) {
     table.add_row(vec![
         Cell::new("Monoisotopic mass"),
-        Cell::new(format!("{:.3} g/mol", r.mono_mass)).fg(TableColor::Yellow),
+        Cell::new(mass_in_unit(r, r.mono_mass)).fg(TableColor::Yellow),
     ]);
     if let Some(d) = r.delta_mass {
         let (sign, color) = delta_style(d, r.mono_mass);
         table.add_row(vec![
             Cell::new("Δ mono (achieved − target)").fg(TableColor::DarkGrey),
-            Cell::new(format!("{sign}{d:.3} g/mol")).fg(color),
+            Cell::new(format!("{sign}{}", mass_in_unit(r, d))).fg(color),
+        ]);
+    }
+}
+
+/// `"  (C₂H₆, 30.070 g/mol)"` for a begin/end block, empty if it has no formula.
+fn terminal_block_details(r: &AnalysisResult, block: &TerminalBlock) -> String {
+    let (Some(formula), Some(mass)) = (&block.formula_raw, block.mass) else {
+        return String::new();
+    };
+    let formula = if r.ascii {
+        formula.clone()
+    } else {
+        subscript_digits(formula)
+    };
+    format!("  ({formula}, {})", mass_in_unit(r, mass))
+}
+
+/// Formats a mass stored in g/mol in the unit selected with `--mass-unit`.
+fn mass_in_unit(r: &AnalysisResult, g_per_mol: f64) -> String {
+    r.mass_unit.format(g_per_mol)
+}
+
+// ═══ Sweep report ════════════════════════════════════════════════════════════
+
+/// Prints one row per repeat count of an `analyze --sweep-repeat` run.
+pub fn print_sweep_report(
+    bigsmiles_str: &str,
+    architecture_label: &str,
+    rows: &[(usize, f64, String)],
+    mass_unit: MassUnit,
+    ascii: bool,
+) {
+    print_banner();
+    println!("  {:<11}{}", "BigSMILES".bold(), bigsmiles_str.yellow());
+    println!("  {:<11}{}", "Arch".bold(), architecture_label.cyan());
+    println!("  {:<11}By repeat count sweep", "Strategy".bold());
+    println!();
+
+    let mut table = Table::new();
+    table.load_preset(comfy_table::presets::UTF8_FULL);
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec![
+        Cell::new("n").add_attribute(Attribute::Bold),
+        Cell::new(format!("Mn ({})", mass_unit.label())).add_attribute(Attribute::Bold),
+        Cell::new("Formula").add_attribute(Attribute::Bold),
+    ]);
+    let decimals = mass_unit.decimals();
+    for (n, mn, formula) in rows {
+        let formula = if ascii {
+            formula.clone()
+        } else {
+            subscript_digits(formula)
+        };
+        table.add_row(vec![
+            Cell::new(n),
+            Cell::new(format!("{:.decimals$}", mass_unit.scale(*mn))).fg(TableColor::Green),
+            Cell::new(formula),
         ]);
     }
+    for line in table.to_string().lines() {
+        println!("  {line}");
+    }
+    println!();
 }
 
 // ═══ Ensemble report ═════════════════════════════════════════════════════════
@@ -182,7 +478,7 @@ fn print_ensemble_summary(
         "Distribution".bold(),
         distribution.label().cyan()
     );
-    println!("  {:<15}{:.1} g/mol", "Target Mn".bold(), target_mn);
+    println!("  {:<15}{}", "Target Mn".bold(), format_mass(target_mn));
     println!("  {:<15}{:.3}", "Target PDI".bold(), target_pdi);
     println!("  {:<15}{}", "Chains".bold(), stats.num_chains);
     println!();
@@ -200,19 +496,19 @@ fn print_ensemble_table(stats: &EnsembleStats, target_mn: f64, target_pdi: f64)
     // Mn
     table.add_row(vec![
         Cell::new("Mn (number-average)"),
-        Cell::new(format!("{:.1} g/mol", stats.mn)).fg(TableColor::Green),
+        Cell::new(format_mass(stats.mn)).fg(TableColor::Green),
     ]);
     let delta_mn = stats.mn - target_mn;
     let (sign, color) = delta_style(delta_mn, target_mn);
     table.add_row(vec![
         Cell::new("Δ Mn (achieved − target)").fg(TableColor::DarkGrey),
-        Cell::new(format!("{sign}{delta_mn:.1} g/mol")).fg(color),
+        Cell::new(format!("{sign}{}", format_mass(delta_mn))).fg(color),
     ]);
 
     // Mw
     table.add_row(vec![
         Cell::new("Mw (weight-average)"),
-        Cell::new(format!("{:.1} g/mol", stats.mw)).fg(TableColor::Green),
+        Cell::new(format_mass(stats.mw)).fg(TableColor::Green),
     ]);
 
     // PDI
@@ -229,22 +525,108 @@ fn print_ensemble_table(stats: &EnsembleStats, target_mn: f64, target_pdi: f64)
     // Median
     table.add_row(vec![
         Cell::new("Mn median"),
-        Cell::new(format!("{:.1} g/mol", stats.mn_median)).fg(TableColor::Green),
+        Cell::new(format_mass(stats.mn_median)).fg(TableColor::Green),
     ]);
 
     // Std dev
     table.add_row(vec![
         Cell::new("Mn std dev (σ)"),
-        Cell::new(format!("{:.1} g/mol", stats.mn_std_dev)).fg(TableColor::Yellow),
+        Cell::new(format_mass(stats.mn_std_dev)).fg(TableColor::Yellow),
     ]);
 
     // Range
     table.add_row(vec![
         Cell::new("Mn range"),
-        Cell::new(format!("{:.1} — {:.1} g/mol", stats.mn_min, stats.mn_max)).fg(TableColor::Cyan),
+        Cell::new(format!(
+            "{} — {}",
+            format_mass(stats.mn_min),
+            format_mass(stats.mn_max)
+        ))
+        .fg(TableColor::Cyan),
     ]);
 
     for line in table.to_string().lines() {
         println!("  {line}");
     }
 }
+
+// ═══ Blend report ════════════════════════════════════════════════════════════
+
+use crate::commands::blend::{BlendComponent, BlendReport};
+
+/// Prints the binary blend report to stdout.
+pub fn print_blend_report(r: &BlendReport) {
+    println!();
+    let title = "  polysim — Polymer Blend  ";
+    let bar = "─".repeat(title.chars().count());
+    println!("  ╭{bar}╮");
+    println!("  │{}│", title.bold().cyan());
+    println!("  ╰{bar}╯");
+    println!();
+
+    let [a, b] = &r.components;
+    println!("  {:<13}{}", "A".bold(), a.bigsmiles.yellow());
+    println!("  {:<13}{}", "B".bold(), b.bigsmiles.yellow());
+    println!("  {:<13}n = {} per chain", "Chains".bold(), r.repeat_count);
+    println!("  {:<13}{:.2} K", "Temperature".bold(), r.temperature_k);
+    println!();
+
+    let mut table = Table::new();
+    table.load_preset(comfy_table::presets::UTF8_FULL);
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec![
+        Cell::new("Property").add_attribute(Attribute::Bold),
+        Cell::new("A").add_attribute(Attribute::Bold),
+        Cell::new("B").add_attribute(Attribute::Bold),
+        Cell::new("Blend").add_attribute(Attribute::Bold),
+    ]);
+    let known = |c: &BlendComponent| c.known_polymer.unwrap_or("—").to_owned();
+    table.add_row(vec![
+        Cell::new("Polymer"),
+        Cell::new(known(a)),
+        Cell::new(known(b)),
+        Cell::new(""),
+    ]);
+    table.add_row(vec![
+        Cell::new("Weight fraction"),
+        Cell::new(format!("{:.3}", a.fraction)),
+        Cell::new(format!("{:.3}", b.fraction)),
+        Cell::new("1.000"),
+    ]);
+    table.add_row(vec![
+        Cell::new("Tg"),
+        Cell::new(format!("{:.1} K", a.tg)).fg(TableColor::Blue),
+        Cell::new(format!("{:.1} K", b.tg)).fg(TableColor::Blue),
+        Cell::new(format!("{:.1} K", r.tg)).fg(TableColor::Blue),
+    ]);
+    table.add_row(vec![
+        Cell::new("Density"),
+        Cell::new(format!("{:.3} g/cm³", a.density)).fg(TableColor::Green),
+        Cell::new(format!("{:.3} g/cm³", b.density)).fg(TableColor::Green),
+        Cell::new(format!("{:.3} g/cm³", r.density)).fg(TableColor::Green),
+    ]);
+    for line in table.to_string().lines() {
+        println!("  {line}");
+    }
+    println!();
+
+    let verdict = if r.chi < r.chi_critical {
+        "miscible (χ < χc)".green()
+    } else {
+        "immiscible (χ ≥ χc)".yellow()
+    };
+    println!("  {:<13}{:.4}", "χ".bold(), r.chi);
+    println!("  {:<13}{:.4}", "χc".bold(), r.chi_critical);
+    println!("  {:<13}{verdict}", "Miscibility".bold());
+    println!();
+    println!(
+        "  {}",
+        "Component Tg: tabulated or Van Krevelen, Fox-Flory corrected; blend Tg: Fox equation."
+            .dimmed()
+    );
+    println!(
+        "  {}",
+        "Blend Tg and density assume a single mixed phase with additive volumes.".dimmed()
+    );
+    println!();
+}
@@ -3,8 +3,10 @@ mod display;
 mod report;
 mod utils;
 
-use clap::{Args, Parser, Subcommand, ValueEnum};
-use polysim_core::BuildStrategy;
+use std::path::PathBuf;
+
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use polysim_core::{BigSmiles, BuildStrategy};
 
 /// Polymer structure generator and property simulator.
 #[derive(Parser)]
@@ -16,6 +18,11 @@ use polysim_core::BuildStrategy;
                   and computes physical/chemical properties on them."
 )]
 struct Cli {
+    /// Reject ambiguous BigSMILES (empty stochastic objects, unbalanced braces)
+    /// instead of parsing leniently.
+    #[arg(long, global = true)]
+    strict: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -26,17 +33,101 @@ enum Commands {
     ///
     /// Generates a single ideal chain and computes its properties:
     /// Mn, Mw, dispersity, molecular formula, monoisotopic mass, and atom count.
+    /// With `--dispersity` > 1, Mn, Mw and Đ come from a simulated distribution.
     Analyze {
-        /// BigSMILES string, e.g. "{[]CC[]}" for polyethylene.
+        /// BigSMILES string, e.g. "{[]CC[]}" for polyethylene, or `-` to read
+        /// it from stdin.
         bigsmiles: String,
 
         #[command(flatten)]
         strategy: StrategyArgs,
 
+        /// Chain-length dispersity (Mw/Mn). Values above 1.0 simulate a
+        /// Schulz-Zimm ensemble around the resolved chain instead of a single ideal chain;
+        /// with `--by-mn`, the ensemble Mn is held at the target.
+        #[arg(long, value_name = "Đ", default_value = "1.0", value_parser = parse_dispersity)]
+        dispersity: f64,
+
+        /// Number of chains in the simulated distribution (`--dispersity` > 1).
+        #[arg(long, value_name = "N", default_value = "2000")]
+        num_chains: usize,
+
+        /// Unit for the mass rows of the report: g/mol or kg/mol, or the
+        /// numerically equal Da or kDa.
+        #[arg(long, value_enum, default_value = "g")]
+        mass_unit: MassUnit,
+
+        /// Print the calculation steps behind Mn and the repeat count.
+        #[arg(long)]
+        explain: bool,
+
+        /// Print the molecular formula with plain digits (C20H42) instead of subscripts.
+        #[arg(long)]
+        ascii: bool,
+
+        /// List the structural groups behind the group-contribution estimates (Tg, …).
+        #[arg(long)]
+        groups: bool,
+
+        /// Compare Tg, density and refractive index with the literature values
+        /// of the identified polymer.
+        #[arg(long)]
+        compare_to_database: bool,
+
+        /// Neutralise charged chains with Na⁺ (anionic) or Cl⁻ (cationic)
+        /// counter-ions, counted in the mass and the formula.
+        #[arg(long)]
+        with_counterions: bool,
+
+        /// Print the wall-clock time of each step (parse, build, properties) to stderr.
+        #[arg(long)]
+        verbose: bool,
+
+        /// Dump the raw analysis result as pretty JSON instead of the report (for tests).
+        #[arg(long, hide = true)]
+        emit_debug_json: bool,
+
         #[command(flatten)]
         arch: ArchitectureArgs,
     },
 
+    /// Print the parsed structure of a BigSMILES string.
+    ///
+    /// Lists the segments in order: begin block, each stochastic object with
+    /// its repeat units and bonding descriptors, and end block.
+    Inspect {
+        /// BigSMILES string, e.g. "CC{[$]CC[$]}O".
+        bigsmiles: String,
+    },
+
+    /// Compute the properties of a binary polymer blend.
+    ///
+    /// Builds one chain of each polymer and reports the Fox-equation Tg, the
+    /// density assuming additive volumes, and the Flory-Huggins χ compared with
+    /// its critical value to flag miscibility. Components recognised as a common
+    /// polymer (PS, PMMA, …) use its tabulated Tg.
+    Blend {
+        /// BigSMILES of polymer A.
+        bigsmiles_a: String,
+
+        /// Weight fraction of A.
+        frac_a: f64,
+
+        /// BigSMILES of polymer B.
+        bigsmiles_b: String,
+
+        /// Weight fraction of B; the two fractions must sum to 1.
+        frac_b: f64,
+
+        /// Repeat units per component chain.
+        #[arg(long, value_name = "N", default_value = "1000")]
+        repeat: usize,
+
+        /// Temperature (K) for the density and χ.
+        #[arg(long, value_name = "K", default_value = "298.15")]
+        temperature: f64,
+    },
+
     /// Generate a polydisperse ensemble of polymer chains.
     ///
     /// Samples chain lengths from a statistical distribution and reports
@@ -65,12 +156,32 @@ enum Commands {
         #[arg(long)]
         seed: Option<u64>,
 
+        /// Do not show a progress bar while the chains are built.
+        #[arg(long, short)]
+        quiet: bool,
+
         #[command(flatten)]
         arch: ArchitectureArgs,
     },
+
+    /// Print the JSON Schema of the property summary.
+    ///
+    /// Lists the keys of a serialized `PropertySummary` with their JSON type
+    /// and unit, as the contract for tools consuming polysim output.
+    Schema,
+
+    /// Print a shell completion script for bash, zsh or fish.
+    ///
+    /// For example `polysim completions bash > ~/.local/share/bash-completion/completions/polysim`.
+    #[command(hide = true)]
+    Completions {
+        /// Target shell.
+        #[arg(value_enum)]
+        shell: commands::completions::Shell,
+    },
 }
 
-/// Build strategy — exactly one of the three flags must be provided.
+/// Build strategy — exactly one of the six flags must be provided.
 #[derive(Args)]
 #[group(required = true, multiple = false)]
 pub(crate) struct StrategyArgs {
@@ -78,34 +189,84 @@ pub(crate) struct StrategyArgs {
     #[arg(long, value_name = "N", help_heading = "Build strategy")]
     pub(crate) by_repeat: Option<usize>,
 
-    /// Build chain targeting the given number-average molecular weight (g/mol).
+    /// Build chain targeting the given number-average molecular weight (g/mol ≡ Da).
     #[arg(long, value_name = "MN", help_heading = "Build strategy")]
     pub(crate) by_mn: Option<f64>,
 
-    /// Build chain targeting the given exact monoisotopic mass (g/mol).
+    /// Build chain targeting the given exact monoisotopic mass (g/mol ≡ Da).
     #[arg(long, value_name = "MASS", help_heading = "Build strategy")]
     pub(crate) by_mass: Option<f64>,
+
+    /// Build chain targeting the given total atom count, hydrogens included.
+    #[arg(long, value_name = "N", help_heading = "Build strategy")]
+    pub(crate) by_atoms: Option<usize>,
+
+    /// Analyze one chain per repeat count (comma-separated, e.g. "1,5,10,20")
+    /// and print a single table with one row per n.
+    #[arg(
+        long,
+        value_name = "N,...",
+        value_delimiter = ',',
+        help_heading = "Build strategy",
+        conflicts_with_all = [
+            "dispersity",
+            "num_chains",
+            "explain",
+            "groups",
+            "compare_to_database",
+            "verbose",
+            "emit_debug_json",
+        ]
+    )]
+    pub(crate) sweep_repeat: Option<Vec<usize>>,
+
+    /// Analyze one chain per target Mn (g/mol) read from a file, one value per
+    /// line (blank lines are skipped), e.g. a batch of GPC results.
+    #[arg(long, value_name = "PATH", help_heading = "Build strategy")]
+    pub(crate) mn_file: Option<PathBuf>,
 }
 
 impl StrategyArgs {
+    /// Strategy args selecting `--by-mn <mn>` alone.
+    pub(crate) fn by_target_mn(mn: f64) -> Self {
+        Self {
+            by_repeat: None,
+            by_mn: Some(mn),
+            by_mass: None,
+            by_atoms: None,
+            sweep_repeat: None,
+            mn_file: None,
+        }
+    }
+
     pub(crate) fn build_strategy(&self) -> BuildStrategy {
         self.by_repeat
             .map(BuildStrategy::ByRepeatCount)
             .or_else(|| self.by_mn.map(BuildStrategy::ByTargetMn))
             .or_else(|| self.by_mass.map(BuildStrategy::ByExactMass))
+            .or_else(|| self.by_atoms.map(BuildStrategy::ByAtomCount))
             .expect("clap enforces required group")
     }
 
-    pub(crate) fn label(&self) -> String {
+    /// Describes the strategy, with the target mass in `mass_unit`.
+    pub(crate) fn label(&self, mass_unit: MassUnit) -> String {
         self.by_repeat
             .map(|n| format!("By repeat count  ·  n = {n}"))
             .or_else(|| {
                 self.by_mn
-                    .map(|mn| format!("By target Mn  ·  Mn = {mn:.3} g/mol"))
+                    .map(|mn| format!("By target Mn  ·  Mn = {}", mass_unit.format(mn)))
             })
             .or_else(|| {
-                self.by_mass
-                    .map(|mass| format!("By exact monoisotopic mass  ·  m = {mass:.3} g/mol"))
+                self.by_mass.map(|mass| {
+                    format!(
+                        "By exact monoisotopic mass  ·  m = {}",
+                        mass_unit.format(mass)
+                    )
+                })
+            })
+            .or_else(|| {
+                self.by_atoms
+                    .map(|atoms| format!("By total atom count  ·  N = {atoms}"))
             })
             .expect("clap enforces required group")
     }
@@ -114,14 +275,30 @@ impl StrategyArgs {
 /// Polymer architecture and copolymer parameters.
 #[derive(Args)]
 pub(crate) struct ArchitectureArgs {
-    /// Polymer architecture.
+    /// Polymer architecture. When omitted it is detected from the number of
+    /// repeat units: one gives a homopolymer, more an alternating copolymer.
+    #[arg(long, value_enum, help_heading = "Architecture")]
+    pub(crate) arch: Option<Architecture>,
+
+    /// Shorthand for --arch random.
     #[arg(
         long,
-        value_enum,
-        default_value = "homo",
+        conflicts_with_all = ["arch", "alternating", "block"],
+        help_heading = "Architecture"
+    )]
+    pub(crate) random: bool,
+
+    /// Shorthand for --arch alternating.
+    #[arg(
+        long,
+        conflicts_with_all = ["arch", "block"],
         help_heading = "Architecture"
     )]
-    pub(crate) arch: Architecture,
+    pub(crate) alternating: bool,
+
+    /// Shorthand for --arch block.
+    #[arg(long, conflicts_with = "arch", help_heading = "Architecture")]
+    pub(crate) block: bool,
 
     /// Weight fractions for random copolymer (comma-separated, e.g. "0.6,0.4").
     #[arg(long, value_delimiter = ',', help_heading = "Architecture")]
@@ -158,6 +335,40 @@ pub(crate) struct ArchitectureArgs {
 }
 
 impl ArchitectureArgs {
+    /// Architecture selected on the command line, or detected from the number
+    /// of repeat units in the first stochastic object.
+    pub(crate) fn resolve(&self, bigsmiles: &BigSmiles) -> Architecture {
+        if self.random {
+            return Architecture::Random;
+        }
+        if self.alternating {
+            return Architecture::Alternating;
+        }
+        if self.block {
+            return Architecture::Block;
+        }
+        if let Some(ref arch) = self.arch {
+            return arch.clone();
+        }
+        match bigsmiles.first_stochastic().map(|s| s.repeat_units.len()) {
+            Some(2..) => Architecture::Alternating,
+            _ => Architecture::Homo,
+        }
+    }
+
+    /// Random copolymer fractions: `--fractions` if given, an equal split otherwise.
+    pub(crate) fn random_fractions(&self, bigsmiles: &BigSmiles) -> Vec<f64> {
+        match &self.fractions {
+            Some(fractions) => fractions.clone(),
+            None => {
+                let k = bigsmiles
+                    .first_stochastic()
+                    .map_or(0, |s| s.repeat_units.len());
+                vec![1.0 / k as f64; k]
+            }
+        }
+    }
+
     pub(crate) fn gradient_profile(&self) -> polysim_core::GradientProfile {
         match self.gradient_profile {
             GradientProfileKind::Linear => polysim_core::GradientProfile::Linear {
@@ -216,15 +427,126 @@ impl DistributionKind {
     }
 }
 
+#[derive(Clone, Copy, ValueEnum, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum MassUnit {
+    /// g/mol (Da).
+    G,
+    /// kg/mol (kDa).
+    Kg,
+    /// Da, numerically equal to g/mol, for mass spectrometry.
+    Da,
+    /// kDa, numerically equal to kg/mol.
+    #[value(name = "kda")]
+    KDa,
+}
+
+impl MassUnit {
+    /// Converts a mass given in g/mol to this unit.
+    pub(crate) fn scale(self, g_per_mol: f64) -> f64 {
+        match self {
+            Self::G | Self::Da => g_per_mol,
+            Self::Kg | Self::KDa => g_per_mol / 1000.0,
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::G => "g/mol",
+            Self::Kg => "kg/mol",
+            Self::Da => "Da",
+            Self::KDa => "kDa",
+        }
+    }
+
+    /// Decimals that keep the same significant digits as g/mol with 3 decimals.
+    pub(crate) fn decimals(self) -> usize {
+        match self {
+            Self::G | Self::Da => 3,
+            Self::Kg | Self::KDa => 6,
+        }
+    }
+
+    /// Formats a mass given in g/mol in this unit, e.g. `"282.556 g/mol"`.
+    pub(crate) fn format(self, g_per_mol: f64) -> String {
+        format!(
+            "{:.decimals$} {}",
+            self.scale(g_per_mol),
+            self.label(),
+            decimals = self.decimals()
+        )
+    }
+}
+
+/// Parses `--dispersity`: Đ = Mw/Mn is at least 1 by definition.
+fn parse_dispersity(value: &str) -> Result<f64, String> {
+    let dispersity: f64 = value
+        .parse()
+        .map_err(|_| format!("'{value}' is not a number"))?;
+    if !dispersity.is_finite() || dispersity < 1.0 {
+        return Err(format!(
+            "dispersity must be a finite number ≥ 1.0 (Đ = Mw/Mn), got {value}"
+        ));
+    }
+    Ok(dispersity)
+}
+
 fn main() {
     let cli = Cli::parse();
     match cli.command {
         Commands::Analyze {
             bigsmiles,
             strategy,
+            dispersity,
+            num_chains,
+            mass_unit,
+            explain,
+            ascii,
+            groups,
+            compare_to_database,
+            with_counterions,
+            verbose,
+            emit_debug_json,
             arch,
         } => {
-            if let Err(code) = commands::analyze::run(&bigsmiles, &strategy, &arch) {
+            let bigsmiles = match commands::analyze::bigsmiles_arg(bigsmiles) {
+                Ok(bigsmiles) => bigsmiles,
+                Err(code) => std::process::exit(code),
+            };
+            let flags = commands::analyze::Flags {
+                strict: cli.strict,
+                explain,
+                verbose,
+                emit_debug_json,
+                ascii,
+                groups,
+                compare_to_database,
+                counterions: with_counterions,
+            };
+            if let Err(code) = commands::analyze::run(
+                &bigsmiles, &strategy, dispersity, num_chains, mass_unit, flags, &arch,
+            ) {
+                std::process::exit(code);
+            }
+        }
+        Commands::Inspect { bigsmiles } => {
+            if let Err(code) = commands::inspect::run(&bigsmiles, cli.strict) {
+                std::process::exit(code);
+            }
+        }
+        Commands::Blend {
+            bigsmiles_a,
+            frac_a,
+            bigsmiles_b,
+            frac_b,
+            repeat,
+            temperature,
+        } => {
+            let components = [
+                (bigsmiles_a.as_str(), frac_a),
+                (bigsmiles_b.as_str(), frac_b),
+            ];
+            if let Err(code) = commands::blend::run(components, repeat, temperature, cli.strict) {
                 std::process::exit(code);
             }
         }
@@ -235,13 +557,28 @@ fn main() {
             distribution,
             num_chains,
             seed,
+            quiet,
             arch,
         } => {
-            if let Err(code) =
-                commands::generate::run(&bigsmiles, mn, pdi, &distribution, num_chains, seed, &arch)
-            {
+            let sampling = commands::generate::Sampling {
+                num_chains,
+                seed,
+                exact_mn: false,
+                show_progress: !quiet,
+            };
+            if let Err(code) = commands::generate::run(
+                &bigsmiles,
+                mn,
+                pdi,
+                &distribution,
+                &sampling,
+                cli.strict,
+                &arch,
+            ) {
                 std::process::exit(code);
             }
         }
+        Commands::Schema => commands::schema::run(),
+        Commands::Completions { shell } => commands::completions::run(shell, Cli::command()),
     }
 }
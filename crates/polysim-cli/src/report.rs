@@ -1,19 +1,104 @@
+use polysim_core::BuildStrategy;
+use serde::{Serialize, Serializer};
+
+use crate::MassUnit;
+
 /// All data needed to render one analysis report.
+#[derive(Serialize)]
 pub struct AnalysisResult {
     pub bigsmiles_str: String,
     pub strategy_label: String,
     pub architecture_label: String,
-    pub begin_block: Option<String>,
-    pub end_block: Option<String>,
+    pub begin_block: Option<TerminalBlock>,
+    pub end_block: Option<TerminalBlock>,
     pub smiles: String,
+    /// Repeat-unit SMILES and molar fraction in the chain.
+    pub composition: Vec<(String, f64)>,
+    /// Closest entry of the known-polymer table and its similarity score.
+    pub known_polymer: Option<(&'static str, f64)>,
     pub repeat_count: usize,
+    /// Counter-ions added to the chain, present only when `--with-counterions` was used.
+    pub counterions: Option<usize>,
     pub mn: f64,
+    /// Weight-average molecular weight; equals `mn` for a single ideal chain.
+    pub mw: f64,
+    /// Mw / Mn; 1.0 for a single ideal chain.
+    pub dispersity: f64,
+    /// Number of chains in the simulated distribution, `None` for a single ideal chain.
+    pub ensemble_chains: Option<usize>,
     pub mono_mass: f64,
     /// Raw (ASCII) molecular formula, subscript conversion is done at render time.
     pub formula_raw: String,
     pub n_atoms: usize,
+    /// Van Krevelen Tg (K), i.e. the high-molecular-weight limit Tg∞.
+    pub tg_infinity: f64,
+    /// Tg∞ corrected for chain length with the Fox-Flory equation (K).
+    pub tg: f64,
     /// Mn − target, present only when `--by-mn` was used.
     pub delta_mn: Option<f64>,
     /// monoisotopic mass − target, present only when `--by-mass` was used.
     pub delta_mass: Option<f64>,
+    /// Unit used to render the mass rows; all masses above are stored in g/mol.
+    pub mass_unit: MassUnit,
+    /// Render the formula with plain ASCII digits instead of Unicode subscripts.
+    pub ascii: bool,
+    /// Calculation steps behind Mn, present only when `--explain` was used.
+    pub explanation: Option<Explanation>,
+    /// Structural groups matched by the group-contribution methods and their
+    /// counts in the chain, present only when `--groups` was used.
+    pub groups: Option<Vec<(&'static str, usize)>>,
+    /// Estimates next to the literature values of the identified polymer,
+    /// present only when `--compare-to-database` was used.
+    pub comparison: Option<DatabaseComparison>,
+}
+
+/// A begin or end block of the BigSMILES (initiator, terminator).
+///
+/// Formula and mass are those of the block read as a standalone molecule,
+/// `None` if it is not valid SMILES on its own.
+#[derive(Serialize)]
+pub struct TerminalBlock {
+    pub smiles: String,
+    /// Raw (ASCII) molecular formula.
+    pub formula_raw: Option<String>,
+    /// Average mass (g/mol).
+    pub mass: Option<f64>,
+}
+
+/// Estimated properties compared with the built-in literature table.
+#[derive(Serialize)]
+pub struct DatabaseComparison {
+    /// Known polymer the chain matches exactly, `None` if there is none.
+    pub polymer: Option<&'static str>,
+    pub rows: Vec<Comparison>,
+}
+
+/// One estimated property and its literature value.
+#[derive(Serialize)]
+pub struct Comparison {
+    pub property: &'static str,
+    /// Unit appended to the values, empty for dimensionless properties.
+    pub unit: &'static str,
+    pub predicted: f64,
+    pub reference: f64,
+}
+
+/// How the repeat count and Mn of the analysed chain were obtained.
+#[derive(Serialize)]
+pub struct Explanation {
+    #[serde(serialize_with = "serialize_debug")]
+    pub strategy: BuildStrategy,
+    /// Repeat-unit mass M₀ and end-group mass M_end from the two-point
+    /// calibration MW(n) = n·M₀ + M_end; homopolymers only.
+    pub calibration: Option<(f64, f64)>,
+    /// Average mass of the single analysed chain (g/mol).
+    pub chain_mass: f64,
+}
+
+/// Serializes a value without a `Serialize` impl through its `Debug` output.
+fn serialize_debug<T: std::fmt::Debug, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&format_args!("{value:?}"))
 }
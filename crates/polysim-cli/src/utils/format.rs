@@ -39,6 +39,23 @@ pub fn subscript_digits(s: &str) -> String {
         .collect()
 }
 
+/// Formats a mass in g/mol with the unit that keeps it short.
+///
+/// Below 10 000 g/mol: g/mol with 3 decimals (`"282.554 g/mol"`); up to
+/// 1 000 000 g/mol: kDa with 1 decimal (`"50.0 kDa"`); above: MDa with
+/// 2 decimals (`"1.25 MDa"`). Only the text is scaled, never the value.
+pub fn format_mass(g_per_mol: f64) -> String {
+    let kda = g_per_mol / 1e3;
+    if g_per_mol.abs() < 1e4 || !g_per_mol.is_finite() {
+        format!("{g_per_mol:.3} g/mol")
+    } else if (kda.abs() * 10.0).round() < 10_000.0 {
+        // Compared after rounding, so 999 990 g/mol reads 1.00 MDa, not 1000.0 kDa
+        format!("{kda:.1} kDa")
+    } else {
+        format!("{:.2} MDa", g_per_mol / 1e6)
+    }
+}
+
 /// Truncates a long string with a mid-string ellipsis `…`.
 ///
 /// If `s.chars().count() <= max_len` the original string is returned unchanged.
@@ -93,6 +110,41 @@ mod tests {
         assert_eq!(subscript_digits(""), "");
     }
 
+    // format_mass -------------------------------------------------------------
+
+    #[test]
+    fn format_mass_small_in_g_per_mol() {
+        assert_eq!(format_mass(282.554), "282.554 g/mol");
+    }
+
+    #[test]
+    fn format_mass_medium_in_kda() {
+        assert_eq!(format_mass(50_000.0), "50.0 kDa");
+    }
+
+    #[test]
+    fn format_mass_large_in_mda() {
+        assert_eq!(format_mass(1.25e6), "1.25 MDa");
+    }
+
+    #[test]
+    fn format_mass_switches_at_ten_thousand() {
+        assert_eq!(format_mass(9_999.5), "9999.500 g/mol");
+        assert_eq!(format_mass(10_000.0), "10.0 kDa");
+    }
+
+    #[test]
+    fn format_mass_rounding_to_1000_kda_reads_mda() {
+        assert_eq!(format_mass(999_900.0), "999.9 kDa");
+        assert_eq!(format_mass(999_990.0), "1.00 MDa");
+    }
+
+    #[test]
+    fn format_mass_negative_delta_keeps_sign() {
+        assert_eq!(format_mass(-12.5), "-12.500 g/mol");
+        assert_eq!(format_mass(-25_000.0), "-25.0 kDa");
+    }
+
     // truncate ----------------------------------------------------------------
 
     #[test]
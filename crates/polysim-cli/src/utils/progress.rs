@@ -0,0 +1,34 @@
+//! Progress reporting for long ensemble builds.
+
+/// Callback receiving `(chains_built, total_chains)`.
+pub type ProgressFn = Box<dyn Fn(usize, usize)>;
+
+/// Returns a progress-bar callback for an ensemble of `total` chains, or `None`
+/// when stdout is not a terminal (pipes, redirections, tests).
+#[cfg(feature = "progress")]
+pub fn chain_progress(total: usize) -> Option<ProgressFn> {
+    use indicatif::{ProgressBar, ProgressStyle};
+    use std::io::IsTerminal;
+
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    let bar = ProgressBar::new(total as u64).with_style(
+        ProgressStyle::with_template(
+            "  {spinner} Building chains {bar:40.cyan/blue} {pos}/{len} ({eta})",
+        )
+        .expect("valid progress template"),
+    );
+    Some(Box::new(move |done, total| {
+        bar.set_position(done as u64);
+        if done >= total {
+            bar.finish_and_clear();
+        }
+    }))
+}
+
+/// Progress reporting is compiled out without the `progress` feature.
+#[cfg(not(feature = "progress"))]
+pub fn chain_progress(_total: usize) -> Option<ProgressFn> {
+    None
+}
@@ -5,6 +5,7 @@
 //! codes ANSI et simplifier les assertions sur le contenu texte.
 
 use assert_cmd::Command;
+use predicates::prelude::*;
 use predicates::str::contains;
 
 // ─── Helpers ────────────────────────────────────────────────────────────────
@@ -173,225 +174,1192 @@ fn analyze_ps_by_repeat_formula() {
         .stdout(contains("C₈H₁₀"));
 }
 
+#[test]
+fn analyze_ps_is_identified_as_polystyrene() {
+    polysim()
+        .args(["analyze", "{[]CC(c1ccccc1)[]}", "--by-repeat", "10"])
+        .assert()
+        .success()
+        .stdout(contains("Looks like"))
+        .stdout(contains("Polystyrene"));
+}
+
 // ─── analyze — stratégie --by-mn ─────────────────────────────────────────────
 
 #[test]
-fn analyze_pe_by_mn_exits_ok() {
-    // Cible Mn ≈ 282.554 → doit donner n=10
+fn analyze_pe_by_mn_exits_ok() {
+    // Cible Mn ≈ 282.554 → doit donner n=10
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-mn", "282.554"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn analyze_pe_by_mn_resolves_correct_n() {
+    // ByTargetMn(282.554) → n=10, formule C₂₀H₄₂
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-mn", "282.554"])
+        .assert()
+        .success()
+        .stdout(contains("C₂₀H₄₂"));
+}
+
+#[test]
+fn analyze_ps_by_mn_large_target() {
+    // PS Mn ≈ 5000 → n≈48, formule attendue commence par C₃
+    polysim()
+        .args(["analyze", "{[]CC(c1ccccc1)[]}", "--by-mn", "5000"])
+        .assert()
+        .success()
+        // La formule de départ doit contenir un nombre à 3 chiffres de C
+        .stdout(contains("C₃"));
+}
+
+// ─── analyze — stratégie --by-mass ───────────────────────────────────────────
+
+#[test]
+fn analyze_pe_by_mass_exits_ok() {
+    // Masse monoisotopique de C₂₀H₄₂ ≈ 282.329 g/mol → doit donner n=10
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-mass", "282.329"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn analyze_pe_by_mass_resolves_correct_formula() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-mass", "282.329"])
+        .assert()
+        .success()
+        .stdout(contains("C₂₀H₄₂"));
+}
+
+// ─── analyze — stratégie --by-atoms ──────────────────────────────────────────
+
+#[test]
+fn analyze_pe_by_atoms_resolves_n_10() {
+    // C₂₀H₄₂ = 62 atomes → n = 10
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-atoms", "62"])
+        .assert()
+        .success()
+        .stdout(contains("C₂₀H₄₂"))
+        .stdout(contains("By total atom count"));
+}
+
+#[test]
+fn analyze_by_atoms_conflicts_with_by_repeat() {
+    polysim()
+        .args([
+            "analyze",
+            "{[]CC[]}",
+            "--by-atoms",
+            "62",
+            "--by-repeat",
+            "10",
+        ])
+        .assert()
+        .failure();
+}
+
+// ─── Gestion des erreurs ──────────────────────────────────────────────────────
+
+#[test]
+fn analyze_invalid_bigsmiles_exits_failure() {
+    polysim()
+        .args(["analyze", "not_a_bigsmiles", "--by-repeat", "5"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn analyze_invalid_bigsmiles_reports_error_to_stderr() {
+    polysim()
+        .args(["analyze", "not_a_bigsmiles", "--by-repeat", "5"])
+        .assert()
+        .failure()
+        .stderr(contains("error:"));
+}
+
+#[test]
+fn analyze_empty_stochastic_object_reports_error() {
+    // Objet stochastique sans motif : message d'erreur, pas de panique
+    polysim()
+        .args(["analyze", "{[][]}", "--by-repeat", "5"])
+        .assert()
+        .code(1)
+        .stderr(contains("got 0, need at least 1"))
+        .stderr(contains("panicked").not());
+}
+
+#[test]
+fn analyze_no_strategy_flag_exits_failure() {
+    // Aucune stratégie fournie → erreur clap (groupe requis)
+    polysim().args(["analyze", "{[]CC[]}"]).assert().failure();
+}
+
+#[test]
+fn analyze_two_strategy_flags_exits_failure() {
+    // Les flags sont mutuellement exclusifs (group multiple = false)
+    polysim()
+        .args([
+            "analyze",
+            "{[]CC[]}",
+            "--by-repeat",
+            "10",
+            "--by-mn",
+            "282.0",
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn analyze_repeat_count_zero_exits_failure() {
+    // n=0 est rejeté par le builder
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-repeat", "0"])
+        .assert()
+        .failure()
+        .stderr(contains("error:"));
+}
+
+#[test]
+fn analyze_no_stochastic_object_exits_failure() {
+    // SMILES simple sans objet stochastique {…}
+    polysim()
+        .args(["analyze", "CCO", "--by-repeat", "5"])
+        .assert()
+        .failure()
+        .stderr(contains("error:"));
+}
+
+#[test]
+fn analyze_copolymer_forced_homo_exits_failure() {
+    // Copolymère + --arch homo → homopolymer() retourne une erreur (deux unités répétées)
+    polysim()
+        .args([
+            "analyze",
+            "{[$]CC[$],[$]CC(C)[$]}",
+            "--by-repeat",
+            "5",
+            "--arch",
+            "homo",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("error:"));
+}
+
+// ─── Détection automatique de l'architecture ─────────────────────────────────
+
+#[test]
+fn analyze_two_units_defaults_to_alternating() {
+    polysim()
+        .args(["analyze", "{[$]CC[$],[$]CC(C)[$]}", "--by-repeat", "6"])
+        .assert()
+        .success()
+        .stdout(contains("Alternating copolymer"))
+        .stdout(contains("Units"))
+        .stdout(contains("50.0 %"));
+}
+
+#[test]
+fn analyze_single_unit_defaults_to_homopolymer() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-repeat", "6"])
+        .assert()
+        .success()
+        .stdout(contains("Homopolymer"))
+        .stdout(contains("Units").not());
+}
+
+#[test]
+fn analyze_random_flag_uses_equal_fractions_by_default() {
+    polysim()
+        .args([
+            "analyze",
+            "{[$]CC[$],[$]CC(C)[$]}",
+            "--by-repeat",
+            "20",
+            "--random",
+            "--copolymer-seed",
+            "7",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Random copolymer"))
+        .stdout(contains("Units"));
+}
+
+#[test]
+fn analyze_block_flag_reports_block_composition() {
+    polysim()
+        .args([
+            "analyze",
+            "{[$]CC[$],[$]CC(C)[$]}",
+            "--by-repeat",
+            "4",
+            "--block",
+            "--block-lengths",
+            "3,1",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Block copolymer"))
+        .stdout(contains("75.0 %"))
+        .stdout(contains("25.0 %"));
+}
+
+#[test]
+fn analyze_architecture_shorthands_conflict_with_arch() {
+    polysim()
+        .args([
+            "analyze",
+            "{[$]CC[$],[$]CC(C)[$]}",
+            "--by-repeat",
+            "4",
+            "--random",
+            "--arch",
+            "block",
+        ])
+        .assert()
+        .failure();
+}
+
+// ─── Contenu structurel de la sortie ─────────────────────────────────────────
+
+#[test]
+fn analyze_output_contains_all_property_labels() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-repeat", "5"])
+        .assert()
+        .success()
+        .stdout(contains("Repeat units"))
+        .stdout(contains("Mn"))
+        .stdout(contains("Mw"))
+        .stdout(contains("Dispersity"))
+        .stdout(contains("Monoisotopic mass"))
+        .stdout(contains("Molecular formula"))
+        .stdout(contains("Total atoms"));
+}
+
+#[test]
+fn analyze_output_contains_strategy_label_by_repeat() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-repeat", "5"])
+        .assert()
+        .success()
+        .stdout(contains("By repeat count"));
+}
+
+#[test]
+fn analyze_output_contains_strategy_label_by_mn() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-mn", "100.0"])
+        .assert()
+        .success()
+        .stdout(contains("By target Mn"));
+}
+
+#[test]
+fn analyze_output_contains_strategy_label_by_mass() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-mass", "100.0"])
+        .assert()
+        .success()
+        .stdout(contains("By exact monoisotopic mass"));
+}
+
+#[test]
+fn analyze_output_echoes_input_bigsmiles() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-repeat", "1"])
+        .assert()
+        .success()
+        .stdout(contains("{[]CC[]}"));
+}
+
+// ─── Libellé de stratégie dans la sortie ─────────────────────────────────────
+
+#[test]
+fn analyze_pe_by_repeat_strategy_label_shows_n() {
+    // Le label doit contenir "n = 10" pour que la stratégie soit non-ambiguë.
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-repeat", "10"])
+        .assert()
+        .success()
+        .stdout(contains("n = 10"));
+}
+
+// ─── Lignes Δ (delta) ─────────────────────────────────────────────────────────
+
+#[test]
+fn analyze_pe_by_mn_shows_delta_mn_row() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-mn", "200.0"])
+        .assert()
+        .success()
+        .stdout(contains("Δ Mn"));
+}
+
+#[test]
+fn analyze_by_mn_between_two_n_shows_relative_error() {
+    // Cible 1000 g/mol → n=36, Mn = 1011.960 g/mol → +1.196 %
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-mn", "1000"])
+        .assert()
+        .success()
+        .stdout(contains("Δ Mn relative"))
+        .stdout(contains("+1.196 %"))
+        .stdout(contains("warning:").not());
+}
+
+#[test]
+fn analyze_by_mn_delta_is_the_single_chain_error() {
+    // Sel de sodium : Δ Mn est l'écart de la chaîne retenue, Na⁺ compris
+    let json = analyze_json(&[
+        "{[]CC(C(=O)[O-])[]}",
+        "--by-mn",
+        "2000",
+        "--with-counterions",
+    ]);
+    let (mn, delta) = (
+        json["mn"].as_f64().unwrap(),
+        json["delta_mn"].as_f64().unwrap(),
+    );
+    assert!((delta - (mn - 2000.0)).abs() < 1e-9);
+    assert!(delta.abs() <= 94.045 / 2.0, "Δ Mn = {delta}");
+}
+
+#[test]
+fn analyze_by_mn_far_from_target_is_flagged() {
+    // Cible 40 g/mol → n=1 (éthane, 30.070 g/mol) : écart ≈ −24.8 %
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-mn", "40"])
+        .assert()
+        .success()
+        .stdout(contains("-24.8"))
+        .stdout(contains("warning:"))
+        .stdout(contains("% off the target"));
+}
+
+#[test]
+fn analyze_by_mn_below_one_unit_fails_cleanly() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-mn", "10"])
+        .assert()
+        .failure()
+        .stderr(contains("error:"))
+        .stderr(contains("minimum achievable Mn is 30.070 g/mol"));
+}
+
+#[test]
+fn analyze_pe_by_mass_shows_delta_mono_row() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-mass", "200.0"])
+        .assert()
+        .success()
+        .stdout(contains("Δ mono"));
+}
+
+// ─── Groupes terminaux (begin / end) ─────────────────────────────────────────
+
+#[test]
+fn analyze_output_shows_begin_and_end_when_terminal_groups_present() {
+    // CC{[]CC[]}CC — "CC" en tête et en queue → lignes Begin et End visibles.
+    polysim()
+        .args(["analyze", "CC{[]CC[]}CC", "--by-repeat", "3"])
+        .assert()
+        .success()
+        .stdout(contains("Begin"))
+        .stdout(contains("End"));
+}
+
+#[test]
+fn analyze_output_shows_formula_and_mass_of_terminal_blocks() {
+    // Blocs lus comme molécules isolées : CC → C₂H₆, O → H₂O
+    polysim()
+        .args(["analyze", "CC{[]CC[]}O", "--by-repeat", "3", "--ascii"])
+        .assert()
+        .success()
+        .stdout(contains("(C2H6, 30.070 g/mol)"))
+        .stdout(contains("(H2O, 18.015 g/mol)"));
+}
+
+#[test]
+fn analyze_debug_json_reports_terminal_blocks() {
+    let json = analyze_json(&["CC{[]CC[]}", "--by-repeat", "3"]);
+    assert_eq!(json["begin_block"]["smiles"], "CC");
+    assert_eq!(json["begin_block"]["formula_raw"], "C2H6");
+    assert!((json["begin_block"]["mass"].as_f64().unwrap() - 30.07).abs() < 0.01);
+    assert!(json["end_block"].is_null());
+}
+
+// ─── Température de transition vitreuse (Tg) ─────────────────────────────────
+
+#[test]
+fn analyze_output_shows_tg_rows() {
+    polysim()
+        .args(["analyze", "{[]CC(c1ccccc1)[]}", "--by-mn", "50000"])
+        .assert()
+        .success()
+        .stdout(contains("Tg∞ (Van Krevelen)"))
+        .stdout(contains("Tg (Fox-Flory, Mn)"));
+}
+
+#[test]
+fn analyze_tg_of_oligomer_is_clamped_at_zero() {
+    // PE n=10 (Mn ≈ 282.55 g/mol) : K/Mn dépasse Tg∞, Tg est ramenée à 0 K
+    let json = analyze_json(&["{[]CC[]}", "--by-repeat", "10"]);
+    assert!(json["tg_infinity"].as_f64().unwrap() > 0.0);
+    assert_eq!(json["tg"].as_f64().unwrap(), 0.0);
+}
+
+// ─── Dispersité (--dispersity) ───────────────────────────────────────────────
+
+/// Extrait la valeur de la cellule « Dispersity Đ¹ » du tableau.
+fn dispersity_cell(stdout: &[u8]) -> String {
+    let text = String::from_utf8_lossy(stdout);
+    let line = text
+        .lines()
+        .find(|l| l.contains("Dispersity Đ¹"))
+        .expect("dispersity row missing");
+    line.split(['│', '┆'])
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .nth(1)
+        .expect("dispersity value missing")
+        .to_owned()
+}
+
+#[test]
+fn analyze_dispersity_two_changes_dispersity_cell() {
+    let out = polysim()
+        .args([
+            "analyze",
+            "{[]CC[]}",
+            "--by-mn",
+            "28000",
+            "--dispersity",
+            "2.0",
+            "--copolymer-seed",
+            "42",
+        ])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let cell = dispersity_cell(&out.stdout);
+    assert_ne!(cell, "1.000");
+    let pdi: f64 = cell.parse().unwrap();
+    assert!(pdi > 1.3, "Đ = {pdi}");
+}
+
+#[test]
+fn analyze_dispersity_updates_footnote() {
+    polysim()
+        .args([
+            "analyze",
+            "{[]CC[]}",
+            "--by-repeat",
+            "100",
+            "--dispersity",
+            "1.5",
+            "--num-chains",
+            "100",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("distribution of 100 chains simulated"));
+}
+
+#[test]
+fn analyze_by_mn_with_dispersity_realizes_both_targets() {
+    let json = analyze_json(&["{[]CC[]}", "--by-mn", "50000", "--dispersity", "1.8"]);
+    // Mn de l'ensemble recalé sur la cible, Đ proche de la consigne quelle que
+    // soit la graine : 2000 chaînes par défaut
+    let mn = json["mn"].as_f64().unwrap();
+    let pdi = json["dispersity"].as_f64().unwrap();
+    assert!((mn - 50_000.0).abs() / 50_000.0 < 0.001, "Mn = {mn}");
+    assert!((pdi - 1.8).abs() < 0.06, "Đ = {pdi}");
+    assert_eq!(json["ensemble_chains"], 2000);
+}
+
+#[test]
+fn analyze_dispersity_one_keeps_single_chain_report() {
+    let out = polysim()
+        .args([
+            "analyze",
+            "{[]CC[]}",
+            "--by-repeat",
+            "10",
+            "--dispersity",
+            "1.0",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(dispersity_cell(&out.stdout), "1.000");
+}
+
+#[test]
+fn analyze_dispersity_below_one_is_rejected() {
+    // Đ = Mw/Mn ≥ 1 : 0.8, 0, les négatifs et NaN sont refusés par clap
+    for value in ["0.8", "0", "-2", "NaN", "inf", "abc"] {
+        polysim()
+            .args(["analyze", "{[]CC[]}", "--by-repeat", "10"])
+            .arg(format!("--dispersity={value}"))
+            .assert()
+            .failure()
+            .stderr(contains("--dispersity"));
+    }
+}
+
+// ─── Unité de masse (--mass-unit) ────────────────────────────────────────────
+
+#[test]
+fn analyze_mass_unit_kg_scales_mass_rows() {
+    // PE n=10 : Mn = 282.556 g/mol → 0.282556 kg/mol
+    polysim()
+        .args([
+            "analyze",
+            "{[]CC[]}",
+            "--by-repeat",
+            "10",
+            "--mass-unit",
+            "kg",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("0.282556 kg/mol"))
+        .stdout(contains("282.556 g/mol").not());
+}
+
+#[test]
+fn analyze_mass_unit_defaults_to_g_per_mol() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-repeat", "10"])
+        .assert()
+        .success()
+        .stdout(contains("282.556 g/mol"))
+        .stdout(contains("kg/mol").not());
+}
+
+#[test]
+fn analyze_mass_unit_da_relabels_without_scaling() {
+    // Da ≡ g/mol : mêmes valeurs, seule l'unité change
+    polysim()
+        .args([
+            "analyze",
+            "{[]CC[]}",
+            "--by-repeat",
+            "10",
+            "--mass-unit",
+            "da",
+        ])
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(contains("282.556 Da"))
+        .stdout(contains("g/mol").not());
+}
+
+#[test]
+fn analyze_mass_unit_kda_scales_like_kg() {
+    polysim()
+        .args([
+            "analyze",
+            "{[]CC[]}",
+            "--by-repeat",
+            "10",
+            "--mass-unit",
+            "kda",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("0.282556 kDa"));
+}
+
+#[test]
+fn analyze_by_mass_in_da_labels_target_and_delta() {
+    // Cible, masse monoisotopique et écart dans la même unité
+    let output = polysim()
+        .args([
+            "analyze",
+            "{[]CC[]}",
+            "--by-mass",
+            "282.3",
+            "--mass-unit",
+            "da",
+        ])
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("m = 282.300 Da"), "{stdout}");
+    assert!(stdout.contains("┆ 282.329 Da"), "{stdout}");
+    assert!(stdout.contains("┆ +0.029 Da"), "{stdout}");
+}
+
+#[test]
+fn analyze_mass_unit_rejects_unknown_unit() {
+    polysim()
+        .args([
+            "analyze",
+            "{[]CC[]}",
+            "--by-repeat",
+            "10",
+            "--mass-unit",
+            "lb",
+        ])
+        .assert()
+        .failure();
+}
+
+// ─── Explication du calcul (--explain) ───────────────────────────────────────
+
+#[test]
+fn analyze_explain_shows_repeat_unit_mass() {
+    // PE : M₀ = 2 × 12.011 + 4 × 1.008 = 28.054 g/mol, M_end = 2 H = 2.016 g/mol
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-repeat", "10", "--explain"])
+        .assert()
+        .success()
+        .stdout(contains("repeat-unit mass"))
+        .stdout(contains("28.054 g/mol"))
+        .stdout(contains("2.016 g/mol"))
+        .stdout(contains("given with --by-repeat"));
+}
+
+#[test]
+fn analyze_explain_shows_target_mn_resolution() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-mn", "1000", "--explain"])
+        .assert()
+        .success()
+        .stdout(contains("round((Mn_target − M_end) / M₀)"))
+        .stdout(contains("n = 36"));
+}
+
+#[test]
+fn analyze_explain_includes_counterions() {
+    // Poly(acrylate de sodium) : M₀ = 71.055 + 22.990 (Na⁺) = 94.045 g/mol
+    polysim()
+        .args([
+            "analyze",
+            "{[]CC(C(=O)[O-])[]}",
+            "--by-mn",
+            "2000",
+            "--with-counterions",
+            "--explain",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("94.045 g/mol"))
+        .stdout(contains("n = 21"));
+}
+
+#[test]
+fn analyze_without_explain_has_no_breakdown() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-repeat", "10"])
+        .assert()
+        .success()
+        .stdout(contains("repeat-unit mass").not());
+}
+
+// ─── Groupes structuraux (--groups) ──────────────────────────────────────────
+
+#[test]
+fn analyze_groups_lists_pmma_groups() {
+    // PMMA n = 10 : 10 esters (C=O + –O–), 21 CH3 dont l'extrémité
+    let output = polysim()
+        .args([
+            "analyze",
+            "{[]CC(C)(C(=O)OC)[]}",
+            "--by-repeat",
+            "10",
+            "--groups",
+        ])
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Structural groups"), "{stdout}");
+    let row = |label: &str| {
+        stdout
+            .lines()
+            .find(|line| line.contains(&format!("│ {label} ")))
+            .unwrap_or_else(|| panic!("{label} row in {stdout}"))
+            .to_owned()
+    };
+    assert!(row(">C=O").contains("┆ 10 "), "{}", row(">C=O"));
+    assert!(row("-O-").contains("┆ 10 "), "{}", row("-O-"));
+    assert!(row("-CH3").contains("┆ 21 "), "{}", row("-CH3"));
+}
+
+#[test]
+fn analyze_without_groups_has_no_group_table() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-repeat", "10"])
+        .assert()
+        .success()
+        .stdout(contains("Structural groups").not());
+}
+
+#[test]
+fn analyze_groups_in_debug_json() {
+    let json = analyze_json(&["{[]CC[]}", "--by-repeat", "10", "--groups"]);
+    // PE : 2 CH3 d'extrémité, 18 CH2
+    assert_eq!(
+        json["groups"],
+        serde_json::json!([["-CH3", 2], ["-CH2-", 18]])
+    );
+}
+
+// ─── Valeurs de la littérature (--compare-to-database) ───────────────────────
+
+#[test]
+fn analyze_compares_polystyrene_with_reference_tg() {
+    let output = polysim()
+        .args([
+            "analyze",
+            "{[]CC(c1ccccc1)[]}",
+            "--by-repeat",
+            "100",
+            "--compare-to-database",
+        ])
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let (_, comparison) = stdout
+        .split_once("Literature comparison")
+        .unwrap_or_else(|| panic!("comparison section in {stdout}"));
+    // Tg de référence du PS : 373 K
+    let tg_row = comparison
+        .lines()
+        .find(|line| line.contains("│ Tg∞ "))
+        .unwrap_or_else(|| panic!("Tg row in {comparison}"));
+    assert!(tg_row.contains("┆ 373.0 K "), "{tg_row}");
+    assert!(comparison.contains("│ Density "), "{comparison}");
+    assert!(comparison.contains("│ Refractive index "), "{comparison}");
+}
+
+#[test]
+fn analyze_comparison_without_exact_match_has_no_rows() {
+    // Poly(1-butène) : absent de la table
+    polysim()
+        .args([
+            "analyze",
+            "{[]CC(CC)[]}",
+            "--by-repeat",
+            "10",
+            "--compare-to-database",
+        ])
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(contains("No exact match"))
+        .stdout(contains("Predicted").not());
+}
+
+#[test]
+fn analyze_comparison_in_debug_json() {
+    let json = analyze_json(&["{[]CC(Cl)[]}", "--by-repeat", "50", "--compare-to-database"]);
+    let comparison = &json["comparison"];
+    assert_eq!(comparison["polymer"], "Poly(vinyl chloride)");
+    assert_eq!(comparison["rows"][0]["property"], "Tg∞");
+    assert_eq!(comparison["rows"][0]["reference"], 354.0);
+    assert_eq!(comparison["rows"][1]["reference"], 1.385);
+}
+
+// ─── Sortie JSON de débogage (--emit-debug-json) ────────────────────────────
+
+fn analyze_json(args: &[&str]) -> serde_json::Value {
+    let output = polysim()
+        .arg("analyze")
+        .args(args)
+        .arg("--emit-debug-json")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).expect("stdout must be valid JSON")
+}
+
+#[test]
+fn analyze_debug_json_pe_by_repeat() {
+    let json = analyze_json(&["{[]CC[]}", "--by-repeat", "10"]);
+    assert_eq!(json["repeat_count"], 10);
+    assert_eq!(json["formula_raw"], "C20H42");
+    assert_eq!(json["n_atoms"], 62);
+    assert!((json["mn"].as_f64().unwrap() - 282.554).abs() < 0.01);
+    assert!(json["explanation"].is_null());
+}
+
+#[test]
+fn analyze_debug_json_includes_explanation() {
+    let json = analyze_json(&["{[]CC[]}", "--by-mn", "1000", "--explain"]);
+    assert_eq!(json["repeat_count"], 36);
+    assert_eq!(json["explanation"]["strategy"], "ByTargetMn(1000.0)");
+}
+
+#[test]
+fn emit_debug_json_is_hidden_from_help() {
     polysim()
-        .args(["analyze", "{[]CC[]}", "--by-mn", "282.554"])
+        .args(["analyze", "--help"])
         .assert()
-        .success();
+        .success()
+        .stdout(contains("emit-debug-json").not());
 }
 
+// ─── Atomes joker (*) ────────────────────────────────────────────────────────
+
 #[test]
-fn analyze_pe_by_mn_resolves_correct_n() {
-    // ByTargetMn(282.554) → n=10, formule C₂₀H₄₂
+fn analyze_wildcard_chain_warns() {
     polysim()
-        .args(["analyze", "{[]CC[]}", "--by-mn", "282.554"])
+        .args(["analyze", "*{[]CC[]}*", "--by-repeat", "3"])
         .assert()
         .success()
-        .stdout(contains("C₂₀H₄₂"));
+        .stderr(contains("warning:"))
+        .stderr(contains("wildcard"));
 }
 
 #[test]
-fn analyze_ps_by_mn_large_target() {
-    // PS Mn ≈ 5000 → n≈48, formule attendue commence par C₃
+fn analyze_plain_chain_has_no_wildcard_warning() {
     polysim()
-        .args(["analyze", "{[]CC(c1ccccc1)[]}", "--by-mn", "5000"])
+        .args(["analyze", "{[]CC[]}", "--by-repeat", "3"])
         .assert()
         .success()
-        // La formule de départ doit contenir un nombre à 3 chiffres de C
-        .stdout(contains("C₃"));
+        .stderr(contains("wildcard").not());
 }
 
-// ─── analyze — stratégie --by-mass ───────────────────────────────────────────
+// ─── Formule en ASCII (--ascii) ──────────────────────────────────────────────
 
 #[test]
-fn analyze_pe_by_mass_exits_ok() {
-    // Masse monoisotopique de C₂₀H₄₂ ≈ 282.329 g/mol → doit donner n=10
+fn analyze_ascii_prints_plain_formula() {
     polysim()
-        .args(["analyze", "{[]CC[]}", "--by-mass", "282.329"])
+        .args(["analyze", "{[]CC[]}", "--by-repeat", "10", "--ascii"])
         .assert()
-        .success();
+        .success()
+        .stdout(contains("C20H42"))
+        .stdout(contains("C₂₀H₄₂").not());
 }
 
+// ─── Chronométrage (--verbose) ───────────────────────────────────────────────
+
 #[test]
-fn analyze_pe_by_mass_resolves_correct_formula() {
+fn analyze_verbose_prints_timings_to_stderr() {
     polysim()
-        .args(["analyze", "{[]CC[]}", "--by-mass", "282.329"])
+        .args(["analyze", "{[]CC[]}", "--by-repeat", "10", "--verbose"])
         .assert()
         .success()
-        .stdout(contains("C₂₀H₄₂"));
+        .stderr(contains("timing:"))
+        .stderr(contains("build"))
+        .stdout(contains("timing:").not());
 }
 
-// ─── Gestion des erreurs ──────────────────────────────────────────────────────
+#[test]
+fn analyze_without_verbose_has_no_timings() {
+    polysim()
+        .args(["analyze", "{[]CC[]}", "--by-repeat", "10"])
+        .assert()
+        .success()
+        .stderr(contains("timing:").not());
+}
+
+// ─── Mode strict (--strict) ──────────────────────────────────────────────────
 
 #[test]
-fn analyze_invalid_bigsmiles_exits_failure() {
+fn analyze_strict_rejects_empty_stochastic_object() {
     polysim()
-        .args(["analyze", "not_a_bigsmiles", "--by-repeat", "5"])
+        .args(["analyze", "{}", "--by-repeat", "5", "--strict"])
         .assert()
-        .failure();
+        .failure()
+        .stderr(contains("empty stochastic object"));
 }
 
 #[test]
-fn analyze_invalid_bigsmiles_reports_error_to_stderr() {
+fn analyze_strict_accepts_valid_polyethylene() {
     polysim()
-        .args(["analyze", "not_a_bigsmiles", "--by-repeat", "5"])
+        .args(["--strict", "analyze", "{[]CC[]}", "--by-repeat", "10"])
+        .assert()
+        .success()
+        .stdout(contains("C₂₀H₄₂"));
+}
+
+#[test]
+fn generate_strict_rejects_unbalanced_braces() {
+    polysim()
+        .args(["generate", "{[]CC[]", "--mn", "2805", "--strict"])
         .assert()
         .failure()
-        .stderr(contains("error:"));
+        .stderr(contains("unclosed '{'"));
 }
 
+// ─── Balayage du nombre d'unités (--sweep-repeat) ────────────────────────────
+
 #[test]
-fn analyze_no_strategy_flag_exits_failure() {
-    // Aucune stratégie fournie → erreur clap (groupe requis)
-    polysim().args(["analyze", "{[]CC[]}"]).assert().failure();
+fn analyze_sweep_repeat_prints_one_row_per_n() {
+    // PE : Cₙ₂H₂ₙ₊₂ pour chaque n du balayage
+    polysim()
+        .args([
+            "analyze",
+            "{[]CC[]}",
+            "--sweep-repeat",
+            "1,5,10,20",
+            "--ascii",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("C2H6"))
+        .stdout(contains("C10H22"))
+        .stdout(contains("C20H42"))
+        .stdout(contains("C40H82"));
 }
 
 #[test]
-fn analyze_two_strategy_flags_exits_failure() {
-    // Les flags sont mutuellement exclusifs (group multiple = false)
+fn analyze_sweep_repeat_conflicts_with_other_strategies() {
     polysim()
         .args([
             "analyze",
             "{[]CC[]}",
+            "--sweep-repeat",
+            "1,5",
             "--by-repeat",
             "10",
-            "--by-mn",
-            "282.0",
         ])
         .assert()
         .failure();
 }
 
 #[test]
-fn analyze_repeat_count_zero_exits_failure() {
-    // n=0 est rejeté par le builder
-    polysim()
-        .args(["analyze", "{[]CC[]}", "--by-repeat", "0"])
-        .assert()
-        .failure()
-        .stderr(contains("error:"));
+fn analyze_sweep_repeat_rejects_single_chain_options() {
+    // Ces options n'ont pas d'effet sur le tableau du balayage
+    for flag in [
+        &["--dispersity", "2.0"][..],
+        &["--explain"],
+        &["--groups"],
+        &["--compare-to-database"],
+        &["--verbose"],
+        &["--emit-debug-json"],
+    ] {
+        polysim()
+            .args(["analyze", "{[]CC[]}", "--sweep-repeat", "1,5"])
+            .args(flag)
+            .assert()
+            .failure()
+            .stderr(contains("cannot be used with"));
+    }
 }
 
+// ─── Contre-ions (--with-counterions) ────────────────────────────────────────
+
 #[test]
-fn analyze_no_stochastic_object_exits_failure() {
-    // SMILES simple sans objet stochastique {…}
+fn analyze_with_counterions_adds_sodium_to_formula() {
     polysim()
-        .args(["analyze", "CCO", "--by-repeat", "5"])
+        .args([
+            "analyze",
+            "{[]CC(c1ccc(cc1)S(=O)(=O)[O-])[]}",
+            "--by-repeat",
+            "10",
+            "--with-counterions",
+            "--ascii",
+        ])
         .assert()
-        .failure()
-        .stderr(contains("error:"));
+        .success()
+        .stdout(contains("Counter-ions"))
+        .stdout(contains("C80H72Na10O30S10"));
 }
 
 #[test]
-fn analyze_copolymer_bigsmiles_exits_failure() {
-    // Copolymère → homopolymer() retourne une erreur (deux unités répétées)
+fn analyze_without_counterions_omits_row() {
     polysim()
-        .args(["analyze", "{[$]CC[$],[$]CC(C)[$]}", "--by-repeat", "5"])
+        .args([
+            "analyze",
+            "{[]CC(c1ccc(cc1)S(=O)(=O)[O-])[]}",
+            "--by-repeat",
+            "10",
+            "--ascii",
+        ])
         .assert()
-        .failure()
-        .stderr(contains("error:"));
+        .success()
+        .stdout(contains("Counter-ions").not())
+        .stdout(contains("C80H72O30S10"));
 }
 
-// ─── Contenu structurel de la sortie ─────────────────────────────────────────
+// ─── Fichier de cibles Mn (--mn-file) ────────────────────────────────────────
+
+/// Écrit `contents` dans un fichier temporaire propre au test et renvoie son chemin.
+fn mn_file(name: &str, contents: &str) -> std::path::PathBuf {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
 
 #[test]
-fn analyze_output_contains_all_property_labels() {
+fn analyze_mn_file_runs_one_analysis_per_target() {
+    // PE : 2805 → n = 100, 5610 → n = 200 ; la ligne vide est ignorée
+    let path = mn_file("mn_two_targets.txt", "2805\n\n5610\n");
     polysim()
-        .args(["analyze", "{[]CC[]}", "--by-repeat", "5"])
+        .args(["analyze", "{[]CC[]}", "--mn-file"])
+        .arg(&path)
         .assert()
         .success()
-        .stdout(contains("Repeat units"))
-        .stdout(contains("Mn"))
-        .stdout(contains("Mw"))
-        .stdout(contains("Dispersity"))
-        .stdout(contains("Monoisotopic mass"))
-        .stdout(contains("Molecular formula"))
-        .stdout(contains("Total atoms"));
+        .stdout(contains("Mn = 2805.000 g/mol"))
+        .stdout(contains("Mn = 5610.000 g/mol"))
+        .stdout(predicate::str::is_match(r"Repeat units \(n\)\s+┆ 100 ").unwrap())
+        .stdout(predicate::str::is_match(r"Repeat units \(n\)\s+┆ 200 ").unwrap());
 }
 
 #[test]
-fn analyze_output_contains_strategy_label_by_repeat() {
+fn analyze_mn_file_rejects_invalid_line() {
+    let path = mn_file("mn_invalid_line.txt", "2805\nabc\n");
     polysim()
-        .args(["analyze", "{[]CC[]}", "--by-repeat", "5"])
+        .args(["analyze", "{[]CC[]}", "--mn-file"])
+        .arg(&path)
         .assert()
-        .success()
-        .stdout(contains("By repeat count"));
+        .failure()
+        .stderr(contains("invalid Mn 'abc' on line 2"));
 }
 
 #[test]
-fn analyze_output_contains_strategy_label_by_mn() {
+fn analyze_mn_file_missing_file_fails() {
     polysim()
-        .args(["analyze", "{[]CC[]}", "--by-mn", "100.0"])
+        .args([
+            "analyze",
+            "{[]CC[]}",
+            "--mn-file",
+            "/nonexistent/targets.txt",
+        ])
         .assert()
-        .success()
-        .stdout(contains("By target Mn"));
+        .failure()
+        .stderr(contains("cannot read"));
 }
 
 #[test]
-fn analyze_output_contains_strategy_label_by_mass() {
+fn analyze_mn_file_conflicts_with_other_strategies() {
+    let path = mn_file("mn_conflict.txt", "2805\n");
     polysim()
-        .args(["analyze", "{[]CC[]}", "--by-mass", "100.0"])
+        .args(["analyze", "{[]CC[]}", "--by-mn", "2805", "--mn-file"])
+        .arg(&path)
         .assert()
-        .success()
-        .stdout(contains("By exact monoisotopic mass"));
+        .failure();
 }
 
+// ─── BigSMILES depuis stdin (-) ──────────────────────────────────────────────
+
 #[test]
-fn analyze_output_echoes_input_bigsmiles() {
+fn analyze_reads_bigsmiles_from_stdin() {
+    // Équivalent de : echo '{[]CC[]}' | polysim analyze - --by-repeat 10
     polysim()
-        .args(["analyze", "{[]CC[]}", "--by-repeat", "1"])
+        .args(["analyze", "-", "--by-repeat", "10", "--ascii"])
+        .write_stdin("{[]CC[]}\n")
         .assert()
         .success()
+        .stdout(contains("C20H42"))
         .stdout(contains("{[]CC[]}"));
 }
 
-// ─── Libellé de stratégie dans la sortie ─────────────────────────────────────
-
 #[test]
-fn analyze_pe_by_repeat_strategy_label_shows_n() {
-    // Le label doit contenir "n = 10" pour que la stratégie soit non-ambiguë.
+fn analyze_empty_stdin_fails() {
     polysim()
-        .args(["analyze", "{[]CC[]}", "--by-repeat", "10"])
+        .args(["analyze", "-", "--by-repeat", "10"])
+        .write_stdin("  \n")
         .assert()
-        .success()
-        .stdout(contains("n = 10"));
+        .failure()
+        .stderr(contains("no BigSMILES on stdin"));
 }
 
-// ─── Lignes Δ (delta) ─────────────────────────────────────────────────────────
+// ═══════════════════════════════════════════════════════════════════════════════
+// ─── blend ─────────────────────────────────────────────────────────────────────
+// ═══════════════════════════════════════════════════════════════════════════════
+
+const PS: &str = "{[]CC(c1ccccc1)[]}";
+const PMMA: &str = "{[]CC(C)(C(=O)OC)[]}";
 
 #[test]
-fn analyze_pe_by_mn_shows_delta_mn_row() {
+fn blend_ps_pmma_tg_lies_between_components() {
+    let output = polysim()
+        .args(["blend", PS, "0.5", PMMA, "0.5"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // Ligne « Tg » du tableau : A, B, puis le mélange
+    let row = stdout
+        .lines()
+        .find(|line| line.contains("│ Tg "))
+        .expect("Tg row");
+    let tgs: Vec<f64> = row
+        .split('┆')
+        .skip(1)
+        .map(|cell| {
+            cell.trim()
+                .trim_end_matches('│')
+                .trim()
+                .trim_end_matches(" K")
+        })
+        .map(|value| value.parse().unwrap())
+        .collect();
+    let (a, b, blend) = (tgs[0], tgs[1], tgs[2]);
+    assert!(blend > a.min(b) && blend < a.max(b), "{row}");
+}
+
+#[test]
+fn blend_reports_chi_and_miscibility() {
     polysim()
-        .args(["analyze", "{[]CC[]}", "--by-mn", "200.0"])
+        .args(["blend", PS, "0.5", PMMA, "0.5"])
         .assert()
         .success()
-        .stdout(contains("Δ Mn"));
+        .stdout(contains("χc"))
+        .stdout(contains("immiscible"));
 }
 
 #[test]
-fn analyze_pe_by_mass_shows_delta_mono_row() {
+fn blend_of_identical_polymers_is_miscible() {
     polysim()
-        .args(["analyze", "{[]CC[]}", "--by-mass", "200.0"])
+        .args(["blend", PS, "0.3", PS, "0.7"])
         .assert()
         .success()
-        .stdout(contains("Δ mono"));
+        .stdout(contains("miscible (χ < χc)"));
 }
 
-// ─── Groupes terminaux (begin / end) ─────────────────────────────────────────
+#[test]
+fn blend_uses_tabulated_tg_of_known_polymers() {
+    let output = polysim()
+        .args(["blend", PS, "0.5", PMMA, "0.5"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Polystyrene"), "{stdout}");
+    assert!(stdout.contains("Poly(methyl methacrylate)"), "{stdout}");
+    // PS : 373 K tabulée, −1 K de correction Fox-Flory à n = 1000
+    assert!(stdout.contains("372.0 K"), "{stdout}");
+}
 
 #[test]
-fn analyze_output_shows_begin_and_end_when_terminal_groups_present() {
-    // CC{[]CC[]}CC — "CC" en tête et en queue → lignes Begin et End visibles.
+fn blend_falls_back_to_van_krevelen_for_unknown_polymer() {
+    // Polyisobutylène : absent de la table
     polysim()
-        .args(["analyze", "CC{[]CC[]}CC", "--by-repeat", "3"])
+        .args(["blend", PS, "0.5", "{[]CC(C)(C)[]}", "0.5"])
         .assert()
         .success()
-        .stdout(contains("Begin"))
-        .stdout(contains("End"));
+        .stdout(contains("Polystyrene"))
+        .stdout(contains("│ Polymer ").and(contains("—")));
+}
+
+#[test]
+fn blend_fractions_must_sum_to_one() {
+    polysim()
+        .args(["blend", PS, "0.5", PMMA, "0.6"])
+        .assert()
+        .failure()
+        .stderr(contains("must sum to 1.0"));
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -426,6 +1394,17 @@ fn generate_output_shows_mn_mw_pdi() {
         .stdout(contains("Dispersity"));
 }
 
+#[test]
+fn generate_output_shows_large_masses_in_kda() {
+    // 50 000 g/mol s'affiche « 50.0 kDa » plutôt qu'en chiffres bruts
+    polysim()
+        .args(["generate", "{[]CC[]}", "--mn", "50000", "--seed", "42"])
+        .assert()
+        .success()
+        .stdout(contains("50.0 kDa"))
+        .stdout(contains("50000.0 g/mol").not());
+}
+
 #[test]
 fn generate_output_shows_median_and_std_dev() {
     polysim()
@@ -608,3 +1587,133 @@ fn generate_shows_target_mn() {
         .success()
         .stdout(contains("2805"));
 }
+
+// ─── generate — barre de progression ─────────────────────────────────────────
+
+#[test]
+#[cfg(feature = "progress")]
+fn generate_with_progress_feature_succeeds_without_tty() {
+    // stdout est un pipe : la barre est désactivée, le rapport reste intact
+    let out = polysim()
+        .args([
+            "generate",
+            "{[]CC[]}",
+            "--mn",
+            "2805",
+            "--num-chains",
+            "2000",
+            "--seed",
+            "42",
+        ])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("Polydisperse Ensemble"));
+    assert!(!stdout.contains("Building chains"));
+    assert!(!String::from_utf8_lossy(&out.stderr).contains("Building chains"));
+}
+
+#[test]
+fn generate_quiet_exits_ok() {
+    polysim()
+        .args([
+            "generate", "{[]CC[]}", "--mn", "2805", "--seed", "42", "--quiet",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Polydisperse Ensemble"));
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ─── inspect ───────────────────────────────────────────────────────────────────
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn inspect_lists_begin_stochastic_object_and_end() {
+    polysim()
+        .args(["inspect", "CC{[$]CC[$]}O"])
+        .assert()
+        .success()
+        .stdout(contains("1 stochastic object(s)"))
+        .stdout(predicate::str::is_match(r"Begin\s+CC\n").unwrap())
+        .stdout(contains("Stochastic object 1"))
+        .stdout(contains("[$]"))
+        .stdout(predicate::str::is_match(r"End\s+O\n").unwrap())
+        .stdout(contains("Stochastic object 2").not());
+}
+
+#[test]
+fn inspect_invalid_bigsmiles_fails() {
+    polysim()
+        .args(["inspect", "{[]CC["])
+        .assert()
+        .failure()
+        .stderr(contains("error:"));
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ─── schema ────────────────────────────────────────────────────────────────────
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn schema_lists_summary_fields_with_units() {
+    let output = polysim().arg("schema").output().unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout must be valid JSON");
+
+    assert_eq!(schema["title"], "PropertySummary");
+    let properties = schema["properties"].as_object().unwrap();
+    // Chaque champ est obligatoire
+    assert_eq!(
+        schema["required"].as_array().unwrap().len(),
+        properties.len()
+    );
+    assert!(properties.contains_key("chain"));
+    assert_eq!(properties["mn"]["unit"], "g/mol");
+    assert_eq!(properties["tg"]["unit"], "K");
+    assert_eq!(properties["formula"]["type"], "string");
+    assert!(properties["formula"].get("unit").is_none());
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ─── completions ───────────────────────────────────────────────────────────────
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn completions_bash_lists_subcommands_and_flags() {
+    polysim()
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(contains("complete -F _polysim"))
+        .stdout(contains("analyze"))
+        .stdout(contains("--by-repeat"))
+        // Valeurs possibles des options à choix
+        .stdout(contains("g kg da kda"));
+}
+
+#[test]
+fn completions_zsh_and_fish_succeed() {
+    polysim()
+        .args(["completions", "zsh"])
+        .assert()
+        .success()
+        .stdout(contains("#compdef polysim"))
+        .stdout(contains("analyze"));
+    polysim()
+        .args(["completions", "fish"])
+        .assert()
+        .success()
+        .stdout(contains("__fish_seen_subcommand_from analyze"));
+}
+
+#[test]
+fn completions_subcommand_is_hidden_from_help() {
+    polysim()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(contains("completions").not());
+}
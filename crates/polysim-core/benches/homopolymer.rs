@@ -32,5 +32,31 @@ fn bench_polystyrene(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_polyethylene, bench_polystyrene);
+fn bench_long_ring_chains(c: &mut Criterion) {
+    // Chaînes très longues : l'unité est analysée une seule fois (ring template),
+    // chaque copie ne fait que substituer ses numéros de cycle (1..=99, puis %dd).
+    let mut group = c.benchmark_group("homopolymer/long_ring_chains");
+    group.sample_size(10);
+
+    let n = 100_000usize;
+    for (name, bigsmiles) in [
+        ("polystyrene", "{[]CC(c1ccccc1)[]}"),
+        ("pet", "{[]OCCOC(=O)c1ccc(cc1)C(=O)[]}"),
+    ] {
+        let bs = parse(bigsmiles).unwrap();
+        let builder = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(n));
+        group.throughput(Throughput::Elements(n as u64));
+        group.bench_with_input(BenchmarkId::new(name, n), &builder, |b, builder| {
+            b.iter(|| builder.homopolymer().unwrap());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_polyethylene,
+    bench_polystyrene,
+    bench_long_ring_chains
+);
 criterion_main!(benches);
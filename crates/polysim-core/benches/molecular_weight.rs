@@ -1,7 +1,8 @@
 use bigsmiles::parse;
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use polysim_core::{
-    builder::{linear::LinearBuilder, BuildStrategy},
+    builder::{linear::LinearBuilder, BuildStrategy, EnsembleBuilder, RepeatUnitCache},
+    distribution::SchulzZimm,
     properties::molecular_weight::{average_mass, monoisotopic_mass},
 };
 
@@ -78,10 +79,51 @@ fn bench_by_target_mn(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_repeat_unit_cache(c: &mut Criterion) {
+    let mut group = c.benchmark_group("molecular_weight/repeat_unit_cache");
+
+    // Masse d'une chaîne : cache (multiplication) contre parsing du SMILES
+    for n in [10usize, 100, 1_000] {
+        let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+        let cache = RepeatUnitCache::new(&bs).unwrap();
+        group.bench_with_input(BenchmarkId::new("cached", n), &n, |b, &n| {
+            b.iter(|| cache.average_mass(&[n], 0, 0));
+        });
+        let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(n))
+            .homopolymer()
+            .unwrap();
+        group.bench_with_input(BenchmarkId::new("parsed", n), &chain, |b, chain| {
+            b.iter(|| average_mass(chain));
+        });
+    }
+
+    // Ensemble complet : le Mn de chaque chaîne vient du cache
+    for num_chains in [1_000usize, 10_000] {
+        let bs = parse("{[]CC(C)[]}").unwrap();
+        group.throughput(Throughput::Elements(num_chains as u64));
+        group.bench_with_input(
+            BenchmarkId::new("ensemble", num_chains),
+            &num_chains,
+            |b, &num_chains| {
+                b.iter(|| {
+                    EnsembleBuilder::new(bs.clone(), SchulzZimm, 5_000.0, 2.0)
+                        .num_chains(num_chains)
+                        .seed(42)
+                        .homopolymer_ensemble()
+                        .unwrap()
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_average_mass,
     bench_monoisotopic_mass,
-    bench_by_target_mn
+    bench_by_target_mn,
+    bench_repeat_unit_cache
 );
 criterion_main!(benches);
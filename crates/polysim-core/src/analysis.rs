@@ -0,0 +1,189 @@
+//! One-shot property analysis of a BigSMILES string.
+//!
+//! [`analyze_bigsmiles`] chains parsing, homopolymer building and the standard
+//! property calculators, like the `analyze` command of the CLI.
+
+use serde::Serialize;
+
+use crate::builder::{linear::LinearBuilder, BuildStrategy};
+use crate::error::PolySimError;
+use crate::polymer::PolymerChain;
+use crate::properties::{
+    formula::{molecular_formula, total_atom_count},
+    molecular_weight::monoisotopic_mass,
+    solubility::{solubility_parameter, water_uptake},
+    thermal::{tg_fox_flory, tg_infinite_chain, DEFAULT_FOX_FLORY_K},
+    topology::backbone_atom_count,
+    validate::{has_standard_composition, mass_matches_formula},
+    volume::density,
+};
+
+/// Standard properties of a single homopolymer chain.
+///
+/// Serializes to an object with the keys listed in [`PropertySummary::FIELDS`].
+#[derive(Debug, Clone, Serialize)]
+pub struct PropertySummary {
+    /// The built chain.
+    pub chain: PolymerChain,
+    /// Number-average molecular weight (g/mol).
+    pub mn: f64,
+    /// Monoisotopic mass (g/mol).
+    pub monoisotopic_mass: f64,
+    /// Molecular formula in Hill notation, e.g. `"C20H42"`.
+    pub formula: String,
+    /// Total number of atoms, implicit hydrogens included.
+    pub atom_count: usize,
+    /// Van Krevelen Tg of the infinite chain (K).
+    pub tg_infinity: f64,
+    /// Tg corrected for the chain length with the Fox-Flory equation (K).
+    pub tg: f64,
+    /// Amorphous density at 298.15 K (g/cm³).
+    pub density: f64,
+    /// Heavy atoms outside the repeat units (see [`PolymerChain::end_group_atoms`]).
+    pub end_group_atoms: usize,
+    /// Mass of the chain ends, terminal hydrogens included (g/mol).
+    pub end_group_mass: f64,
+    /// Equilibrium water uptake at 50 % RH (g/g), end groups included.
+    pub water_uptake: f64,
+    /// Hildebrand solubility parameter at 298.15 K (MPa^½), end groups included.
+    pub solubility_parameter: f64,
+    /// Heavy atoms on the backbone (see [`backbone_atom_count`]).
+    pub backbone_atoms: usize,
+}
+
+/// Chain properties divided by the number of backbone atoms.
+///
+/// Normalizing by backbone length rather than by repeat unit makes chains of
+/// different architectures or unit sizes comparable; multiply by 100 for the
+/// usual "per 100 backbone atoms" figures.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PerBackboneAtom {
+    /// Number-average molecular weight per backbone atom (g/mol).
+    pub mass: f64,
+    /// Molar volume at 298.15 K per backbone atom (cm³/mol).
+    pub volume: f64,
+}
+
+/// One serialized field of [`PropertySummary`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SummaryField {
+    /// Key in the serialized object.
+    pub name: &'static str,
+    /// JSON type of the value: `"number"`, `"integer"`, `"string"` or `"object"`.
+    pub json_type: &'static str,
+    /// Unit of the value, `None` for counts, strings and objects.
+    pub unit: Option<&'static str>,
+}
+
+impl PropertySummary {
+    /// Serialized fields, in declaration order.
+    ///
+    /// `chain` is the serialized [`PolymerChain`] (`smiles`, `repeat_count`,
+    /// `mn`, `composition`, `sequence`, `architecture`, `counterion_count`).
+    pub const FIELDS: &'static [SummaryField] = &[
+        SummaryField::new("chain", "object", None),
+        SummaryField::new("mn", "number", Some("g/mol")),
+        SummaryField::new("monoisotopic_mass", "number", Some("g/mol")),
+        SummaryField::new("formula", "string", None),
+        SummaryField::new("atom_count", "integer", None),
+        SummaryField::new("tg_infinity", "number", Some("K")),
+        SummaryField::new("tg", "number", Some("K")),
+        SummaryField::new("density", "number", Some("g/cm³")),
+        SummaryField::new("end_group_atoms", "integer", None),
+        SummaryField::new("end_group_mass", "number", Some("g/mol")),
+        SummaryField::new("water_uptake", "number", Some("g/g")),
+        SummaryField::new("solubility_parameter", "number", Some("MPa^½")),
+        SummaryField::new("backbone_atoms", "integer", None),
+    ];
+
+    /// Normalizes the mass and molar volume of the chain by its number of
+    /// backbone atoms.
+    ///
+    /// Both fields are NaN when the chain has no backbone atom.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{analyze_bigsmiles, BuildStrategy};
+    ///
+    /// // Polyéthylène : ~14 g/mol par carbone du squelette (CH₂)
+    /// let summary = analyze_bigsmiles("{[]CC[]}", BuildStrategy::ByRepeatCount(100)).unwrap();
+    /// let per_atom = summary.per_backbone_atom();
+    /// assert!((per_atom.mass - 14.04).abs() < 0.01, "{} g/mol", per_atom.mass);
+    /// ```
+    pub fn per_backbone_atom(&self) -> PerBackboneAtom {
+        if self.backbone_atoms == 0 {
+            return PerBackboneAtom {
+                mass: f64::NAN,
+                volume: f64::NAN,
+            };
+        }
+        let atoms = self.backbone_atoms as f64;
+        PerBackboneAtom {
+            mass: self.mn / atoms,
+            volume: self.mn / self.density / atoms,
+        }
+    }
+}
+
+impl SummaryField {
+    const fn new(name: &'static str, json_type: &'static str, unit: Option<&'static str>) -> Self {
+        Self {
+            name,
+            json_type,
+            unit,
+        }
+    }
+}
+
+/// Parses `bigsmiles`, builds a homopolymer with `strategy` and computes its
+/// standard properties.
+///
+/// # Errors
+///
+/// Returns [`PolySimError::Parse`] if the string is not valid BigSMILES, and
+/// any error of [`LinearBuilder::homopolymer`].
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{analyze_bigsmiles, BuildStrategy};
+///
+/// // Polyéthylène — 100 unités répétées
+/// let summary = analyze_bigsmiles("{[]CC[]}", BuildStrategy::ByRepeatCount(100)).unwrap();
+///
+/// assert_eq!(summary.chain.repeat_count, 100);
+/// assert!((summary.mn - 2807.4).abs() < 0.1, "Mn = {} g/mol", summary.mn);
+/// assert_eq!(summary.formula, "C200H402");
+/// ```
+pub fn analyze_bigsmiles(
+    bigsmiles: &str,
+    strategy: BuildStrategy,
+) -> Result<PropertySummary, PolySimError> {
+    let parsed = bigsmiles::parse(bigsmiles)?;
+    let builder = LinearBuilder::new(parsed, strategy);
+    let chain = builder.homopolymer()?;
+    let (_, end_group_mass) = builder.repeat_unit_mass()?;
+    let tg_infinity = tg_infinite_chain(&chain);
+    debug_assert!(
+        !has_standard_composition(&chain) || mass_matches_formula(&chain),
+        "average mass of {} disagrees with its formula",
+        chain.smiles
+    );
+
+    Ok(PropertySummary {
+        mn: chain.mn,
+        monoisotopic_mass: monoisotopic_mass(&chain),
+        formula: molecular_formula(&chain),
+        atom_count: total_atom_count(&chain),
+        tg_infinity,
+        tg: tg_fox_flory(tg_infinity, chain.mn, DEFAULT_FOX_FLORY_K),
+        density: density(&chain, 298.15),
+        end_group_atoms: chain.end_group_atoms(),
+        end_group_mass,
+        water_uptake: water_uptake(&chain),
+        solubility_parameter: solubility_parameter(&chain, 298.15),
+        backbone_atoms: backbone_atom_count(&chain),
+        chain,
+    })
+}
@@ -5,19 +5,21 @@ use rand::rngs::StdRng;
 use crate::{
     error::PolySimError,
     polymer::{Architecture, MonomerUnit, PolymerChain},
-    properties::molecular_weight::{average_mass, monoisotopic_mass},
+    properties::molecular_weight::average_mass,
 };
 
 use super::linear::{
     build_linear_smiles, collect_smiles_segments, max_ring_number, renumber_ring_closures,
-    resolve_n_by_mass,
+    resolve_n_by_mass, saturate_chain_ends,
 };
-use super::strategy::BuildStrategy;
+use super::strategy::{BuildStrategy, RoundingMode};
 
 /// Builder for non-linear polymer architectures (comb, graft, star, dendrimer).
 ///
 /// Unlike [`LinearBuilder`](super::linear::LinearBuilder), this builder takes
 /// two BigSMILES strings: one for the **backbone** and one for the **branch**.
+/// The branch is itself a polymer: each side chain of a comb or graft is its
+/// first repeat unit written [`Self::branch_length`] times (1 by default).
 pub struct BranchedBuilder {
     /// BigSMILES of the backbone chain.
     backbone: BigSmiles,
@@ -27,6 +29,8 @@ pub struct BranchedBuilder {
     strategy: BuildStrategy,
     /// Optional seed for reproducible random placement.
     seed: Option<u64>,
+    /// Number of branch repeat units per side chain.
+    branch_length: usize,
 }
 
 impl BranchedBuilder {
@@ -38,6 +42,7 @@ impl BranchedBuilder {
             branch,
             strategy,
             seed: None,
+            branch_length: 1,
         }
     }
 
@@ -47,9 +52,37 @@ impl BranchedBuilder {
         self
     }
 
+    /// Sets the number of branch repeat units in every side chain (default 1).
+    ///
+    /// The side chains of [`Self::comb_polymer`] and [`Self::graft_copolymer`]
+    /// are then polymeric: the branch repeat unit written `length` times, as a
+    /// linear homopolymer. Their units count in the chain `repeat_count`. The
+    /// build strategy still governs the backbone only.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{branched::BranchedBuilder, BuildStrategy}};
+    ///
+    /// let backbone = parse("{[]CC[]}").unwrap();
+    /// let branch = parse("{[]CC(C)[]}").unwrap();
+    /// let chain = BranchedBuilder::new(backbone, branch, BuildStrategy::ByRepeatCount(4))
+    ///     .branch_length(2)
+    ///     .comb_polymer(2)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chain.smiles, "CCCC(CC(C)CC(C))CCCC(CC(C)CC(C))");
+    /// assert_eq!(chain.repeat_count, 4 + 2 * 2);
+    /// ```
+    pub fn branch_length(mut self, length: usize) -> Self {
+        self.branch_length = length;
+        self
+    }
+
     /// Generates a comb (regularly branched) polymer.
     ///
-    /// `branch_every` -- attach one branch every N backbone repeat units.
+    /// `branch_every` -- attach one branch every N backbone repeat units. Each
+    /// branch holds [`Self::branch_length`] branch repeat units.
     pub fn comb_polymer(&self, branch_every: usize) -> Result<PolymerChain, PolySimError> {
         let backbone_raw = self.first_repeat_unit(&self.backbone, "comb_polymer backbone")?;
         let branch_raw = self.first_repeat_unit(&self.branch, "comb_polymer branch")?;
@@ -61,27 +94,31 @@ impl BranchedBuilder {
             ));
         }
 
-        let smiles = build_comb_smiles(&backbone_raw, &branch_raw, n, branch_every)?;
+        let side_chain = self.side_chain(&branch_raw)?;
+        let smiles = build_comb_smiles(&backbone_raw, &side_chain, n, branch_every)?;
         let smiles = self.with_backbone_end_groups(&smiles);
 
-        let branch_count = (1..=n).filter(|i| i % branch_every == 0).count();
-        let total_units = n + branch_count;
-
-        let chain = PolymerChain::new(smiles, total_units, 0.0);
-        let mn = average_mass(&chain);
+        let branch_count = n.checked_div(branch_every).unwrap_or(0);
+        let branch_units = branch_count * self.branch_length;
+        let total_units = n + branch_units;
 
         let backbone_frac = n as f64 / total_units as f64;
-        let branch_frac = branch_count as f64 / total_units as f64;
+        let branch_frac = branch_units as f64 / total_units as f64;
         let composition = vec![
             MonomerUnit::new(&backbone_raw, backbone_frac),
             MonomerUnit::new(&branch_raw, branch_frac),
         ];
 
-        Ok(PolymerChain::new(chain.smiles, total_units, mn)
+        // The composition goes on first so that `average_mass` can fall back
+        // on it when the parser rejects the branched SMILES.
+        let chain = PolymerChain::new(smiles, total_units, 0.0)
             .with_composition(composition)
             .with_architecture(Architecture::Comb {
                 branch_spacing: branch_every,
-            }))
+            });
+        let mn = average_mass(&chain);
+
+        Ok(PolymerChain { mn, ..chain })
     }
 
     /// Generates a graft copolymer (random branch-point placement).
@@ -90,6 +127,8 @@ impl BranchedBuilder {
     /// (0.0 = no grafting, 1.0 = every backbone unit is grafted).
     ///
     /// `seed` -- optional random seed for reproducibility (overrides builder seed).
+    ///
+    /// Each branch holds [`Self::branch_length`] branch repeat units.
     pub fn graft_copolymer(
         &self,
         graft_fraction: f64,
@@ -105,6 +144,7 @@ impl BranchedBuilder {
             ));
         }
 
+        let side_chain = self.side_chain(&branch_raw)?;
         let effective_seed = seed.or(self.seed);
         let mut rng: Box<dyn RngCore> = match effective_seed {
             Some(s) => Box::new(StdRng::seed_from_u64(s)),
@@ -136,28 +176,31 @@ impl BranchedBuilder {
             let roll: f64 = rng.random();
             if roll < graft_fraction {
                 result.push('(');
-                result.push_str(&branch_raw);
+                result.push_str(&side_chain);
                 result.push(')');
                 branch_count += 1;
             }
         }
 
         let smiles = self.with_backbone_end_groups(&result);
-        let total_units = n + branch_count;
-
-        let chain = PolymerChain::new(smiles, total_units, 0.0);
-        let mn = average_mass(&chain);
+        let branch_units = branch_count * self.branch_length;
+        let total_units = n + branch_units;
 
         let backbone_frac = n as f64 / total_units as f64;
-        let branch_frac = branch_count as f64 / total_units as f64;
+        let branch_frac = branch_units as f64 / total_units as f64;
         let composition = vec![
             MonomerUnit::new(&backbone_raw, backbone_frac),
             MonomerUnit::new(&branch_raw, branch_frac),
         ];
 
-        Ok(PolymerChain::new(chain.smiles, total_units, mn)
+        // The composition goes on first so that `average_mass` can fall back
+        // on it when the parser rejects the branched SMILES.
+        let chain = PolymerChain::new(smiles, total_units, 0.0)
             .with_composition(composition)
-            .with_architecture(Architecture::Graft { graft_fraction }))
+            .with_architecture(Architecture::Graft { graft_fraction });
+        let mn = average_mass(&chain);
+
+        Ok(PolymerChain { mn, ..chain })
     }
 
     /// Generates a star polymer with `arms` arms radiating from a central atom.
@@ -182,8 +225,9 @@ impl BranchedBuilder {
             ));
         }
 
-        // Build each arm SMILES
-        let arm_smiles = build_linear_smiles(&unit_raw, arm_length)?;
+        // Build each arm SMILES; its head is bonded to the hub, its tail is free
+        let arm_smiles =
+            saturate_chain_ends(&build_linear_smiles(&unit_raw, arm_length)?, false, true);
 
         // Star SMILES: C(ARM1)(ARM2)...(ARM_{n-1})ARM_n
         // The central "C" is the hub atom.
@@ -263,17 +307,21 @@ impl BranchedBuilder {
         Ok(stoch.repeat_units[0].smiles_raw.clone())
     }
 
+    /// Builds one side chain: the branch repeat unit written `branch_length` times,
+    /// its free end saturated.
+    fn side_chain(&self, branch_raw: &str) -> Result<String, PolySimError> {
+        if self.branch_length == 0 {
+            return Err(PolySimError::BuildStrategy(
+                "branch length must be >= 1".to_string(),
+            ));
+        }
+        let body = build_linear_smiles(branch_raw, self.branch_length)?;
+        Ok(saturate_chain_ends(&body, false, true))
+    }
+
     /// Resolves repeat count from the build strategy.
     fn resolve_n(&self, smiles_raw: &str) -> Result<usize, PolySimError> {
-        match &self.strategy {
-            BuildStrategy::ByRepeatCount(n) => Ok(*n),
-            BuildStrategy::ByTargetMn(target) => {
-                resolve_n_by_mass(smiles_raw, *target, average_mass)
-            }
-            BuildStrategy::ByExactMass(target) => {
-                resolve_n_by_mass(smiles_raw, *target, monoisotopic_mass)
-            }
-        }
+        resolve_n_by_mass(smiles_raw, &self.strategy, 0.0, 0.0, RoundingMode::Nearest)
     }
 
     /// Prepends prefix and appends suffix SMILES segments from the backbone BigSMILES.
@@ -290,11 +338,11 @@ impl BranchedBuilder {
 
 // --- free functions -----------------------------------------------------------
 
-/// Builds a comb polymer SMILES by inserting `(branch)` after every
+/// Builds a comb polymer SMILES by inserting `(side_chain)` after every
 /// `branch_every`-th backbone unit.
 fn build_comb_smiles(
     backbone_raw: &str,
-    branch_raw: &str,
+    side_chain: &str,
     n: usize,
     branch_every: usize,
 ) -> Result<String, PolySimError> {
@@ -321,7 +369,7 @@ fn build_comb_smiles(
 
         if branch_every > 0 && (i + 1) % branch_every == 0 {
             result.push('(');
-            result.push_str(branch_raw);
+            result.push_str(side_chain);
             result.push(')');
         }
     }
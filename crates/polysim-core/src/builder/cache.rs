@@ -0,0 +1,323 @@
+//! Repeat-unit properties computed once and reused for every chain length.
+
+use std::collections::BTreeMap;
+
+use bigsmiles::BigSmiles;
+
+use crate::{
+    error::PolySimError,
+    polymer::{Architecture, PolymerChain},
+    properties::{
+        formula::{element_counts, format_formula, total_atom_count, FormulaOrder},
+        molecular_weight::{monoisotopic_mass, try_average_mass},
+    },
+};
+
+use super::linear::{build_copolymer_smiles, build_free_linear_smiles};
+
+/// Additive contribution of one repeat unit, or of the two chain ends, to the
+/// composition of a chain.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UnitContribution {
+    /// Average mass (g/mol).
+    pub average_mass: f64,
+    /// Monoisotopic mass (g/mol), NaN if an element has no tabulated isotope.
+    pub monoisotopic_mass: f64,
+    /// Number of atoms, hydrogens included.
+    pub atom_count: i64,
+    /// Number of atoms of each element, hydrogens included.
+    pub elements: BTreeMap<&'static str, i64>,
+}
+
+impl UnitContribution {
+    /// Contribution of a whole molecule, or the error if its SMILES cannot be
+    /// parsed.
+    fn of_smiles(smiles: String) -> Result<Self, PolySimError> {
+        let chain = PolymerChain::new(smiles, 0, 0.0);
+        Ok(Self {
+            average_mass: try_average_mass(&chain)?,
+            monoisotopic_mass: monoisotopic_mass(&chain),
+            atom_count: total_atom_count(&chain) as i64,
+            elements: element_counts(&chain)
+                .into_iter()
+                .map(|(symbol, count)| (symbol, count as i64))
+                .collect(),
+        })
+    }
+
+    /// `self − other`, element by element.
+    fn minus(&self, other: &Self) -> Self {
+        self.combine(other, -1)
+    }
+
+    /// `self + other`, element by element.
+    fn plus(&self, other: &Self) -> Self {
+        self.combine(other, 1)
+    }
+
+    /// `self + factor × other`, element by element.
+    fn combine(&self, other: &Self, factor: i64) -> Self {
+        let mut elements = self.elements.clone();
+        for (symbol, count) in &other.elements {
+            *elements.entry(symbol).or_insert(0) += factor * count;
+        }
+        elements.retain(|_, count| *count != 0);
+        Self {
+            average_mass: self.average_mass + factor as f64 * other.average_mass,
+            monoisotopic_mass: self.monoisotopic_mass + factor as f64 * other.monoisotopic_mass,
+            atom_count: self.atom_count + factor * other.atom_count,
+            elements,
+        }
+    }
+}
+
+/// Per-unit masses, atom counts and element counts of the repeat units of a
+/// BigSMILES, calibrated once.
+///
+/// Each unit contribution comes from the two-point calibration
+/// P(n) = n·P₀ + P_end on chains of one and two units, so the properties of a
+/// chain of any length and sequence follow by multiplication and addition
+/// instead of parsing its SMILES. Chains are the bare, hydrogen-capped chains
+/// of the ensemble builders: begin/end SMILES segments of the BigSMILES and
+/// explicit end groups are not included.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::RepeatUnitCache};
+///
+/// let cache = RepeatUnitCache::new(&parse("{[]CC[]}").unwrap()).unwrap();
+/// // Polyéthylène n = 1000 : C₂₀₀₀H₄₀₀₂, sans construire la chaîne
+/// assert_eq!(cache.molecular_formula(&[1000], 0, 0), "C2000H4002");
+/// assert!((cache.average_mass(&[1000], 0, 0) - 28056.0).abs() < 0.1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepeatUnitCache {
+    units: Vec<UnitContribution>,
+    /// Chain-end contributions, indexed by `head × units.len() + tail`.
+    ends: Vec<UnitContribution>,
+}
+
+impl RepeatUnitCache {
+    /// Calibrates every repeat unit of the first stochastic object.
+    ///
+    /// The chain-end contribution depends on the units at both ends: a unit
+    /// whose end atom is a bracket atom (`[Si]`) takes no capping hydrogen.
+    /// It is calibrated on the free chain of each pair of units.
+    ///
+    /// # Errors
+    ///
+    /// - [`PolySimError::NoStochasticObject`] if the BigSMILES has no stochastic object.
+    /// - [`PolySimError::RepeatUnitCount`] if the stochastic object has no repeat unit.
+    /// - [`PolySimError::RingNumberOverflow`] if a unit uses too many ring closures.
+    pub fn new(bigsmiles: &BigSmiles) -> Result<Self, PolySimError> {
+        let stoch = bigsmiles
+            .first_stochastic()
+            .ok_or(PolySimError::NoStochasticObject)?;
+        if stoch.repeat_units.is_empty() {
+            return Err(PolySimError::RepeatUnitCount {
+                architecture: "repeat-unit cache",
+                got: 0,
+                need_min: 1,
+            });
+        }
+        let units: Vec<&str> = stoch
+            .repeat_units
+            .iter()
+            .map(|fragment| fragment.smiles_raw.as_str())
+            .collect();
+        Self::from_units(&units)
+    }
+
+    /// Calibrates the repeat units `units`, given as SMILES with or without
+    /// bonding descriptors.
+    pub(crate) fn from_units(units: &[&str]) -> Result<Self, PolySimError> {
+        let k = units.len();
+        let mut contributions = Vec::with_capacity(k);
+        let mut own_ends = Vec::with_capacity(k);
+        for &unit in units {
+            let one = UnitContribution::of_smiles(build_free_linear_smiles(unit, 1)?)?;
+            let two = UnitContribution::of_smiles(build_free_linear_smiles(unit, 2)?)?;
+            let contribution = two.minus(&one);
+            own_ends.push(one.minus(&contribution));
+            contributions.push(contribution);
+        }
+
+        let mut ends: Vec<Option<UnitContribution>> = vec![None; k * k];
+        for head in 0..k {
+            for tail in 0..k {
+                ends[head * k + tail] = if head == tail {
+                    Some(own_ends[head].clone())
+                } else if let Ok(smiles) = build_copolymer_smiles(&[units[head], units[tail]]) {
+                    Some(
+                        UnitContribution::of_smiles(smiles)?
+                            .minus(&contributions[head])
+                            .minus(&contributions[tail]),
+                    )
+                } else {
+                    None
+                };
+            }
+        }
+        // Units that cannot bond directly may still meet through a third one:
+        // head(a) + tail(b) = ends(a, c) + ends(c, b) − ends(c, c).
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for pair in 0..k * k {
+                if ends[pair].is_some() {
+                    continue;
+                }
+                let (head, tail) = (pair / k, pair % k);
+                ends[pair] = (0..k).find_map(|via| {
+                    let first = ends[head * k + via].as_ref()?;
+                    let second = ends[via * k + tail].as_ref()?;
+                    Some(first.plus(second).minus(&own_ends[via]))
+                });
+                changed |= ends[pair].is_some();
+            }
+        }
+        // Still unknown: no chain can start with `head` and end with `tail`.
+        let ends = ends
+            .into_iter()
+            .enumerate()
+            .map(|(pair, end)| end.unwrap_or_else(|| own_ends[pair / k].clone()))
+            .collect();
+        Ok(Self {
+            units: contributions,
+            ends,
+        })
+    }
+
+    /// Contribution of each repeat unit, in BigSMILES order.
+    pub fn units(&self) -> &[UnitContribution] {
+        &self.units
+    }
+
+    /// Contribution of the two chain ends of a chain that starts with unit
+    /// `head` and ends with unit `tail`.
+    ///
+    /// # Panics
+    ///
+    /// If `head` or `tail` is not the index of a repeat unit.
+    pub fn ends(&self, head: usize, tail: usize) -> &UnitContribution {
+        let k = self.units.len();
+        assert!(head < k && tail < k, "unit index out of range");
+        &self.ends[head * k + tail]
+    }
+
+    /// Average mass (g/mol) of a chain holding `counts[i]` copies of unit `i`,
+    /// starting with unit `head` and ending with unit `tail`.
+    ///
+    /// Missing trailing counts are zero; extra counts are ignored.
+    pub fn average_mass(&self, counts: &[usize], head: usize, tail: usize) -> f64 {
+        self.ends(head, tail).average_mass + self.sum(counts, |unit| unit.average_mass)
+    }
+
+    /// Monoisotopic mass (g/mol) of a chain holding `counts[i]` copies of unit
+    /// `i`, starting with unit `head` and ending with unit `tail`.
+    pub fn monoisotopic_mass(&self, counts: &[usize], head: usize, tail: usize) -> f64 {
+        self.ends(head, tail).monoisotopic_mass + self.sum(counts, |unit| unit.monoisotopic_mass)
+    }
+
+    /// Number of atoms, hydrogens included, of a chain holding `counts[i]`
+    /// copies of unit `i`, starting with unit `head` and ending with unit `tail`.
+    pub fn atom_count(&self, counts: &[usize], head: usize, tail: usize) -> usize {
+        let total = self.ends(head, tail).atom_count
+            + self
+                .units
+                .iter()
+                .zip(counts)
+                .map(|(unit, &n)| unit.atom_count * n as i64)
+                .sum::<i64>();
+        total.max(0) as usize
+    }
+
+    /// Hill-notation formula of a chain holding `counts[i]` copies of unit `i`,
+    /// starting with unit `head` and ending with unit `tail`.
+    pub fn molecular_formula(&self, counts: &[usize], head: usize, tail: usize) -> String {
+        let mut elements = self.ends(head, tail).elements.clone();
+        for (unit, &n) in self.units.iter().zip(counts) {
+            for (symbol, count) in &unit.elements {
+                *elements.entry(symbol).or_insert(0) += count * n as i64;
+            }
+        }
+        let elements = elements
+            .into_iter()
+            .filter(|&(_, count)| count > 0)
+            .map(|(symbol, count)| (symbol, count as usize))
+            .collect();
+        format_formula(&elements, &FormulaOrder::Hill)
+    }
+
+    /// Contribution of the whole `chain`, estimated from its recorded
+    /// composition without parsing its SMILES.
+    ///
+    /// Unit counts and end units come from `sequence` when it is recorded,
+    /// otherwise from the molar fractions of a chain of `repeat_count` units
+    /// ending with the first unit. A cyclic chain has no ends. Explicit end
+    /// groups and counter-ions are not part of the composition and are left
+    /// out. Returns `None` without composition or if a unit cannot be
+    /// calibrated.
+    pub(crate) fn estimate(chain: &PolymerChain) -> Option<UnitContribution> {
+        if chain.composition.is_empty() {
+            return None;
+        }
+        let units: Vec<&str> = chain
+            .composition
+            .iter()
+            .map(|unit| unit.smiles.as_str())
+            .collect();
+        let cache = Self::from_units(&units).ok()?;
+        let counts: Vec<usize> = match &chain.sequence[..] {
+            [] => chain
+                .composition
+                .iter()
+                .map(|unit| (unit.fraction * chain.repeat_count as f64).round() as usize)
+                .collect(),
+            sequence => (0..units.len())
+                .map(|i| sequence.iter().filter(|&&j| j == i).count())
+                .collect(),
+        };
+        let (head, tail) = match &chain.sequence[..] {
+            [first, .., last] => (*first, *last),
+            [only] => (*only, *only),
+            [] => (0, 0),
+        };
+        if head >= units.len() || tail >= units.len() {
+            return None;
+        }
+        let ends = if chain.architecture == Architecture::Cyclic {
+            UnitContribution::default()
+        } else {
+            cache.ends(head, tail).clone()
+        };
+        Some(
+            cache
+                .units
+                .iter()
+                .zip(&counts)
+                .fold(ends, |total, (unit, &n)| total.combine(unit, n as i64)),
+        )
+    }
+
+    /// Contribution of one `unit` to an additive chain property `value`,
+    /// calibrated like the unit masses: `value` of a free chain of two units
+    /// minus `value` of a single unit.
+    pub(crate) fn unit_increment(
+        unit: &str,
+        value: impl Fn(&PolymerChain) -> f64,
+    ) -> Result<f64, PolySimError> {
+        let one = PolymerChain::new(build_free_linear_smiles(unit, 1)?, 1, 0.0);
+        let two = PolymerChain::new(build_free_linear_smiles(unit, 2)?, 2, 0.0);
+        Ok(value(&two) - value(&one))
+    }
+
+    fn sum(&self, counts: &[usize], value: impl Fn(&UnitContribution) -> f64) -> f64 {
+        self.units
+            .iter()
+            .zip(counts)
+            .map(|(unit, &n)| value(unit) * n as f64)
+            .sum()
+    }
+}
@@ -0,0 +1,127 @@
+//! Matching candidate polymers against a measured elemental analysis.
+
+use std::collections::BTreeMap;
+
+use bigsmiles::parse;
+
+use crate::properties::formula::mass_fractions;
+
+use super::{linear::LinearBuilder, BuildStrategy};
+
+/// Degree of polymerisation at which candidates are built by
+/// [`match_elemental_analysis`]: long enough for end groups to shift the mass
+/// percentages by well under 0.1 %.
+pub const REPRESENTATIVE_DP: usize = 100;
+
+/// Measured mass percentages of an elemental analysis (e.g. CHN combustion).
+///
+/// Only the elements that were measured are set; the others are not compared.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::builder::ElementalComposition;
+///
+/// let measured = ElementalComposition::new().with("C", 92.1).with("H", 7.8);
+/// assert_eq!(measured.percent("C"), Some(92.1));
+/// assert_eq!(measured.percent("N"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ElementalComposition {
+    percentages: BTreeMap<String, f64>,
+}
+
+impl ElementalComposition {
+    /// Creates an empty composition.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the measured mass percentage (0–100) of the element `symbol`.
+    pub fn with(mut self, symbol: &str, percent: f64) -> Self {
+        self.percentages.insert(symbol.to_string(), percent);
+        self
+    }
+
+    /// Measured mass percentage of `symbol`, `None` if it was not measured.
+    pub fn percent(&self, symbol: &str) -> Option<f64> {
+        self.percentages.get(symbol).copied()
+    }
+
+    /// Measured `(symbol, percent)` pairs, sorted by symbol.
+    pub fn elements(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.percentages
+            .iter()
+            .map(|(symbol, &percent)| (symbol.as_str(), percent))
+    }
+}
+
+/// A candidate whose composition agrees with the measured one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementalMatch {
+    /// BigSMILES of the candidate, as given.
+    pub bigsmiles: String,
+    /// Repeat count of the chain the percentages were computed on.
+    pub repeat_count: usize,
+    /// Computed mass percentage of every element of the chain.
+    pub percentages: BTreeMap<&'static str, f64>,
+    /// Largest absolute gap (percentage points) over the measured elements.
+    pub max_deviation: f64,
+}
+
+/// Finds the candidate polymers whose composition matches an elemental analysis.
+///
+/// Each candidate BigSMILES is built as a homopolymer of [`REPRESENTATIVE_DP`]
+/// units and its [`mass_fractions`] are compared, in percent, with every
+/// element of `target`. Candidates within `tol` percentage points on all
+/// measured elements are returned, best match first. Candidates that cannot be
+/// parsed or built as a homopolymer are skipped. Polymers with the same
+/// empirical formula, such as PE and PP, cannot be told apart.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::builder::{match_elemental_analysis, ElementalComposition};
+///
+/// // Measured: C 92.1 %, H 7.8 % (polystyrene, C8H8: C 92.26 %, H 7.74 %)
+/// let measured = ElementalComposition::new().with("C", 92.1).with("H", 7.8);
+/// let candidates = ["{[]CC[]}", "{[]CC(c1ccccc1)[]}", "{[]CC(C)(C(=O)OC)[]}"];
+/// let matches = match_elemental_analysis(&candidates, &measured, 0.3);
+/// assert_eq!(matches.len(), 1);
+/// assert_eq!(matches[0].bigsmiles, "{[]CC(c1ccccc1)[]}");
+/// ```
+pub fn match_elemental_analysis(
+    candidates: &[&str],
+    target: &ElementalComposition,
+    tol: f64,
+) -> Vec<ElementalMatch> {
+    let mut matches: Vec<ElementalMatch> = candidates
+        .iter()
+        .filter_map(|&bigsmiles| {
+            let chain = LinearBuilder::new(
+                parse(bigsmiles).ok()?,
+                BuildStrategy::ByRepeatCount(REPRESENTATIVE_DP),
+            )
+            .homopolymer()
+            .ok()?;
+            let percentages: BTreeMap<&'static str, f64> = mass_fractions(&chain)
+                .into_iter()
+                .map(|(symbol, fraction)| (symbol, fraction * 100.0))
+                .collect();
+            let max_deviation = target
+                .elements()
+                .map(|(symbol, measured)| {
+                    (percentages.get(symbol).copied().unwrap_or(0.0) - measured).abs()
+                })
+                .fold(0.0, f64::max);
+            (max_deviation <= tol).then(|| ElementalMatch {
+                bigsmiles: bigsmiles.to_string(),
+                repeat_count: chain.repeat_count,
+                percentages,
+                max_deviation,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| a.max_deviation.total_cmp(&b.max_deviation));
+    matches
+}
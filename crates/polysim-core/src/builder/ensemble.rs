@@ -7,11 +7,11 @@ use crate::{
     distribution::ChainLengthDistribution,
     error::PolySimError,
     polymer::{PolymerChain, PolymerEnsemble},
-    properties::molecular_weight::average_mass,
 };
 
+use super::cache::RepeatUnitCache;
 use super::linear::{
-    build_copolymer_smiles, build_linear_smiles, gradient_fraction, GradientProfile,
+    build_copolymer_smiles, build_free_linear_smiles, gradient_fraction, GradientProfile,
 };
 
 /// Default number of chains in an ensemble.
@@ -25,6 +25,8 @@ pub struct EnsembleBuilder<D: ChainLengthDistribution> {
     pdi: f64,
     num_chains: usize,
     seed: Option<u64>,
+    exact_mn: bool,
+    progress: Option<Box<dyn Fn(usize, usize)>>,
 }
 
 impl<D: ChainLengthDistribution> EnsembleBuilder<D> {
@@ -42,6 +44,8 @@ impl<D: ChainLengthDistribution> EnsembleBuilder<D> {
             pdi,
             num_chains: DEFAULT_NUM_CHAINS,
             seed: None,
+            exact_mn: false,
+            progress: None,
         }
     }
 
@@ -57,6 +61,23 @@ impl<D: ChainLengthDistribution> EnsembleBuilder<D> {
         self
     }
 
+    /// Rescales the sampled chain lengths so that the ensemble Mn matches the
+    /// target instead of fluctuating with the finite number of chains.
+    ///
+    /// All lengths are multiplied by the same factor, which leaves the
+    /// dispersity unchanged up to rounding (default: off).
+    pub fn exact_mn(mut self, exact: bool) -> Self {
+        self.exact_mn = exact;
+        self
+    }
+
+    /// Registers a callback invoked after each chain is built with
+    /// `(chains_built, total_chains)`, e.g. to drive a progress bar.
+    pub fn on_progress(mut self, callback: impl Fn(usize, usize) + 'static) -> Self {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
     /// Build a polydisperse ensemble of homopolymer chains.
     ///
     /// # Errors
@@ -82,44 +103,22 @@ impl<D: ChainLengthDistribution> EnsembleBuilder<D> {
 
         // Two-point calibration to separate repeat-unit mass (m0) from
         // end-group mass (m_end): MW(n) = n × m0 + m_end.
-        let mw1 = average_mass(&PolymerChain::new(
-            build_linear_smiles(smiles_raw, 1)?,
-            1,
-            0.0,
-        ));
-        let mw2 = average_mass(&PolymerChain::new(
-            build_linear_smiles(smiles_raw, 2)?,
-            2,
-            0.0,
-        ));
-        let m0 = mw2 - mw1;
-        let m_end = mw1 - m0;
+        let cache = RepeatUnitCache::new(&self.bigsmiles)?;
+        let m0 = cache.units()[0].average_mass;
+        let m_end = cache.ends(0, 0).average_mass;
 
         // Adjust target Mn to account for end groups: Mn_target = Xn × m0 + m_end
         let target_mn_corrected = self.mn - m_end;
 
         // Sample chain lengths from distribution.
         let mut rng = self.make_rng();
-        let lengths = self.distribution.sample(
-            target_mn_corrected,
-            self.pdi,
-            m0,
-            self.num_chains,
-            &mut *rng,
-        );
+        let lengths = self.sample_lengths(target_mn_corrected, m0, &mut *rng);
 
         // Build each chain.
-        let chains: Result<Vec<PolymerChain>, PolySimError> = lengths
-            .into_iter()
-            .map(|n| {
-                let smiles = build_linear_smiles(smiles_raw, n)?;
-                let chain = PolymerChain::new(smiles, n, 0.0);
-                let mn = average_mass(&chain);
-                Ok(PolymerChain::new(chain.smiles, n, mn))
-            })
-            .collect();
-
-        PolymerEnsemble::new(chains?)
+        self.collect_chains(lengths.into_iter().map(|n| {
+            let smiles = build_free_linear_smiles(smiles_raw, n)?;
+            Ok(PolymerChain::new(smiles, n, cache.average_mass(&[n], 0, 0)))
+        }))
     }
 
     /// Build a polydisperse ensemble of random copolymer chains.
@@ -134,33 +133,29 @@ impl<D: ChainLengthDistribution> EnsembleBuilder<D> {
             return Err(PolySimError::InvalidFractions { sum });
         }
 
-        let (units, m0_avg, m_end) = self.copolymer_calibration(fractions)?;
+        let (units, cache, m0_avg) = self.copolymer_calibration(fractions)?;
 
+        // Expected chain ends: both end units are drawn with the same weights.
+        let m_end = expected_end_mass(&cache, fractions, fractions);
         let target_mn_corrected = self.mn - m_end;
         let mut rng = self.make_rng();
-        let lengths = self.distribution.sample(
-            target_mn_corrected,
-            self.pdi,
-            m0_avg,
-            self.num_chains,
-            &mut *rng,
-        );
+        let lengths = self.sample_lengths(target_mn_corrected, m0_avg, &mut *rng);
 
         let dist = WeightedIndex::new(fractions)
             .map_err(|e| PolySimError::BuildStrategy(format!("invalid weight fractions: {e}")))?;
 
-        let chains: Result<Vec<PolymerChain>, PolySimError> = lengths
-            .into_iter()
-            .map(|n| {
-                let sequence: Vec<&str> = (0..n).map(|_| units[dist.sample(&mut *rng)]).collect();
-                let smiles = build_copolymer_smiles(&sequence)?;
-                let chain = PolymerChain::new(smiles, n, 0.0);
-                let mn = average_mass(&chain);
-                Ok(PolymerChain::new(chain.smiles, n, mn))
-            })
-            .collect();
-
-        PolymerEnsemble::new(chains?)
+        self.collect_chains(lengths.into_iter().map(|n| {
+            let mut counts = vec![0; units.len()];
+            let indices: Vec<usize> = (0..n).map(|_| dist.sample(&mut *rng)).collect();
+            for &i in &indices {
+                counts[i] += 1;
+            }
+            let sequence: Vec<&str> = indices.iter().map(|&i| units[i]).collect();
+            let smiles = build_copolymer_smiles(&sequence)?;
+            let (head, tail) = end_units(&indices);
+            let mn = cache.average_mass(&counts, head, tail);
+            Ok(PolymerChain::new(smiles, n, mn))
+        }))
     }
 
     /// Build a polydisperse ensemble of alternating copolymer chains.
@@ -185,42 +180,25 @@ impl<D: ChainLengthDistribution> EnsembleBuilder<D> {
             .collect();
         let k = units.len();
 
-        // Calibrate using one full cycle as the "composite repeat unit".
-        let cycle_smiles: Vec<&str> = units.to_vec();
-        let cycle1 = build_copolymer_smiles(&cycle_smiles)?;
-        let cycle2_seq: Vec<&str> = units.iter().chain(units.iter()).copied().collect();
-        let cycle2 = build_copolymer_smiles(&cycle2_seq)?;
-
-        let mw1 = average_mass(&PolymerChain::new(cycle1, k, 0.0));
-        let mw2 = average_mass(&PolymerChain::new(cycle2, k * 2, 0.0));
-        let m0_cycle = mw2 - mw1; // mass per full cycle
-        let m_end = mw1 - m0_cycle;
+        // One full cycle acts as the "composite repeat unit".
+        let cache = RepeatUnitCache::new(&self.bigsmiles)?;
+        let m0_cycle: f64 = cache.units().iter().map(|unit| unit.average_mass).sum();
+        let m_end = cache.ends(0, k - 1).average_mass;
 
         let target_mn_corrected = self.mn - m_end;
         // m0 for distribution = mass per individual unit (average)
         let m0_per_unit = m0_cycle / k as f64;
 
         let mut rng = self.make_rng();
-        let lengths = self.distribution.sample(
-            target_mn_corrected,
-            self.pdi,
-            m0_per_unit,
-            self.num_chains,
-            &mut *rng,
-        );
-
-        let chains: Result<Vec<PolymerChain>, PolySimError> = lengths
-            .into_iter()
-            .map(|n| {
-                let sequence: Vec<&str> = (0..n).map(|i| units[i % k]).collect();
-                let smiles = build_copolymer_smiles(&sequence)?;
-                let chain = PolymerChain::new(smiles, n, 0.0);
-                let mn = average_mass(&chain);
-                Ok(PolymerChain::new(chain.smiles, n, mn))
-            })
-            .collect();
-
-        PolymerEnsemble::new(chains?)
+        let lengths = self.sample_lengths(target_mn_corrected, m0_per_unit, &mut *rng);
+
+        self.collect_chains(lengths.into_iter().map(|n| {
+            let sequence: Vec<&str> = (0..n).map(|i| units[i % k]).collect();
+            let counts: Vec<usize> = (0..k).map(|i| n / k + usize::from(i < n % k)).collect();
+            let smiles = build_copolymer_smiles(&sequence)?;
+            let mn = cache.average_mass(&counts, 0, n.saturating_sub(1) % k);
+            Ok(PolymerChain::new(smiles, n, mn))
+        }))
     }
 
     /// Build a polydisperse ensemble of block copolymer chains.
@@ -264,51 +242,36 @@ impl<D: ChainLengthDistribution> EnsembleBuilder<D> {
             .collect();
 
         // Calibrate: weighted average mass per unit
-        let mut unit_masses = Vec::with_capacity(units.len());
-        let mut m_end_sum = 0.0;
-        for &unit in &units {
-            let mw1 = average_mass(&PolymerChain::new(build_linear_smiles(unit, 1)?, 1, 0.0));
-            let mw2 = average_mass(&PolymerChain::new(build_linear_smiles(unit, 2)?, 2, 0.0));
-            let m0 = mw2 - mw1;
-            unit_masses.push(m0);
-            m_end_sum += mw1 - m0;
-        }
-        let m_end = m_end_sum / units.len() as f64;
+        let cache = RepeatUnitCache::new(&self.bigsmiles)?;
+        let k = units.len();
+        let m_end = cache.ends(0, k - 1).average_mass;
         let m0_avg: f64 = block_ratios
             .iter()
-            .zip(unit_masses.iter())
-            .map(|(r, m)| r * m)
+            .zip(cache.units())
+            .map(|(r, unit)| r * unit.average_mass)
             .sum();
 
         let target_mn_corrected = self.mn - m_end;
         let mut rng = self.make_rng();
-        let lengths = self.distribution.sample(
-            target_mn_corrected,
-            self.pdi,
-            m0_avg,
-            self.num_chains,
-            &mut *rng,
-        );
-
-        let chains: Result<Vec<PolymerChain>, PolySimError> = lengths
-            .into_iter()
-            .map(|n| {
-                // Distribute n across blocks proportionally to ratios
-                let block_lengths: Vec<usize> = distribute_n_by_ratios(n, block_ratios);
-                let sequence: Vec<&str> = block_lengths
-                    .iter()
-                    .zip(units.iter())
-                    .flat_map(|(&len, &unit)| std::iter::repeat_n(unit, len))
-                    .collect();
-                let smiles = build_copolymer_smiles(&sequence)?;
-                let total = sequence.len();
-                let chain = PolymerChain::new(smiles, total, 0.0);
-                let mn = average_mass(&chain);
-                Ok(PolymerChain::new(chain.smiles, total, mn))
-            })
-            .collect();
-
-        PolymerEnsemble::new(chains?)
+        let lengths = self.sample_lengths(target_mn_corrected, m0_avg, &mut *rng);
+
+        self.collect_chains(lengths.into_iter().map(|n| {
+            // Distribute n across blocks proportionally to ratios
+            let block_lengths: Vec<usize> = distribute_n_by_ratios(n, block_ratios);
+            let sequence: Vec<&str> = block_lengths
+                .iter()
+                .zip(units.iter())
+                .flat_map(|(&len, &unit)| std::iter::repeat_n(unit, len))
+                .collect();
+            let smiles = build_copolymer_smiles(&sequence)?;
+            let head = block_lengths.iter().position(|&len| len > 0).unwrap_or(0);
+            let tail = block_lengths
+                .iter()
+                .rposition(|&len| len > 0)
+                .unwrap_or(k - 1);
+            let mn = cache.average_mass(&block_lengths, head, tail);
+            Ok(PolymerChain::new(smiles, sequence.len(), mn))
+        }))
     }
 
     /// Build a polydisperse ensemble of gradient copolymer chains.
@@ -339,20 +302,17 @@ impl<D: ChainLengthDistribution> EnsembleBuilder<D> {
             .collect();
 
         // Calibrate mass per unit for each type
-        let mut unit_masses = Vec::with_capacity(2);
-        let mut m_end_sum = 0.0;
-        for &unit in &units {
-            let mw1 = average_mass(&PolymerChain::new(build_linear_smiles(unit, 1)?, 1, 0.0));
-            let mw2 = average_mass(&PolymerChain::new(build_linear_smiles(unit, 2)?, 2, 0.0));
-            let m0 = mw2 - mw1;
-            unit_masses.push(m0);
-            m_end_sum += mw1 - m0;
-        }
-        let m_end = m_end_sum / 2.0;
+        let cache = RepeatUnitCache::new(&self.bigsmiles)?;
+        let unit_masses = [cache.units()[0].average_mass, cache.units()[1].average_mass];
+        let n_sample = 100usize;
+        let fraction_at = |i: usize| {
+            let f_a = gradient_fraction(profile, i, n_sample);
+            [f_a, 1.0 - f_a]
+        };
+        let m_end = expected_end_mass(&cache, &fraction_at(0), &fraction_at(n_sample - 1));
 
         // Average mass per unit using the mean gradient fraction across the chain
         // For a chain of variable length, approximate with 100-point average.
-        let n_sample = 100usize;
         let avg_f_a: f64 = (0..n_sample)
             .map(|i| gradient_fraction(profile, i, n_sample))
             .sum::<f64>()
@@ -361,40 +321,61 @@ impl<D: ChainLengthDistribution> EnsembleBuilder<D> {
 
         let target_mn_corrected = self.mn - m_end;
         let mut rng = self.make_rng();
-        let lengths = self.distribution.sample(
-            target_mn_corrected,
-            self.pdi,
-            m0_avg,
-            self.num_chains,
-            &mut *rng,
-        );
-
-        let chains: Result<Vec<PolymerChain>, PolySimError> = lengths
-            .into_iter()
-            .map(|n| {
-                let sequence: Vec<&str> = (0..n)
-                    .map(|i| {
-                        let f_a = gradient_fraction(profile, i, n);
-                        let pick: f64 = rng.random();
-                        if pick < f_a {
-                            units[0]
-                        } else {
-                            units[1]
-                        }
-                    })
-                    .collect();
-                let smiles = build_copolymer_smiles(&sequence)?;
-                let chain = PolymerChain::new(smiles, n, 0.0);
-                let mn = average_mass(&chain);
-                Ok(PolymerChain::new(chain.smiles, n, mn))
-            })
-            .collect();
-
-        PolymerEnsemble::new(chains?)
+        let lengths = self.sample_lengths(target_mn_corrected, m0_avg, &mut *rng);
+
+        self.collect_chains(lengths.into_iter().map(|n| {
+            let mut counts = [0; 2];
+            let indices: Vec<usize> = (0..n)
+                .map(|i| {
+                    let f_a = gradient_fraction(profile, i, n);
+                    let pick: f64 = rng.random();
+                    let unit = usize::from(pick >= f_a);
+                    counts[unit] += 1;
+                    unit
+                })
+                .collect();
+            let sequence: Vec<&str> = indices.iter().map(|&i| units[i]).collect();
+            let smiles = build_copolymer_smiles(&sequence)?;
+            let (head, tail) = end_units(&indices);
+            let mn = cache.average_mass(&counts, head, tail);
+            Ok(PolymerChain::new(smiles, n, mn))
+        }))
     }
 
     // --- private helpers ---
 
+    /// Builds the chains one by one, reporting progress after each of them.
+    fn collect_chains(
+        &self,
+        chains: impl ExactSizeIterator<Item = Result<PolymerChain, PolySimError>>,
+    ) -> Result<PolymerEnsemble, PolySimError> {
+        let total = chains.len();
+        let mut built = Vec::with_capacity(total);
+        for chain in chains {
+            built.push(chain?);
+            if let Some(progress) = &self.progress {
+                progress(built.len(), total);
+            }
+        }
+        PolymerEnsemble::new(built)
+    }
+
+    /// Samples the chain lengths, rescaled to the target Mn when `exact_mn` is set.
+    fn sample_lengths(&self, mn: f64, m0: f64, rng: &mut dyn rand::RngCore) -> Vec<usize> {
+        let lengths = self
+            .distribution
+            .sample(mn, self.pdi, m0, self.num_chains, rng);
+        if !self.exact_mn || lengths.is_empty() {
+            return lengths;
+        }
+        let mean = lengths.iter().sum::<usize>() as f64 / lengths.len() as f64;
+        let scale = mn / m0 / mean;
+        lengths
+            .into_iter()
+            .map(|n| ((n as f64 * scale).round() as usize).max(1))
+            .collect()
+    }
+
     fn make_rng(&self) -> Box<dyn rand::RngCore> {
         match self.seed {
             Some(s) => Box::new(StdRng::seed_from_u64(s)),
@@ -406,7 +387,7 @@ impl<D: ChainLengthDistribution> EnsembleBuilder<D> {
     fn copolymer_calibration(
         &self,
         fractions: &[f64],
-    ) -> Result<(Vec<&str>, f64, f64), PolySimError> {
+    ) -> Result<(Vec<&str>, RepeatUnitCache, f64), PolySimError> {
         let stoch = self
             .bigsmiles
             .first_stochastic()
@@ -434,24 +415,35 @@ impl<D: ChainLengthDistribution> EnsembleBuilder<D> {
             .map(|f| f.smiles_raw.as_str())
             .collect();
 
-        let mut unit_masses = Vec::with_capacity(units.len());
-        let mut m_end_sum = 0.0;
-        for &unit in &units {
-            let mw1 = average_mass(&PolymerChain::new(build_linear_smiles(unit, 1)?, 1, 0.0));
-            let mw2 = average_mass(&PolymerChain::new(build_linear_smiles(unit, 2)?, 2, 0.0));
-            let m0 = mw2 - mw1;
-            unit_masses.push(m0);
-            m_end_sum += mw1 - m0;
-        }
-
-        let m_end = m_end_sum / units.len() as f64;
+        let cache = RepeatUnitCache::new(&self.bigsmiles)?;
         let m0_avg: f64 = fractions
             .iter()
-            .zip(unit_masses.iter())
-            .map(|(f, m)| f * m)
+            .zip(cache.units())
+            .map(|(f, unit)| f * unit.average_mass)
             .sum();
 
-        Ok((units, m0_avg, m_end))
+        Ok((units, cache, m0_avg))
+    }
+}
+
+/// Mean chain-end mass when the head unit is drawn with weights `head` and
+/// the tail unit with weights `tail`.
+fn expected_end_mass(cache: &RepeatUnitCache, head: &[f64], tail: &[f64]) -> f64 {
+    let mut mass = 0.0;
+    for (i, wi) in head.iter().enumerate() {
+        for (j, wj) in tail.iter().enumerate() {
+            mass += wi * wj * cache.ends(i, j).average_mass;
+        }
+    }
+    mass
+}
+
+/// Indices of the first and last units of a sequence, `(0, 0)` if it is empty.
+fn end_units(indices: &[usize]) -> (usize, usize) {
+    match indices {
+        [] => (0, 0),
+        [first, .., last] => (*first, *last),
+        [only] => (*only, *only),
     }
 }
 
@@ -0,0 +1,99 @@
+//! Identification of a chain against a small table of common polymers.
+
+use std::collections::BTreeMap;
+
+use crate::polymer::PolymerChain;
+use crate::properties::groups::{decompose, GroupName};
+use crate::properties::reference::{KnownPolymer, KNOWN_POLYMERS};
+use crate::properties::similarity::weighted_tanimoto;
+
+use super::linear::{build_free_linear_smiles, cap_dangling_bonds};
+
+/// Minimum similarity for a match to be reported by [`closest_known`].
+pub const MIN_SIMILARITY: f64 = 0.5;
+
+/// Finds the common polymer that most resembles `chain`.
+///
+/// The repeat unit recorded in the composition of `chain` is compared with each
+/// entry of the built-in table (PE, PP, PS, PMMA, PVC, PET, Nylon-6, PTFE), so
+/// chain length and end groups play no part. An identical
+/// canonical repeat unit, attachment points included, scores 1.0; otherwise
+/// the score is the Tanimoto similarity of the structural groups of one repeat
+/// unit, capped just below 1.0. Returns the name and score of the best entry,
+/// or `None` for chains with other than one repeat unit or if no entry reaches
+/// [`MIN_SIMILARITY`].
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::builder::identify::closest_known;
+///
+/// let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+/// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(10))
+///     .homopolymer()
+///     .unwrap();
+/// assert_eq!(closest_known(&chain), Some(("Polystyrene", 1.0)));
+/// ```
+pub fn closest_known(chain: &PolymerChain) -> Option<(&'static str, f64)> {
+    best_match(chain).map(|(known, score)| (known.name, score))
+}
+
+/// Resin identification code of the closest known polymer, with its score.
+///
+/// Same matching as [`closest_known`]. Polyethylene is reported as HDPE (2):
+/// the table does not tell linear from branched PE.
+pub(crate) fn closest_resin_code(chain: &PolymerChain) -> Option<(u8, f64)> {
+    best_match(chain).map(|(known, score)| (known.resin_code, score))
+}
+
+fn best_match(chain: &PolymerChain) -> Option<(&'static KnownPolymer, f64)> {
+    let [unit] = &chain.composition[..] else {
+        return None;
+    };
+    let canonical = canonical_unit(&unit.smiles);
+    let groups = unit_groups(&unit.smiles)?;
+
+    KNOWN_POLYMERS
+        .iter()
+        .filter_map(|known| {
+            let score = if canonical_unit(known.repeat_unit) == canonical {
+                1.0
+            } else {
+                weighted_tanimoto(&groups, &unit_groups(known.repeat_unit)?).min(0.99)
+            };
+            Some((known, score))
+        })
+        .filter(|&(_, score)| score >= MIN_SIMILARITY)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Canonical SMILES of a repeat unit with its head and tail bonds written as
+/// `*`, so that units with the same atoms but other attachment points (`CC(C)`
+/// and `CCC`) differ.
+fn canonical_unit(unit: &str) -> String {
+    PolymerChain::new(format!("*{}*", cap_dangling_bonds(unit)), 1, 0.0).canonical_smiles()
+}
+
+/// Structural groups of one repeat unit: the groups of a free chain of two
+/// units minus those of a single unit, so the end groups cancel out.
+fn unit_groups(unit: &str) -> Option<BTreeMap<GroupName, f64>> {
+    let chain = |n| {
+        build_free_linear_smiles(unit, n)
+            .ok()
+            .map(|smiles| PolymerChain::new(smiles, n, 0.0))
+    };
+    let mut groups = group_counts(&chain(2)?);
+    for (group, count) in group_counts(&chain(1)?) {
+        *groups.entry(group).or_insert(0.0) -= count;
+    }
+    groups.retain(|_, count| *count > 0.0);
+    Some(groups)
+}
+
+fn group_counts(chain: &PolymerChain) -> BTreeMap<GroupName, f64> {
+    decompose(chain)
+        .into_iter()
+        .map(|(group, count)| (group, count as f64))
+        .collect()
+}
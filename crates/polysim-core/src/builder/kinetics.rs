@@ -0,0 +1,58 @@
+//! Monomer conversion needed to reach a degree of polymerisation.
+//!
+//! Complements [`dp_from_mn`](super::dp_from_mn): once the DP of a sample is
+//! known, [`conversion_from_dp`] gives the conversion at which it is reached.
+
+/// Polymerisation mechanism, which sets how DP grows with conversion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StepOrChain {
+    /// Step growth of a stoichiometric A–B or AA/BB system (Carothers):
+    /// DP = 1 / (1 − p).
+    StepGrowth,
+    /// Living chain growth, one chain per initiator: DP = p × [M]₀ / [I]₀.
+    ChainGrowth {
+        /// Initial monomer-to-initiator ratio [M]₀ / [I]₀.
+        monomer_to_initiator: f64,
+    },
+}
+
+/// Returns the monomer conversion p (0–1) at which the number-average degree
+/// of polymerisation reaches `dp`.
+///
+/// - Step growth (Carothers): p = 1 − 1/DP.
+/// - Living chain growth: p = DP × [I]₀ / [M]₀.
+///
+/// Returns `f64::NAN` when `dp` is below 1 or cannot be reached, i.e. when it
+/// exceeds [M]₀/[I]₀ in living chain growth.
+///
+/// # Reference
+///
+/// Carothers, W. H. (1936). Polymers and polyfunctionality.
+/// *Trans. Faraday Soc.*, 32, 39–49.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::builder::{conversion_from_dp, StepOrChain};
+///
+/// assert!((conversion_from_dp(20.0, StepOrChain::StepGrowth) - 0.95).abs() < 1e-12);
+///
+/// let living = StepOrChain::ChainGrowth { monomer_to_initiator: 200.0 };
+/// assert!((conversion_from_dp(150.0, living) - 0.75).abs() < 1e-12);
+/// ```
+pub fn conversion_from_dp(dp: f64, architecture: StepOrChain) -> f64 {
+    if dp.is_nan() || dp < 1.0 {
+        return f64::NAN;
+    }
+    let p = match architecture {
+        StepOrChain::StepGrowth => 1.0 - 1.0 / dp,
+        StepOrChain::ChainGrowth {
+            monomer_to_initiator,
+        } => dp / monomer_to_initiator,
+    };
+    if (0.0..=1.0).contains(&p) {
+        p
+    } else {
+        f64::NAN
+    }
+}
@@ -1,3 +1,6 @@
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::io::Write;
+
 use bigsmiles::{BigSmiles, BigSmilesSegment};
 use rand::distr::weighted::WeightedIndex;
 use rand::prelude::*;
@@ -6,10 +9,18 @@ use rand::rngs::StdRng;
 use crate::{
     error::PolySimError,
     polymer::{Architecture, MonomerUnit, PolymerChain},
-    properties::molecular_weight::{average_mass, monoisotopic_mass},
+    properties::{
+        conformation::radius_of_gyration,
+        formula::total_atom_count,
+        graph::{default_valences, element_symbol, BondOrder, MolGraph},
+        molecular_weight::{
+            average_mass, monoisotopic_mass, try_average_mass, try_monoisotopic_mass,
+        },
+    },
 };
 
-use super::strategy::BuildStrategy;
+use super::cache::{RepeatUnitCache, UnitContribution};
+use super::strategy::{BuildStrategy, RoundingMode};
 
 /// Gradient composition profile for gradient copolymers.
 #[derive(Debug, Clone)]
@@ -20,14 +31,33 @@ pub enum GradientProfile {
     Sigmoid { f_start: f64, f_end: f64 },
 }
 
+/// Configuration of the backbone double bonds set by
+/// [`LinearBuilder::homopolymer_with_geometry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoubleBondGeometry {
+    /// Backbone neighbours on the same side (`C/C=C\C`), as in natural rubber.
+    Cis,
+    /// Backbone neighbours on opposite sides (`C/C=C/C`), as in gutta-percha.
+    Trans,
+    /// Cis or trans drawn independently for every double bond.
+    Random,
+}
+
 /// Builder for linear polymer architectures.
 ///
 /// Supports homopolymers, random/alternating/block copolymers — all derived
 /// from a single BigSMILES string.
+#[derive(Clone)]
 pub struct LinearBuilder {
     bigsmiles: BigSmiles,
     strategy: BuildStrategy,
     seed: Option<u64>,
+    head: Option<String>,
+    tail: Option<String>,
+    ring_offset: u32,
+    counterions: bool,
+    vinyl_end: bool,
+    rounding: RoundingMode,
 }
 
 impl LinearBuilder {
@@ -37,6 +67,12 @@ impl LinearBuilder {
             bigsmiles,
             strategy,
             seed: None,
+            head: None,
+            tail: None,
+            ring_offset: 0,
+            counterions: false,
+            vinyl_end: false,
+            rounding: RoundingMode::Nearest,
         }
     }
 
@@ -46,6 +82,146 @@ impl LinearBuilder {
         self
     }
 
+    /// Sets explicit head (initiator) and tail (terminator) end groups.
+    ///
+    /// `head` is prepended and `tail` appended to the chain as SMILES fragments,
+    /// replacing any begin/end SMILES segments of the BigSMILES. An empty string
+    /// leaves the corresponding end unchanged. Their atoms are included in the
+    /// mass and formula of the built chain; ring-closure numbers are shifted past
+    /// those of the chain body when possible so the fragments stay readable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    ///
+    /// // Anionic polystyrene: sec-butyllithium initiator, protonated chain end
+    /// let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+    /// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(2))
+    ///     .with_end_groups("CCC(C)", "")
+    ///     .homopolymer()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chain.smiles, "CCC(C)CC(c1ccccc1)CC(c2ccccc2)");
+    /// ```
+    pub fn with_end_groups(mut self, head: &str, tail: &str) -> Self {
+        self.head = (!head.is_empty()).then(|| head.to_string());
+        self.tail = (!tail.is_empty()).then(|| tail.to_string());
+        self
+    }
+
+    /// Reserves ring-closure numbers `1..=offset` for the end groups.
+    ///
+    /// Repeat units are then numbered from `offset + 1` (still cycling up to 99)
+    /// and the end groups keep their own numbers unchanged, so an initiator with
+    /// ring closures such as pyrene reads as written. Without an offset the end
+    /// groups are shifted past the body instead (see [`Self::with_end_groups`]).
+    ///
+    /// Building fails with [`PolySimError::RingNumberOverflow`] if `offset` plus
+    /// the ring closures of one repeat unit exceeds 99.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    ///
+    /// let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+    /// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(2))
+    ///     .with_end_groups("c1ccccc1", "")
+    ///     .ring_offset(1)
+    ///     .homopolymer()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chain.smiles, "c1ccccc1CC(c2ccccc2)CC(c3ccccc3)");
+    /// ```
+    pub fn ring_offset(mut self, offset: u32) -> Self {
+        self.ring_offset = offset;
+        self
+    }
+
+    /// Neutralises charged chains with monovalent counter-ions.
+    ///
+    /// When enabled, one `[Na+]` per net negative charge, or one `[Cl-]` per net
+    /// positive charge, of the built chain (end groups included) is appended as
+    /// a separate `.`-fragment, so they count in the mass and the formula. The
+    /// number added is recorded in [`PolymerChain::counterion_count`]. Target-mass
+    /// strategies resolve *n* on the neutralised chain, counter-ions included.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    ///
+    /// // Poly(styrene sulfonate), sodium salt
+    /// let bs = parse("{[]CC(c1ccc(cc1)S(=O)(=O)[O-])[]}").unwrap();
+    /// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(2))
+    ///     .counterions(true)
+    ///     .homopolymer()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chain.counterion_count, 2);
+    /// assert!(chain.smiles.ends_with(".[Na+].[Na+]"));
+    /// ```
+    pub fn counterions(mut self, include: bool) -> Self {
+        self.counterions = include;
+        self
+    }
+
+    /// Ends the chain with a terminal double bond, as in macromonomers.
+    ///
+    /// `=C` is appended to the last backbone atom in place of the tail end group
+    /// (explicit or from the BigSMILES), so the chain carries one degree of
+    /// unsaturation: two hydrogens fewer than the same chain capped with a
+    /// methyl. The last backbone atom must have at least two hydrogens to give
+    /// up; otherwise building fails with [`PolySimError::BuildStrategy`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    ///
+    /// let bs = parse("{[]CC[]}").unwrap();
+    /// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3))
+    ///     .vinyl_terminated()
+    ///     .homopolymer()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chain.smiles, "CCCCCC=C"); // 1-heptene
+    /// ```
+    pub fn vinyl_terminated(mut self) -> Self {
+        self.vinyl_end = true;
+        self
+    }
+
+    /// Sets how a target-mass strategy rounds the matching repeat count.
+    ///
+    /// [`RoundingMode::Floor`] never exceeds the target and
+    /// [`RoundingMode::Ceil`] never falls below it; the default,
+    /// [`RoundingMode::Nearest`], picks the closest chain. Applies to
+    /// [`Self::homopolymer`], [`Self::gradient_copolymer`] and
+    /// [`Self::cyclic`]; [`BuildStrategy::ByRepeatCount`] is exact
+    /// and unaffected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy, RoundingMode}};
+    ///
+    /// // PE: Mn(n) = 28.05·n + 2.02, so 300 g/mol lies between n = 10 and n = 11
+    /// let bs = parse("{[]CC[]}").unwrap();
+    /// let chain = LinearBuilder::new(bs, BuildStrategy::ByTargetMn(300.0))
+    ///     .rounding(RoundingMode::Floor)
+    ///     .homopolymer()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chain.repeat_count, 10);
+    /// assert!(chain.mn <= 300.0);
+    /// ```
+    pub fn rounding(mut self, mode: RoundingMode) -> Self {
+        self.rounding = mode;
+        self
+    }
+
     /// Generates a linear homopolymer (single repeat unit, repeated *n* times).
     ///
     /// # Errors
@@ -70,6 +246,131 @@ impl LinearBuilder {
     /// assert_eq!(chain.repeat_count, 3);
     /// ```
     pub fn homopolymer(&self) -> Result<PolymerChain, PolySimError> {
+        let (smiles_raw, n) = self.homopolymer_unit()?;
+        let body = LinearTemplate::with_base(smiles_raw, n, self.ring_offset)?.build(n);
+        let (smiles, counterions) = self.assemble(&body)?;
+        let chain = PolymerChain::new(smiles, n, 0.0)
+            .with_composition(vec![MonomerUnit::new(
+                normalize_repeat_unit(smiles_raw),
+                1.0,
+            )])
+            .with_counterion_count(counterions);
+        let mn = self.chain_mn(&chain, smiles_raw)?;
+        Ok(PolymerChain { mn, ..chain })
+    }
+
+    /// Streams the SMILES of the homopolymer built by [`Self::homopolymer`] to `w`.
+    ///
+    /// Each renumbered repeat unit is written as soon as it is produced, so the
+    /// full chain SMILES is never held in memory; use this for very long chains
+    /// that only go to a file. Returns the number of repeat units written.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::homopolymer`], plus [`PolySimError::Io`] if writing fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    ///
+    /// let bs = parse("{[]CC(C)[]}").unwrap();
+    /// let mut out = Vec::new();
+    /// let n = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3))
+    ///     .write_smiles(&mut out)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(n, 3);
+    /// assert_eq!(out, b"CC(C)CC(C)CC(C)");
+    /// ```
+    pub fn write_smiles<W: Write>(&self, w: &mut W) -> Result<usize, PolySimError> {
+        let (smiles_raw, n) = self.homopolymer_unit()?;
+        let linear = LinearTemplate::with_base(smiles_raw, n, self.ring_offset)?;
+        let (prefix, suffix) = self.end_groups(linear.max_ring_number(n))?;
+        if self.vinyl_end {
+            // The last unit carries the double bond: check it on a single unit
+            self.attach_end_groups(&build_linear_smiles(smiles_raw, 1)?)?;
+        }
+
+        w.write_all(prefix.as_bytes())?;
+        let mut unit = String::new();
+        for i in 0..n {
+            unit.clear();
+            linear.write_unit(&mut unit, i);
+            let (head, tail) = (i == 0 && prefix.is_empty(), i + 1 == n && suffix.is_empty());
+            if head || tail {
+                unit = saturate_chain_ends(&unit, head, tail);
+            }
+            w.write_all(unit.as_bytes())?;
+        }
+        w.write_all(suffix.as_bytes())?;
+        if self.counterions {
+            let (one, two) = self.unit_charges(smiles_raw)?;
+            let charge = one + (n as i32 - 1) * (two - one);
+            w.write_all(counterion_suffix(charge).as_bytes())?;
+        }
+        Ok(n)
+    }
+
+    /// Generates a homopolymer whose backbone double bonds carry a cis/trans
+    /// geometry.
+    ///
+    /// Every non-ring double bond of the backbone that has a single-bonded
+    /// backbone atom on each side gets `/` and `\` directional bonds around it,
+    /// so polydienes such as 1,4-polybutadiene can be written as cis, trans, or
+    /// as a random mix. `seed` drives the draw of [`DoubleBondGeometry::Random`]
+    /// and is ignored otherwise. Double bonds at the very ends of the chain, or in
+    /// side groups, are left unspecified. Mass and formula are those of
+    /// [`Self::homopolymer`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::homopolymer`], plus [`PolySimError::InvalidRepeatUnit`] if
+    /// the chain has no backbone double bond to configure.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy, DoubleBondGeometry}};
+    ///
+    /// let bs = parse("{[]CC=CC[]}").unwrap(); // 1,4-polybutadiene
+    /// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(2))
+    ///     .homopolymer_with_geometry(DoubleBondGeometry::Trans, 0)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chain.smiles, "C/C=C/CC/C=C/C");
+    /// ```
+    pub fn homopolymer_with_geometry(
+        &self,
+        geometry: DoubleBondGeometry,
+        seed: u64,
+    ) -> Result<PolymerChain, PolySimError> {
+        let (smiles_raw, n) = self.homopolymer_unit()?;
+        let body = LinearTemplate::with_base(smiles_raw, n, self.ring_offset)?.build(n);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let body = set_double_bond_geometry(&body, || match geometry {
+            DoubleBondGeometry::Cis => false,
+            DoubleBondGeometry::Trans => true,
+            DoubleBondGeometry::Random => rng.random(),
+        })
+        .ok_or_else(|| {
+            PolySimError::InvalidRepeatUnit(format!(
+                "'{smiles_raw}' has no backbone double bond to give a cis/trans geometry"
+            ))
+        })?;
+        let (smiles, counterions) = self.assemble(&body)?;
+        let chain = PolymerChain::new(smiles, n, 0.0)
+            .with_composition(vec![MonomerUnit::new(
+                normalize_repeat_unit(smiles_raw),
+                1.0,
+            )])
+            .with_counterion_count(counterions);
+        let mn = self.chain_mn(&chain, smiles_raw)?;
+        Ok(PolymerChain { mn, ..chain })
+    }
+
+    /// Returns the single repeat unit of a homopolymer and the resolved repeat count.
+    fn homopolymer_unit(&self) -> Result<(&str, usize), PolySimError> {
         let stoch = self
             .bigsmiles
             .first_stochastic()
@@ -83,20 +384,15 @@ impl LinearBuilder {
             });
         }
 
-        let fragment = &stoch.repeat_units[0];
-        let n = self.resolve_n(&fragment.smiles_raw)?;
+        let smiles_raw = stoch.repeat_units[0].smiles_raw.as_str();
+        let n = self.resolve_n(smiles_raw, true)?;
 
         if n == 0 {
             return Err(PolySimError::BuildStrategy(
                 "repeat count must be ≥ 1".to_string(),
             ));
         }
-
-        let body = build_linear_smiles(&fragment.smiles_raw, n)?;
-        let smiles = self.with_end_groups(&body);
-        let chain = PolymerChain::new(smiles, n, 0.0);
-        let mn = average_mass(&chain);
-        Ok(PolymerChain::new(chain.smiles, n, mn))
+        Ok((smiles_raw, n))
     }
 
     /// Generates a random (statistical) copolymer.
@@ -146,6 +442,7 @@ impl LinearBuilder {
         let dist = WeightedIndex::new(fractions)
             .map_err(|e| PolySimError::BuildStrategy(format!("invalid weight fractions: {e}")))?;
 
+        let cache = || RepeatUnitCache::from_units(&units);
         let sequence = match &self.strategy {
             BuildStrategy::ByRepeatCount(n) => {
                 let n = *n;
@@ -157,20 +454,37 @@ impl LinearBuilder {
                 (0..n).map(|_| dist.sample(&mut *rng)).collect::<Vec<_>>()
             }
             BuildStrategy::ByTargetMn(target) => {
-                build_incremental_sequence(&units, *target, average_mass, &mut *rng, &dist)?
+                build_incremental_sequence(&cache()?, *target, unit_average_mass, &mut *rng, &dist)
             }
             BuildStrategy::ByExactMass(target) => {
-                build_incremental_sequence(&units, *target, monoisotopic_mass, &mut *rng, &dist)?
+                ensure_monoisotopic_mass(&units)?;
+                build_incremental_sequence(
+                    &cache()?,
+                    *target,
+                    unit_monoisotopic_mass,
+                    &mut *rng,
+                    &dist,
+                )
             }
+            BuildStrategy::ByAtomCount(target) => build_incremental_sequence(
+                &cache()?,
+                *target as f64,
+                unit_atom_count,
+                &mut *rng,
+                &dist,
+            ),
         };
 
         let smiles_seq: Vec<&str> = sequence.iter().map(|&i| units[i]).collect();
-        let body = build_copolymer_smiles(&smiles_seq)?;
-        let smiles = self.with_end_groups(&body);
+        let body = build_copolymer_smiles_from(&smiles_seq, self.ring_offset)?;
+        let (smiles, counterions) = self.assemble(&body)?;
         let n = sequence.len();
-        let chain = PolymerChain::new(smiles, n, 0.0);
-        let mn = average_mass(&chain);
-        Ok(PolymerChain::new(chain.smiles, n, mn))
+        let chain = PolymerChain::new(smiles, n, 0.0)
+            .with_composition(sequence_composition(&units, &sequence))
+            .with_sequence(sequence)
+            .with_counterion_count(counterions);
+        let mn = self.chain_mn(&chain, smiles_seq[0])?;
+        Ok(PolymerChain { mn, ..chain })
     }
 
     /// Generates an alternating copolymer (–A–B–A–B– or –A–B–C–A–B–C–).
@@ -197,6 +511,7 @@ impl LinearBuilder {
             .collect();
         let k = units.len();
 
+        let cache = || RepeatUnitCache::from_units(&units);
         let sequence: Vec<usize> = match &self.strategy {
             BuildStrategy::ByRepeatCount(n) => {
                 let n = *n;
@@ -208,20 +523,27 @@ impl LinearBuilder {
                 (0..n).map(|i| i % k).collect()
             }
             BuildStrategy::ByTargetMn(target) => {
-                build_incremental_alternating(&units, *target, average_mass)?
+                build_incremental_alternating(&cache()?, *target, unit_average_mass)
             }
             BuildStrategy::ByExactMass(target) => {
-                build_incremental_alternating(&units, *target, monoisotopic_mass)?
+                ensure_monoisotopic_mass(&units)?;
+                build_incremental_alternating(&cache()?, *target, unit_monoisotopic_mass)
+            }
+            BuildStrategy::ByAtomCount(target) => {
+                build_incremental_alternating(&cache()?, *target as f64, unit_atom_count)
             }
         };
 
         let smiles_seq: Vec<&str> = sequence.iter().map(|&i| units[i]).collect();
-        let body = build_copolymer_smiles(&smiles_seq)?;
-        let smiles = self.with_end_groups(&body);
+        let body = build_copolymer_smiles_from(&smiles_seq, self.ring_offset)?;
+        let (smiles, counterions) = self.assemble(&body)?;
         let n = sequence.len();
-        let chain = PolymerChain::new(smiles, n, 0.0);
-        let mn = average_mass(&chain);
-        Ok(PolymerChain::new(chain.smiles, n, mn))
+        let chain = PolymerChain::new(smiles, n, 0.0)
+            .with_composition(sequence_composition(&units, &sequence))
+            .with_sequence(sequence)
+            .with_counterion_count(counterions);
+        let mn = self.chain_mn(&chain, smiles_seq[0])?;
+        Ok(PolymerChain { mn, ..chain })
     }
 
     /// Generates a block copolymer (–AAAA–BBBB–).
@@ -258,11 +580,12 @@ impl LinearBuilder {
             .map(|f| f.smiles_raw.as_str())
             .collect();
 
-        let smiles_seq: Vec<&str> = block_lengths
+        let sequence: Vec<usize> = block_lengths
             .iter()
-            .zip(units.iter())
-            .flat_map(|(&len, &unit)| std::iter::repeat_n(unit, len))
+            .enumerate()
+            .flat_map(|(i, &len)| std::iter::repeat_n(i, len))
             .collect();
+        let smiles_seq: Vec<&str> = sequence.iter().map(|&i| units[i]).collect();
 
         let n = smiles_seq.len();
         if n == 0 {
@@ -271,11 +594,86 @@ impl LinearBuilder {
             ));
         }
 
-        let body = build_copolymer_smiles(&smiles_seq)?;
-        let smiles = self.with_end_groups(&body);
-        let chain = PolymerChain::new(smiles, n, 0.0);
-        let mn = average_mass(&chain);
-        Ok(PolymerChain::new(chain.smiles, n, mn))
+        let body = build_copolymer_smiles_from(&smiles_seq, self.ring_offset)?;
+        let (smiles, counterions) = self.assemble(&body)?;
+        let chain = PolymerChain::new(smiles, n, 0.0)
+            .with_composition(sequence_composition(&units, &sequence))
+            .with_sequence(sequence)
+            .with_counterion_count(counterions);
+        let mn = self.chain_mn(&chain, smiles_seq[0])?;
+        Ok(PolymerChain { mn, ..chain })
+    }
+
+    /// Generates a sequence-controlled copolymer from an explicit pattern.
+    ///
+    /// Each letter of `pattern` selects a repeat unit of the stochastic object:
+    /// `A` is the first unit, `B` the second, and so on. The chain has one repeat
+    /// unit per letter; ring-closure numbers stay disjoint across fragments.
+    ///
+    /// The `BuildStrategy` is ignored — `pattern` fully determines the chain.
+    ///
+    /// # Errors
+    ///
+    /// - [`PolySimError::NoStochasticObject`] if there is no stochastic object.
+    /// - [`PolySimError::BuildStrategy`] if `pattern` is empty or holds a
+    ///   character that does not name an available repeat unit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    ///
+    /// let bs = parse("{[$]CC[$],[$]CC(C)[$]}").unwrap();
+    /// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1))
+    ///     .from_sequence("AABAB")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chain.repeat_count, 5);
+    /// assert_eq!(chain.sequence_string(), "AABAB");
+    /// ```
+    pub fn from_sequence(&self, pattern: &str) -> Result<PolymerChain, PolySimError> {
+        let stoch = self
+            .bigsmiles
+            .first_stochastic()
+            .ok_or(PolySimError::NoStochasticObject)?;
+
+        let units: Vec<&str> = stoch
+            .repeat_units
+            .iter()
+            .map(|f| f.smiles_raw.as_str())
+            .collect();
+
+        if pattern.is_empty() {
+            return Err(PolySimError::BuildStrategy(
+                "sequence pattern must not be empty".to_string(),
+            ));
+        }
+
+        let sequence = pattern
+            .chars()
+            .map(|c| {
+                let index = (c as u32).wrapping_sub('A' as u32) as usize;
+                if c.is_ascii_uppercase() && index < units.len() {
+                    Ok(index)
+                } else {
+                    Err(PolySimError::BuildStrategy(format!(
+                        "invalid sequence letter '{c}': the BigSMILES has {} repeat unit(s)",
+                        units.len()
+                    )))
+                }
+            })
+            .collect::<Result<Vec<usize>, _>>()?;
+
+        let smiles_seq: Vec<&str> = sequence.iter().map(|&i| units[i]).collect();
+        let body = build_copolymer_smiles_from(&smiles_seq, self.ring_offset)?;
+        let (smiles, counterions) = self.assemble(&body)?;
+        let n = sequence.len();
+        let chain = PolymerChain::new(smiles, n, 0.0)
+            .with_composition(sequence_composition(&units, &sequence))
+            .with_sequence(sequence)
+            .with_counterion_count(counterions);
+        let mn = self.chain_mn(&chain, smiles_seq[0])?;
+        Ok(PolymerChain { mn, ..chain })
     }
 
     /// Generates a gradient copolymer where the composition of monomer A varies
@@ -306,7 +704,7 @@ impl LinearBuilder {
             .collect();
 
         // Resolve chain length using unit A
-        let n = self.resolve_n(units[0])?;
+        let n = self.resolve_n(units[0], true)?;
         if n == 0 {
             return Err(PolySimError::BuildStrategy(
                 "repeat count must be >= 1".to_string(),
@@ -332,26 +730,58 @@ impl LinearBuilder {
         }
 
         let smiles_seq: Vec<&str> = sequence.iter().map(|&i| units[i]).collect();
-        let body = build_copolymer_smiles(&smiles_seq)?;
-        let smiles = self.with_end_groups(&body);
-        let chain = PolymerChain::new(smiles, n, 0.0);
-        let mn = average_mass(&chain);
+        let body = build_copolymer_smiles_from(&smiles_seq, self.ring_offset)?;
+        let (smiles, counterions) = self.assemble(&body)?;
 
         let frac_a = count_a as f64 / n as f64;
         let composition = vec![
-            MonomerUnit::new(units[0], frac_a),
-            MonomerUnit::new(units[1], 1.0 - frac_a),
+            MonomerUnit::new(normalize_repeat_unit(units[0]), frac_a),
+            MonomerUnit::new(normalize_repeat_unit(units[1]), 1.0 - frac_a),
         ];
 
-        Ok(PolymerChain::new(chain.smiles, n, mn)
+        let chain = PolymerChain::new(smiles, n, 0.0)
             .with_composition(composition)
-            .with_architecture(Architecture::Gradient))
+            .with_sequence(sequence)
+            .with_architecture(Architecture::Gradient)
+            .with_counterion_count(counterions);
+        let mn = self.chain_mn(&chain, smiles_seq[0])?;
+        Ok(PolymerChain { mn, ..chain })
     }
 
-    /// Generates a cyclic homopolymer (ring closure connecting first and last atom).
+    /// Generates a cyclic homopolymer: *n* repeat units whose first and last
+    /// backbone atoms are joined by a ring-closure bond.
+    ///
+    /// A ring has no chain ends, so no end group is attached and the formula
+    /// lacks the two terminal hydrogens of the linear chain (C₂ₙH₄ₙ for cyclic
+    /// PE). The closing bond uses ring-closure number `ring_offset + 1`, kept
+    /// out of the repeat units, whose own rings are numbered above it.
     ///
     /// The BigSMILES must contain exactly 1 repeat unit.
-    pub fn cyclic_homopolymer(&self) -> Result<PolymerChain, PolySimError> {
+    ///
+    /// # Errors
+    ///
+    /// - [`PolySimError::NoStochasticObject`] if there is no stochastic object.
+    /// - [`PolySimError::RepeatUnitCount`] if it does not hold exactly one repeat unit.
+    /// - [`PolySimError::RingNumberOverflow`] if a repeat unit does not fit in
+    ///   the ring-closure numbers left above the reserved one.
+    /// - [`PolySimError::BuildStrategy`] if the ring would have fewer than
+    ///   3 backbone atoms (e.g. `{[]CC[]}` with n = 1).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    /// use polysim_core::properties::formula::molecular_formula;
+    ///
+    /// let bs = parse("{[]CC[]}").unwrap();
+    /// let ring = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3))
+    ///     .cyclic()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(ring.smiles, "C1CCCCC1"); // cyclohexane
+    /// assert_eq!(molecular_formula(&ring), "C6H12");
+    /// ```
+    pub fn cyclic(&self) -> Result<PolymerChain, PolySimError> {
         let stoch = self
             .bigsmiles
             .first_stochastic()
@@ -366,7 +796,7 @@ impl LinearBuilder {
         }
 
         let fragment = &stoch.repeat_units[0];
-        let n = self.resolve_n(&fragment.smiles_raw)?;
+        let n = self.resolve_n(&fragment.smiles_raw, false)?;
 
         if n == 0 {
             return Err(PolySimError::BuildStrategy(
@@ -374,150 +804,753 @@ impl LinearBuilder {
             ));
         }
 
-        let linear = build_linear_smiles(&fragment.smiles_raw, n)?;
-        let smiles = make_cyclic_smiles(&linear);
-        let chain = PolymerChain::new(smiles, n, 0.0);
+        // The closure joins the last unit to the first, even when n = 1
+        let smiles_raw = &fragment.smiles_raw;
+        check_descriptor_pairing(smiles_raw, smiles_raw)?;
+        let bond = join_bond(smiles_raw, smiles_raw)?;
+        let closure = self.ring_offset + 1;
+        let linear = LinearTemplate::with_base(smiles_raw, n, closure)?;
+        let smiles = close_ring(&linear.build(n), closure, bond)?;
+        let chain = PolymerChain::new(smiles, n, 0.0)
+            .with_composition(vec![MonomerUnit::new(
+                normalize_repeat_unit(&fragment.smiles_raw),
+                1.0,
+            )])
+            .with_architecture(Architecture::Cyclic);
         let mn = average_mass(&chain);
-        Ok(PolymerChain::new(chain.smiles, n, mn).with_architecture(Architecture::Cyclic))
+        Ok(PolymerChain { mn, ..chain })
     }
 
-    /// Prepends prefix and appends suffix SMILES segments from the BigSMILES.
-    fn with_end_groups(&self, body: &str) -> String {
-        let prefix = collect_smiles_segments(self.bigsmiles.prefix_segments());
-        let suffix = collect_smiles_segments(self.bigsmiles.suffix_segments());
-        let mut result = String::with_capacity(prefix.len() + body.len() + suffix.len());
-        result.push_str(&prefix);
-        result.push_str(body);
-        result.push_str(&suffix);
-        result
+    /// Generates a cyclic homopolymer; same as [`Self::cyclic`].
+    pub fn cyclic_homopolymer(&self) -> Result<PolymerChain, PolySimError> {
+        self.cyclic()
     }
 
-    fn resolve_n(&self, smiles_raw: &str) -> Result<usize, PolySimError> {
-        match &self.strategy {
-            BuildStrategy::ByRepeatCount(n) => Ok(*n),
-            BuildStrategy::ByTargetMn(target) => {
-                resolve_n_by_mass(smiles_raw, *target, average_mass)
-            }
-            BuildStrategy::ByExactMass(target) => {
-                resolve_n_by_mass(smiles_raw, *target, monoisotopic_mass)
-            }
-        }
+    /// Returns `(repeat_count, Mn)` for every homopolymer length from 1 to `n_max`.
+    ///
+    /// Mn follows MW(n) = n·M₀ + M_end, with the repeat-unit mass M₀ and the
+    /// end-group mass M_end (including explicit end groups) calibrated once, so
+    /// no chain SMILES is built per point. The build strategy is ignored.
+    ///
+    /// Returns an empty series if the BigSMILES does not describe a homopolymer
+    /// (see [`Self::homopolymer`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    ///
+    /// let bs = parse("{[]CC[]}").unwrap();
+    /// let series = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1)).mn_series(10);
+    ///
+    /// assert_eq!(series.len(), 10);
+    /// assert_eq!(series[9].0, 10);
+    /// assert!((series[9].1 - 282.556).abs() < 0.01);
+    /// ```
+    pub fn mn_series(&self, n_max: usize) -> Vec<(usize, f64)> {
+        let Ok((m0, m_end)) = self.repeat_unit_mass() else {
+            return Vec::new();
+        };
+        (1..=n_max).map(|n| (n, n as f64 * m0 + m_end)).collect()
     }
-}
-
-// --- internal helpers -------------------------------------------------------
-
-/// Déduit le nombre de répétitions à partir d'une masse cible.
-///
-/// Construit deux chaînes d'essai (n=1 et n=2) pour déterminer la masse par
-/// unité et la masse des groupements terminaux, puis résout par extrapolation
-/// linéaire : MW(n) = n × mw_per_unit + mw_end.
-///
-/// `mass_fn` peut être [`average_mass`] (pour [`BuildStrategy::ByTargetMn`]) ou
-/// [`monoisotopic_mass`] (pour [`BuildStrategy::ByExactMass`]).
-pub(crate) fn resolve_n_by_mass(
-    smiles_raw: &str,
-    target: f64,
-    mass_fn: fn(&PolymerChain) -> f64,
-) -> Result<usize, PolySimError> {
-    let mw1 = mass_fn(&PolymerChain::new(
-        build_linear_smiles(smiles_raw, 1)?,
-        1,
-        0.0,
-    ));
-    let mw2 = mass_fn(&PolymerChain::new(
-        build_linear_smiles(smiles_raw, 2)?,
-        2,
-        0.0,
-    ));
-    let mw_per_unit = mw2 - mw1;
-    let mw_end = mw1 - mw_per_unit;
-    let n = ((target - mw_end) / mw_per_unit).round().max(1.0) as usize;
-    Ok(n)
-}
-
-/// Calibrates per-unit masses for each distinct repeat unit via 2-point method.
-///
-/// Returns `(unit_masses, m_end)` where:
-/// - `unit_masses[i]` is the mass contribution of one copy of unit i
-/// - `m_end` is the end-group mass (constant across all units)
-fn calibrate_unit_masses(
-    units: &[&str],
-    mass_fn: fn(&PolymerChain) -> f64,
-) -> Result<(Vec<f64>, f64), PolySimError> {
-    let mut unit_masses = Vec::with_capacity(units.len());
-    let mut m_end_sum = 0.0;
 
-    for &unit in units {
-        let mw1 = mass_fn(&PolymerChain::new(build_linear_smiles(unit, 1)?, 1, 0.0));
-        let mw2 = mass_fn(&PolymerChain::new(build_linear_smiles(unit, 2)?, 2, 0.0));
-        let m0 = mw2 - mw1;
-        unit_masses.push(m0);
-        m_end_sum += mw1 - m0;
+    /// Builds the homopolymer whose Mn best matches `target` (g/mol) and returns
+    /// it with the Mn error, achieved − target.
+    ///
+    /// Same as [`Self::homopolymer`] with [`BuildStrategy::ByTargetMn`]; the
+    /// builder's own strategy is ignored, its end groups and options are kept.
+    /// With the default [`RoundingMode::Nearest`] the chain is the closest to
+    /// `target` and the error is at most half a repeat-unit mass; with
+    /// [`RoundingMode::Floor`] (or [`RoundingMode::Ceil`]) it is the longest
+    /// chain not above (shortest not below) `target`, within one repeat-unit
+    /// mass.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::homopolymer`], including [`PolySimError::BuildStrategy`]
+    /// if `target` is below one repeat unit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    ///
+    /// let bs = parse("{[]CC[]}").unwrap();
+    /// let (chain, delta) = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1))
+    ///     .build_closest_to_mn(2805.0)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chain.repeat_count, 100);
+    /// assert!((delta - 2.416).abs() < 0.01); // 2807.416 − 2805 g/mol
+    /// ```
+    pub fn build_closest_to_mn(&self, target: f64) -> Result<(PolymerChain, f64), PolySimError> {
+        let builder = Self {
+            strategy: BuildStrategy::ByTargetMn(target),
+            ..self.clone()
+        };
+        let chain = builder.homopolymer()?;
+        let delta = chain.mn - target;
+        Ok((chain, delta))
     }
 
-    // Average end-group mass (should be ~identical for all units, but average for safety)
-    let m_end = m_end_sum / units.len() as f64;
-    Ok((unit_masses, m_end))
-}
-
-/// Builds a copolymer unit sequence incrementally for random copolymers.
-///
-/// Adds units one at a time (sampled from weighted distribution), tracking
-/// accumulated mass. Stops when closest to the target mass.
-fn build_incremental_sequence(
-    units: &[&str],
-    target: f64,
-    mass_fn: fn(&PolymerChain) -> f64,
-    rng: &mut dyn RngCore,
-    dist: &WeightedIndex<f64>,
-) -> Result<Vec<usize>, PolySimError> {
-    let (unit_masses, m_end) = calibrate_unit_masses(units, mass_fn)?;
-
-    let mut sequence = Vec::new();
-    let mut running_mass = m_end;
+    /// Builds the homopolymer whose unperturbed radius of gyration is closest
+    /// to `target_rg_nm` (nm).
+    ///
+    /// Inverts the ideal-chain relation of [`radius_of_gyration`]: Rg² grows
+    /// linearly with the number of backbone bonds, so it is calibrated on the
+    /// end-capped chains with n = 1 and n = 2 and solved for n, rounded with
+    /// [`Self::rounding`]. `char_ratio` is the characteristic ratio C∞ of the
+    /// polymer and `bond_length_nm` its backbone bond length. The builder's own
+    /// strategy is ignored, its end groups and options are kept.
+    ///
+    /// # Errors
+    ///
+    /// - [`PolySimError::BuildStrategy`] if an argument is not a positive number,
+    ///   or if `target_rg_nm` is below the Rg of a single repeat unit.
+    /// - Same as [`Self::homopolymer`] otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    /// use polysim_core::properties::conformation::radius_of_gyration;
+    ///
+    /// // PE: C∞ = 6.7, C–C = 0.154 nm
+    /// let bs = parse("{[]CC[]}").unwrap();
+    /// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1))
+    ///     .build_for_rg(5.0, 6.7, 0.154)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chain.repeat_count, 473);
+    /// assert!((radius_of_gyration(&chain, 6.7, 0.154) - 5.0).abs() < 0.01);
+    /// ```
+    pub fn build_for_rg(
+        &self,
+        target_rg_nm: f64,
+        char_ratio: f64,
+        bond_length_nm: f64,
+    ) -> Result<PolymerChain, PolySimError> {
+        for (name, value) in [
+            ("target Rg", target_rg_nm),
+            ("characteristic ratio", char_ratio),
+            ("bond length", bond_length_nm),
+        ] {
+            if !(value.is_finite() && value > 0.0) {
+                return Err(PolySimError::BuildStrategy(format!(
+                    "{name} must be a positive number, got {value}"
+                )));
+            }
+        }
+        let with_count = |n: usize| Self {
+            strategy: BuildStrategy::ByRepeatCount(n),
+            ..self.clone()
+        };
+        let unit_builder = with_count(1);
+        let (smiles_raw, _) = unit_builder.homopolymer_unit()?;
+        let rg_squared = |n: usize| -> Result<f64, PolySimError> {
+            let smiles = self.attach_end_groups(&build_linear_smiles(smiles_raw, n)?)?;
+            let chain = PolymerChain::new(smiles, n, 0.0);
+            Ok(radius_of_gyration(&chain, char_ratio, bond_length_nm).powi(2))
+        };
+        let (rg1, rg2) = (rg_squared(1)?, rg_squared(2)?);
+        let per_unit = rg2 - rg1;
+        let n = self
+            .rounding
+            .apply((target_rg_nm.powi(2) - (rg1 - per_unit)) / per_unit);
+        if n.is_nan() || n < 1.0 {
+            return Err(PolySimError::BuildStrategy(format!(
+                "target Rg of {target_rg_nm} nm is below one repeat unit; \
+                 the minimum achievable Rg is {:.3} nm",
+                rg1.sqrt()
+            )));
+        }
+        with_count(n as usize).homopolymer()
+    }
 
-    loop {
-        let idx = dist.sample(rng);
-        running_mass += unit_masses[idx];
-        sequence.push(idx);
+    /// Repeat-unit mass M₀ and end-group mass M_end (g/mol) of the homopolymer,
+    /// so that Mn(n) = n·M₀ + M_end.
+    ///
+    /// This is the calibration target-mass strategies resolve *n* with: explicit
+    /// end groups and, with [`Self::counterions`], the counter-ions are included.
+    /// The builder's strategy is ignored.
+    ///
+    /// # Errors
+    ///
+    /// [`PolySimError::NoStochasticObject`], or [`PolySimError::RepeatUnitCount`]
+    /// if the stochastic object does not hold exactly one repeat unit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    ///
+    /// let bs = parse("{[]CC[]}").unwrap();
+    /// let (m0, m_end) = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1))
+    ///     .repeat_unit_mass()
+    ///     .unwrap();
+    ///
+    /// assert!((m0 - 28.054).abs() < 0.01);
+    /// assert!((m_end - 2.016).abs() < 0.01); // one H at each end
+    /// ```
+    pub fn repeat_unit_mass(&self) -> Result<(f64, f64), PolySimError> {
+        let stoch = self
+            .bigsmiles
+            .first_stochastic()
+            .ok_or(PolySimError::NoStochasticObject)?;
+        if stoch.repeat_units.len() != 1 {
+            return Err(PolySimError::RepeatUnitCount {
+                architecture: "homopolymer",
+                got: stoch.repeat_units.len(),
+                need_min: 1,
+            });
+        }
+        let smiles_raw = &stoch.repeat_units[0].smiles_raw;
+        let cache = RepeatUnitCache::from_units(&[smiles_raw])?;
+        let (ions_per_unit, ions_end) = self.counterion_offsets(smiles_raw, average_mass)?;
+        let m_end = cache.ends(0, 0).average_mass
+            + self.end_group_offset(smiles_raw, average_mass)?
+            + ions_end;
+        Ok((cache.units()[0].average_mass + ions_per_unit, m_end))
+    }
 
-        if running_mass >= target {
-            // Check if removing last unit gets closer
-            if sequence.len() > 1 {
-                let mass_without = running_mass - unit_masses[idx];
-                if (mass_without - target).abs() < (running_mass - target).abs() {
-                    sequence.pop();
-                }
+    /// Prepends the head and appends the tail end group.
+    ///
+    /// Explicit groups set with [`Self::with_end_groups`] take precedence over
+    /// the begin/end SMILES segments of the BigSMILES. An end left free is
+    /// saturated with [`saturate_chain_ends`].
+    ///
+    /// Returns [`PolySimError::BuildStrategy`] if an explicit group is not valid
+    /// SMILES, or if the chain end cannot carry the vinyl group of
+    /// [`Self::vinyl_terminated`].
+    fn attach_end_groups(&self, body: &str) -> Result<String, PolySimError> {
+        let (prefix, suffix) = self.end_groups(max_ring_number(body))?;
+        let body = saturate_chain_ends(body, prefix.is_empty(), suffix.is_empty());
+        let mut result = String::with_capacity(prefix.len() + body.len() + suffix.len());
+        result.push_str(&prefix);
+        result.push_str(&body);
+        result.push_str(&suffix);
+        if self.vinyl_end {
+            check_vinyl_end(&result)?;
+        }
+        Ok(result)
+    }
+
+    /// Attaches the end groups to `body`, then the counter-ions if requested.
+    ///
+    /// Returns the chain SMILES and the number of counter-ions appended.
+    fn assemble(&self, body: &str) -> Result<(String, usize), PolySimError> {
+        let mut smiles = self.attach_end_groups(body)?;
+        if !self.counterions {
+            return Ok((smiles, 0));
+        }
+        let charge = net_charge(&smiles)?;
+        smiles.push_str(&counterion_suffix(charge));
+        Ok((smiles, charge.unsigned_abs() as usize))
+    }
+
+    /// Net charge of chains of one and two `smiles_raw` units with their end
+    /// groups; the charge of longer chains follows linearly.
+    fn unit_charges(&self, smiles_raw: &str) -> Result<(i32, i32), PolySimError> {
+        let charge = |n| -> Result<i32, PolySimError> {
+            net_charge(&self.attach_end_groups(&build_linear_smiles(smiles_raw, n)?)?)
+        };
+        Ok((charge(1)?, charge(2)?))
+    }
+
+    /// `mass_fn` of the counter-ions added by [`Self::assemble`], split into a
+    /// per-unit part and a constant part like the chain mass itself.
+    ///
+    /// Both are zero unless [`Self::counterions`] is set. The charge is read on
+    /// chains of one and two `smiles_raw` units with their end groups.
+    fn counterion_offsets(
+        &self,
+        smiles_raw: &str,
+        mass_fn: fn(&PolymerChain) -> f64,
+    ) -> Result<(f64, f64), PolySimError> {
+        if !self.counterions {
+            return Ok((0.0, 0.0));
+        }
+        let (one, two) = self.unit_charges(smiles_raw)?;
+        let ion = counterion(if two != 0 { two } else { one });
+        let ion_value = mass_fn(&PolymerChain::new(ion.to_string(), 0, 0.0));
+        let per_unit = f64::from(two.abs() - one.abs()) * ion_value;
+        Ok((per_unit, f64::from(one.abs()) * ion_value - per_unit))
+    }
+
+    /// Returns the head and tail end groups for a body whose highest
+    /// ring-closure number is `body_max`.
+    fn end_groups(&self, body_max: u32) -> Result<(String, String), PolySimError> {
+        for group in self.head.iter().chain(&self.tail) {
+            opensmiles::parse(group).map_err(|e| {
+                PolySimError::BuildStrategy(format!("invalid end group '{group}': {e}"))
+            })?;
+        }
+        let prefix = match &self.head {
+            Some(head) => head.clone(),
+            None => collect_smiles_segments(self.bigsmiles.prefix_segments()),
+        };
+        let suffix = match &self.tail {
+            _ if self.vinyl_end => VINYL_END.to_string(),
+            Some(tail) => tail.clone(),
+            None => collect_smiles_segments(self.bigsmiles.suffix_segments()),
+        };
+
+        // Every fragment closes its own rings, so reusing numbers is valid SMILES;
+        // shifting them past the body's numbers only avoids visual collisions.
+        // With a ring offset the body already starts above the end groups.
+        let shift = |group: String| {
+            if self.ring_offset == 0 && body_max + max_ring_number(&group) <= 99 {
+                renumber_ring_closures(&group, body_max)
+            } else {
+                group
             }
-            break;
+        };
+        Ok((shift(prefix), shift(suffix)))
+    }
+
+    /// Resolves the repeat count of a chain of `smiles_raw` units.
+    ///
+    /// For the mass and atom-count strategies, the contribution of the end
+    /// groups (explicit or from the BigSMILES) and of the counter-ions is
+    /// subtracted from the target when `end_groups` is set, so short
+    /// end-capped and neutralised chains get the right *n*.
+    fn resolve_n(&self, smiles_raw: &str, end_groups: bool) -> Result<usize, PolySimError> {
+        let mass_fn: fn(&PolymerChain) -> f64 = match &self.strategy {
+            BuildStrategy::ByRepeatCount(n) => return Ok(*n),
+            BuildStrategy::ByTargetMn(_) => average_mass,
+            BuildStrategy::ByExactMass(_) => monoisotopic_mass,
+            BuildStrategy::ByAtomCount(_) => atom_count,
+        };
+        let (unit_offset, end_offset) = if end_groups {
+            let (ions_per_unit, ions_end) = self.counterion_offsets(smiles_raw, mass_fn)?;
+            (
+                ions_per_unit,
+                self.end_group_offset(smiles_raw, mass_fn)? + ions_end,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+        resolve_n_by_mass(
+            smiles_raw,
+            &self.strategy,
+            unit_offset,
+            end_offset,
+            self.rounding,
+        )
+    }
+
+    /// Mn (g/mol) of a built `chain` whose first unit is `head_unit`.
+    ///
+    /// When the parser rejects the chain SMILES, the mass is estimated from
+    /// the composition recorded on `chain` (see [`average_mass`]) and the end
+    /// groups are added back.
+    fn chain_mn(&self, chain: &PolymerChain, head_unit: &str) -> Result<f64, PolySimError> {
+        match try_average_mass(chain) {
+            Ok(mass) => Ok(mass),
+            Err(_) => Ok(average_mass(chain) + self.end_group_offset(head_unit, average_mass)?),
         }
     }
 
-    Ok(sequence)
+    /// Extra `mass_fn` of a chain once the end groups are attached.
+    fn end_group_offset(
+        &self,
+        smiles_raw: &str,
+        mass_fn: fn(&PolymerChain) -> f64,
+    ) -> Result<f64, PolySimError> {
+        let body = build_linear_smiles(smiles_raw, 1)?;
+        let bare = build_free_linear_smiles(smiles_raw, 1)?;
+        let capped = self.attach_end_groups(&body)?;
+        if capped == bare {
+            return Ok(0.0);
+        }
+        Ok(mass_fn(&PolymerChain::new(capped, 1, 0.0)) - mass_fn(&PolymerChain::new(bare, 1, 0.0)))
+    }
+}
+
+/// Resolves the degree of polymerisation matching a measured Mn (g/mol).
+///
+/// Uses the same two-point calibration and rounding to the nearest integer as
+/// [`BuildStrategy::ByTargetMn`], without building the full chain. The BigSMILES
+/// must contain a single repeat unit.
+///
+/// # Errors
+///
+/// - [`PolySimError::NoStochasticObject`] if there is no stochastic object.
+/// - [`PolySimError::RepeatUnitCount`] if it does not hold exactly one repeat unit.
+/// - [`PolySimError::BuildStrategy`] if `measured_mn` is not a positive number.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::dp_from_mn};
+///
+/// let bs = parse("{[]CC[]}").unwrap();
+/// assert_eq!(dp_from_mn(&bs, 282.5).unwrap(), 10);
+/// ```
+pub fn dp_from_mn(bigsmiles: &BigSmiles, measured_mn: f64) -> Result<usize, PolySimError> {
+    if !(measured_mn.is_finite() && measured_mn > 0.0) {
+        return Err(PolySimError::BuildStrategy(format!(
+            "measured Mn must be a positive number, got {measured_mn}"
+        )));
+    }
+    let stoch = bigsmiles
+        .first_stochastic()
+        .ok_or(PolySimError::NoStochasticObject)?;
+    if stoch.repeat_units.len() != 1 {
+        return Err(PolySimError::RepeatUnitCount {
+            architecture: "homopolymer",
+            got: stoch.repeat_units.len(),
+            need_min: 1,
+        });
+    }
+    resolve_n_by_mass(
+        &stoch.repeat_units[0].smiles_raw,
+        &BuildStrategy::ByTargetMn(measured_mn),
+        0.0,
+        0.0,
+        RoundingMode::Nearest,
+    )
+}
+
+/// Returns the canonical SMILES of repeat unit `index` of the first stochastic object.
+///
+/// Bonding descriptors are stripped and dangling bonds capped with hydrogen, as
+/// when building a chain, then the unit is written in the same canonical form
+/// as [`PolymerChain::canonical_smiles`]. Two spellings of the same unit
+/// (atom order, ring numbers, descriptor style) give the same string.
+///
+/// # Errors
+///
+/// - [`PolySimError::NoStochasticObject`] if there is no stochastic object.
+/// - [`PolySimError::InvalidRepeatUnit`] if `index` is out of range or the unit
+///   is not valid SMILES.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::canonical_repeat_unit};
+///
+/// let a = canonical_repeat_unit(&parse("{[]CC(c1ccccc1)[]}").unwrap(), 0).unwrap();
+/// let b = canonical_repeat_unit(&parse("{[$]C(c2ccccc2)C[$]}").unwrap(), 0).unwrap();
+/// assert_eq!(a, b);
+/// ```
+pub fn canonical_repeat_unit(bigsmiles: &BigSmiles, index: usize) -> Result<String, PolySimError> {
+    let stoch = bigsmiles
+        .first_stochastic()
+        .ok_or(PolySimError::NoStochasticObject)?;
+    let unit = stoch.repeat_units.get(index).ok_or_else(|| {
+        PolySimError::InvalidRepeatUnit(format!(
+            "no repeat unit at index {index} ({} in the stochastic object)",
+            stoch.repeat_units.len()
+        ))
+    })?;
+    validate_repeat_unit(&unit.smiles_raw)?;
+    let capped = normalize_repeat_unit(&unit.smiles_raw);
+    Ok(PolymerChain::new(capped, 1, 0.0).canonical_smiles())
+}
+
+/// Returns the SMILES of every repeat unit of every stochastic object, in order.
+///
+/// Bonding descriptors are stripped from the fragments as written in the
+/// BigSMILES; the SMILES is otherwise untouched (not canonicalised, Kekulé
+/// rings kept), see [`canonical_repeat_unit`] for a normalised form.
+///
+/// # Errors
+///
+/// - [`PolySimError::NoStochasticObject`] if there is no stochastic object.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::repeat_units};
+///
+/// let units = repeat_units(&parse("{[$]CC[$],[$]CC(C)[$]}").unwrap()).unwrap();
+/// assert_eq!(units, ["CC", "CC(C)"]);
+/// ```
+pub fn repeat_units(bigsmiles: &BigSmiles) -> Result<Vec<String>, PolySimError> {
+    let mut objects = bigsmiles
+        .segments
+        .iter()
+        .filter_map(|segment| match segment {
+            BigSmilesSegment::Stochastic(object) => Some(object),
+            BigSmilesSegment::Smiles(_) => None,
+        })
+        .peekable();
+    if objects.peek().is_none() {
+        return Err(PolySimError::NoStochasticObject);
+    }
+    Ok(objects
+        .flat_map(|object| &object.repeat_units)
+        .map(|unit| cap_dangling_bonds(&unit.smiles_raw))
+        .collect())
+}
+
+/// Predicts the highest ring-closure number a chain of `n` repeat units will use.
+///
+/// Follows the renumbering of [`LinearBuilder::homopolymer`]: each copy of the
+/// repeat unit is shifted past the previous one and numbers are recycled once
+/// they would exceed 99, then the begin/end SMILES segments are shifted past
+/// the body when they fit. Call it before building a very long chain to detect
+/// the overflow case: a result above 99 means the build will fail with
+/// [`PolySimError::RingNumberOverflow`]. For copolymers the widest repeat unit
+/// is assumed at every position, giving an upper bound.
+///
+/// Returns 0 if the BigSMILES has no stochastic object.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::max_ring_number_for_chain};
+///
+/// let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+/// assert_eq!(max_ring_number_for_chain(&bs, 10), 10);
+/// assert_eq!(max_ring_number_for_chain(&bs, 1000), 99); // recycled
+/// ```
+pub fn max_ring_number_for_chain(bigsmiles: &BigSmiles, n: usize) -> u32 {
+    let Some(stoch) = bigsmiles.first_stochastic() else {
+        return 0;
+    };
+    let unit_max = stoch
+        .repeat_units
+        .iter()
+        .map(|unit| max_ring_number(&unit.smiles_raw))
+        .max()
+        .unwrap_or(0);
+    let body_max = match ring_cycle_length(unit_max, 0) {
+        Ok(cycle_length) => n.min(cycle_length) as u32 * unit_max,
+        Err(_) => unit_max,
+    };
+
+    [bigsmiles.prefix_segments(), bigsmiles.suffix_segments()]
+        .into_iter()
+        .map(
+            |segs| match max_ring_number(&collect_smiles_segments(segs)) {
+                0 => 0,
+                group if body_max + group <= 99 => body_max + group,
+                group => group,
+            },
+        )
+        .fold(body_max, u32::max)
+}
+
+// --- internal helpers -------------------------------------------------------
+
+/// Groupement terminal de [`LinearBuilder::vinyl_terminated`] : CH₂ lié par une
+/// double liaison au dernier atome du squelette.
+const VINYL_END: &str = "=C";
+
+/// Vérifie que l'atome portant le groupement vinyle final (le voisin du dernier
+/// atome de `smiles`) ne dépasse pas sa valence normale, c.-à-d. qu'il avait au
+/// moins deux hydrogènes dans la chaîne saturée.
+fn check_vinyl_end(smiles: &str) -> Result<(), PolySimError> {
+    let graph = MolGraph::parse(smiles).map_err(PolySimError::InvalidRepeatUnit)?;
+    let vinyl = graph.atoms.len() - 1;
+    let Some(&(anchor, _)) = graph.neighbors(vinyl).first() else {
+        return Err(PolySimError::BuildStrategy(
+            "vinyl end group has no chain atom to attach to".to_string(),
+        ));
+    };
+    let atom = &graph.atoms[anchor];
+    let used: u32 = graph
+        .neighbors(anchor)
+        .iter()
+        .map(|&(_, k)| graph.bonds[k].order.valence())
+        .sum();
+    let fits = !atom.aromatic
+        && default_valences(atom.atomic_number)
+            .last()
+            .is_some_and(|&max| used <= max);
+    if fits {
+        Ok(())
+    } else {
+        Err(PolySimError::BuildStrategy(format!(
+            "the chain end atom ({}) has fewer than two hydrogens and cannot carry a vinyl group",
+            element_symbol(atom.atomic_number)
+        )))
+    }
+}
+
+/// Déduit le nombre de répétitions à partir de la stratégie de construction.
+///
+/// Pour les stratégies par cible, la masse par unité et la masse des
+/// groupements terminaux viennent du [`RepeatUnitCache`] du motif, puis n est
+/// résolu par extrapolation linéaire : MW(n) = n × mw_per_unit + mw_end.
+/// `unit_offset` s'ajoute à mw_per_unit et `end_offset` à mw_end pour tenir
+/// compte des groupes terminaux et des contre-ions absents du cache. Le n
+/// fractionnaire obtenu est arrondi selon `rounding`.
+///
+/// La grandeur comparée à la cible est [`average_mass`] pour
+/// [`BuildStrategy::ByTargetMn`], [`monoisotopic_mass`] pour
+/// [`BuildStrategy::ByExactMass`] et [`atom_count`] pour
+/// [`BuildStrategy::ByAtomCount`] : le nombre d'atomes est lui aussi affine en n.
+///
+/// Retourne [`PolySimError::BuildStrategy`] si l'arrondi donne moins d'une
+/// unité, en indiquant la valeur minimale atteignable.
+pub(crate) fn resolve_n_by_mass(
+    smiles_raw: &str,
+    strategy: &BuildStrategy,
+    unit_offset: f64,
+    end_offset: f64,
+    rounding: RoundingMode,
+) -> Result<usize, PolySimError> {
+    let (target, value, quantity, unit): (f64, fn(&UnitContribution) -> f64, &str, &str) =
+        match strategy {
+            BuildStrategy::ByRepeatCount(n) => return Ok(*n),
+            BuildStrategy::ByTargetMn(target) => (*target, unit_average_mass, "Mn", " g/mol"),
+            BuildStrategy::ByExactMass(target) => {
+                ensure_monoisotopic_mass(&[smiles_raw])?;
+                (
+                    *target,
+                    unit_monoisotopic_mass,
+                    "monoisotopic mass",
+                    " g/mol",
+                )
+            }
+            BuildStrategy::ByAtomCount(target) => {
+                (*target as f64, unit_atom_count, "atom count", "")
+            }
+        };
+    let cache = RepeatUnitCache::from_units(&[smiles_raw])?;
+    let mw_per_unit = value(&cache.units()[0]) + unit_offset;
+    let mw_end = value(cache.ends(0, 0)) + end_offset;
+    let n = rounding.apply((target - mw_end) / mw_per_unit);
+    if n.is_nan() || n < 1.0 {
+        return Err(PolySimError::BuildStrategy(format!(
+            "target {quantity} of {target}{unit} is below one repeat unit; \
+             the minimum achievable {quantity} is {:.3}{unit}",
+            mw_per_unit + mw_end
+        )));
+    }
+    Ok(n as usize)
+}
+
+/// Net formal charge of `smiles`.
+fn net_charge(smiles: &str) -> Result<i32, PolySimError> {
+    let graph = MolGraph::parse(smiles).map_err(PolySimError::InvalidRepeatUnit)?;
+    Ok(graph.atoms.iter().map(|atom| atom.charge).sum())
+}
+
+/// Monovalent counter-ion neutralising a net `charge`: `[Na+]` for anions,
+/// `[Cl-]` otherwise.
+fn counterion(charge: i32) -> &'static str {
+    if charge < 0 {
+        "[Na+]"
+    } else {
+        "[Cl-]"
+    }
+}
+
+/// `.`-separated counter-ions neutralising a net `charge`.
+fn counterion_suffix(charge: i32) -> String {
+    let ion = counterion(charge);
+    let count = charge.unsigned_abs() as usize;
+    let mut suffix = String::with_capacity((ion.len() + 1) * count);
+    for _ in 0..count {
+        suffix.push('.');
+        suffix.push_str(ion);
+    }
+    suffix
+}
+
+/// Vérifie que la masse monoisotopique de chaque motif est définie, pour ne pas
+/// calibrer sur `NaN` (élément sans nucléide tabulé).
+fn ensure_monoisotopic_mass(units: &[&str]) -> Result<(), PolySimError> {
+    for &unit in units {
+        try_monoisotopic_mass(&PolymerChain::new(build_linear_smiles(unit, 1)?, 1, 0.0))?;
+    }
+    Ok(())
+}
+
+/// Nombre total d'atomes de la chaîne, sous la forme attendue par
+/// [`resolve_n_by_mass`].
+pub(crate) fn atom_count(chain: &PolymerChain) -> f64 {
+    total_atom_count(chain) as f64
+}
+
+/// Average mass of a [`UnitContribution`], as compared with a target Mn.
+fn unit_average_mass(unit: &UnitContribution) -> f64 {
+    unit.average_mass
+}
+
+/// Monoisotopic mass of a [`UnitContribution`].
+fn unit_monoisotopic_mass(unit: &UnitContribution) -> f64 {
+    unit.monoisotopic_mass
+}
+
+/// Atom count of a [`UnitContribution`], as compared with a target atom count.
+fn unit_atom_count(unit: &UnitContribution) -> f64 {
+    unit.atom_count as f64
+}
+
+/// Molar fraction of each repeat unit in a sequence of unit indices.
+///
+/// Units are recorded as plain SMILES, without their bonding descriptors.
+fn sequence_composition(units: &[&str], sequence: &[usize]) -> Vec<MonomerUnit> {
+    let mut counts = vec![0usize; units.len()];
+    for &i in sequence {
+        counts[i] += 1;
+    }
+    units
+        .iter()
+        .zip(counts)
+        .map(|(&unit, count)| {
+            MonomerUnit::new(
+                normalize_repeat_unit(unit),
+                count as f64 / sequence.len() as f64,
+            )
+        })
+        .collect()
+}
+
+/// Builds a copolymer unit sequence incrementally for random copolymers.
+///
+/// Adds units one at a time (sampled from weighted distribution), tracking
+/// accumulated mass. Stops when closest to the target mass.
+fn build_incremental_sequence(
+    cache: &RepeatUnitCache,
+    target: f64,
+    value: fn(&UnitContribution) -> f64,
+    rng: &mut dyn RngCore,
+    dist: &WeightedIndex<f64>,
+) -> Vec<usize> {
+    grow_sequence(cache, target, value, |_| dist.sample(rng))
 }
 
 /// Builds a copolymer unit sequence incrementally for alternating copolymers.
 fn build_incremental_alternating(
-    units: &[&str],
+    cache: &RepeatUnitCache,
     target: f64,
-    mass_fn: fn(&PolymerChain) -> f64,
-) -> Result<Vec<usize>, PolySimError> {
-    let (unit_masses, m_end) = calibrate_unit_masses(units, mass_fn)?;
-    let k = units.len();
+    value: fn(&UnitContribution) -> f64,
+) -> Vec<usize> {
+    let k = cache.units().len();
+    grow_sequence(cache, target, value, |i| i % k)
+}
 
-    let mut sequence = Vec::new();
-    let mut running_mass = m_end;
+/// Appends the units drawn by `next` (given the current length) until the
+/// chain reaches `target`, keeping the length closest to it.
+///
+/// The running value counts the ends of the actual first and last units.
+fn grow_sequence(
+    cache: &RepeatUnitCache,
+    target: f64,
+    value: fn(&UnitContribution) -> f64,
+    mut next: impl FnMut(usize) -> usize,
+) -> Vec<usize> {
+    let units = cache.units();
+    let mut sequence: Vec<usize> = Vec::new();
+    let mut body = 0.0;
 
     loop {
-        let idx = sequence.len() % k;
-        running_mass += unit_masses[idx];
+        let idx = next(sequence.len());
+        body += value(&units[idx]);
         sequence.push(idx);
+        let running = body + value(cache.ends(sequence[0], idx));
 
-        if running_mass >= target {
-            if sequence.len() > 1 {
-                let mass_without = running_mass - unit_masses[idx];
-                if (mass_without - target).abs() < (running_mass - target).abs() {
+        if running >= target {
+            // Check if removing last unit gets closer
+            if let [.., previous, _] = sequence[..] {
+                let without = body - value(&units[idx]) + value(cache.ends(sequence[0], previous));
+                if (without - target).abs() < (running - target).abs() {
                     sequence.pop();
                 }
             }
@@ -525,90 +1558,265 @@ fn build_incremental_alternating(
         }
     }
 
-    Ok(sequence)
+    sequence
 }
 
 /// Builds the SMILES string for a linear chain of `n` repeat units.
 ///
-/// Ring closure numbers are renumbered for each copy. Because each copy is
-/// self-contained (every ring opened within a copy is also closed within that
-/// copy), the offsets cycle over 1..=99, allowing chains of arbitrary length.
+/// The unit's tail must be able to bond to its own head (see
+/// [`check_descriptor_pairing`]). Consecutive copies are joined by the bond
+/// written next to the unit's descriptors (see [`join_bond`]), so `[$]=CC=[$]`
+/// gives `CC=CC=CC`. The bonds left dangling at the two chain ends are then
+/// capped with hydrogen and Kekulé benzene rings written as aromatic (see
+/// [`normalize_repeat_unit`]). The repeat unit is scanned once into a
+/// [`RingTemplate`] and its ring closure numbers are renumbered for each copy.
+/// Because each copy is self-contained (every ring opened within a copy is also
+/// closed within that copy), the offsets cycle over 1..=99, allowing chains of
+/// arbitrary length.
 ///
 /// # Errors
 ///
-/// Returns [`PolySimError::RingNumberOverflow`] if the repeat unit itself uses
-/// more than 99 distinct ring-closure numbers (already invalid SMILES).
+/// - [`PolySimError::InvalidRepeatUnit`] if the unit is not valid SMILES (see
+///   [`validate_repeat_unit`]), if it leaves a bond dangling anywhere but next
+///   to its head and tail descriptors, or if `n ≥ 2` and the unit's ends
+///   cannot bond to each other (e.g. `[<]CC[<]` or `[$]=CC#[$]`).
+/// - [`PolySimError::RingNumberOverflow`] if the repeat unit itself uses
+///   more than 99 distinct ring-closure numbers (already invalid SMILES).
 pub(crate) fn build_linear_smiles(smiles_raw: &str, n: usize) -> Result<String, PolySimError> {
-    let max_ring = max_ring_number(smiles_raw);
+    Ok(LinearTemplate::with_base(smiles_raw, n, 0)?.build(n))
+}
 
-    // Pathological case: the repeat unit alone already overflows SMILES ring numbers.
-    if max_ring > 99 {
-        return Err(PolySimError::RingNumberOverflow {
-            max_ring,
-            max_supported: 99,
-        });
+/// [`build_linear_smiles`] for a chain whose two ends stay free, saturated
+/// with [`saturate_chain_ends`].
+pub(crate) fn build_free_linear_smiles(smiles_raw: &str, n: usize) -> Result<String, PolySimError> {
+    Ok(saturate_chain_ends(
+        &build_linear_smiles(smiles_raw, n)?,
+        true,
+        true,
+    ))
+}
+
+/// A validated repeat unit ready to be written *n* times, with ring-closure
+/// numbers cycling over `base + 1..=99` (see [`build_linear_smiles`]).
+struct LinearTemplate {
+    template: RingTemplate,
+    /// Bond symbol written between consecutive copies (empty for single).
+    bond: String,
+    /// Ring-closure numbers `1..=base` are left to the end groups.
+    base: u32,
+    /// Number of distinct copies before ring numbers are recycled.
+    cycle_length: usize,
+}
+
+impl LinearTemplate {
+    fn with_base(smiles_raw: &str, n: usize, base: u32) -> Result<Self, PolySimError> {
+        validate_repeat_unit(smiles_raw)?;
+        let bond = if n >= 2 {
+            check_descriptor_pairing(smiles_raw, smiles_raw)?;
+            join_bond(smiles_raw, smiles_raw)?
+        } else {
+            unit_end_bonds(smiles_raw)?;
+            ""
+        };
+        let template = RingTemplate::new(&normalize_repeat_unit(smiles_raw));
+        let cycle_length = ring_cycle_length(template.max_ring, base)?;
+        Ok(Self {
+            template,
+            bond: bond.to_string(),
+            base,
+            cycle_length,
+        })
     }
 
-    // Number of distinct copies before ring numbers must be recycled.
-    // Since each copy closes its own rings before the next copy starts,
-    // the same numbers can be safely reused.
-    let cycle_length: usize = if max_ring == 0 {
-        usize::MAX // no ring closures — no cycling needed
-    } else {
-        99 / max_ring as usize
-    };
+    /// Writes all `n` copies into a new string.
+    fn build(&self, n: usize) -> String {
+        let template = &self.template;
+        let mut result =
+            String::with_capacity((template.literal_len() + template.slots.len() * 2) * n);
+        for i in 0..n {
+            self.write_unit(&mut result, i);
+        }
+        result
+    }
 
-    let mut result = String::with_capacity(smiles_raw.len() * n);
-    for i in 0..n {
-        let slot = i % cycle_length;
-        let offset = slot as u32 * max_ring;
-        result.push_str(&renumber_ring_closures(smiles_raw, offset));
+    /// Appends copy `i` (0-based) of the repeat unit to `out`, preceded by
+    /// the bond to the previous copy.
+    fn write_unit(&self, out: &mut String, i: usize) {
+        if i > 0 {
+            out.push_str(&self.bond);
+        }
+        let offset = self.base + (i % self.cycle_length) as u32 * self.template.max_ring;
+        self.template.write(out, offset);
+    }
+
+    /// Highest ring-closure number used by a chain of `n` copies.
+    fn max_ring_number(&self, n: usize) -> u32 {
+        self.base + n.min(self.cycle_length) as u32 * self.template.max_ring
     }
-    Ok(result)
+}
+
+/// Number of copies of a unit using `max_ring` ring closures that fit in
+/// `base + 1..=99` before ring numbers must be recycled.
+///
+/// Since each copy closes its own rings before the next copy starts, the same
+/// numbers can be safely reused.
+fn ring_cycle_length(max_ring: u32, base: u32) -> Result<usize, PolySimError> {
+    // Pathological case: a single copy already overflows SMILES ring numbers.
+    let available = 99u32.saturating_sub(base);
+    if max_ring > available {
+        return Err(PolySimError::RingNumberOverflow {
+            max_ring,
+            max_supported: available,
+        });
+    }
+    // No ring closures (division by zero) — no cycling needed.
+    Ok(available
+        .checked_div(max_ring)
+        .map_or(usize::MAX, |copies| copies as usize))
 }
 
 /// Builds the SMILES string for a copolymer from a heterogeneous sequence of
 /// repeat-unit SMILES fragments.
 ///
-/// Ring closure numbers are renumbered globally so they never collide across
-/// consecutive units, regardless of which unit type follows which.
+/// Consecutive units must have compatible bonding descriptors (see
+/// [`check_descriptor_pairing`]) and are joined by the bond written next to
+/// them (see [`join_bond`]). Ring closure numbers are renumbered globally
+/// so they never collide across consecutive units, regardless of which unit
+/// type follows which.
 pub(crate) fn build_copolymer_smiles(unit_sequence: &[&str]) -> Result<String, PolySimError> {
-    // Compute max ring number across ALL distinct units.
-    let global_max_ring = unit_sequence
-        .iter()
-        .map(|u| max_ring_number(u))
-        .max()
-        .unwrap_or(0);
+    let body = build_copolymer_smiles_from(unit_sequence, 0)?;
+    Ok(saturate_chain_ends(&body, true, true))
+}
 
-    if global_max_ring > 99 {
-        return Err(PolySimError::RingNumberOverflow {
-            max_ring: global_max_ring,
-            max_supported: 99,
-        });
+/// [`build_copolymer_smiles`] with ring-closure numbers starting above `base`.
+fn build_copolymer_smiles_from(unit_sequence: &[&str], base: u32) -> Result<String, PolySimError> {
+    // Validate and scan each distinct unit once.
+    let mut templates: HashMap<&str, RingTemplate> = HashMap::new();
+    for &unit in unit_sequence {
+        if !templates.contains_key(unit) {
+            validate_repeat_unit(unit)?;
+            unit_end_bonds(unit)?;
+            templates.insert(unit, RingTemplate::new(&normalize_repeat_unit(unit)));
+        }
     }
 
-    let cycle_length: usize = if global_max_ring == 0 {
-        usize::MAX
-    } else {
-        99 / global_max_ring as usize
-    };
+    // Each distinct (tail, head) pair only needs to be checked once.
+    let mut bonds: HashMap<(&str, &str), &str> = HashMap::new();
+    for pair in unit_sequence.windows(2) {
+        if let Entry::Vacant(entry) = bonds.entry((pair[0], pair[1])) {
+            check_descriptor_pairing(pair[0], pair[1])?;
+            entry.insert(join_bond(pair[0], pair[1])?);
+        }
+    }
+    let global_max_ring = templates.values().map(|t| t.max_ring).max().unwrap_or(0);
+    let cycle_length = ring_cycle_length(global_max_ring, base)?;
 
     let total_len: usize = unit_sequence.iter().map(|u| u.len()).sum();
     let mut result = String::with_capacity(total_len + unit_sequence.len() * 4);
 
-    for (i, &unit) in unit_sequence.iter().enumerate() {
+    for (i, unit) in unit_sequence.iter().enumerate() {
+        if i > 0 {
+            result.push_str(bonds[&(unit_sequence[i - 1], *unit)]);
+        }
         let slot = i % cycle_length;
-        let offset = slot as u32 * global_max_ring;
-        result.push_str(&renumber_ring_closures(unit, offset));
+        let offset = base + slot as u32 * global_max_ring;
+        templates[unit].write(&mut result, offset);
     }
 
     Ok(result)
 }
 
+/// A SMILES fragment pre-split around its ring-closure numbers.
+///
+/// Built by a single scan, so that writing many renumbered copies of the same
+/// repeat unit does not re-parse it each time. [`RingTemplate::write`] produces
+/// exactly the output of [`renumber_ring_closures`].
+pub(crate) struct RingTemplate {
+    /// Literal text between ring-closure numbers; one more entry than `slots`.
+    literals: Vec<String>,
+    /// Ring-closure numbers, with `true` when written in `%dd` notation.
+    slots: Vec<(u32, bool)>,
+    /// Highest ring-closure number in the fragment.
+    pub(crate) max_ring: u32,
+}
+
+impl RingTemplate {
+    pub(crate) fn new(smiles: &str) -> Self {
+        let mut literals = Vec::new();
+        let mut slots = Vec::new();
+        let mut current = String::new();
+        let mut in_bracket = false;
+        let mut chars = smiles.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '[' => {
+                    in_bracket = true;
+                    current.push(c);
+                }
+                ']' => {
+                    in_bracket = false;
+                    current.push(c);
+                }
+                _ if in_bracket => current.push(c),
+                '%' => match (chars.next(), chars.next()) {
+                    (Some(d1), Some(d2)) if d1.is_ascii_digit() && d2.is_ascii_digit() => {
+                        let n = (d1 as u32 - '0' as u32) * 10 + (d2 as u32 - '0' as u32);
+                        literals.push(std::mem::take(&mut current));
+                        slots.push((n, true));
+                    }
+                    (d1, d2) => {
+                        current.push('%');
+                        current.extend(d1);
+                        current.extend(d2);
+                    }
+                },
+                c if c.is_ascii_digit() => {
+                    literals.push(std::mem::take(&mut current));
+                    slots.push((c as u32 - '0' as u32, false));
+                }
+                _ => current.push(c),
+            }
+        }
+        literals.push(current);
+
+        let max_ring = slots.iter().map(|&(n, _)| n).max().unwrap_or(0);
+        Self {
+            literals,
+            slots,
+            max_ring,
+        }
+    }
+
+    /// Length of the literal text, ring-closure numbers excluded.
+    fn literal_len(&self) -> usize {
+        self.literals.iter().map(String::len).sum()
+    }
+
+    /// Appends the fragment to `out` with every ring-closure number shifted by `offset`.
+    pub(crate) fn write(&self, out: &mut String, offset: u32) {
+        out.push_str(&self.literals[0]);
+        for (&(n, two_digit), literal) in self.slots.iter().zip(&self.literals[1..]) {
+            let new_n = n + offset;
+            if two_digit || new_n > 9 {
+                out.push('%');
+                if new_n < 10 {
+                    out.push('0');
+                }
+                out.push_str(&new_n.to_string());
+            } else {
+                out.push(char::from_digit(new_n, 10).unwrap());
+            }
+            out.push_str(literal);
+        }
+    }
+}
+
 /// Returns the highest ring-closure number used in a SMILES string.
 ///
 /// Digits inside `[...]` (isotopes, hydrogen counts, charges, atom classes)
-/// are ignored.
+/// are ignored. A ring bond symbol before the number (`C=1CCCCC=1`) is kept
+/// as literal text, so bond-prefixed closures count like plain ones.
 pub(crate) fn max_ring_number(smiles: &str) -> u32 {
     let mut max = 0u32;
     let mut in_bracket = false;
@@ -701,6 +1909,522 @@ pub(crate) fn collect_smiles_segments(segs: &[BigSmilesSegment]) -> String {
         .collect()
 }
 
+/// Caps dangling bonds of a repeat-unit fragment with hydrogen.
+///
+/// Bonding descriptors (`[$]`, `[<]`, `[>]`, `[]`) may leave bond symbols with
+/// nothing to attach to once stripped: a leading `=`, a trailing `-`, an empty
+/// branch `()` or `(=)`. These are removed so the open valences are filled by
+/// implicit hydrogens and the fragment is valid SMILES on its own. Leftover
+/// descriptors are dropped too. Fragments without dangling bonds are returned
+/// unchanged.
+pub(crate) fn cap_dangling_bonds(smiles: &str) -> String {
+    let mut tokens = smiles_tokens(smiles);
+    tokens.retain(|t| !is_bonding_descriptor(t));
+
+    loop {
+        let before = tokens.len();
+        let mut kept: Vec<&str> = Vec::with_capacity(tokens.len());
+        for (i, &token) in tokens.iter().enumerate() {
+            let next = tokens.get(i + 1).copied();
+            let dangling = is_bond_symbol(token)
+                && (kept.is_empty() || next.is_none_or(|n| n == ")" || is_bond_symbol(n)));
+            if dangling {
+                continue;
+            }
+            if token == ")" && kept.last() == Some(&"(") {
+                kept.pop();
+                continue;
+            }
+            kept.push(token);
+        }
+        tokens = kept;
+        if tokens.len() == before {
+            break;
+        }
+    }
+    tokens.concat()
+}
+
+/// Writes a repeat unit as it appears in built chains: dangling bonds capped
+/// (see [`cap_dangling_bonds`]) and Kekulé six-membered rings in aromatic form
+/// (see [`aromatize_kekule_rings`]).
+pub(crate) fn normalize_repeat_unit(smiles: &str) -> String {
+    aromatize_kekule_rings(&cap_dangling_bonds(smiles))
+}
+
+/// Fills the open valence left at the free ends of a built chain.
+///
+/// Unbracketed atoms take the missing hydrogen implicitly, but a bracket atom
+/// keeps its written count: a stereocentre such as `[C@@H]` ending the chain
+/// would otherwise lose the hydrogen replacing the bond to the next unit, so
+/// tactic and atactic chains would differ in mass and formula. The first atom
+/// (when `head` is set) and the last backbone atom (when `tail` is set) get
+/// one more hydrogen if they carry a chirality mark, which no longer applies
+/// and is dropped. Other atoms are left unchanged.
+pub(crate) fn saturate_chain_ends(smiles: &str, head: bool, tail: bool) -> String {
+    let tokens = smiles_tokens(smiles);
+    let (first, last) = end_atoms(&tokens);
+
+    let mut out = String::with_capacity(smiles.len() + 2);
+    for (i, &token) in tokens.iter().enumerate() {
+        let open = (head && Some(i) == first) || (tail && Some(i) == last);
+        match open.then(|| saturate_stereocentre(token)).flatten() {
+            Some(saturated) => out.push_str(&saturated),
+            None => out.push_str(token),
+        }
+    }
+    out
+}
+
+/// Token indices of the first atom and of the last backbone atom (outside any
+/// branch) of the first molecule in `tokens`.
+fn end_atoms(tokens: &[&str]) -> (Option<usize>, Option<usize>) {
+    let is_atom = |token: &str| token.starts_with(|c: char| c == '[' || c.is_ascii_alphabetic());
+    let first = tokens.iter().position(|t| is_atom(t));
+    let mut depth = 0usize;
+    let mut last = None;
+    for (i, &token) in tokens.iter().enumerate() {
+        match token {
+            "(" => depth += 1,
+            ")" => depth = depth.saturating_sub(1),
+            "." => break,
+            _ if depth == 0 && is_atom(token) => last = Some(i),
+            _ => {}
+        }
+    }
+    (first, last)
+}
+
+/// Rewrites a chiral bracket atom with one more hydrogen and no chirality mark
+/// (`[C@@H]` → `[CH2]`, `[13C@](F)` → `[13CH](F)`). Returns `None` for other
+/// tokens.
+fn saturate_stereocentre(token: &str) -> Option<String> {
+    let inner = token.strip_prefix('[')?.strip_suffix(']')?;
+    let at = inner.find('@')?;
+    let (head, rest) = inner.split_at(at);
+    let rest = rest.trim_start_matches('@');
+    // Extended chirality classes: @TH1, @AL2, @SP3, @TB12, @OH30.
+    let rest = match rest.get(..2) {
+        Some("TH" | "AL" | "SP" | "TB" | "OH") => {
+            rest[2..].trim_start_matches(|c: char| c.is_ascii_digit())
+        }
+        _ => rest,
+    };
+    let (hydrogens, rest) = match rest.strip_prefix('H') {
+        Some(after) => {
+            let digits = after.len() - after.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            (after[..digits].parse().unwrap_or(1u32), &after[digits..])
+        }
+        None => (0, rest),
+    };
+    let count = hydrogens + 1;
+    let count = if count == 1 {
+        String::new()
+    } else {
+        count.to_string()
+    };
+    Some(format!("[{head}H{count}{rest}]"))
+}
+
+/// Rewrites six-membered rings written in Kekulé form with aromatic atoms, so
+/// `C1=CC=CC=C1` and `c1ccccc1` give the same chain.
+///
+/// A ring qualifies when its six atoms are unbracketed `C` or `N` and each
+/// has exactly one double bond, made inside the ring or within an already
+/// aromatic ring (fused systems such as naphthalene). Its atoms are then
+/// lowercased and its bond symbols dropped; a bond between two aromatic
+/// atoms of different rings (biphenyl) is written as an explicit `-` so it
+/// stays single. Hydrogen counts, hence formula and mass, are unchanged, but
+/// the group contributions see aromatic carbons instead of alternating
+/// double bonds. Other fragments are returned unchanged.
+fn aromatize_kekule_rings(smiles: &str) -> String {
+    struct Bond {
+        a: usize,
+        b: usize,
+        double: bool,
+        /// Tokens of the explicit bond symbols (both ends of a ring closure).
+        symbols: Vec<usize>,
+        /// Token before which a bond symbol can be inserted.
+        site: usize,
+    }
+
+    if !smiles.contains('=') {
+        return smiles.to_string();
+    }
+    let mut tokens = smiles_tokens(smiles);
+    let mut atoms: Vec<usize> = Vec::new();
+    let mut bonds: Vec<Bond> = Vec::new();
+    let mut prev: Option<usize> = None;
+    let mut branches: Vec<Option<usize>> = Vec::new();
+    let mut pending: Option<usize> = None;
+    let mut open_rings: HashMap<&str, (usize, Option<usize>)> = HashMap::new();
+    let is_double =
+        |symbol: Option<usize>, tokens: &[&str]| symbol.is_some_and(|t| tokens[t] == "=");
+
+    for (t, &token) in tokens.iter().enumerate() {
+        match token {
+            "(" => branches.push(prev),
+            ")" => prev = branches.pop().flatten(),
+            "." => prev = None,
+            _ if is_bond_symbol(token) => pending = Some(t),
+            _ if token.starts_with(|c: char| c.is_ascii_digit() || c == '%') => {
+                let Some(current) = prev else {
+                    return smiles.to_string();
+                };
+                if let Some((other, opening)) = open_rings.remove(token) {
+                    let symbols: Vec<usize> = opening.into_iter().chain(pending).collect();
+                    bonds.push(Bond {
+                        a: other,
+                        b: current,
+                        double: symbols.iter().any(|&k| tokens[k] == "="),
+                        symbols,
+                        site: t,
+                    });
+                } else {
+                    open_rings.insert(token, (current, pending));
+                }
+                pending = None;
+            }
+            _ => {
+                let idx = atoms.len();
+                atoms.push(t);
+                if let Some(p) = prev {
+                    bonds.push(Bond {
+                        a: p,
+                        b: idx,
+                        double: is_double(pending, &tokens),
+                        symbols: pending.into_iter().collect(),
+                        site: t,
+                    });
+                }
+                pending = None;
+                prev = Some(idx);
+            }
+        }
+    }
+    if !open_rings.is_empty() {
+        return smiles.to_string();
+    }
+
+    // Candidate atoms and, for each, the partner of its only double bond.
+    let mut adjacency: Vec<Vec<(usize, usize)>> = vec![Vec::new(); atoms.len()];
+    for (k, bond) in bonds.iter().enumerate() {
+        adjacency[bond.a].push((bond.b, k));
+        adjacency[bond.b].push((bond.a, k));
+    }
+    let double_partner: Vec<Option<(usize, usize)>> = (0..atoms.len())
+        .map(|i| {
+            if !matches!(tokens[atoms[i]], "C" | "N") {
+                return None;
+            }
+            let mut doubles = adjacency[i].iter().filter(|&&(_, k)| bonds[k].double);
+            let only = doubles.next().copied();
+            let plain = adjacency[i].iter().all(|&(_, k)| {
+                bonds[k].double || bonds[k].symbols.iter().all(|&s| tokens[s] == "-")
+            });
+            only.filter(|_| doubles.next().is_none() && plain)
+        })
+        .collect();
+
+    // Six-membered rings of candidate atoms, each found once.
+    let mut rings: Vec<Vec<usize>> = Vec::new();
+    let mut seen: HashSet<Vec<usize>> = HashSet::new();
+    for start in (0..atoms.len()).filter(|&i| double_partner[i].is_some()) {
+        let mut stack = vec![vec![start]];
+        while let Some(path) = stack.pop() {
+            let last = *path.last().expect("non-empty path");
+            for &(next, _) in &adjacency[last] {
+                if double_partner[next].is_none() || next < start {
+                    continue;
+                }
+                if path.len() == 6 {
+                    let mut key = path.clone();
+                    key.sort_unstable();
+                    if next == start && seen.insert(key) {
+                        rings.push(path.clone());
+                    }
+                } else if !path.contains(&next) {
+                    let mut longer = path.clone();
+                    longer.push(next);
+                    stack.push(longer);
+                }
+            }
+        }
+    }
+
+    // Aromatize qualifying rings until no more qualify (fused rings).
+    let mut aromatic = vec![false; atoms.len()];
+    let mut ring_bonds: HashSet<usize> = HashSet::new();
+    let mut done = vec![false; rings.len()];
+    loop {
+        let mut changed = false;
+        for (r, ring) in rings.iter().enumerate() {
+            let edges: Vec<usize> = (0..6)
+                .filter_map(|j| {
+                    let (a, b) = (ring[j], ring[(j + 1) % 6]);
+                    adjacency[a].iter().find(|&&(n, _)| n == b).map(|&(_, k)| k)
+                })
+                .collect();
+            let qualifies = ring.iter().all(|&i| {
+                double_partner[i].is_some_and(|(p, k)| {
+                    edges.contains(&k) || (aromatic[p] && ring_bonds.contains(&k))
+                })
+            });
+            if done[r] || !qualifies {
+                continue;
+            }
+            for &i in ring {
+                aromatic[i] = true;
+            }
+            ring_bonds.extend(edges);
+            done[r] = true;
+            changed = true;
+        }
+        if !changed {
+            break;
+        }
+    }
+    if !aromatic.contains(&true) {
+        return smiles.to_string();
+    }
+
+    let mut insert_single: HashSet<usize> = HashSet::new();
+    for (k, bond) in bonds.iter().enumerate() {
+        if !(aromatic[bond.a] && aromatic[bond.b]) {
+            continue;
+        }
+        if ring_bonds.contains(&k) {
+            for &s in &bond.symbols {
+                tokens[s] = "";
+            }
+        } else if bond.symbols.is_empty() {
+            insert_single.insert(bond.site);
+        }
+    }
+    for (i, &t) in atoms.iter().enumerate() {
+        if aromatic[i] {
+            tokens[t] = if tokens[t] == "C" { "c" } else { "n" };
+        }
+    }
+    let mut out = String::with_capacity(smiles.len() + insert_single.len());
+    for (t, token) in tokens.iter().enumerate() {
+        if insert_single.contains(&t) {
+            out.push('-');
+        }
+        out.push_str(token);
+    }
+    out
+}
+
+/// Checks that a repeat unit, once its bonding descriptors are stripped, is
+/// valid SMILES on its own.
+///
+/// Catching a malformed fragment here gives a clear build error instead of a
+/// panic in the property calculators, which assume valid chain SMILES.
+///
+/// Returns [`PolySimError::InvalidRepeatUnit`] with the SMILES parse error.
+pub(crate) fn validate_repeat_unit(smiles_raw: &str) -> Result<(), PolySimError> {
+    let unit = cap_dangling_bonds(smiles_raw);
+    opensmiles::parse(&unit).map(drop).map_err(|e| {
+        PolySimError::InvalidRepeatUnit(format!("'{smiles_raw}' is not valid SMILES: {e}"))
+    })
+}
+
+/// Checks that the tail of repeat unit `left` may bond to the head of `right`.
+///
+/// The head and tail descriptors are the bonding descriptors written first and
+/// last in each fragment. `[$]` pairs with `[$]`, and `[<]` pairs only with
+/// `[>]` (and vice versa), with matching indices. Ends without a descriptor,
+/// or with an empty `[]`, are unconstrained.
+pub(crate) fn check_descriptor_pairing(left: &str, right: &str) -> Result<(), PolySimError> {
+    fn descriptor<'a>(token: Option<&&'a str>) -> Option<&'a str> {
+        token
+            .copied()
+            .filter(|t| is_bonding_descriptor(t))
+            .map(|t| &t[1..t.len() - 1])
+            .filter(|inner| !inner.is_empty())
+    }
+    let left_tokens = smiles_tokens(left);
+    let right_tokens = smiles_tokens(right);
+    let (Some(tail), Some(head)) = (
+        descriptor(left_tokens.last()),
+        descriptor(right_tokens.first()),
+    ) else {
+        return Ok(());
+    };
+
+    let (tail_kind, tail_index) = tail.split_at(1);
+    let (head_kind, head_index) = head.split_at(1);
+    let kinds_pair = matches!((tail_kind, head_kind), ("$", "$") | ("<", ">") | (">", "<"));
+    if kinds_pair && tail_index == head_index {
+        Ok(())
+    } else {
+        Err(PolySimError::InvalidRepeatUnit(format!(
+            "tail descriptor [{tail}] of '{left}' cannot bond to head descriptor [{head}] of '{right}' \
+             ('<' pairs only with '>', '$' with '$', with matching indices)"
+        )))
+    }
+}
+
+/// Bond symbol joining the tail of repeat unit `left` to the head of `right`.
+///
+/// It is the bond written next to either descriptor (`[$]=CC=[$]` → `=`), or
+/// an empty string for a single bond when neither end has one.
+///
+/// Returns [`PolySimError::InvalidRepeatUnit`] if both ends write a bond and
+/// they differ, or if either unit leaves another bond dangling (see
+/// [`unit_end_bonds`]).
+fn join_bond<'a>(left: &'a str, right: &'a str) -> Result<&'a str, PolySimError> {
+    let (_, tail) = unit_end_bonds(left)?;
+    let (head, _) = unit_end_bonds(right)?;
+    match (tail, head) {
+        (Some(tail), Some(head)) if tail != head => Err(PolySimError::InvalidRepeatUnit(format!(
+            "tail bond '{tail}' of '{left}' does not match head bond '{head}' of '{right}'"
+        ))),
+        (tail, head) => Ok(tail.or(head).unwrap_or("")),
+    }
+}
+
+/// Bond symbols written before the first atom and after the last atom of a
+/// repeat unit, next to its head and tail descriptors when they are kept
+/// (`[$]=CC=[$]` → `(Some("="), Some("="))`).
+///
+/// These bonds join the unit to its neighbours and are capped with hydrogen
+/// only at the two ends of a chain. Returns [`PolySimError::InvalidRepeatUnit`]
+/// if the unit leaves any other bond dangling, such as a descriptor inside a
+/// branch.
+fn unit_end_bonds(smiles_raw: &str) -> Result<(Option<&str>, Option<&str>), PolySimError> {
+    let tokens = smiles_tokens(smiles_raw);
+    let (mut start, mut end) = (0, tokens.len());
+    let (mut head, mut tail) = (None, None);
+    if tokens.first().is_some_and(|t| is_bonding_descriptor(t)) {
+        start = 1;
+    }
+    if tokens.get(start).is_some_and(|t| is_bond_symbol(t)) {
+        head = Some(tokens[start]);
+        start += 1;
+    }
+    if end > start && is_bonding_descriptor(tokens[end - 1]) {
+        end -= 1;
+    }
+    if end > start && is_bond_symbol(tokens[end - 1]) {
+        tail = Some(tokens[end - 1]);
+        end -= 1;
+    }
+    let body = tokens[start..end].concat();
+    if cap_dangling_bonds(&body) != body {
+        return Err(PolySimError::InvalidRepeatUnit(format!(
+            "'{smiles_raw}' leaves a bond dangling inside the repeat unit; only the \
+             bonds next to its head and tail descriptors can join other units"
+        )));
+    }
+    Ok((head, tail))
+}
+
+/// Writes `/` and `\` directional bonds around the backbone double bonds of
+/// `smiles`.
+///
+/// A double bond `a=b` qualifies when it is not in a ring and its backbone
+/// neighbours `p` and `q` are single-bonded to it. `trans()` is called once per
+/// qualifying bond, in order, to pick its geometry. Conjugated double bonds
+/// share the mark of their common single bond. Returns `None` if no double bond
+/// qualifies or the SMILES cannot be parsed.
+fn set_double_bond_geometry(smiles: &str, mut trans: impl FnMut() -> bool) -> Option<String> {
+    let graph = MolGraph::parse(smiles).ok()?;
+    let tokens = smiles_tokens(smiles);
+    let atom_tokens: Vec<usize> = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| t.starts_with(|c: char| c.is_ascii_alphabetic() || c == '[' || c == '*'))
+        .map(|(i, _)| i)
+        .collect();
+    if atom_tokens.len() != graph.atoms.len() {
+        return None;
+    }
+    let bond = |x: usize, y: usize| {
+        graph
+            .neighbors(x)
+            .iter()
+            .find(|&&(nbr, _)| nbr == y)
+            .map(|&(_, k)| &graph.bonds[k])
+    };
+    let is_single = |x: usize, y: usize| bond(x, y).is_some_and(|b| b.order == BondOrder::Single);
+
+    let backbone: Vec<usize> = (0..graph.atoms.len())
+        .filter(|&i| graph.main_chain[i])
+        .collect();
+    // Directional mark of the single bond written just before each atom
+    let mut marks: Vec<Option<char>> = vec![None; graph.atoms.len()];
+    for w in backbone.windows(4) {
+        let [p, a, b, q] = [w[0], w[1], w[2], w[3]];
+        let qualifies = bond(a, b).is_some_and(|d| d.order == BondOrder::Double && !d.in_ring)
+            && is_single(p, a)
+            && is_single(b, q);
+        if !qualifies {
+            continue;
+        }
+        let first = *marks[a].get_or_insert('/');
+        let opposite = if first == '/' { '\\' } else { '/' };
+        marks[q] = Some(if trans() { first } else { opposite });
+    }
+    if marks.iter().all(Option::is_none) {
+        return None;
+    }
+
+    let mut out = String::with_capacity(smiles.len() + marks.len());
+    let mut pending: Option<&str> = None;
+    let mut atom = 0;
+    for (t, token) in tokens.iter().enumerate() {
+        if is_bond_symbol(token) {
+            pending = Some(token);
+            continue;
+        }
+        if atom_tokens.get(atom) == Some(&t) {
+            match marks[atom] {
+                Some(mark) => out.push(mark),
+                None => out.extend(pending),
+            }
+            atom += 1;
+        } else {
+            out.extend(pending);
+        }
+        pending = None;
+        out.push_str(token);
+    }
+    out.extend(pending);
+    Some(out)
+}
+
+/// Splits a SMILES string into tokens: bracket atoms, two-letter organic atoms,
+/// `%nn` ring numbers, and single characters.
+fn smiles_tokens(smiles: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut rest = smiles;
+    while let Some(c) = rest.chars().next() {
+        let len = match c {
+            '[' => rest.find(']').map_or(rest.len(), |end| end + 1),
+            '%' => 3.min(rest.len()),
+            'C' if rest[1..].starts_with('l') => 2,
+            'B' if rest[1..].starts_with('r') => 2,
+            _ => c.len_utf8(),
+        };
+        tokens.push(&rest[..len]);
+        rest = &rest[len..];
+    }
+    tokens
+}
+
+fn is_bond_symbol(token: &str) -> bool {
+    matches!(token, "-" | "=" | "#" | "$" | ":" | "/" | "\\")
+}
+
+fn is_bonding_descriptor(token: &str) -> bool {
+    token
+        .strip_prefix('[')
+        .and_then(|t| t.strip_suffix(']'))
+        .is_some_and(|inner| inner.is_empty() || inner.starts_with(['$', '<', '>']))
+}
+
 /// Computes the fraction of monomer A at position `i` in a chain of length `n`.
 pub(crate) fn gradient_fraction(profile: &GradientProfile, i: usize, n: usize) -> f64 {
     match profile {
@@ -723,60 +2447,57 @@ pub(crate) fn gradient_fraction(profile: &GradientProfile, i: usize, n: usize) -
     }
 }
 
-/// Converts a linear SMILES into a cyclic one by inserting ring closure label "1"
-/// after the first atom and appending "1" at the end.
+/// Closes `linear` into a ring: ring-closure number `label` is written after
+/// its first atom and after its last backbone atom (before that atom's
+/// branches, where OpenSMILES puts ring bonds), the latter preceded by the
+/// ring bond symbol `bond`.
 ///
-/// Handles bracket atoms (`[...]`) and two-letter organic atoms (`Cl`, `Br`).
-fn make_cyclic_smiles(linear: &str) -> String {
-    let mut result = String::with_capacity(linear.len() + 2);
-    let mut chars = linear.chars().peekable();
+/// Returns [`PolySimError::BuildStrategy`] if the backbone has fewer than
+/// 3 atoms: the closure would duplicate the bond between two atoms (`C1C1`)
+/// or bond an atom to itself (`C11`).
+fn close_ring(linear: &str, label: u32, bond: &str) -> Result<String, PolySimError> {
+    let tokens = smiles_tokens(linear);
+    let backbone = backbone_atom_count(&tokens);
+    if backbone < 3 {
+        return Err(PolySimError::BuildStrategy(format!(
+            "a ring needs at least 3 backbone atoms, got {backbone}; \
+             use more repeat units"
+        )));
+    }
+    let label = if label > 9 {
+        format!("%{label:02}")
+    } else {
+        label.to_string()
+    };
+    let (first, last) = end_atoms(&tokens);
 
-    // Find and copy the first atom, then insert "1"
-    if let Some(c) = chars.next() {
-        if c == '[' {
-            // Bracket atom: copy up to and including ']'
-            result.push(c);
-            for ch in chars.by_ref() {
-                result.push(ch);
-                if ch == ']' {
-                    break;
-                }
-            }
-        } else {
-            result.push(c);
-            // Check for two-letter organic atoms (Cl, Br, Si, etc.)
-            if c.is_ascii_uppercase() {
-                if let Some(&next) = chars.peek() {
-                    if next.is_ascii_lowercase() && next != 'c'
-                        || matches!(
-                            (c, next),
-                            ('C', 'l')
-                                | ('B', 'r')
-                                | ('S', 'i')
-                                | ('S', 'e')
-                                | ('A', 'l')
-                                | ('A', 's')
-                                | ('A', 'r')
-                                | ('A', 't')
-                                | ('M', 'g')
-                                | ('N', 'a')
-                                | ('G', 'e')
-                        )
-                    {
-                        result.push(chars.next().unwrap());
-                    }
-                }
-            }
+    let mut result = String::with_capacity(linear.len() + 2 * label.len());
+    for (i, &token) in tokens.iter().enumerate() {
+        result.push_str(token);
+        if Some(i) == first {
+            result.push_str(&label);
+        }
+        if Some(i) == last {
+            result.push_str(bond);
+            result.push_str(&label);
         }
-        result.push('1');
     }
+    Ok(result)
+}
 
-    // Copy the rest
-    for ch in chars {
-        result.push(ch);
+/// Number of atoms of `tokens` outside any branch.
+fn backbone_atom_count(tokens: &[&str]) -> usize {
+    let mut depth = 0usize;
+    let mut count = 0;
+    for &token in tokens {
+        match token {
+            "(" => depth += 1,
+            ")" => depth = depth.saturating_sub(1),
+            _ if depth == 0 && token.starts_with(|c: char| c == '[' || c.is_ascii_alphabetic()) => {
+                count += 1
+            }
+            _ => {}
+        }
     }
-
-    // Append ring closure at end
-    result.push('1');
-    result
+    count
 }
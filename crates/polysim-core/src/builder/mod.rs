@@ -5,10 +5,20 @@
 //! [`PolymerChain`](crate::PolymerChain) instances.
 
 pub mod branched;
+pub mod cache;
+pub mod elemental;
 pub mod ensemble;
+pub mod identify;
+pub mod kinetics;
 pub mod linear;
 pub mod strategy;
 
+pub use cache::{RepeatUnitCache, UnitContribution};
+pub use elemental::{match_elemental_analysis, ElementalComposition, ElementalMatch};
 pub use ensemble::EnsembleBuilder;
-pub use linear::GradientProfile;
-pub use strategy::BuildStrategy;
+pub use kinetics::{conversion_from_dp, StepOrChain};
+pub use linear::{
+    canonical_repeat_unit, dp_from_mn, max_ring_number_for_chain, repeat_units, DoubleBondGeometry,
+    GradientProfile,
+};
+pub use strategy::{BuildStrategy, RoundingMode};
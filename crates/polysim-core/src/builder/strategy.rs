@@ -3,7 +3,11 @@
 /// All mass-based variants use SI/chemistry conventions:
 /// - molecular weights in **g/mol**
 /// - monoisotopic masses in **g/mol**
+///
+/// New strategies may be added in minor releases, so matches outside this
+/// crate need a wildcard arm.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum BuildStrategy {
     /// Generate exactly `n` repeat units.
     ByRepeatCount(usize),
@@ -11,8 +15,9 @@ pub enum BuildStrategy {
     /// Target number-average molecular weight (Mn) in g/mol.
     ///
     /// The repeat count is chosen so that the chain Mn is as close as possible
-    /// to the given target. Requires molecular weight calculation to be
-    /// implemented (see `properties::molecular_weight`).
+    /// to the given target, end groups included (see [`RoundingMode`] to round
+    /// down or up instead). Requires molecular weight
+    /// calculation to be implemented (see `properties::molecular_weight`).
     ByTargetMn(f64),
 
     /// Target an exact (monoisotopic) chain mass in g/mol.
@@ -21,4 +26,42 @@ pub enum BuildStrategy {
     /// possible to the given target. Requires molecular weight calculation to be
     /// implemented (see `properties::molecular_weight`).
     ByExactMass(f64),
+
+    /// Target a total atom count, implicit hydrogens included.
+    ///
+    /// The repeat count is chosen so that the chain atom count is as close as
+    /// possible to the given target, e.g. to size a coarse-grained model (see
+    /// `properties::formula::total_atom_count`).
+    ByAtomCount(usize),
+}
+
+/// How a mass-based [`BuildStrategy`] rounds the fractional repeat count that
+/// matches its target to a whole number of units.
+///
+/// Repeat counts that land on the target to within floating-point error are
+/// kept as they are in every mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    /// Closest repeat count to the target (default).
+    #[default]
+    Nearest,
+    /// Largest repeat count whose chain does not exceed the target.
+    Floor,
+    /// Smallest repeat count whose chain is not below the target.
+    Ceil,
+}
+
+impl RoundingMode {
+    /// Relative slack under which a fractional repeat count counts as whole.
+    const TOLERANCE: f64 = 1e-9;
+
+    /// Rounds the fractional repeat count `n` according to the mode.
+    pub(crate) fn apply(self, n: f64) -> f64 {
+        let slack = Self::TOLERANCE * n.abs().max(1.0);
+        match self {
+            Self::Nearest => n.round(),
+            Self::Floor => (n + slack).floor(),
+            Self::Ceil => (n - slack).ceil(),
+        }
+    }
 }
@@ -7,6 +7,10 @@ pub enum PolySimError {
     #[error("BigSMILES parse error: {0}")]
     Parse(#[from] bigsmiles::ParseError),
 
+    /// A BigSMILES string was rejected by [`parse_strict`](crate::parse_strict).
+    #[error("Strict BigSMILES validation failed: {0}")]
+    StrictValidation(String),
+
     /// The [`BuildStrategy`](crate::BuildStrategy) is invalid or not yet supported.
     #[error("Invalid build strategy: {0}")]
     BuildStrategy(String),
@@ -27,6 +31,12 @@ pub enum PolySimError {
         need_min: usize,
     },
 
+    /// A repeat unit is not valid SMILES, or the bonding descriptors of
+    /// consecutive repeat units cannot pair, e.g. two `[<]` ends meeting in an
+    /// AA/BB step-growth polymer.
+    #[error("Invalid repeat unit: {0}")]
+    InvalidRepeatUnit(String),
+
     /// The block ratios supplied to a block copolymer ensemble do not sum to 1.0.
     #[error("Block ratios must sum to 1.0 (got {sum:.4})")]
     InvalidBlockRatios { sum: f64 },
@@ -39,11 +49,35 @@ pub enum PolySimError {
     #[error("Cannot create an ensemble with zero chains")]
     EmptyEnsemble,
 
-    /// A single repeat unit already uses more than 99 distinct ring-closure numbers,
-    /// which exceeds the SMILES specification.
+    /// A single repeat unit already uses more ring-closure numbers than are
+    /// available: 99 in SMILES, minus any offset reserved for end groups.
     #[error(
         "Ring number overflow: the repeat unit uses {max_ring} ring closure(s), \
          SMILES maximum is {max_supported}"
     )]
     RingNumberOverflow { max_ring: u32, max_supported: u32 },
+
+    /// An atom's element has no tabulated most-abundant isotope, so the
+    /// monoisotopic mass is undefined (set one with
+    /// [`MassTable::with_monoisotopic_mass`](crate::properties::molecular_weight::MassTable::with_monoisotopic_mass)).
+    #[error("No monoisotopic mass known for element {element}")]
+    UnknownIsotopeMass { element: String },
+
+    /// An atom of a chain SMILES has more bonds and hydrogens than its element
+    /// allows, e.g. a five-bonded carbon left by a malformed concatenation.
+    /// `atom_index` counts heavy atoms from 0 in SMILES order.
+    #[error("Invalid valence: atom {atom_index} ({element}) has too many bonds")]
+    InvalidValence { atom_index: usize, element: String },
+
+    /// A per-repeat-unit value, such as a homopolymer Tg, was not supplied for
+    /// one of the units of a chain.
+    #[error("No {property} given for repeat unit '{unit}'")]
+    MissingUnitProperty {
+        property: &'static str,
+        unit: String,
+    },
+
+    /// Writing a chain to an output stream failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
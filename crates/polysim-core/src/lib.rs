@@ -15,6 +15,8 @@
 //! 2. **Build** a chain with one of the builders in [`builder`].
 //! 3. **Compute** properties via [`properties`].
 //!
+//! For a single homopolymer, [`analyze_bigsmiles`] does all three in one call.
+//!
 //! ## Quick start
 //!
 //! ```rust
@@ -32,14 +34,19 @@
 //! assert!((chain.mn - 282.554).abs() < 0.01, "Mn = {} g/mol", chain.mn);
 //! ```
 
+pub mod analysis;
 pub mod builder;
 pub mod distribution;
 pub mod error;
 pub mod polymer;
 pub mod properties;
+pub mod validation;
 
+pub use analysis::{analyze_bigsmiles, PerBackboneAtom, PropertySummary, SummaryField};
 pub use bigsmiles::{parse, BigSmiles};
-pub use builder::{BuildStrategy, EnsembleBuilder, GradientProfile};
+pub use builder::{BuildStrategy, EnsembleBuilder, GradientProfile, RoundingMode};
 pub use distribution::ChainLengthDistribution;
 pub use error::PolySimError;
 pub use polymer::{Architecture, MonomerUnit, PolymerChain, PolymerEnsemble};
+pub use properties::property::PolymerProperty;
+pub use validation::parse_strict;
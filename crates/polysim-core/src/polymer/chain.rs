@@ -1,7 +1,18 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::builder::linear::cap_dangling_bonds;
+use crate::error::PolySimError;
+use crate::properties::{
+    canonical::canonical_smiles,
+    graph::{element_symbol, max_valence, MolGraph},
+};
+
 /// Composition unit for copolymer chains.
 ///
 /// Stores a single repeat unit type with its molar fraction in the chain.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct MonomerUnit {
     /// SMILES string of the repeat unit (e.g. "CC" for ethylene).
     pub smiles: String,
@@ -17,10 +28,17 @@ impl MonomerUnit {
             fraction,
         }
     }
+
+    /// Canonical SMILES of the repeat unit with its open bonds capped by
+    /// hydrogens, so that units written with different atom orderings or
+    /// bonding descriptors compare equal.
+    pub fn canonical_smiles(&self) -> String {
+        PolymerChain::new(cap_dangling_bonds(&self.smiles), 1, 0.0).canonical_smiles()
+    }
 }
 
 /// Polymer chain architecture classification.
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
 pub enum Architecture {
     /// Simple linear chain (default).
     #[default]
@@ -39,11 +57,24 @@ pub enum Architecture {
     Graft { graft_fraction: f64 },
 }
 
+/// Species of a [`PolymerChain`], returned by [`PolymerChain::canonical_key`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CanonicalKey {
+    /// Canonical SMILES of the chain.
+    pub smiles: String,
+    /// Number of repeat units.
+    pub repeat_count: usize,
+}
+
 /// A single, fully resolved polymer chain instance.
 ///
 /// A `PolymerChain` is the output of a builder: it holds the concrete SMILES
 /// string for the generated chain together with metadata computed at build time.
-#[derive(Debug, Clone)]
+///
+/// Equality compares every field as written. Use [`Self::canonical_eq`] or
+/// [`Self::canonical_key`] to recognise the same species written with
+/// different atom orderings.
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct PolymerChain {
     /// SMILES string representing this specific chain.
     pub smiles: String,
@@ -56,23 +87,33 @@ pub struct PolymerChain {
     /// Homopolymers have a single element with fraction 1.0.
     /// Empty when composition was not tracked by the builder.
     pub composition: Vec<MonomerUnit>,
+    /// Index into `composition` of each incorporated repeat unit, in chain order.
+    ///
+    /// Empty for homopolymers and when the sequence was not tracked by the builder.
+    pub sequence: Vec<usize>,
     /// Polymer architecture (linear by default).
     pub architecture: Architecture,
+    /// Number of counter-ions written as separate `.`-fragments at the end of
+    /// `smiles` (0 unless the builder was asked to neutralise the chain).
+    pub counterion_count: usize,
 }
 
 impl PolymerChain {
     /// Creates a new `PolymerChain` with the given SMILES, repeat count, and Mn.
     ///
-    /// `composition` defaults to empty and `architecture` to `Linear`.
-    /// Use the builder methods [`Self::with_composition`] and
-    /// [`Self::with_architecture`] to populate these fields.
+    /// `composition` and `sequence` default to empty and `architecture` to
+    /// `Linear`. Use the builder methods [`Self::with_composition`],
+    /// [`Self::with_sequence`] and [`Self::with_architecture`] to populate
+    /// these fields.
     pub fn new(smiles: String, repeat_count: usize, mn: f64) -> Self {
         Self {
             smiles,
             repeat_count,
             mn,
             composition: Vec::new(),
+            sequence: Vec::new(),
             architecture: Architecture::default(),
+            counterion_count: 0,
         }
     }
 
@@ -82,11 +123,288 @@ impl PolymerChain {
         self
     }
 
+    /// Attaches the repeat-unit sequence (indices into `composition`) to this chain.
+    pub fn with_sequence(mut self, sequence: Vec<usize>) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
     /// Attaches architecture metadata to this chain.
     pub fn with_architecture(mut self, architecture: Architecture) -> Self {
         self.architecture = architecture;
         self
     }
+
+    /// Records the number of counter-ions included in `smiles`.
+    pub fn with_counterion_count(mut self, count: usize) -> Self {
+        self.counterion_count = count;
+        self
+    }
+
+    /// Returns a canonical SMILES for this chain.
+    ///
+    /// Any two SMILES strings describing the same constitution yield the same
+    /// canonical string. Stereochemistry is not encoded. If `smiles` cannot be
+    /// parsed it is returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::PolymerChain;
+    ///
+    /// let a = PolymerChain::new("OCC".to_string(), 1, 0.0);
+    /// let b = PolymerChain::new("C(O)C".to_string(), 1, 0.0);
+    /// assert_eq!(a.canonical_smiles(), b.canonical_smiles());
+    /// ```
+    pub fn canonical_smiles(&self) -> String {
+        match MolGraph::parse(&self.smiles) {
+            Ok(graph) => canonical_smiles(&graph),
+            Err(_) => self.smiles.clone(),
+        }
+    }
+
+    /// Returns the key identifying the species of this chain: its
+    /// [canonical SMILES](Self::canonical_smiles) and repeat count.
+    ///
+    /// The key is computed once, so collect keys rather than chains to
+    /// deduplicate or count unique species. `mn`, `composition`, `sequence`,
+    /// `architecture` and `counterion_count` are not part of it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashSet;
+    /// use polysim_core::PolymerChain;
+    ///
+    /// let chains = [
+    ///     PolymerChain::new("CCCC".to_string(), 2, 0.0),
+    ///     PolymerChain::new("C(C)CC".to_string(), 2, 0.0),
+    ///     PolymerChain::new("CC(C)C".to_string(), 2, 0.0),
+    /// ];
+    /// let species: HashSet<_> = chains.iter().map(PolymerChain::canonical_key).collect();
+    /// assert_eq!(species.len(), 2);
+    /// ```
+    pub fn canonical_key(&self) -> CanonicalKey {
+        CanonicalKey {
+            smiles: self.canonical_smiles(),
+            repeat_count: self.repeat_count,
+        }
+    }
+
+    /// Returns `true` if both chains are the same species: same repeat count
+    /// and same [canonical SMILES](Self::canonical_smiles).
+    ///
+    /// Chains with identical SMILES are compared without canonicalising.
+    pub fn canonical_eq(&self, other: &Self) -> bool {
+        self.repeat_count == other.repeat_count
+            && (self.smiles == other.smiles || self.canonical_smiles() == other.canonical_smiles())
+    }
+
+    /// Checks that every atom of the chain has a chemically sensible valence.
+    ///
+    /// For each atom of the SMILES organic subset (B, C, N, O, P, S, halogens),
+    /// the bond orders plus the hydrogens must not exceed the highest normal
+    /// valence of the element, adjusted for its formal charge: `[NH4+]` and
+    /// `[O-]` pass, a five-bonded carbon does not. Aromatic bonds count as one.
+    /// Metals, counter-ions and wildcards are not checked.
+    ///
+    /// The builders never produce such atoms from valid repeat units; this
+    /// catches malformed BigSMILES concatenations before properties are
+    /// computed.
+    ///
+    /// # Errors
+    ///
+    /// - [`PolySimError::InvalidValence`] for the first over-bonded atom.
+    /// - [`PolySimError::InvalidRepeatUnit`] if `smiles` is not valid SMILES.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{PolySimError, PolymerChain};
+    ///
+    /// let good = PolymerChain::new("CC(C)CC(C)".to_string(), 2, 0.0);
+    /// assert!(good.validate().is_ok());
+    ///
+    /// let bad = PolymerChain::new("CC(C)(C)(C)C".to_string(), 1, 0.0);
+    /// assert!(matches!(
+    ///     bad.validate(),
+    ///     Err(PolySimError::InvalidValence { atom_index: 1, .. })
+    /// ));
+    /// ```
+    pub fn validate(&self) -> Result<(), PolySimError> {
+        let graph = MolGraph::parse(&self.smiles).map_err(PolySimError::InvalidRepeatUnit)?;
+        for (i, atom) in graph.atoms.iter().enumerate() {
+            let Some(max) = max_valence(atom.atomic_number, atom.charge) else {
+                continue;
+            };
+            if graph.bond_order_sum(i) + atom.hydrogens > max {
+                return Err(PolySimError::InvalidValence {
+                    atom_index: i,
+                    element: element_symbol(atom.atomic_number).to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the repeat-unit sequence as letters, e.g. `"ABBABAB"`.
+    ///
+    /// Each unit is named after its position in `composition`: the first is
+    /// `A`, the second `B`, and so on (`?` past `Z`). Chains without a recorded
+    /// sequence, such as homopolymers, are written as `A` repeated
+    /// `repeat_count` times.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    ///
+    /// let bs = parse("{[$]CC[$],[$]CC(C)[$]}").unwrap();
+    /// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(6))
+    ///     .alternating_copolymer()
+    ///     .unwrap();
+    /// assert_eq!(chain.sequence_string(), "ABABAB");
+    /// ```
+    pub fn sequence_string(&self) -> String {
+        if self.sequence.is_empty() {
+            return "A".repeat(self.repeat_count);
+        }
+        self.sequence
+            .iter()
+            .map(|&i| match u8::try_from(i) {
+                Ok(i) if i < 26 => char::from(b'A' + i),
+                _ => '?',
+            })
+            .collect()
+    }
+
+    /// Returns the number of distinct repeat-unit species incorporated in the chain.
+    ///
+    /// Counted from `composition`: units with a zero fraction are skipped and
+    /// units with the same SMILES count once. Homopolymers return 1, binary
+    /// copolymers 2, and chains without a recorded composition 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    ///
+    /// let bs = parse("{[$]CC[$],[$]CC(C)[$]}").unwrap();
+    /// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(6))
+    ///     .alternating_copolymer()
+    ///     .unwrap();
+    /// assert_eq!(chain.species_count(), 2);
+    /// ```
+    pub fn species_count(&self) -> usize {
+        self.composition
+            .iter()
+            .filter(|unit| unit.fraction > 0.0)
+            .map(|unit| unit.smiles.as_str())
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Returns the number of heavy atoms outside the repeat units.
+    ///
+    /// These are the atoms of the initiator and terminator fragments (explicit
+    /// end groups or the begin/end SMILES of the BigSMILES). They are found by
+    /// subtracting the atoms of every incorporated repeat unit, as recorded in
+    /// `composition`, from those of the whole chain. Chains without a recorded
+    /// composition, or whose SMILES cannot be parsed, return 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    ///
+    /// // α,ω-dihydroxy polyethylene
+    /// let bs = parse("O{[]CC[]}O").unwrap();
+    /// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(10))
+    ///     .homopolymer()
+    ///     .unwrap();
+    /// assert_eq!(chain.end_group_atoms(), 2);
+    /// ```
+    pub fn end_group_atoms(&self) -> usize {
+        let heavy_atoms = |smiles: &str| {
+            MolGraph::parse(smiles)
+                .map(|graph| graph.atoms.iter().filter(|a| a.atomic_number != 0).count())
+                .ok()
+        };
+        let Some(total) = heavy_atoms(&self.smiles) else {
+            return 0;
+        };
+        let in_units: Option<usize> = self
+            .composition
+            .iter()
+            .map(|unit| {
+                let copies = (unit.fraction * self.repeat_count as f64).round() as usize;
+                heavy_atoms(&cap_dangling_bonds(&unit.smiles)).map(|atoms| atoms * copies)
+            })
+            .sum();
+        match in_units {
+            Some(in_units) if !self.composition.is_empty() => total.saturating_sub(in_units),
+            _ => 0,
+        }
+    }
+
+    /// Returns `true` if the chain SMILES contains wildcard atoms (`*` or `[*]`).
+    ///
+    /// Wildcards are unresolved attachment points, e.g. from `*` end groups.
+    /// They carry no mass and are skipped by the formula, so the mass and
+    /// formula of such a chain are those of the real atoms only.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    ///
+    /// let bs = parse("*{[]CC[]}*").unwrap();
+    /// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3))
+    ///     .homopolymer()
+    ///     .unwrap();
+    /// assert!(chain.has_wildcards());
+    /// ```
+    pub fn has_wildcards(&self) -> bool {
+        self.smiles.contains('*')
+    }
+
+    /// Returns the fraction of meso (m) dyads among consecutive stereocentres.
+    ///
+    /// Stereocentres are the `@`/`@@` atoms, taken in SMILES order. For a chain
+    /// built by repeating a unit written the same way each time, two consecutive
+    /// centres with the same mark have the same configuration and form a meso
+    /// dyad; opposite marks form a racemo (r) dyad. Isotactic chains give ~1.0,
+    /// syndiotactic ~0.0 and atactic ~0.5. Chains with fewer than two
+    /// stereocentres, or whose SMILES cannot be parsed, have unspecified
+    /// stereochemistry and return 0.5.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+    ///
+    /// let bs = parse("{[]C[C@@H](C)[]}").unwrap();
+    /// let isotactic_pp = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(20))
+    ///     .homopolymer()
+    ///     .unwrap();
+    /// assert_eq!(isotactic_pp.tacticity_fraction(), 1.0);
+    /// ```
+    pub fn tacticity_fraction(&self) -> f64 {
+        let Ok(graph) = MolGraph::parse(&self.smiles) else {
+            return 0.5;
+        };
+        let marks: Vec<u8> = graph
+            .atoms
+            .iter()
+            .map(|atom| atom.chirality)
+            .filter(|&c| c > 0)
+            .collect();
+        if marks.len() < 2 {
+            return 0.5;
+        }
+        let meso = marks.windows(2).filter(|w| w[0] == w[1]).count();
+        meso as f64 / (marks.len() - 1) as f64
+    }
 }
 
 impl std::fmt::Display for PolymerChain {
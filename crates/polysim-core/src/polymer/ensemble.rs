@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 use crate::error::PolySimError;
 
 use super::PolymerChain;
@@ -36,6 +38,28 @@ impl PolymerEnsemble {
         false
     }
 
+    /// Combines two ensembles into one holding the chains of both.
+    ///
+    /// Useful to model a blend of two batches: the merged Mn, Mw and PDI reflect
+    /// the resulting (e.g. bimodal) distribution.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::polymer::{PolymerChain, PolymerEnsemble};
+    ///
+    /// let low = PolymerEnsemble::new(vec![PolymerChain::new("CC".to_string(), 1, 100.0)]).unwrap();
+    /// let high = PolymerEnsemble::new(vec![PolymerChain::new("CCCCCC".to_string(), 3, 300.0)]).unwrap();
+    /// let blend = low.merge(high);
+    ///
+    /// assert_eq!(blend.len(), 2);
+    /// assert!((blend.pdi() - 1.25).abs() < 1e-9);
+    /// ```
+    pub fn merge(mut self, other: PolymerEnsemble) -> PolymerEnsemble {
+        self.chains.extend(other.chains);
+        self
+    }
+
     /// Number-average molecular weight: Mn = Σ Mi / N
     pub fn mn(&self) -> f64 {
         let sum: f64 = self.chains.iter().map(|c| c.mn).sum();
@@ -53,4 +77,193 @@ impl PolymerEnsemble {
     pub fn pdi(&self) -> f64 {
         self.mw() / self.mn()
     }
+
+    /// Number-average degree of polymerization: Xn = Σ xi / N
+    ///
+    /// `xi` is the repeat count of chain *i*.
+    pub fn xn(&self) -> f64 {
+        let sum: f64 = self.chains.iter().map(|c| c.repeat_count as f64).sum();
+        sum / self.chains.len() as f64
+    }
+
+    /// Weight-average degree of polymerization: Xw = Σ Mi·xi / Σ Mi
+    ///
+    /// Each repeat count is weighted by the chain mass, so Xw / Xn equals the
+    /// PDI when every chain is made of the same repeat unit and the end groups
+    /// are negligible.
+    pub fn xw(&self) -> f64 {
+        let sum_mi: f64 = self.chains.iter().map(|c| c.mn).sum();
+        let sum_mi_xi: f64 = self
+            .chains
+            .iter()
+            .map(|c| c.mn * c.repeat_count as f64)
+            .sum();
+        sum_mi_xi / sum_mi
+    }
+
+    /// Writes the molar mass distribution as a CSV histogram.
+    ///
+    /// Chain masses are grouped into bins of `bin_width` g/mol aligned on
+    /// multiples of `bin_width`; every bin from the lightest to the heaviest
+    /// chain is written, empty ones included. Columns are `bin_center` (g/mol),
+    /// `count` (number of chains) and `weight_fraction` (share of the total
+    /// mass), preceded by a header line.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::ErrorKind::InvalidInput`] if `bin_width` is not a positive
+    /// number, and any error raised by `w`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::polymer::{PolymerChain, PolymerEnsemble};
+    ///
+    /// let ensemble = PolymerEnsemble::new(vec![
+    ///     PolymerChain::new("CC".to_string(), 1, 100.0),
+    ///     PolymerChain::new("CCCCCC".to_string(), 3, 300.0),
+    /// ])
+    /// .unwrap();
+    /// let mut csv = Vec::new();
+    /// ensemble.write_distribution_csv(&mut csv, 100.0).unwrap();
+    ///
+    /// let csv = String::from_utf8(csv).unwrap();
+    /// assert_eq!(csv.lines().next(), Some("bin_center,count,weight_fraction"));
+    /// assert_eq!(csv.lines().count(), 4); // header + bins at 150, 250, 350
+    /// ```
+    pub fn write_distribution_csv<W: Write>(&self, mut w: W, bin_width: f64) -> io::Result<()> {
+        check_bin_width(bin_width)?;
+        writeln!(w, "bin_center,count,weight_fraction")?;
+        for (center, count, weight_fraction) in self.mass_histogram(bin_width) {
+            writeln!(w, "{center:.3},{count},{weight_fraction:.6}")?;
+        }
+        Ok(())
+    }
+
+    /// Draws the molar mass distribution as a standalone SVG bar chart.
+    ///
+    /// Uses the bins of [`Self::write_distribution_csv`]: one `<rect>` per bin,
+    /// empty ones included (zero height), whose height is the weight fraction
+    /// of the bin relative to the tallest one. The x axis is labelled with the
+    /// lower and upper bin edges (g/mol). Open the output in any browser.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::ErrorKind::InvalidInput`] if `bin_width` is not a positive
+    /// number, and any error raised by `w`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polysim_core::polymer::{PolymerChain, PolymerEnsemble};
+    ///
+    /// let ensemble = PolymerEnsemble::new(vec![
+    ///     PolymerChain::new("CC".to_string(), 1, 100.0),
+    ///     PolymerChain::new("CCCCCC".to_string(), 3, 300.0),
+    /// ])
+    /// .unwrap();
+    /// let mut svg = Vec::new();
+    /// ensemble.write_distribution_svg(&mut svg, 100.0).unwrap();
+    ///
+    /// let svg = String::from_utf8(svg).unwrap();
+    /// assert!(svg.starts_with("<svg"));
+    /// assert_eq!(svg.matches("<rect").count(), 3); // bins at 150, 250, 350
+    /// ```
+    pub fn write_distribution_svg<W: Write>(&self, mut w: W, bin_width: f64) -> io::Result<()> {
+        const WIDTH: f64 = 640.0;
+        const HEIGHT: f64 = 400.0;
+        const MARGIN: f64 = 48.0;
+
+        check_bin_width(bin_width)?;
+        let bins = self.mass_histogram(bin_width);
+        let tallest = bins.iter().map(|b| b.2).fold(0.0, f64::max);
+        let plot_width = WIDTH - 2.0 * MARGIN;
+        let plot_height = HEIGHT - 2.0 * MARGIN;
+        let bar_width = plot_width / bins.len() as f64;
+        let baseline = HEIGHT - MARGIN;
+
+        writeln!(
+            w,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="sans-serif" font-size="12">"#
+        )?;
+        writeln!(w, "<title>Molar mass distribution</title>")?;
+        for (i, &(center, count, weight_fraction)) in bins.iter().enumerate() {
+            let height = if tallest > 0.0 {
+                weight_fraction / tallest * plot_height
+            } else {
+                0.0
+            };
+            writeln!(
+                w,
+                r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{height:.2}" fill="steelblue" stroke="white"><title>{center:.1} g/mol: {count} chain(s), w = {weight_fraction:.4}</title></rect>"#,
+                MARGIN + i as f64 * bar_width,
+                baseline - height,
+                bar_width,
+            )?;
+        }
+        writeln!(
+            w,
+            r#"<path d="M{MARGIN} {MARGIN} V{baseline} H{}" fill="none" stroke="black"/>"#,
+            WIDTH - MARGIN
+        )?;
+        let (first, last) = (bins[0].0, bins[bins.len() - 1].0);
+        writeln!(
+            w,
+            r#"<text x="{MARGIN}" y="{}" text-anchor="start">{:.0}</text>"#,
+            baseline + 16.0,
+            first - bin_width / 2.0
+        )?;
+        writeln!(
+            w,
+            r#"<text x="{}" y="{}" text-anchor="end">{:.0}</text>"#,
+            WIDTH - MARGIN,
+            baseline + 16.0,
+            last + bin_width / 2.0
+        )?;
+        writeln!(
+            w,
+            r#"<text x="{}" y="{}" text-anchor="middle">M (g/mol)</text>"#,
+            WIDTH / 2.0,
+            baseline + 32.0
+        )?;
+        writeln!(
+            w,
+            r#"<text x="{}" y="{}" text-anchor="start">weight fraction</text>"#,
+            MARGIN,
+            MARGIN - 8.0
+        )?;
+        writeln!(w, "</svg>")
+    }
+
+    /// Bins chain masses: `(bin_center, count, weight_fraction)` per bin.
+    fn mass_histogram(&self, bin_width: f64) -> Vec<(f64, usize, f64)> {
+        let bin_of = |mass: f64| (mass / bin_width).floor() as i64;
+        let first = self.chains.iter().map(|c| bin_of(c.mn)).min().unwrap_or(0);
+        let last = self.chains.iter().map(|c| bin_of(c.mn)).max().unwrap_or(0);
+
+        let mut bins = vec![(0usize, 0.0f64); (last - first + 1) as usize];
+        for chain in &self.chains {
+            let bin = &mut bins[(bin_of(chain.mn) - first) as usize];
+            bin.0 += 1;
+            bin.1 += chain.mn;
+        }
+
+        let total: f64 = self.chains.iter().map(|c| c.mn).sum();
+        bins.into_iter()
+            .zip(first..)
+            .map(|((count, mass), i)| ((i as f64 + 0.5) * bin_width, count, mass / total))
+            .collect()
+    }
+}
+
+/// Rejects histogram bin widths that are not a positive number.
+fn check_bin_width(bin_width: f64) -> io::Result<()> {
+    if bin_width.is_finite() && bin_width > 0.0 {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("bin width must be a positive number, got {bin_width}"),
+        ))
+    }
 }
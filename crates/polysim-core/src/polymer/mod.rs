@@ -3,5 +3,5 @@
 pub mod chain;
 pub mod ensemble;
 
-pub use chain::{Architecture, MonomerUnit, PolymerChain};
+pub use chain::{Architecture, CanonicalKey, MonomerUnit, PolymerChain};
 pub use ensemble::PolymerEnsemble;
@@ -0,0 +1,449 @@
+//! Canonical SMILES generation.
+//!
+//! Atoms are ranked by refining an ordered partition of atom invariants until
+//! it is equitable (1-dimensional Weisfeiler-Lehman colour refinement), ties
+//! are broken one cell at a time, and the SMILES is then written by a
+//! depth-first walk that always visits the lowest-ranked neighbour first. Two
+//! SMILES describing the same constitution produce the same string.
+//! Stereochemistry is not encoded.
+
+use std::collections::VecDeque;
+
+use super::graph::{default_valences, element_symbol, BondOrder, MolGraph};
+
+/// Returns the canonical SMILES of `graph`.
+pub(crate) fn canonical_smiles(graph: &MolGraph) -> String {
+    if graph.atoms.is_empty() {
+        return String::new();
+    }
+    let ranks = canonical_ranks(graph);
+    Writer::new(graph, &ranks).write()
+}
+
+// --- ranking ----------------------------------------------------------------
+
+/// Assigns a unique, canonical rank (0..n) to every atom.
+fn canonical_ranks(graph: &MolGraph) -> Vec<usize> {
+    let n = graph.atoms.len();
+    let invariants: Vec<_> = (0..n)
+        .map(|i| {
+            let atom = &graph.atoms[i];
+            let ring_bonds = graph
+                .neighbors(i)
+                .iter()
+                .filter(|&&(_, k)| graph.bonds[k].in_ring)
+                .count();
+            (
+                graph.degree(i),
+                atom.atomic_number,
+                atom.isotope,
+                atom.aromatic,
+                atom.hydrogens,
+                atom.charge,
+                ring_bonds,
+            )
+        })
+        .collect();
+
+    let mut partition = Partition::new(&invariants);
+    let mut queue: VecDeque<usize> = partition.cell_starts().collect();
+    partition.refine(graph, &mut queue);
+
+    // Break remaining ties: individualise the first atom of the first
+    // non-singleton cell, then refine again.
+    while let Some(start) = partition.first_non_singleton() {
+        partition.individualize(start);
+        queue.push_back(start);
+        queue.push_back(start + 1);
+        partition.refine(graph, &mut queue);
+    }
+
+    let mut ranks = vec![0; n];
+    for (position, &atom) in partition.lab.iter().enumerate() {
+        ranks[atom] = position;
+    }
+    ranks
+}
+
+/// Ordered partition of the atoms into cells of equivalent atoms.
+///
+/// A cell is identified by its start position in `lab`; `cell_of[atom]`
+/// gives the cell of an atom and `cell_end[start]` its end (exclusive).
+struct Partition {
+    lab: Vec<usize>,
+    cell_of: Vec<usize>,
+    cell_end: Vec<usize>,
+    in_queue: Vec<bool>,
+}
+
+impl Partition {
+    fn new<T: Ord>(invariants: &[T]) -> Self {
+        let n = invariants.len();
+        let mut lab: Vec<usize> = (0..n).collect();
+        lab.sort_by(|&a, &b| invariants[a].cmp(&invariants[b]));
+        let mut partition = Self {
+            lab,
+            cell_of: vec![0; n],
+            cell_end: vec![0; n],
+            in_queue: vec![false; n],
+        };
+        let mut start = 0;
+        for pos in 1..=n {
+            if pos == n || invariants[partition.lab[pos]] != invariants[partition.lab[start]] {
+                partition.set_cell(start, pos);
+                start = pos;
+            }
+        }
+        partition
+    }
+
+    fn set_cell(&mut self, start: usize, end: usize) {
+        self.cell_end[start] = end;
+        for pos in start..end {
+            self.cell_of[self.lab[pos]] = start;
+        }
+    }
+
+    fn cell_starts(&self) -> impl Iterator<Item = usize> + '_ {
+        let n = self.lab.len();
+        std::iter::successors((n > 0).then_some(0), move |&start| {
+            let end = self.cell_end[start];
+            (end < n).then_some(end)
+        })
+    }
+
+    fn first_non_singleton(&self) -> Option<usize> {
+        self.cell_starts()
+            .find(|&start| self.cell_end[start] - start > 1)
+    }
+
+    /// Splits the first atom of cell `start` into its own cell.
+    fn individualize(&mut self, start: usize) {
+        let end = self.cell_end[start];
+        self.set_cell(start, start + 1);
+        self.set_cell(start + 1, end);
+    }
+
+    /// Refines the partition until it is equitable: atoms of the same cell have
+    /// the same number of neighbours (per bond order) in every cell.
+    ///
+    /// Cells are used as splitters in queue order; when a cell splits, only the
+    /// smaller fragments need to be queued (Hopcroft's trick).
+    fn refine(&mut self, graph: &MolGraph, queue: &mut VecDeque<usize>) {
+        for &start in queue.iter() {
+            self.in_queue[start] = true;
+        }
+        let mut key: Vec<u64> = vec![0; self.lab.len()];
+
+        while let Some(splitter) = queue.pop_front() {
+            self.in_queue[splitter] = false;
+            let members: Vec<usize> = self.lab[splitter..self.cell_end[splitter]].to_vec();
+
+            let mut touched: Vec<usize> = Vec::new();
+            for &atom in &members {
+                for &(nbr, k) in graph.neighbors(atom) {
+                    if key[nbr] == 0 {
+                        touched.push(nbr);
+                    }
+                    key[nbr] += 1 << (12 * bond_code(graph.bonds[k].order));
+                }
+            }
+
+            let mut cells: Vec<usize> = touched.iter().map(|&a| self.cell_of[a]).collect();
+            cells.sort_unstable();
+            cells.dedup();
+            for start in cells {
+                self.split(start, &key, queue);
+            }
+            for atom in touched {
+                key[atom] = 0;
+            }
+        }
+    }
+
+    /// Splits cell `start` by `key`, in ascending key order.
+    fn split(&mut self, start: usize, key: &[u64], queue: &mut VecDeque<usize>) {
+        let end = self.cell_end[start];
+        if end - start == 1 {
+            return;
+        }
+        self.lab[start..end].sort_by_key(|&a| key[a]);
+        if key[self.lab[start]] == key[self.lab[end - 1]] {
+            return;
+        }
+
+        let mut fragments: Vec<(usize, usize)> = Vec::new();
+        let mut from = start;
+        for pos in start + 1..=end {
+            if pos == end || key[self.lab[pos]] != key[self.lab[from]] {
+                fragments.push((from, pos));
+                from = pos;
+            }
+        }
+        for &(from, to) in &fragments {
+            self.set_cell(from, to);
+        }
+
+        let was_queued = self.in_queue[start];
+        let largest = fragments
+            .iter()
+            .enumerate()
+            .max_by_key(|&(i, &(from, to))| (to - from, std::cmp::Reverse(i)))
+            .map(|(i, _)| i)
+            .expect("at least two fragments");
+        for (i, &(from, _)) in fragments.iter().enumerate() {
+            if self.in_queue[from] || (!was_queued && i == largest) {
+                continue;
+            }
+            self.in_queue[from] = true;
+            queue.push_back(from);
+        }
+    }
+}
+
+fn bond_code(order: BondOrder) -> u32 {
+    match order {
+        BondOrder::Single => 1,
+        BondOrder::Double => 2,
+        BondOrder::Triple => 3,
+        BondOrder::Quadruple => 4,
+        BondOrder::Aromatic => 5,
+    }
+}
+
+// --- writer -----------------------------------------------------------------
+
+struct Writer<'a> {
+    graph: &'a MolGraph,
+    ranks: &'a [usize],
+    /// Tree children of each atom, in writing order.
+    children: Vec<Vec<(usize, usize)>>,
+    /// Ring-closure bonds incident to each atom.
+    ring_bonds: Vec<Vec<usize>>,
+    /// DFS roots, one per connected component.
+    roots: Vec<usize>,
+}
+
+impl<'a> Writer<'a> {
+    fn new(graph: &'a MolGraph, ranks: &'a [usize]) -> Self {
+        let n = graph.atoms.len();
+        let mut writer = Self {
+            graph,
+            ranks,
+            children: vec![Vec::new(); n],
+            ring_bonds: vec![Vec::new(); n],
+            roots: Vec::new(),
+        };
+        writer.build_tree();
+        writer
+    }
+
+    /// Depth-first spanning tree; non-tree bonds become ring closures.
+    fn build_tree(&mut self) {
+        let n = self.graph.atoms.len();
+        let mut visited = vec![false; n];
+        let mut tree_bond = vec![false; self.graph.bonds.len()];
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by_key(|&i| self.ranks[i]);
+
+        for &root in &order {
+            if visited[root] {
+                continue;
+            }
+            self.roots.push(root);
+            visited[root] = true;
+            let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+            while let Some(top) = stack.last_mut() {
+                let v = top.0;
+                let nbrs = self.sorted_neighbors(v);
+                if top.1 >= nbrs.len() {
+                    stack.pop();
+                    continue;
+                }
+                let (w, k) = nbrs[top.1];
+                top.1 += 1;
+                if !visited[w] {
+                    visited[w] = true;
+                    tree_bond[k] = true;
+                    self.children[v].push((w, k));
+                    stack.push((w, 0));
+                }
+            }
+        }
+
+        for (k, bond) in self.graph.bonds.iter().enumerate() {
+            if !tree_bond[k] {
+                self.ring_bonds[bond.a].push(k);
+                self.ring_bonds[bond.b].push(k);
+            }
+        }
+    }
+
+    fn sorted_neighbors(&self, v: usize) -> Vec<(usize, usize)> {
+        let mut nbrs = self.graph.neighbors(v).to_vec();
+        nbrs.sort_by_key(|&(w, _)| self.ranks[w]);
+        nbrs
+    }
+
+    fn write(&self) -> String {
+        let mut out = String::new();
+        // ring digit currently assigned to each open ring-closure bond
+        let mut open: Vec<Option<u32>> = vec![None; self.graph.bonds.len()];
+        let mut used_digits: Vec<bool> = Vec::new();
+
+        for (c, &root) in self.roots.iter().enumerate() {
+            if c > 0 {
+                out.push('.');
+            }
+            let mut stack: Vec<Frame> = vec![Frame::Atom(root, None)];
+            while let Some(frame) = stack.pop() {
+                match frame {
+                    Frame::Close => out.push(')'),
+                    Frame::Atom(v, via) => {
+                        if let Some(k) = via {
+                            out.push_str(self.bond_symbol(k));
+                        }
+                        out.push_str(&self.atom_symbol(v));
+                        self.write_ring_digits(v, &mut open, &mut used_digits, &mut out);
+
+                        let children = &self.children[v];
+                        if let Some((&(last, k_last), branches)) = children.split_last() {
+                            stack.push(Frame::Atom(last, Some(k_last)));
+                            for &(w, k) in branches.iter().rev() {
+                                stack.push(Frame::Close);
+                                stack.push(Frame::Atom(w, Some(k)));
+                                stack.push(Frame::Open);
+                            }
+                        }
+                    }
+                    Frame::Open => out.push('('),
+                }
+            }
+        }
+        out
+    }
+
+    fn write_ring_digits(
+        &self,
+        v: usize,
+        open: &mut [Option<u32>],
+        used_digits: &mut Vec<bool>,
+        out: &mut String,
+    ) {
+        let other = |k: usize| {
+            let bond = &self.graph.bonds[k];
+            if bond.a == v {
+                bond.b
+            } else {
+                bond.a
+            }
+        };
+        let (mut closing, mut opening): (Vec<usize>, Vec<usize>) =
+            self.ring_bonds[v].iter().partition(|&&k| open[k].is_some());
+        closing.sort_by_key(|&k| open[k]);
+        opening.sort_by_key(|&k| self.ranks[other(k)]);
+
+        for k in closing {
+            let digit = open[k].take().expect("ring bond is open");
+            used_digits[digit as usize] = false;
+            out.push_str(&ring_label(digit));
+        }
+        for k in opening {
+            let digit = (1..)
+                .find(|&d| !used_digits.get(d).copied().unwrap_or(false))
+                .expect("unbounded range");
+            if used_digits.len() <= digit {
+                used_digits.resize(digit + 1, false);
+            }
+            used_digits[digit] = true;
+            open[k] = Some(digit as u32);
+            out.push_str(self.bond_symbol(k));
+            out.push_str(&ring_label(digit as u32));
+        }
+    }
+
+    fn bond_symbol(&self, k: usize) -> &'static str {
+        let bond = &self.graph.bonds[k];
+        let both_aromatic = self.graph.atoms[bond.a].aromatic && self.graph.atoms[bond.b].aromatic;
+        match bond.order {
+            BondOrder::Single if both_aromatic => "-",
+            BondOrder::Single => "",
+            BondOrder::Double => "=",
+            BondOrder::Triple => "#",
+            BondOrder::Quadruple => "$",
+            BondOrder::Aromatic if both_aromatic => "",
+            BondOrder::Aromatic => ":",
+        }
+    }
+
+    fn atom_symbol(&self, v: usize) -> String {
+        let atom = &self.graph.atoms[v];
+        let symbol = element_symbol(atom.atomic_number);
+        let symbol = if atom.aromatic {
+            symbol.to_ascii_lowercase()
+        } else {
+            symbol.to_owned()
+        };
+
+        let organic = matches!(
+            atom.atomic_number,
+            0 | 5 | 6 | 7 | 8 | 9 | 15 | 16 | 17 | 35 | 53
+        );
+        if organic && atom.isotope == 0 && atom.charge == 0 && atom.hydrogens == self.implicit_h(v)
+        {
+            return symbol;
+        }
+
+        let mut s = String::from("[");
+        if atom.isotope > 0 {
+            s.push_str(&atom.isotope.to_string());
+        }
+        s.push_str(&symbol);
+        match atom.hydrogens {
+            0 => {}
+            1 => s.push('H'),
+            h => s.push_str(&format!("H{h}")),
+        }
+        match atom.charge {
+            0 => {}
+            1 => s.push('+'),
+            -1 => s.push('-'),
+            c if c > 0 => s.push_str(&format!("+{c}")),
+            c => s.push_str(&format!("-{}", -c)),
+        }
+        s.push(']');
+        s
+    }
+
+    /// Hydrogen count a SMILES reader would infer for an organic-subset atom.
+    fn implicit_h(&self, v: usize) -> u32 {
+        let atom = &self.graph.atoms[v];
+        let used: u32 = self
+            .graph
+            .neighbors(v)
+            .iter()
+            .map(|&(_, k)| self.graph.bonds[k].order.valence())
+            .sum::<u32>()
+            + u32::from(atom.aromatic);
+        default_valences(atom.atomic_number)
+            .iter()
+            .find(|&&val| val >= used)
+            .map_or(0, |&val| val - used)
+    }
+}
+
+/// Pending output while walking the spanning tree.
+enum Frame {
+    /// An atom and the bond that leads to it from its parent.
+    Atom(usize, Option<usize>),
+    Open,
+    Close,
+}
+
+fn ring_label(digit: u32) -> String {
+    if digit < 10 {
+        digit.to_string()
+    } else {
+        format!("%{digit:02}")
+    }
+}
@@ -0,0 +1,117 @@
+//! Chain conformation: dimensions of a single chain.
+
+use crate::polymer::PolymerChain;
+
+use super::graph::{BondOrder, GraphBond, MolGraph};
+
+/// Valence angle at an sp3 atom (degrees).
+const TETRAHEDRAL_ANGLE_DEG: f64 = 109.5;
+
+/// Valence angle at an sp2 or aromatic atom (degrees).
+const TRIGONAL_ANGLE_DEG: f64 = 120.0;
+
+/// Fully extended (all-trans) length of the backbone of `chain`, in nm.
+///
+/// Each bond between two main-chain heavy atoms contributes its projection on
+/// the chain axis, `bond_length_nm × sin(θ/2)`, where θ is the valence angle
+/// of its atoms: 109.5° for sp3 (projection 0.817), 120° for sp2 and aromatic
+/// atoms, 180° (no shortening) for sp atoms. A bond between atoms of different
+/// hybridisation uses the mean of their two projections. A single bond length
+/// is used for every bond (0.154 nm for C–C).
+///
+/// The contour length is the upper bound of the end-to-end distance. Bonds
+/// around backbone rings are walked along the ring, so aromatic backbones
+/// come out somewhat long.
+///
+/// # Panics
+///
+/// Panics if `chain.smiles` is not valid SMILES (never the case for chains
+/// produced by the builders).
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::conformation::contour_length;
+///
+/// let bs = parse("{[]CC[]}").unwrap();
+/// let pe = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(100))
+///     .homopolymer()
+///     .unwrap();
+/// // 199 C–C bonds × 0.154 nm × sin(54.75°)
+/// let length = contour_length(&pe, 0.154);
+/// assert!((length - 25.03).abs() < 0.01, "L = {length} nm");
+/// ```
+pub fn contour_length(chain: &PolymerChain, bond_length_nm: f64) -> f64 {
+    let graph = MolGraph::parse(&chain.smiles).expect("chain SMILES must be valid SMILES");
+    let projected: f64 = backbone_bonds(&graph)
+        .map(|bond| (axial_projection(&graph, bond.a) + axial_projection(&graph, bond.b)) / 2.0)
+        .sum();
+    projected * bond_length_nm
+}
+
+/// Unperturbed radius of gyration of `chain`, in nm.
+///
+/// Ideal-chain model: the mean-square end-to-end distance is
+/// `⟨R²⟩₀ = C∞ · n · l²`, with `n` the number of bonds between main-chain
+/// heavy atoms (as in [`contour_length`]), `l = bond_length_nm` and
+/// `C∞ = char_ratio` the characteristic ratio (about 6.7 for PE, 10 for PS).
+/// Then `Rg = √(⟨R²⟩₀ / 6)`, so Rg grows as √n. Valid in a theta solvent or
+/// in the melt; excluded-volume swelling in good solvents is not included.
+///
+/// # Panics
+///
+/// Panics if `chain.smiles` is not valid SMILES (never the case for chains
+/// produced by the builders).
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::conformation::radius_of_gyration;
+///
+/// let bs = parse("{[]CC[]}").unwrap();
+/// let pe = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1000))
+///     .homopolymer()
+///     .unwrap();
+/// // √(6.7 × 1999 × 0.154² / 6) ≈ 7.27 nm
+/// let rg = radius_of_gyration(&pe, 6.7, 0.154);
+/// assert!((rg - 7.27).abs() < 0.01, "Rg = {rg} nm");
+/// ```
+pub fn radius_of_gyration(chain: &PolymerChain, char_ratio: f64, bond_length_nm: f64) -> f64 {
+    let graph = MolGraph::parse(&chain.smiles).expect("chain SMILES must be valid SMILES");
+    let bonds = backbone_bonds(&graph).count() as f64;
+    (char_ratio * bonds * bond_length_nm * bond_length_nm / 6.0).sqrt()
+}
+
+/// Bonds between two main-chain heavy atoms of `graph`.
+fn backbone_bonds(graph: &MolGraph) -> impl Iterator<Item = &GraphBond> {
+    let on_backbone = |i: usize| graph.main_chain[i] && graph.atoms[i].atomic_number > 1;
+    graph
+        .bonds
+        .iter()
+        .filter(move |bond| on_backbone(bond.a) && on_backbone(bond.b))
+}
+
+/// Fraction of a bond length projected on the axis of a zigzag through atom `i`.
+fn axial_projection(graph: &MolGraph, i: usize) -> f64 {
+    let mut multiple_bonds = 0;
+    let mut triple = false;
+    for &(_, k) in graph.neighbors(i) {
+        match graph.bonds[k].order {
+            BondOrder::Single => {}
+            BondOrder::Double | BondOrder::Aromatic => multiple_bonds += 1,
+            BondOrder::Triple | BondOrder::Quadruple => triple = true,
+        }
+    }
+    // sp: linear (C≡C, cumulated C=C=C)
+    if triple || multiple_bonds >= 2 && !graph.atoms[i].aromatic {
+        return 1.0;
+    }
+    let angle = if graph.atoms[i].aromatic || multiple_bonds > 0 {
+        TRIGONAL_ANGLE_DEG
+    } else {
+        TETRAHEDRAL_ANGLE_DEG
+    };
+    (angle.to_radians() / 2.0).sin()
+}
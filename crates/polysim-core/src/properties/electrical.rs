@@ -0,0 +1,170 @@
+//! Dipole moment, dielectric constant, refractive index and charge of the chain.
+//!
+//! The dipole moment, the dielectric constant and the refractive index are
+//! estimated from structural group contributions: the polar groups of the chain
+//! carry a dipole moment, and every group adds to the molar polarization used in
+//! the Clausius-Mossotti relation. The charge is read from the formal charges of
+//! the SMILES.
+
+use crate::polymer::PolymerChain;
+
+use super::graph::MolGraph;
+use super::groups::{self, GroupName};
+use super::volume::molar_volume;
+
+/// Temperature (K) at which the dielectric constant is evaluated.
+const REFERENCE_TEMPERATURE: f64 = 298.15;
+
+/// Frequency (Hz) above which dipole orientation no longer follows the field.
+const DIPOLE_RELAXATION_FREQUENCY: f64 = 1.0e9;
+
+/// Estimates the dipole moment (Debye) of one repeat unit.
+///
+/// Group dipoles are assumed to point in uncorrelated directions, so their
+/// squares add: μ² = Σ μᵢ². End groups are spread over the repeat units.
+/// Symmetric structures whose dipoles cancel (e.g. PTFE) are overestimated.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::electrical::dipole_moment;
+///
+/// let bs = parse("{[]CC(Cl)[]}").unwrap();
+/// let pvc = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(100))
+///     .homopolymer()
+///     .unwrap();
+/// assert!((dipole_moment(&pvc) - 1.9).abs() < 0.1); // C–Cl ≈ 1.9 D
+/// ```
+pub fn dipole_moment(chain: &PolymerChain) -> f64 {
+    let squared = groups::sum_increments(chain, |g| g.dipole_moment().powi(2));
+    (squared / chain.repeat_count as f64).sqrt()
+}
+
+/// Estimates the dielectric constant ε at `frequency_hz`, at 298.15 K.
+///
+/// The Clausius-Mossotti relation (ε − 1)/(ε + 2) = P/V links ε to the molar
+/// polarization P and the [`molar_volume`] V. P is the electronic part (molar
+/// refraction) plus a dipole orientation part that relaxes in a Debye fashion
+/// above 1 GHz, so ε tends to n² at optical frequencies.
+///
+/// # Reference
+///
+/// Van Krevelen, D. W. & te Nijenhuis, K. (2009).
+/// *Properties of Polymers*, 4th ed., Elsevier. Chapter 11.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::electrical::dielectric_constant;
+///
+/// let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+/// let ps = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(100))
+///     .homopolymer()
+///     .unwrap();
+/// let eps = dielectric_constant(&ps, 1.0e3);
+/// assert!((eps - 2.55).abs() < 0.15); // atactic PS ≈ 2.55
+/// ```
+pub fn dielectric_constant(chain: &PolymerChain, frequency_hz: f64) -> f64 {
+    let n = chain.repeat_count as f64;
+    let electronic = groups::sum_increments(chain, GroupName::molar_refraction) / n;
+    let orientation = groups::sum_increments(chain, GroupName::molar_polarization) / n - electronic;
+    let relaxation = 1.0 / (1.0 + (frequency_hz / DIPOLE_RELAXATION_FREQUENCY).powi(2));
+    let polarization = electronic + orientation * relaxation;
+    let x = polarization / molar_volume(chain, REFERENCE_TEMPERATURE);
+    (1.0 + 2.0 * x) / (1.0 - x)
+}
+
+/// Estimates the refractive index n_D (sodium D line) at 298.15 K.
+///
+/// The Lorentz-Lorenz relation (n² − 1)/(n² + 2) = R/V links n to the molar
+/// refraction R of the structural groups and the [`molar_volume`] V; it is the
+/// optical-frequency limit of [`dielectric_constant`], ε = n².
+///
+/// # Reference
+///
+/// Van Krevelen, D. W. & te Nijenhuis, K. (2009).
+/// *Properties of Polymers*, 4th ed., Elsevier. Chapter 10.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::electrical::refractive_index;
+///
+/// let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+/// let ps = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(100))
+///     .homopolymer()
+///     .unwrap();
+/// let n = refractive_index(&ps);
+/// assert!((n - 1.59).abs() < 0.03, "n = {n}"); // PS ≈ 1.59
+/// ```
+pub fn refractive_index(chain: &PolymerChain) -> f64 {
+    let refraction =
+        groups::sum_increments(chain, GroupName::molar_refraction) / chain.repeat_count as f64;
+    let x = refraction / molar_volume(chain, REFERENCE_TEMPERATURE);
+    ((1.0 + 2.0 * x) / (1.0 - x)).sqrt()
+}
+
+/// Net formal charge of the chain (elementary charges).
+///
+/// Counter-ions appended by [`LinearBuilder::counterions`] are part of the
+/// chain SMILES, so a neutralised chain has a net charge of zero.
+///
+/// [`LinearBuilder::counterions`]: crate::builder::linear::LinearBuilder::counterions
+///
+/// # Panics
+///
+/// Panics if `chain.smiles` is not valid SMILES (never the case for chains
+/// produced by the builders).
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::electrical::net_charge;
+///
+/// let bs = parse("{[]CC(c1ccc(cc1)S(=O)(=O)[O-])[]}").unwrap();
+/// let pss = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(50))
+///     .homopolymer()
+///     .unwrap();
+/// assert_eq!(net_charge(&pss), -50);
+/// ```
+pub fn net_charge(chain: &PolymerChain) -> i32 {
+    let graph = MolGraph::parse(&chain.smiles).expect("chain SMILES must be valid SMILES");
+    graph.atoms.iter().map(|atom| atom.charge).sum()
+}
+
+/// Linear charge density (elementary charges per nm): the [`net_charge`]
+/// divided by `contour_length_nm`.
+///
+/// The sign follows the net charge. Compared with the Bjerrum length
+/// (0.71 nm in water at 25 °C), it sets the Manning counter-ion condensation
+/// of a polyelectrolyte. `contour_length_nm` is typically the result of
+/// [`contour_length`](super::conformation::contour_length).
+///
+/// # Reference
+///
+/// Manning, G. S. (1969). Limiting laws and counterion condensation in
+/// polyelectrolyte solutions I. *J. Chem. Phys.*, 51(3), 924–933.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::{
+///     conformation::contour_length, electrical::linear_charge_density,
+/// };
+///
+/// let bs = parse("{[]CC(c1ccc(cc1)S(=O)(=O)[O-])[]}").unwrap();
+/// let pss = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(100))
+///     .homopolymer()
+///     .unwrap();
+/// // One sulfonate per ≈ 0.25 nm of backbone
+/// let density = linear_charge_density(&pss, contour_length(&pss, 0.154));
+/// assert!((density + 4.0).abs() < 0.1, "λ = {density} e/nm");
+/// ```
+pub fn linear_charge_density(chain: &PolymerChain, contour_length_nm: f64) -> f64 {
+    net_charge(chain) as f64 / contour_length_nm
+}
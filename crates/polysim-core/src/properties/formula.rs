@@ -1,14 +1,35 @@
 use std::collections::BTreeMap;
 
+use bigsmiles::BigSmiles;
 use opensmiles::parse as parse_smiles;
 
-use crate::polymer::PolymerChain;
+use crate::{builder::RepeatUnitCache, error::PolySimError, polymer::PolymerChain};
+
+use super::molecular_weight::H_AVERAGE_MASS;
+
+/// Ordre des éléments dans une formule brute.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FormulaOrder<'a> {
+    /// Notation Hill : C, puis H, puis les autres éléments par ordre
+    /// alphabétique ; sans carbone, tout par ordre alphabétique.
+    #[default]
+    Hill,
+    /// Tous les éléments par ordre alphabétique strict du symbole.
+    Alphabetical,
+    /// Les symboles donnés, dans cet ordre, puis les éléments restants par
+    /// ordre alphabétique. Les symboles absents de la chaîne sont ignorés.
+    Custom(Vec<&'a str>),
+}
 
 /// Calcule la formule moléculaire brute d'une chaîne en notation Hill.
 ///
 /// La notation Hill place **C** en premier, puis **H**, puis les autres éléments
 /// par ordre alphabétique du symbole. Les hydrogènes implicites sont inclus.
 ///
+/// Si le SMILES de la chaîne ne peut pas être analysé, la formule est estimée à
+/// partir de `composition`, comme pour
+/// [`average_mass`](super::molecular_weight::average_mass).
+///
 /// # Exemple
 ///
 /// ```rust
@@ -23,24 +44,137 @@ use crate::polymer::PolymerChain;
 /// assert_eq!(molecular_formula(&chain), "C20H42");
 /// ```
 pub fn molecular_formula(chain: &PolymerChain) -> String {
-    let mol = parse_smiles(&chain.smiles).expect("chain SMILES must be valid SMILES");
-    let mut counts: BTreeMap<&'static str, usize> = BTreeMap::new();
+    molecular_formula_with_order(chain, &FormulaOrder::Hill)
+}
 
-    for node in mol.nodes() {
-        let atomic_num = node.atom().element().atomic_number();
-        if atomic_num == 0 {
-            continue; // wildcard (*)
-        }
-        if let Some(sym) = element_symbol(atomic_num) {
-            *counts.entry(sym).or_insert(0) += 1;
-        }
-        let h = node.hydrogens() as usize;
-        if h > 0 {
-            *counts.entry("H").or_insert(0) += h;
+/// Comme [`molecular_formula`], mais échoue au lieu d'estimer la formule à partir
+/// de la composition.
+///
+/// # Erreurs
+///
+/// [`PolySimError::InvalidRepeatUnit`] si le SMILES de la chaîne ne peut pas être
+/// analysé.
+pub fn try_molecular_formula(chain: &PolymerChain) -> Result<String, PolySimError> {
+    molecular_formula_of_smiles(&chain.smiles)
+}
+
+/// Formule brute idéalisée du motif de répétition d'un homopolymère, en notation Hill.
+///
+/// C'est la formule entre parenthèses de `(C2H4)n` : sans les hydrogènes de
+/// bout de chaîne, contrairement à la chaîne n = 1 (`C2H6` pour le PE). Elle
+/// est obtenue par différence entre les chaînes n = 2 et n = 1, comme
+/// [`RepeatUnitCache`].
+///
+/// # Erreurs
+///
+/// - [`PolySimError::NoStochasticObject`] sans objet stochastique.
+/// - [`PolySimError::RepeatUnitCount`] si l'objet stochastique ne contient
+///   pas exactement un motif.
+///
+/// # Exemple
+///
+/// ```rust
+/// use polysim_core::{parse, properties::formula::repeat_unit_formula};
+///
+/// assert_eq!(repeat_unit_formula(&parse("{[]CC[]}").unwrap()).unwrap(), "C2H4");
+/// assert_eq!(repeat_unit_formula(&parse("{[]CC(Cl)[]}").unwrap()).unwrap(), "C2H3Cl");
+/// ```
+pub fn repeat_unit_formula(bigsmiles: &BigSmiles) -> Result<String, PolySimError> {
+    let cache = RepeatUnitCache::new(bigsmiles)?;
+    let [unit] = cache.units() else {
+        return Err(PolySimError::RepeatUnitCount {
+            architecture: "homopolymer",
+            got: cache.units().len(),
+            need_min: 1,
+        });
+    };
+    let counts = unit
+        .elements
+        .iter()
+        .filter(|&(_, &count)| count > 0)
+        .map(|(&symbol, &count)| (symbol, count as usize))
+        .collect();
+    Ok(format_formula(&counts, &FormulaOrder::Hill))
+}
+
+/// Formule brute d'un fragment SMILES quelconque, en notation Hill.
+///
+/// Le fragment est lu comme une molécule à part entière, hydrogènes implicites
+/// inclus : un bloc de début `CC` donne `C2H6`, alors qu'il n'apporte que
+/// `C2H5` une fois lié à la chaîne. Sert notamment à détailler les blocs de
+/// début et de fin (amorceur, terminaison) d'un BigSMILES.
+///
+/// # Erreurs
+///
+/// [`PolySimError::InvalidRepeatUnit`] si `smiles` n'est pas un SMILES valide.
+///
+/// # Exemple
+///
+/// ```rust
+/// use polysim_core::properties::formula::molecular_formula_of_smiles;
+///
+/// assert_eq!(molecular_formula_of_smiles("CC").unwrap(), "C2H6");
+/// assert_eq!(molecular_formula_of_smiles("OCCO").unwrap(), "C2H6O2");
+/// assert!(molecular_formula_of_smiles("C(").is_err());
+/// ```
+pub fn molecular_formula_of_smiles(smiles: &str) -> Result<String, PolySimError> {
+    let counts = smiles_element_counts(smiles).map_err(|e| {
+        PolySimError::InvalidRepeatUnit(format!("'{smiles}' is not valid SMILES: {e}"))
+    })?;
+    Ok(format_formula(&counts, &FormulaOrder::Hill))
+}
+
+/// Calcule la formule moléculaire brute avec l'ordre d'éléments `order`.
+///
+/// # Exemple
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy},
+///                    properties::formula::{molecular_formula_with_order, FormulaOrder}};
+///
+/// let bs = parse("{[]CC(Cl)[]}").unwrap();
+/// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(2))
+///     .homopolymer()
+///     .unwrap();
+/// assert_eq!(molecular_formula_with_order(&chain, &FormulaOrder::Hill), "C4H8Cl2");
+/// assert_eq!(molecular_formula_with_order(&chain, &FormulaOrder::Alphabetical), "C4Cl2H8");
+/// assert_eq!(
+///     molecular_formula_with_order(&chain, &FormulaOrder::Custom(vec!["Cl", "C"])),
+///     "Cl2C4H8"
+/// );
+/// ```
+pub fn molecular_formula_with_order(chain: &PolymerChain, order: &FormulaOrder) -> String {
+    format_formula(&element_counts(chain), order)
+}
+
+/// Écrit la formule brute des comptes d'éléments `counts` dans l'ordre `order`.
+pub(crate) fn format_formula(
+    counts: &BTreeMap<&'static str, usize>,
+    order: &FormulaOrder,
+) -> String {
+    let first: &[&str] = match order {
+        FormulaOrder::Hill if counts.contains_key("C") => &["C", "H"],
+        FormulaOrder::Hill | FormulaOrder::Alphabetical => &[],
+        FormulaOrder::Custom(first) => first,
+    };
+    // Puis le reste par ordre alphabétique (BTreeMap est déjà trié)
+    let mut symbols: Vec<&str> = Vec::with_capacity(counts.len());
+    for &sym in first.iter().chain(counts.keys()) {
+        if !symbols.contains(&sym) {
+            symbols.push(sym);
         }
     }
 
-    hill_notation(&counts)
+    let mut result = String::new();
+    for sym in symbols {
+        if let Some(&n) = counts.get(sym) {
+            result.push_str(sym);
+            if n > 1 {
+                result.push_str(&n.to_string());
+            }
+        }
+    }
+    result
 }
 
 /// Nombre total d'atomes dans la chaîne (atomes lourds + hydrogènes implicites/explicites).
@@ -66,60 +200,165 @@ pub fn total_atom_count(chain: &PolymerChain) -> usize {
         .sum()
 }
 
-/// Formate les counts en notation Hill : C en premier, H en second,
-/// puis les autres éléments par ordre alphabétique de symbole.
-fn hill_notation(counts: &BTreeMap<&'static str, usize>) -> String {
-    let mut result = String::new();
-    let has_carbon = counts.contains_key("C");
-
-    if has_carbon {
-        // C et H en premier
-        for sym in ["C", "H"] {
-            if let Some(&n) = counts.get(sym) {
-                result.push_str(sym);
-                if n > 1 {
-                    result.push_str(&n.to_string());
-                }
-            }
+/// Différence élément par élément des formules brutes de deux chaînes, `a − b`.
+///
+/// Chaque élément présent dans l'une des chaînes est associé à son nombre
+/// d'atomes dans `a` moins celui dans `b` (hydrogènes implicites inclus) ; les
+/// éléments de même compte sont omis, de sorte que deux chaînes de même
+/// formule donnent une table vide. Utile pour comprendre pourquoi deux
+/// chaînes n'ont pas la même masse.
+///
+/// # Exemple
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy},
+///                    properties::formula::formula_diff};
+///
+/// let pe = |n| {
+///     LinearBuilder::new(parse("{[]CC[]}").unwrap(), BuildStrategy::ByRepeatCount(n))
+///         .homopolymer()
+///         .unwrap()
+/// };
+/// let pvc = LinearBuilder::new(parse("{[]CC(Cl)[]}").unwrap(), BuildStrategy::ByRepeatCount(3))
+///     .homopolymer()
+///     .unwrap();
+/// // PVC n=3 (C6H11Cl3) − PE n=3 (C6H14)
+/// let diff = formula_diff(&pvc, &pe(3));
+/// assert_eq!(diff.get("Cl"), Some(&3));
+/// assert_eq!(diff.get("H"), Some(&-3));
+/// assert_eq!(diff.get("C"), None);
+/// ```
+pub fn formula_diff(a: &PolymerChain, b: &PolymerChain) -> BTreeMap<&'static str, i64> {
+    let mut diff: BTreeMap<&'static str, i64> = element_counts(a)
+        .into_iter()
+        .map(|(sym, count)| (sym, count as i64))
+        .collect();
+    for (sym, count) in element_counts(b) {
+        *diff.entry(sym).or_insert(0) -= count as i64;
+    }
+    diff.retain(|_, delta| *delta != 0);
+    diff
+}
+
+/// Fraction massique de chaque élément de la chaîne (entre 0 et 1).
+///
+/// Chaque atome contribue par son poids atomique standard (ou la masse de
+/// l'isotope indiqué, `[13C]`), les hydrogènes implicites/explicites par
+/// 1.008 g/mol : les fractions se comparent directement aux pourcentages
+/// massiques d'une analyse élémentaire (C/H/N). Les atomes joker (`*`) et les
+/// éléments sans symbole connu sont ignorés ; la somme vaut alors moins de 1.
+///
+/// # Exemple
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy},
+///                    properties::formula::mass_fractions};
+///
+/// let bs = parse("{[]CC[]}").unwrap();
+/// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1))
+///     .homopolymer()
+///     .unwrap();
+/// // Éthane C₂H₆ : 24.022 / 30.070 g/mol de carbone
+/// let fractions = mass_fractions(&chain);
+/// assert!((fractions["C"] - 0.7989).abs() < 1e-4);
+/// assert!((fractions["H"] - 0.2011).abs() < 1e-4);
+/// ```
+pub fn mass_fractions(chain: &PolymerChain) -> BTreeMap<&'static str, f64> {
+    let mol = parse_smiles(&chain.smiles).expect("chain SMILES must be valid SMILES");
+    let mut masses: BTreeMap<&'static str, f64> = BTreeMap::new();
+    let mut total = 0.0;
+
+    for node in mol.nodes() {
+        let atom = node.atom();
+        let atomic_num = atom.element().atomic_number();
+        let h_mass = node.hydrogens() as f64 * H_AVERAGE_MASS;
+        if h_mass > 0.0 {
+            *masses.entry("H").or_insert(0.0) += h_mass;
         }
-        // Reste par ordre alphabétique (BTreeMap est déjà trié)
-        for (&sym, &n) in counts {
-            if sym == "C" || sym == "H" {
-                continue;
-            }
-            result.push_str(sym);
-            if n > 1 {
-                result.push_str(&n.to_string());
-            }
+        total += h_mass;
+        if atomic_num == 0 {
+            continue; // wildcard (*)
         }
-    } else {
-        // Pas de carbone → tout par ordre alphabétique
-        for (&sym, &n) in counts {
-            result.push_str(sym);
-            if n > 1 {
-                result.push_str(&n.to_string());
-            }
+        if let Some(sym) = element_symbol(atomic_num) {
+            *masses.entry(sym).or_insert(0.0) += atom.mass();
         }
+        total += atom.mass();
     }
-    result
+    if total > 0.0 {
+        for mass in masses.values_mut() {
+            *mass /= total;
+        }
+    }
+    masses
+}
+
+/// Compte les atomes de chaque élément, hydrogènes implicites inclus.
+///
+/// Les atomes joker (`*`) et les éléments sans symbole connu sont ignorés. Si le
+/// SMILES de la chaîne ne peut pas être analysé, les comptes sont estimés à
+/// partir de la composition (voir [`composition_element_counts`]).
+pub(crate) fn element_counts(chain: &PolymerChain) -> BTreeMap<&'static str, usize> {
+    smiles_element_counts(&chain.smiles).unwrap_or_else(|_| composition_element_counts(chain))
+}
+
+/// Comptes d'éléments d'une chaîne SMILES, ou l'erreur d'analyse.
+fn smiles_element_counts(smiles: &str) -> Result<BTreeMap<&'static str, usize>, String> {
+    let mol = parse_smiles(smiles).map_err(|e| e.to_string())?;
+    let mut counts: BTreeMap<&'static str, usize> = BTreeMap::new();
+
+    for node in mol.nodes() {
+        let atomic_num = node.atom().element().atomic_number();
+        if atomic_num == 0 {
+            continue; // wildcard (*)
+        }
+        if let Some(sym) = element_symbol(atomic_num) {
+            *counts.entry(sym).or_insert(0) += 1;
+        }
+        let h = node.hydrogens() as usize;
+        if h > 0 {
+            *counts.entry("H").or_insert(0) += h;
+        }
+    }
+    Ok(counts)
+}
+
+/// Comptes d'éléments estimés à partir de la composition de la chaîne, par
+/// [`RepeatUnitCache`] comme pour la masse moyenne. Sans composition, le
+/// résultat est vide.
+fn composition_element_counts(chain: &PolymerChain) -> BTreeMap<&'static str, usize> {
+    RepeatUnitCache::estimate(chain)
+        .map(|estimate| {
+            estimate
+                .elements
+                .into_iter()
+                .filter(|&(_, count)| count > 0)
+                .map(|(symbol, count)| (symbol, count as usize))
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 /// Retourne le symbole IUPAC de l'élément pour le numéro atomique donné.
 ///
-/// Couvre les éléments courants en chimie des polymères.
+/// Couvre les éléments courants en chimie des polymères et les contre-ions usuels.
 /// Retourne `None` pour les éléments inconnus ou rares.
 fn element_symbol(atomic_number: u8) -> Option<&'static str> {
     match atomic_number {
         1 => Some("H"),
+        3 => Some("Li"),
         5 => Some("B"),
         6 => Some("C"),
         7 => Some("N"),
         8 => Some("O"),
         9 => Some("F"),
+        11 => Some("Na"),
+        12 => Some("Mg"),
         14 => Some("Si"),
         15 => Some("P"),
         16 => Some("S"),
         17 => Some("Cl"),
+        19 => Some("K"),
+        20 => Some("Ca"),
         35 => Some("Br"),
         53 => Some("I"),
         _ => None,
@@ -0,0 +1,589 @@
+//! Lightweight molecular graph built directly from a SMILES string.
+//!
+//! The mass and formula calculators only need per-atom data, which `opensmiles`
+//! provides. Structure-based estimators (group contributions, topology) also need
+//! connectivity: which atoms are bonded, with which bond order, and whether a bond
+//! belongs to a ring. This module parses the SMILES subset produced by the
+//! builders into such a graph.
+
+/// Bond order between two atoms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BondOrder {
+    Single,
+    Double,
+    Triple,
+    Quadruple,
+    Aromatic,
+}
+
+impl BondOrder {
+    /// Contribution of the bond to the valence of each of its atoms.
+    pub(crate) fn valence(self) -> u32 {
+        match self {
+            Self::Single | Self::Aromatic => 1,
+            Self::Double => 2,
+            Self::Triple => 3,
+            Self::Quadruple => 4,
+        }
+    }
+}
+
+/// A heavy atom of the graph (hydrogens are stored as counts).
+#[derive(Debug, Clone)]
+pub(crate) struct GraphAtom {
+    /// Atomic number (0 for the wildcard `*`).
+    pub atomic_number: u8,
+    /// Mass number for isotopically labelled atoms (`[13C]`), 0 otherwise.
+    pub isotope: u16,
+    /// Lowercase (aromatic) atom in the SMILES.
+    pub aromatic: bool,
+    /// Implicit + explicit hydrogen count.
+    pub hydrogens: u32,
+    /// Formal charge.
+    pub charge: i32,
+    /// Tetrahedral chirality mark: 1 for `@`, 2 for `@@`, 0 when unspecified.
+    pub chirality: u8,
+}
+
+/// A bond between atoms `a` and `b` (indices into [`MolGraph::atoms`]).
+#[derive(Debug, Clone)]
+pub(crate) struct GraphBond {
+    pub a: usize,
+    pub b: usize,
+    pub order: BondOrder,
+    /// `true` when the bond belongs to at least one ring.
+    pub in_ring: bool,
+}
+
+/// Heavy-atom molecular graph with hydrogen counts.
+#[derive(Debug, Clone)]
+pub(crate) struct MolGraph {
+    pub atoms: Vec<GraphAtom>,
+    pub bonds: Vec<GraphBond>,
+    /// `true` for atoms written outside any branch parentheses. For chains
+    /// built by this crate these atoms form the polymer backbone.
+    pub main_chain: Vec<bool>,
+    adjacency: Vec<Vec<(usize, usize)>>,
+}
+
+impl MolGraph {
+    /// Parses a SMILES string into a graph.
+    ///
+    /// Returns a human-readable message when the string is not valid SMILES.
+    pub fn parse(smiles: &str) -> Result<Self, String> {
+        Parser::new(smiles).run()
+    }
+
+    /// Neighbours of atom `i` as `(neighbour_index, bond_index)` pairs.
+    pub fn neighbors(&self, i: usize) -> &[(usize, usize)] {
+        &self.adjacency[i]
+    }
+
+    /// Sum of the bond orders of atom `i`, aromatic bonds counting as one.
+    pub fn bond_order_sum(&self, i: usize) -> u32 {
+        self.neighbors(i)
+            .iter()
+            .map(|&(_, k)| self.bonds[k].order.valence())
+            .sum()
+    }
+
+    /// Number of heavy-atom neighbours of atom `i`.
+    pub fn degree(&self, i: usize) -> usize {
+        self.adjacency[i].len()
+    }
+
+    fn from_parts(atoms: Vec<GraphAtom>, bonds: Vec<GraphBond>, main_chain: Vec<bool>) -> Self {
+        let mut adjacency = vec![Vec::new(); atoms.len()];
+        for (k, bond) in bonds.iter().enumerate() {
+            adjacency[bond.a].push((bond.b, k));
+            adjacency[bond.b].push((bond.a, k));
+        }
+        let mut graph = Self {
+            atoms,
+            bonds,
+            main_chain,
+            adjacency,
+        };
+        graph.mark_ring_bonds();
+        graph
+    }
+
+    /// Flags every bond that is not a bridge (Tarjan's low-link algorithm).
+    fn mark_ring_bonds(&mut self) {
+        let n = self.atoms.len();
+        let mut disc = vec![usize::MAX; n];
+        let mut low = vec![0usize; n];
+        let mut timer = 0usize;
+        let mut bridges = vec![false; self.bonds.len()];
+
+        for root in 0..n {
+            if disc[root] != usize::MAX {
+                continue;
+            }
+            // Iterative DFS: (atom, bond used to reach it, next neighbour slot)
+            let mut stack: Vec<(usize, Option<usize>, usize)> = vec![(root, None, 0)];
+            disc[root] = timer;
+            low[root] = timer;
+            timer += 1;
+
+            while let Some(top) = stack.last_mut() {
+                let (v, parent_bond) = (top.0, top.1);
+                if top.2 < self.adjacency[v].len() {
+                    let (w, k) = self.adjacency[v][top.2];
+                    top.2 += 1;
+                    if Some(k) == parent_bond {
+                        continue;
+                    }
+                    if disc[w] == usize::MAX {
+                        disc[w] = timer;
+                        low[w] = timer;
+                        timer += 1;
+                        stack.push((w, Some(k), 0));
+                    } else {
+                        low[v] = low[v].min(disc[w]);
+                    }
+                } else {
+                    stack.pop();
+                    if let (Some(k), Some(&(u, _, _))) = (parent_bond, stack.last()) {
+                        low[u] = low[u].min(low[v]);
+                        if low[v] > disc[u] {
+                            bridges[k] = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        for (bond, is_bridge) in self.bonds.iter_mut().zip(bridges) {
+            bond.in_ring = !is_bridge;
+        }
+    }
+}
+
+// --- parser -----------------------------------------------------------------
+
+struct Parser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    src: &'a str,
+    atoms: Vec<GraphAtom>,
+    bonds: Vec<GraphBond>,
+    /// Explicit hydrogen count for bracket atoms, `None` for organic-subset atoms.
+    bracket_h: Vec<Option<u32>>,
+    /// Whether each atom was written outside any branch.
+    main_chain: Vec<bool>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            chars: src.chars().collect(),
+            pos: 0,
+            src,
+            atoms: Vec::new(),
+            bonds: Vec::new(),
+            bracket_h: Vec::new(),
+            main_chain: Vec::new(),
+        }
+    }
+
+    fn error(&self, msg: &str) -> String {
+        format!(
+            "invalid SMILES '{}' at position {}: {msg}",
+            self.src, self.pos
+        )
+    }
+
+    fn run(mut self) -> Result<MolGraph, String> {
+        if self.chars.is_empty() {
+            return Err(self.error("empty string"));
+        }
+
+        let mut prev: Option<usize> = None;
+        let mut branches: Vec<Option<usize>> = Vec::new();
+        let mut pending: Option<BondOrder> = None;
+        let mut open_rings: Vec<(u32, usize, Option<BondOrder>)> = Vec::new();
+
+        while self.pos < self.chars.len() {
+            let c = self.chars[self.pos];
+            match c {
+                '(' => {
+                    if prev.is_none() {
+                        return Err(self.error("branch without a preceding atom"));
+                    }
+                    branches.push(prev);
+                    self.pos += 1;
+                }
+                ')' => {
+                    prev = branches.pop().ok_or_else(|| self.error("unbalanced ')'"))?;
+                    self.pos += 1;
+                }
+                '-' | '/' | '\\' => {
+                    pending = Some(BondOrder::Single);
+                    self.pos += 1;
+                }
+                '=' => {
+                    pending = Some(BondOrder::Double);
+                    self.pos += 1;
+                }
+                '#' => {
+                    pending = Some(BondOrder::Triple);
+                    self.pos += 1;
+                }
+                '$' => {
+                    pending = Some(BondOrder::Quadruple);
+                    self.pos += 1;
+                }
+                ':' => {
+                    pending = Some(BondOrder::Aromatic);
+                    self.pos += 1;
+                }
+                '.' => {
+                    prev = None;
+                    self.pos += 1;
+                }
+                '%' | '0'..='9' => {
+                    let number = self.ring_number()?;
+                    let current = prev.ok_or_else(|| self.error("ring bond without an atom"))?;
+                    if let Some(idx) = open_rings.iter().position(|&(n, _, _)| n == number) {
+                        let (_, other, opening_order) = open_rings.swap_remove(idx);
+                        let order = pending.or(opening_order);
+                        self.add_bond(other, current, order);
+                    } else {
+                        open_rings.push((number, current, pending));
+                    }
+                    pending = None;
+                }
+                '[' => {
+                    let atom = self.bracket_atom()?;
+                    prev = Some(self.push_atom(atom, prev, pending.take()));
+                    self.main_chain.push(branches.is_empty());
+                }
+                _ => {
+                    let atom = self.organic_atom()?;
+                    prev = Some(self.push_atom(atom, prev, pending.take()));
+                    self.main_chain.push(branches.is_empty());
+                }
+            }
+        }
+
+        if !branches.is_empty() {
+            return Err(self.error("unbalanced '('"));
+        }
+        if !open_rings.is_empty() {
+            return Err(self.error("unclosed ring bond"));
+        }
+        if pending.is_some() {
+            return Err(self.error("dangling bond symbol"));
+        }
+
+        self.assign_implicit_hydrogens();
+        Ok(MolGraph::from_parts(
+            self.atoms,
+            self.bonds,
+            self.main_chain,
+        ))
+    }
+
+    fn ring_number(&mut self) -> Result<u32, String> {
+        let c = self.chars[self.pos];
+        if c == '%' {
+            let digits: String = self.chars.iter().skip(self.pos + 1).take(2).collect();
+            if digits.len() != 2 || !digits.chars().all(|d| d.is_ascii_digit()) {
+                return Err(self.error("'%' must be followed by two digits"));
+            }
+            self.pos += 3;
+            Ok(digits.parse().expect("two ASCII digits"))
+        } else {
+            self.pos += 1;
+            Ok(c as u32 - '0' as u32)
+        }
+    }
+
+    fn push_atom(
+        &mut self,
+        atom: (GraphAtom, Option<u32>),
+        prev: Option<usize>,
+        bond: Option<BondOrder>,
+    ) -> usize {
+        let (atom, explicit_h) = atom;
+        self.atoms.push(atom);
+        self.bracket_h.push(explicit_h);
+        let idx = self.atoms.len() - 1;
+        if let Some(p) = prev {
+            self.add_bond(p, idx, bond);
+        }
+        idx
+    }
+
+    fn add_bond(&mut self, a: usize, b: usize, order: Option<BondOrder>) {
+        let order = order.unwrap_or(if self.atoms[a].aromatic && self.atoms[b].aromatic {
+            BondOrder::Aromatic
+        } else {
+            BondOrder::Single
+        });
+        self.bonds.push(GraphBond {
+            a,
+            b,
+            order,
+            in_ring: false,
+        });
+    }
+
+    fn organic_atom(&mut self) -> Result<(GraphAtom, Option<u32>), String> {
+        let c = self.chars[self.pos];
+        let next = self.chars.get(self.pos + 1).copied();
+        let (atomic_number, aromatic, len) = match (c, next) {
+            ('C', Some('l')) => (17, false, 2),
+            ('B', Some('r')) => (35, false, 2),
+            ('B', _) => (5, false, 1),
+            ('C', _) => (6, false, 1),
+            ('N', _) => (7, false, 1),
+            ('O', _) => (8, false, 1),
+            ('P', _) => (15, false, 1),
+            ('S', _) => (16, false, 1),
+            ('F', _) => (9, false, 1),
+            ('I', _) => (53, false, 1),
+            ('*', _) => (0, false, 1),
+            ('b', _) => (5, true, 1),
+            ('c', _) => (6, true, 1),
+            ('n', _) => (7, true, 1),
+            ('o', _) => (8, true, 1),
+            ('p', _) => (15, true, 1),
+            ('s', _) => (16, true, 1),
+            _ => return Err(self.error(&format!("unexpected character '{c}'"))),
+        };
+        self.pos += len;
+        Ok((
+            GraphAtom {
+                atomic_number,
+                isotope: 0,
+                aromatic,
+                hydrogens: 0,
+                charge: 0,
+                chirality: 0,
+            },
+            None,
+        ))
+    }
+
+    fn bracket_atom(&mut self) -> Result<(GraphAtom, Option<u32>), String> {
+        let close = self.chars[self.pos..]
+            .iter()
+            .position(|&c| c == ']')
+            .ok_or_else(|| self.error("unclosed '['"))?
+            + self.pos;
+        let inner: Vec<char> = self.chars[self.pos + 1..close].to_vec();
+        let mut i = 0;
+
+        // isotope
+        let mut isotope = 0u16;
+        while let Some(d) = inner.get(i).and_then(|c| c.to_digit(10)) {
+            isotope = isotope.saturating_mul(10).saturating_add(d as u16);
+            i += 1;
+        }
+
+        // element symbol
+        let (atomic_number, aromatic) = match inner.get(i) {
+            Some('*') => {
+                i += 1;
+                (0, false)
+            }
+            Some(&c) if c.is_ascii_uppercase() => {
+                let two: String = inner.iter().skip(i).take(2).collect();
+                if let Some(z) = element_number(&two).filter(|_| two.len() == 2) {
+                    i += 2;
+                    (z, false)
+                } else {
+                    i += 1;
+                    let z = element_number(&c.to_string())
+                        .ok_or_else(|| self.error("unknown element"))?;
+                    (z, false)
+                }
+            }
+            Some(&c) if c.is_ascii_lowercase() => {
+                let two: String = inner.iter().skip(i).take(2).collect();
+                if let Some(z) = aromatic_bracket_number(&two).filter(|_| two.len() == 2) {
+                    i += 2;
+                    (z, true)
+                } else {
+                    i += 1;
+                    let z = element_number(&c.to_ascii_uppercase().to_string())
+                        .ok_or_else(|| self.error("unknown aromatic element"))?;
+                    (z, true)
+                }
+            }
+            _ => return Err(self.error("missing element symbol in bracket atom")),
+        };
+
+        // chirality
+        let mut chirality = 0u8;
+        while i < inner.len() && inner[i] == '@' {
+            chirality = chirality.saturating_add(1);
+            i += 1;
+        }
+
+        // hydrogen count
+        let mut hydrogens = 0u32;
+        if inner.get(i) == Some(&'H') {
+            i += 1;
+            hydrogens = 1;
+            if let Some(d) = inner.get(i).and_then(|c| c.to_digit(10)) {
+                hydrogens = d;
+                i += 1;
+            }
+        }
+
+        // charge
+        let mut charge = 0i32;
+        while let Some(&sign_char) = inner.get(i).filter(|c| **c == '+' || **c == '-') {
+            let sign = if sign_char == '+' { 1 } else { -1 };
+            i += 1;
+            if let Some(d) = inner.get(i).and_then(|c| c.to_digit(10)) {
+                charge += sign * d as i32;
+                i += 1;
+            } else {
+                charge += sign;
+            }
+        }
+
+        // atom class
+        if inner.get(i) == Some(&':') {
+            i = inner.len();
+        }
+
+        if i != inner.len() {
+            return Err(self.error("unexpected content in bracket atom"));
+        }
+
+        self.pos = close + 1;
+        Ok((
+            GraphAtom {
+                atomic_number,
+                isotope,
+                aromatic,
+                hydrogens,
+                charge,
+                chirality,
+            },
+            Some(hydrogens),
+        ))
+    }
+
+    fn assign_implicit_hydrogens(&mut self) {
+        let mut bond_sum = vec![0u32; self.atoms.len()];
+        for bond in &self.bonds {
+            bond_sum[bond.a] += bond.order.valence();
+            bond_sum[bond.b] += bond.order.valence();
+        }
+        for (i, atom) in self.atoms.iter_mut().enumerate() {
+            if self.bracket_h[i].is_some() {
+                continue;
+            }
+            let used = bond_sum[i] + u32::from(atom.aromatic);
+            atom.hydrogens = default_valences(atom.atomic_number)
+                .iter()
+                .find(|&&v| v >= used)
+                .map_or(0, |&v| v - used);
+        }
+    }
+}
+
+/// Normal valences of the SMILES organic subset.
+pub(crate) fn default_valences(atomic_number: u8) -> &'static [u32] {
+    match atomic_number {
+        5 => &[3],
+        6 => &[4],
+        7 => &[3, 5],
+        8 => &[2],
+        15 => &[3, 5],
+        16 => &[2, 4, 6],
+        9 | 17 | 35 | 53 => &[1],
+        _ => &[],
+    }
+}
+
+/// Highest valence of an organic-subset atom carrying formal charge `charge`.
+///
+/// A cation of N, O, P, S or a halogen gains one bond per charge
+/// (ammonium, oxonium) and an anion loses one; carbon loses one bond either
+/// way (carbocation, carbanion) and boron gains one as an anion
+/// (tetrafluoroborate). Returns `None` for elements outside the organic
+/// subset, whose valence is not checked.
+pub(crate) fn max_valence(atomic_number: u8, charge: i32) -> Option<u32> {
+    let max = *default_valences(atomic_number).last()? as i32;
+    let limit = match atomic_number {
+        5 => max - charge,
+        6 => max - charge.abs(),
+        _ => max + charge,
+    };
+    Some(limit.max(0) as u32)
+}
+
+/// Two-letter aromatic symbols allowed inside brackets (`[se]`, `[as]`).
+fn aromatic_bracket_number(symbol: &str) -> Option<u8> {
+    match symbol {
+        "se" => Some(34),
+        "as" => Some(33),
+        _ => None,
+    }
+}
+
+/// Elements found in common polymers, initiators and counter-ions.
+const ELEMENTS: &[(&str, u8)] = &[
+    ("H", 1),
+    ("He", 2),
+    ("Li", 3),
+    ("Be", 4),
+    ("B", 5),
+    ("C", 6),
+    ("N", 7),
+    ("O", 8),
+    ("F", 9),
+    ("Ne", 10),
+    ("Na", 11),
+    ("Mg", 12),
+    ("Al", 13),
+    ("Si", 14),
+    ("P", 15),
+    ("S", 16),
+    ("Cl", 17),
+    ("Ar", 18),
+    ("K", 19),
+    ("Ca", 20),
+    ("Ti", 22),
+    ("Cr", 24),
+    ("Mn", 25),
+    ("Fe", 26),
+    ("Co", 27),
+    ("Ni", 28),
+    ("Cu", 29),
+    ("Zn", 30),
+    ("Ge", 32),
+    ("As", 33),
+    ("Se", 34),
+    ("Br", 35),
+    ("Sn", 50),
+    ("I", 53),
+    ("Pt", 78),
+    ("Au", 79),
+];
+
+/// Atomic number of an element symbol.
+fn element_number(symbol: &str) -> Option<u8> {
+    ELEMENTS
+        .iter()
+        .find(|&&(sym, _)| sym == symbol)
+        .map(|&(_, z)| z)
+}
+
+/// Element symbol of an atomic number (`*` for the wildcard).
+pub(crate) fn element_symbol(atomic_number: u8) -> &'static str {
+    if atomic_number == 0 {
+        return "*";
+    }
+    ELEMENTS
+        .iter()
+        .find(|&&(_, z)| z == atomic_number)
+        .map_or("*", |&(sym, _)| sym)
+}
@@ -0,0 +1,570 @@
+//! Structural group decomposition for group-contribution methods.
+//!
+//! Each heavy atom is assigned to a small functional group according to its
+//! element, hydrogen count and bonding (a carbonyl carbon absorbs its `=O`, a
+//! nitrile carbon its `≡N`, …). Property estimators then sum per-group increments
+//! over the whole chain, following Van Krevelen's additive scheme.
+
+use crate::polymer::PolymerChain;
+
+use super::graph::{BondOrder, MolGraph};
+
+/// A structural group recognised by the decomposition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum GroupName {
+    /// `-CH3`
+    Methyl,
+    /// `-CH2-`
+    Methylene,
+    /// `>CH-`
+    Methine,
+    /// `>C<`
+    QuaternaryCarbon,
+    /// `=CH2`
+    VinylMethylene,
+    /// `=CH-`
+    Vinylene,
+    /// `=C<`
+    Vinylidene,
+    /// `≡C-` / `≡CH`
+    Acetylenic,
+    /// Aromatic `CH`
+    AromaticCH,
+    /// Substituted aromatic carbon
+    AromaticC,
+    /// `>C=O`
+    Carbonyl,
+    /// `-C≡N`
+    Nitrile,
+    /// `-O-`
+    Ether,
+    /// `-OH`
+    Hydroxyl,
+    /// `-NH2`
+    PrimaryAmine,
+    /// `-NH-`
+    SecondaryAmine,
+    /// `>N-`
+    TertiaryAmine,
+    /// Aromatic nitrogen
+    AromaticN,
+    /// `-S-`
+    Thioether,
+    /// `-SO2-` (and sulfoxide)
+    Sulfone,
+    /// `-F`
+    Fluorine,
+    /// `-Cl`
+    Chlorine,
+    /// `-Br`
+    Bromine,
+    /// `-I`
+    Iodine,
+    /// `>Si<`
+    Silicon,
+    /// Any atom without a tabulated group (metals, counter-ions, …).
+    Other,
+}
+
+impl GroupName {
+    /// Short structural formula of the group, e.g. `-CH3` or `>C=O`.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Methyl => "-CH3",
+            Self::Methylene => "-CH2-",
+            Self::Methine => ">CH-",
+            Self::QuaternaryCarbon => ">C<",
+            Self::VinylMethylene => "=CH2",
+            Self::Vinylene => "=CH-",
+            Self::Vinylidene => "=C<",
+            Self::Acetylenic => "≡C-",
+            Self::AromaticCH => "aromatic CH",
+            Self::AromaticC => "aromatic C",
+            Self::Carbonyl => ">C=O",
+            Self::Nitrile => "-C≡N",
+            Self::Ether => "-O-",
+            Self::Hydroxyl => "-OH",
+            Self::PrimaryAmine => "-NH2",
+            Self::SecondaryAmine => "-NH-",
+            Self::TertiaryAmine => ">N-",
+            Self::AromaticN => "aromatic N",
+            Self::Thioether => "-S-",
+            Self::Sulfone => "-SO2-",
+            Self::Fluorine => "-F",
+            Self::Chlorine => "-Cl",
+            Self::Bromine => "-Br",
+            Self::Iodine => "-I",
+            Self::Silicon => ">Si<",
+            Self::Other => "other",
+        }
+    }
+
+    /// Molar glass transition function Yg (K·g/mol).
+    ///
+    /// Values follow Van Krevelen's table (chapter 6), split into atom-centred
+    /// groups: e.g. `-CH(C6H5)-` (36 100) = `>CH-` + 5 aromatic CH + 1 aromatic C.
+    pub(crate) fn glass_transition_increment(self) -> f64 {
+        match self {
+            Self::Methyl => 3_000.0,
+            Self::Methylene => 2_700.0,
+            Self::Methine => 5_000.0,
+            Self::QuaternaryCarbon => 2_600.0,
+            Self::VinylMethylene => 1_700.0,
+            Self::Vinylene => 2_000.0,
+            Self::Vinylidene => 2_500.0,
+            Self::Acetylenic => 3_000.0,
+            Self::AromaticCH => 5_450.0,
+            Self::AromaticC => 3_850.0,
+            Self::Carbonyl => 8_500.0,
+            Self::Nitrile => 11_900.0,
+            Self::Ether => 4_000.0,
+            Self::Hydroxyl => 8_050.0,
+            Self::PrimaryAmine => 6_000.0,
+            Self::SecondaryAmine => 14_000.0,
+            Self::TertiaryAmine => 3_000.0,
+            Self::AromaticN => 5_000.0,
+            Self::Thioether => 6_000.0,
+            Self::Sulfone => 30_000.0,
+            Self::Fluorine => 4_800.0,
+            Self::Chlorine => 14_400.0,
+            Self::Bromine => 20_000.0,
+            Self::Iodine => 25_000.0,
+            Self::Silicon => 1_100.0,
+            Self::Other => 0.0,
+        }
+    }
+
+    /// Molar melting transition function Ym (K·g/mol).
+    ///
+    /// Values follow Van Krevelen's table (chapter 6), split into atom-centred
+    /// groups: e.g. `-CH(CH3)-` (13 500) = `>CH-` + `-CH3`, `-CF2-` = `>C<` + 2 `-F`.
+    pub(crate) fn melting_transition_increment(self) -> f64 {
+        match self {
+            Self::Methyl => 6_500.0,
+            Self::Methylene => 5_700.0,
+            Self::Methine => 7_000.0,
+            Self::QuaternaryCarbon => 5_000.0,
+            Self::VinylMethylene => 4_000.0,
+            Self::Vinylene => 4_600.0,
+            Self::Vinylidene => 5_000.0,
+            Self::Acetylenic => 6_000.0,
+            Self::AromaticCH => 6_500.0,
+            Self::AromaticC => 7_000.0,
+            Self::Carbonyl => 16_000.0,
+            Self::Nitrile => 18_000.0,
+            Self::Ether => 10_000.0,
+            Self::Hydroxyl => 9_500.0,
+            Self::PrimaryAmine => 10_000.0,
+            Self::SecondaryAmine => 15_700.0,
+            Self::TertiaryAmine => 6_000.0,
+            Self::AromaticN => 7_000.0,
+            Self::Thioether => 11_000.0,
+            Self::Sulfone => 30_000.0,
+            Self::Fluorine => 12_500.0,
+            Self::Chlorine => 17_000.0,
+            Self::Bromine => 22_000.0,
+            Self::Iodine => 27_000.0,
+            Self::Silicon => 2_000.0,
+            Self::Other => 0.0,
+        }
+    }
+
+    /// Molar heat of fusion ΔHm (J/mol) of the fully crystalline polymer.
+    ///
+    /// Increments after Van Krevelen (chapter 5); `-CH2-` (4 000) reproduces
+    /// polyethylene, `-C6H4-` (5 000) = 4 aromatic CH + 2 aromatic C.
+    pub(crate) fn heat_of_fusion(self) -> f64 {
+        match self {
+            Self::Methyl => 2_700.0,
+            Self::Methylene => 4_000.0,
+            Self::Methine => 2_000.0,
+            Self::QuaternaryCarbon => 1_000.0,
+            Self::VinylMethylene => 2_000.0,
+            Self::Vinylene => 3_000.0,
+            Self::Vinylidene => 1_500.0,
+            Self::Acetylenic => 2_000.0,
+            Self::AromaticCH => 800.0,
+            Self::AromaticC => 900.0,
+            Self::Carbonyl => 3_000.0,
+            Self::Nitrile => 1_000.0,
+            Self::Ether => 4_000.0,
+            Self::Hydroxyl => 2_500.0,
+            Self::PrimaryAmine => 2_500.0,
+            Self::SecondaryAmine => 3_000.0,
+            Self::TertiaryAmine => 1_500.0,
+            Self::AromaticN => 900.0,
+            Self::Thioether => 4_500.0,
+            Self::Sulfone => 6_000.0,
+            Self::Fluorine => 1_500.0,
+            Self::Chlorine => 5_000.0,
+            Self::Bromine => 5_000.0,
+            Self::Iodine => 5_000.0,
+            Self::Silicon => 1_500.0,
+            Self::Other => 0.0,
+        }
+    }
+
+    /// Van der Waals volume Vw (cm³/mol), after Bondi.
+    ///
+    /// Increments are those tabulated by Van Krevelen (chapter 4) for the same
+    /// atom-centred groups, e.g. `-C6H5` (45.84) = 5 aromatic CH + 1 aromatic C.
+    pub(crate) fn van_der_waals_volume(self) -> f64 {
+        match self {
+            Self::Methyl => 13.67,
+            Self::Methylene => 10.23,
+            Self::Methine => 6.78,
+            Self::QuaternaryCarbon => 3.33,
+            Self::VinylMethylene => 11.94,
+            Self::Vinylene => 8.47,
+            Self::Vinylidene => 5.01,
+            Self::Acetylenic => 8.05,
+            Self::AromaticCH => 8.06,
+            Self::AromaticC => 5.54,
+            Self::Carbonyl => 11.70,
+            Self::Nitrile => 14.70,
+            Self::Ether => 3.70,
+            Self::Hydroxyl => 8.04,
+            Self::PrimaryAmine => 10.54,
+            Self::SecondaryAmine => 8.08,
+            Self::TertiaryAmine => 4.33,
+            Self::AromaticN => 5.20,
+            Self::Thioether => 10.82,
+            Self::Sulfone => 20.30,
+            Self::Fluorine => 5.72,
+            Self::Chlorine => 11.62,
+            Self::Bromine => 14.40,
+            Self::Iodine => 19.18,
+            Self::Silicon => 16.60,
+            Self::Other => 0.0,
+        }
+    }
+
+    /// Molar polarization P (cm³/mol) at low frequency, after Van Krevelen.
+    ///
+    /// Increments follow Van Krevelen's P_LL table (chapter 11); for nonpolar
+    /// groups P equals the molar refraction, for polar groups it also holds the
+    /// dipole orientation part.
+    pub(crate) fn molar_polarization(self) -> f64 {
+        match self {
+            Self::Carbonyl => 10.0,
+            Self::Nitrile => 11.0,
+            Self::Ether => 5.2,
+            Self::Hydroxyl => 6.0,
+            Self::PrimaryAmine => 6.0,
+            Self::SecondaryAmine => 5.0,
+            Self::TertiaryAmine => 4.0,
+            Self::AromaticN => 5.5,
+            Self::Thioether => 8.5,
+            Self::Sulfone => 16.0,
+            Self::Fluorine => 2.0,
+            Self::Chlorine => 9.5,
+            Self::Bromine => 12.5,
+            Self::Iodine => 18.0,
+            Self::Silicon => 11.0,
+            _ => self.molar_refraction(),
+        }
+    }
+
+    /// Molar refraction R (cm³/mol), i.e. the electronic part of the polarization.
+    ///
+    /// Lorentz-Lorenz increments (Van Krevelen, chapter 10), e.g. `-C6H5` (25.5)
+    /// = 5 aromatic CH + 1 aromatic C.
+    pub(crate) fn molar_refraction(self) -> f64 {
+        match self {
+            Self::Methyl => 5.64,
+            Self::Methylene => 4.65,
+            Self::Methine => 3.62,
+            Self::QuaternaryCarbon => 2.58,
+            Self::VinylMethylene => 5.10,
+            Self::Vinylene => 4.10,
+            Self::Vinylidene => 3.10,
+            Self::Acetylenic => 4.00,
+            Self::AromaticCH => 4.32,
+            Self::AromaticC => 3.90,
+            Self::Carbonyl => 4.60,
+            Self::Nitrile => 5.50,
+            Self::Ether => 1.64,
+            Self::Hydroxyl => 2.55,
+            Self::PrimaryAmine => 4.40,
+            Self::SecondaryAmine => 3.60,
+            Self::TertiaryAmine => 2.80,
+            Self::AromaticN => 4.00,
+            Self::Thioether => 7.90,
+            Self::Sulfone => 8.60,
+            Self::Fluorine => 1.10,
+            Self::Chlorine => 5.97,
+            Self::Bromine => 8.90,
+            Self::Iodine => 13.90,
+            Self::Silicon => 7.00,
+            Self::Other => 0.0,
+        }
+    }
+
+    /// Cohesive energy Ecoh (J/mol), after Fedors.
+    ///
+    /// Increments follow Fedors' table as reproduced by Van Krevelen (chapter 7),
+    /// e.g. `-C6H5` (31 940) = 5 aromatic CH + 1 aromatic C.
+    pub(crate) fn cohesive_energy(self) -> f64 {
+        match self {
+            Self::Methyl => 4_710.0,
+            Self::Methylene => 4_940.0,
+            Self::Methine => 3_430.0,
+            Self::QuaternaryCarbon => 1_470.0,
+            Self::VinylMethylene => 4_310.0,
+            Self::Vinylene => 4_310.0,
+            Self::Vinylidene => 4_310.0,
+            Self::Acetylenic => 3_900.0,
+            Self::AromaticCH => 5_450.0,
+            Self::AromaticC => 4_690.0,
+            Self::Carbonyl => 17_370.0,
+            Self::Nitrile => 25_530.0,
+            Self::Ether => 3_350.0,
+            Self::Hydroxyl => 29_800.0,
+            Self::PrimaryAmine => 12_560.0,
+            Self::SecondaryAmine => 8_370.0,
+            Self::TertiaryAmine => 4_190.0,
+            Self::AromaticN => 11_700.0,
+            Self::Thioether => 14_150.0,
+            Self::Sulfone => 39_140.0,
+            Self::Fluorine => 4_190.0,
+            Self::Chlorine => 11_550.0,
+            Self::Bromine => 15_490.0,
+            Self::Iodine => 19_050.0,
+            Self::Silicon => 3_390.0,
+            Self::Other => 0.0,
+        }
+    }
+
+    /// Molar elastic wave velocity function U_R, after Rao ((cm³/mol)·(cm/s)^⅓).
+    ///
+    /// Increments follow Van Krevelen's table (chapter 14), rescaled so that the
+    /// longitudinal sound velocities of PS and PMMA (2350 and 2690 m/s) are
+    /// reproduced; `-C6H5` = 5 aromatic CH + 1 aromatic C, `-COO-` = carbonyl + ether.
+    pub(crate) fn rao_function(self) -> f64 {
+        match self {
+            Self::Methyl => 1_580.0,
+            Self::Methylene => 990.0,
+            Self::Methine => 450.0,
+            Self::QuaternaryCarbon => 50.0,
+            Self::VinylMethylene => 1_210.0,
+            Self::Vinylene => 770.0,
+            Self::Vinylidene => 340.0,
+            Self::Acetylenic => 680.0,
+            Self::AromaticCH => 800.0,
+            Self::AromaticC => 620.0,
+            Self::Carbonyl => 960.0,
+            Self::Nitrile => 1_470.0,
+            Self::Ether => 450.0,
+            Self::Hydroxyl => 710.0,
+            Self::PrimaryAmine => 1_020.0,
+            Self::SecondaryAmine => 680.0,
+            Self::TertiaryAmine => 170.0,
+            Self::AromaticN => 620.0,
+            Self::Thioether => 620.0,
+            Self::Sulfone => 1_130.0,
+            Self::Fluorine => 560.0,
+            Self::Chlorine => 1_580.0,
+            Self::Bromine => 1_810.0,
+            Self::Iodine => 2_150.0,
+            Self::Silicon => 280.0,
+            Self::Other => 0.0,
+        }
+    }
+
+    /// Molar hydration Hw (mol H₂O per group) at 50 % relative humidity.
+    ///
+    /// Values after Van Krevelen (chapter 18); an ester `-COO-` (0.075) is a
+    /// carbonyl plus an ether oxygen, an amide `-CONH-` a carbonyl plus `-NH-`.
+    pub(crate) fn molar_hydration(self) -> f64 {
+        match self {
+            Self::Carbonyl => 0.05,
+            Self::Nitrile => 0.02,
+            Self::Ether => 0.025,
+            Self::Hydroxyl => 0.5,
+            Self::PrimaryAmine => 0.5,
+            Self::SecondaryAmine => 0.45,
+            Self::TertiaryAmine => 0.2,
+            Self::AromaticN => 0.2,
+            Self::Thioether => 0.01,
+            Self::Sulfone => 0.1,
+            Self::Chlorine => 0.01,
+            _ => 0.0,
+        }
+    }
+
+    /// Group dipole moment (Debye), from the bond moments of small model compounds.
+    pub(crate) fn dipole_moment(self) -> f64 {
+        match self {
+            Self::Carbonyl => 2.7,
+            Self::Nitrile => 3.9,
+            Self::Ether => 1.2,
+            Self::Hydroxyl => 1.7,
+            Self::PrimaryAmine => 1.3,
+            Self::SecondaryAmine => 1.0,
+            Self::TertiaryAmine => 0.7,
+            Self::AromaticN => 2.2,
+            Self::Thioether => 1.5,
+            Self::Sulfone => 4.4,
+            Self::Fluorine => 1.4,
+            Self::Chlorine => 1.9,
+            Self::Bromine => 1.8,
+            Self::Iodine => 1.6,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Decomposes a chain into structural groups.
+///
+/// Returns `(group, count)` pairs sorted by group. Wildcard atoms (`*`) are
+/// ignored, like in [`molecular_formula`](super::formula::molecular_formula).
+/// Every group-contribution estimator (Tg, volume, solubility parameter, …)
+/// sums its increments over this decomposition, so it shows which groups were
+/// matched when an estimate looks wrong.
+///
+/// # Panics
+///
+/// Panics if `chain.smiles` is not valid SMILES (never the case for chains
+/// produced by the builders).
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::groups::{decompose, GroupName};
+///
+/// let bs = parse("{[]CC(Cl)[]}").unwrap();
+/// let pvc = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(10))
+///     .homopolymer()
+///     .unwrap();
+/// // CH3–(CHCl–CH2)9–CH2Cl: both chain ends are hydrogen-capped
+/// assert_eq!(
+///     decompose(&pvc),
+///     vec![
+///         (GroupName::Methyl, 1),
+///         (GroupName::Methylene, 10),
+///         (GroupName::Methine, 9),
+///         (GroupName::Chlorine, 10),
+///     ]
+/// );
+/// ```
+pub fn decompose(chain: &PolymerChain) -> Vec<(GroupName, usize)> {
+    let graph = MolGraph::parse(&chain.smiles).expect("chain SMILES must be valid SMILES");
+    let mut counts = std::collections::BTreeMap::new();
+    for group in classify_atoms(&graph).into_iter().flatten() {
+        *counts.entry(group).or_insert(0) += 1;
+    }
+    counts.into_iter().collect()
+}
+
+/// Sums a per-group increment over the whole chain.
+pub(crate) fn sum_increments(chain: &PolymerChain, increment: fn(GroupName) -> f64) -> f64 {
+    decompose(chain)
+        .into_iter()
+        .map(|(group, count)| increment(group) * count as f64)
+        .sum()
+}
+
+/// Assigns a group to every heavy atom.
+///
+/// Atoms absorbed by a neighbouring group (the `=O` of a carbonyl, the `≡N` of a
+/// nitrile) and wildcard atoms map to `None`.
+fn classify_atoms(graph: &MolGraph) -> Vec<Option<GroupName>> {
+    let mut groups: Vec<Option<GroupName>> = vec![None; graph.atoms.len()];
+    let mut absorbed = vec![false; graph.atoms.len()];
+
+    // First pass: multi-atom groups that absorb terminal heteroatoms.
+    for (i, atom) in graph.atoms.iter().enumerate() {
+        let terminal_partners = |element: u8, order: BondOrder| {
+            graph
+                .neighbors(i)
+                .iter()
+                .filter(|&&(j, k)| {
+                    graph.atoms[j].atomic_number == element
+                        && graph.bonds[k].order == order
+                        && graph.degree(j) == 1
+                })
+                .map(|&(j, _)| j)
+                .collect::<Vec<_>>()
+        };
+        let group = match atom.atomic_number {
+            6 if !atom.aromatic => {
+                let oxo = terminal_partners(8, BondOrder::Double);
+                let nitrile = terminal_partners(7, BondOrder::Triple);
+                if let Some(&o) = oxo.first() {
+                    absorbed[o] = true;
+                    Some(GroupName::Carbonyl)
+                } else if let Some(&n) = nitrile.first() {
+                    absorbed[n] = true;
+                    Some(GroupName::Nitrile)
+                } else {
+                    None
+                }
+            }
+            16 => {
+                let oxo = terminal_partners(8, BondOrder::Double);
+                if oxo.is_empty() {
+                    None
+                } else {
+                    oxo.iter().for_each(|&o| absorbed[o] = true);
+                    Some(GroupName::Sulfone)
+                }
+            }
+            _ => None,
+        };
+        groups[i] = group;
+    }
+
+    // Second pass: single-atom groups.
+    for (i, atom) in graph.atoms.iter().enumerate() {
+        if groups[i].is_some() || absorbed[i] || atom.atomic_number == 0 {
+            continue;
+        }
+        let max_order = graph
+            .neighbors(i)
+            .iter()
+            .map(|&(_, k)| graph.bonds[k].order)
+            .fold(BondOrder::Single, |acc, o| match (acc, o) {
+                (BondOrder::Triple, _) | (_, BondOrder::Triple) => BondOrder::Triple,
+                (BondOrder::Double, _) | (_, BondOrder::Double) => BondOrder::Double,
+                _ => BondOrder::Single,
+            });
+        let h = atom.hydrogens;
+        groups[i] = Some(match atom.atomic_number {
+            6 if atom.aromatic => {
+                if h > 0 {
+                    GroupName::AromaticCH
+                } else {
+                    GroupName::AromaticC
+                }
+            }
+            6 => match (max_order, h) {
+                (BondOrder::Triple, _) => GroupName::Acetylenic,
+                (BondOrder::Double, 2..) => GroupName::VinylMethylene,
+                (BondOrder::Double, 1) => GroupName::Vinylene,
+                (BondOrder::Double, _) => GroupName::Vinylidene,
+                (_, 3..) => GroupName::Methyl,
+                (_, 2) => GroupName::Methylene,
+                (_, 1) => GroupName::Methine,
+                _ => GroupName::QuaternaryCarbon,
+            },
+            7 if atom.aromatic => GroupName::AromaticN,
+            7 => match h {
+                2.. => GroupName::PrimaryAmine,
+                1 => GroupName::SecondaryAmine,
+                _ => GroupName::TertiaryAmine,
+            },
+            8 if h > 0 || atom.charge < 0 => GroupName::Hydroxyl,
+            8 => GroupName::Ether,
+            16 => GroupName::Thioether,
+            9 => GroupName::Fluorine,
+            17 => GroupName::Chlorine,
+            35 => GroupName::Bromine,
+            53 => GroupName::Iodine,
+            14 => GroupName::Silicon,
+            _ => GroupName::Other,
+        });
+    }
+
+    groups
+}
@@ -0,0 +1,121 @@
+//! Mechanical behaviour: ductility class and Young's modulus.
+
+use crate::polymer::PolymerChain;
+
+use super::groups::{self, GroupName};
+use super::molecular_weight::average_mass;
+use super::rheology::entanglement_molecular_weight;
+use super::thermal::{
+    crystallization_tendency, tg_fox_flory, tg_infinite_chain, CrystallizationTendency,
+    DEFAULT_FOX_FLORY_K,
+};
+use super::volume::molar_volume;
+
+/// Poisson ratio of a glassy amorphous polymer.
+const GLASSY_POISSON_RATIO: f64 = 0.33;
+
+/// Molar gas constant (J/(mol·K)).
+const GAS_CONSTANT: f64 = 8.314_462_618;
+
+/// Expected mechanical response of a bulk polymer at a given temperature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuctilityClass {
+    /// Glassy and amorphous — fails with little deformation (e.g. PS, PMMA at room temperature).
+    Brittle,
+    /// Yields and draws before failure, held together by crystallites (e.g. PE, PP, nylons).
+    Ductile,
+    /// Above Tg without crystallites to hold it — soft and elastic (e.g. polyisoprene).
+    Rubbery,
+}
+
+/// Classifies the mechanical behaviour of `chain` at `temperature_k`.
+///
+/// Compares the use temperature with the chain-length corrected Tg
+/// ([`tg_fox_flory`] applied to [`tg_infinite_chain`]) and combines the result
+/// with the [`crystallization_tendency`]:
+///
+/// | | semicrystalline (High, Medium) | amorphous (Low, Amorphous) |
+/// |---|---|---|
+/// | T < Tg | Ductile | Brittle |
+/// | T ≥ Tg | Ductile | Rubbery |
+///
+/// This is a rule of thumb: it ignores entanglement, orientation and rate
+/// effects, and short chains come out more rubbery through their lower Tg.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::mechanical::{ductility_class, DuctilityClass};
+///
+/// let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+/// let ps = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(500))
+///     .homopolymer()
+///     .unwrap();
+/// assert_eq!(ductility_class(&ps, 298.15), DuctilityClass::Brittle);
+/// assert_eq!(ductility_class(&ps, 450.0), DuctilityClass::Rubbery);
+/// ```
+pub fn ductility_class(chain: &PolymerChain, temperature_k: f64) -> DuctilityClass {
+    let semicrystalline = matches!(
+        crystallization_tendency(chain),
+        CrystallizationTendency::High | CrystallizationTendency::Medium
+    );
+    let tg = tg_fox_flory(tg_infinite_chain(chain), chain.mn, DEFAULT_FOX_FLORY_K);
+    match (semicrystalline, temperature_k < tg) {
+        (true, _) => DuctilityClass::Ductile,
+        (false, true) => DuctilityClass::Brittle,
+        (false, false) => DuctilityClass::Rubbery,
+    }
+}
+
+/// Estimates the Young's modulus (GPa) of the amorphous polymer at `temperature_k`.
+///
+/// Below Tg (chain-length corrected with [`tg_fox_flory`]) the glassy modulus
+/// comes from the Rao function U_R: the longitudinal sound velocity
+/// u = (U_R / V)³ gives E = ρ·u²·(1 + ν)(1 − 2ν)/(1 − ν) with ν = 0.33. Above Tg the
+/// rubbery plateau E = 3ρRT / Me is used, with the
+/// [`entanglement_molecular_weight`] Me; chains shorter than Me are not
+/// entangled, flow, and return 0.
+///
+/// Crystallites stiffen semicrystalline polymers well beyond these amorphous
+/// values (PE is ~0.2–1 GPa at room temperature, not a few MPa).
+///
+/// # Reference
+///
+/// Van Krevelen, D. W. & te Nijenhuis, K. (2009).
+/// *Properties of Polymers*, 4th ed., Elsevier. Chapters 13–14.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::mechanical::youngs_modulus;
+///
+/// let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+/// let ps = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(500))
+///     .homopolymer()
+///     .unwrap();
+/// let e = youngs_modulus(&ps, 298.15);
+/// assert!((e - 3.0).abs() < 0.6, "E = {e} GPa"); // glassy PS ≈ 3 GPa
+/// ```
+pub fn youngs_modulus(chain: &PolymerChain, temperature_k: f64) -> f64 {
+    let mass = average_mass(chain);
+    let n = chain.repeat_count as f64;
+    let volume = molar_volume(chain, temperature_k); // cm³/mol per repeat unit
+    let density = mass / n / volume * 1.0e3; // kg/m³
+    let tg = tg_fox_flory(tg_infinite_chain(chain), chain.mn, DEFAULT_FOX_FLORY_K);
+
+    if temperature_k < tg {
+        let rao = groups::sum_increments(chain, GroupName::rao_function) / n;
+        let sound_velocity = (rao / volume).powi(3) * 1.0e-2; // m/s
+        let nu = GLASSY_POISSON_RATIO;
+        density * sound_velocity * sound_velocity * (1.0 + nu) * (1.0 - 2.0 * nu) / (1.0 - nu)
+            * 1.0e-9
+    } else {
+        let entanglement_mass = entanglement_molecular_weight(chain);
+        if mass < entanglement_mass {
+            return 0.0;
+        }
+        3.0 * density * GAS_CONSTANT * temperature_k / (entanglement_mass * 1.0e-3) * 1.0e-9
+    }
+}
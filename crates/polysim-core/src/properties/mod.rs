@@ -2,7 +2,24 @@
 //!
 //! All temperatures are in **Kelvin** and all masses in **g/mol**.
 
+pub(crate) mod canonical;
+pub mod conformation;
+pub mod electrical;
 pub mod ensemble;
 pub mod formula;
+pub(crate) mod graph;
+pub mod groups;
+pub mod mechanical;
 pub mod molecular_weight;
+pub mod network;
+pub mod property;
+pub mod reference;
+pub mod rheology;
+pub mod scattering;
+pub mod similarity;
+pub mod solubility;
+pub mod sustainability;
 pub mod thermal;
+pub mod topology;
+pub mod validate;
+pub mod volume;
@@ -1,18 +1,82 @@
-use opensmiles::{parse as parse_smiles, AtomSymbol};
+use std::collections::BTreeMap;
 
+use opensmiles::parse as parse_smiles;
+
+use crate::builder::RepeatUnitCache;
+use crate::error::PolySimError;
 use crate::polymer::PolymerChain;
 
+use super::graph::element_symbol;
+
 /// Masse standard de l'hydrogène (IUPAC 2021), en g/mol.
-const H_AVERAGE_MASS: f64 = 1.008;
+pub(crate) const H_AVERAGE_MASS: f64 = 1.008;
 
 /// Masse du proton (¹H), en g/mol.
 const H_MONO_MASS: f64 = 1.00782503207;
 
+/// Table des masses atomiques utilisée par [`average_mass_with`] et
+/// [`monoisotopic_mass_with`].
+///
+/// Par défaut, la table contient les poids atomiques standard IUPAC et les masses
+/// des nucléides les plus abondants. Chaque élément peut être surchargé par son
+/// numéro atomique (abondances isotopiques particulières, valeurs IUPAC plus
+/// récentes, …). Les atomes à isotope explicite (`[13C]`) gardent toujours la masse
+/// de l'isotope indiqué.
+///
+/// # Exemple
+///
+/// ```rust
+/// use polysim_core::properties::molecular_weight::MassTable;
+///
+/// // Carbone appauvri en ¹³C
+/// let table = MassTable::default().with_average_mass(6, 12.0096);
+/// assert_eq!(table.average(6), Some(12.0096));
+/// assert_eq!(table.average(1), None); // valeur IUPAC par défaut
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MassTable {
+    average: BTreeMap<u8, f64>,
+    monoisotopic: BTreeMap<u8, f64>,
+}
+
+impl MassTable {
+    /// Surcharge la masse moyenne (g/mol) de l'élément de numéro atomique `atomic_number`.
+    pub fn with_average_mass(mut self, atomic_number: u8, mass: f64) -> Self {
+        self.average.insert(atomic_number, mass);
+        self
+    }
+
+    /// Surcharge la masse monoisotopique (g/mol) de l'élément de numéro atomique
+    /// `atomic_number`.
+    pub fn with_monoisotopic_mass(mut self, atomic_number: u8, mass: f64) -> Self {
+        self.monoisotopic.insert(atomic_number, mass);
+        self
+    }
+
+    /// Masse moyenne surchargée pour `atomic_number`, `None` si la valeur IUPAC s'applique.
+    pub fn average(&self, atomic_number: u8) -> Option<f64> {
+        self.average.get(&atomic_number).copied()
+    }
+
+    /// Masse monoisotopique surchargée pour `atomic_number`, `None` si la valeur par
+    /// défaut s'applique.
+    pub fn monoisotopic(&self, atomic_number: u8) -> Option<f64> {
+        self.monoisotopic.get(&atomic_number).copied()
+    }
+}
+
 /// Calcule la masse moléculaire moyenne (poids atomiques IUPAC) de la chaîne, en g/mol.
 ///
 /// Chaque atome lourd contribue par sa masse standard (moyenne isotopique), et les
 /// hydrogènes implicites/explicites sont ajoutés avec la masse standard de l'hydrogène.
 ///
+/// Si le SMILES de la chaîne ne peut pas être analysé, la masse est estimée à partir
+/// de `composition` : masse de chaque motif × nombre de motifs, plus les extrémités
+/// des motifs de bout de chaîne, calibrées par [`RepeatUnitCache`]. Les groupes
+/// terminaux explicites ne font pas partie de la composition et sont ignorés ; sans
+/// composition, la masse vaut `NaN`. [`try_average_mass`] signale l'échec d'analyse
+/// au lieu d'estimer.
+///
 /// # Exemple
 ///
 /// ```rust
@@ -28,18 +92,107 @@ const H_MONO_MASS: f64 = 1.00782503207;
 /// assert!((mw - 30.070).abs() < 0.01, "got {mw}");
 /// ```
 pub fn average_mass(chain: &PolymerChain) -> f64 {
-    let mol = parse_smiles(&chain.smiles).expect("chain SMILES must be valid SMILES");
-    mol.nodes().iter().fold(0.0, |acc, node| {
+    average_mass_with(chain, &MassTable::default())
+}
+
+/// Calcule la masse moléculaire moyenne de la chaîne avec la table de masses `table`,
+/// en g/mol.
+///
+/// Identique à [`average_mass`] pour les éléments non surchargés. L'estimation à
+/// partir de la composition, faute de SMILES analysable, utilise les masses standard.
+///
+/// # Exemple
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy},
+///                    properties::molecular_weight::{average_mass_with, MassTable}};
+///
+/// let bs = parse("{[]CC[]}").unwrap();
+/// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1))
+///     .homopolymer()
+///     .unwrap();
+/// // C₂H₆ avec C = 12.000 : 24.000 + 6 × 1.008 = 30.048 g/mol
+/// let table = MassTable::default().with_average_mass(6, 12.0);
+/// let mw = average_mass_with(&chain, &table);
+/// assert!((mw - 30.048).abs() < 1e-9, "got {mw}");
+/// ```
+pub fn average_mass_with(chain: &PolymerChain, table: &MassTable) -> f64 {
+    smiles_average_mass(&chain.smiles, table).unwrap_or_else(|_| {
+        RepeatUnitCache::estimate(chain).map_or(f64::NAN, |estimate| estimate.average_mass)
+    })
+}
+
+/// Comme [`average_mass`], mais échoue au lieu d'estimer la masse à partir de la
+/// composition.
+///
+/// # Erreurs
+///
+/// [`PolySimError::InvalidRepeatUnit`] si le SMILES de la chaîne ne peut pas être
+/// analysé.
+///
+/// # Exemple
+///
+/// ```rust
+/// use polysim_core::{PolymerChain, properties::molecular_weight::try_average_mass};
+///
+/// let ethane = PolymerChain::new("CC".to_string(), 1, 0.0);
+/// assert!((try_average_mass(&ethane).unwrap() - 30.070).abs() < 0.01);
+/// let broken = PolymerChain::new("CC(".to_string(), 1, 0.0);
+/// assert!(try_average_mass(&broken).is_err());
+/// ```
+pub fn try_average_mass(chain: &PolymerChain) -> Result<f64, PolySimError> {
+    average_mass_of_smiles(&chain.smiles)
+}
+
+/// Masse moyenne d'une chaîne SMILES, ou l'erreur d'analyse.
+fn smiles_average_mass(smiles: &str, table: &MassTable) -> Result<f64, String> {
+    let mol = parse_smiles(smiles).map_err(|e| e.to_string())?;
+    let h_mass = table.average(1).unwrap_or(H_AVERAGE_MASS);
+    Ok(mol.nodes().iter().fold(0.0, |acc, node| {
+        let atom = node.atom();
         // atom.mass() renvoie la masse standard (ou la masse isotopique si explicite [¹³C])
-        acc + node.atom().mass() + node.hydrogens() as f64 * H_AVERAGE_MASS
+        let heavy_mass = match table.average(atom.element().atomic_number()) {
+            Some(mass) if atom.isotope().is_none() => mass,
+            _ => atom.mass(),
+        };
+        acc + heavy_mass + node.hydrogens() as f64 * h_mass
+    }))
+}
+
+/// Calcule la masse moyenne (poids atomiques IUPAC) d'un fragment SMILES quelconque,
+/// en g/mol.
+///
+/// Le fragment est lu comme une molécule à part entière, hydrogènes implicites
+/// inclus, comme pour
+/// [`molecular_formula_of_smiles`](super::formula::molecular_formula_of_smiles).
+///
+/// # Erreurs
+///
+/// [`PolySimError::InvalidRepeatUnit`] si `smiles` n'est pas un SMILES valide.
+///
+/// # Exemple
+///
+/// ```rust
+/// use polysim_core::properties::molecular_weight::average_mass_of_smiles;
+///
+/// // Éthane C₂H₆ ≈ 30.07 g/mol
+/// let mw = average_mass_of_smiles("CC").unwrap();
+/// assert!((mw - 30.070).abs() < 0.01, "got {mw}");
+/// ```
+pub fn average_mass_of_smiles(smiles: &str) -> Result<f64, PolySimError> {
+    smiles_average_mass(smiles, &MassTable::default()).map_err(|e| {
+        PolySimError::InvalidRepeatUnit(format!("'{smiles}' is not valid SMILES: {e}"))
     })
 }
 
 /// Calcule la masse monoisotopique de la chaîne (nucléide le plus abondant), en g/mol.
 ///
 /// Pour les atomes sans isotope explicite, utilise le nucléide le plus abondant de chaque
-/// élément (ex. ¹²C = 12.000, ¹⁶O = 15.9949…). Pour les atomes avec isotope explicite
-/// (`[13C]`), respecte l'isotope spécifié.
+/// élément (ex. ¹²C = 12.000, ¹⁶O = 15.9949…, voir [`most_abundant_isotope_mass`]). Pour
+/// les atomes avec isotope explicite (`[13C]`), respecte l'isotope spécifié.
+///
+/// Renvoie `NaN` si un élément n'a pas de nucléide connu dans la table : utiliser
+/// [`try_monoisotopic_mass`] pour obtenir une erreur explicite.
 ///
 /// # Exemple
 ///
@@ -56,39 +209,143 @@ pub fn average_mass(chain: &PolymerChain) -> f64 {
 /// assert!((m - 30.047).abs() < 0.01, "got {m}");
 /// ```
 pub fn monoisotopic_mass(chain: &PolymerChain) -> f64 {
+    monoisotopic_mass_with(chain, &MassTable::default())
+}
+
+/// Calcule la masse monoisotopique de la chaîne avec la table de masses `table`,
+/// en g/mol.
+///
+/// Identique à [`monoisotopic_mass`] pour les éléments non surchargés.
+pub fn monoisotopic_mass_with(chain: &PolymerChain, table: &MassTable) -> f64 {
+    try_monoisotopic_mass_with(chain, table).unwrap_or(f64::NAN)
+}
+
+/// Comme [`monoisotopic_mass`], mais échoue au lieu de renvoyer `NaN`.
+///
+/// # Erreurs
+///
+/// [`PolySimError::UnknownIsotopeMass`] si un atome sans isotope explicite est d'un
+/// élément absent de [`most_abundant_isotope_mass`].
+///
+/// # Exemple
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy},
+///                    properties::molecular_weight::try_monoisotopic_mass};
+///
+/// // Tétraméthylétain Sn(CH₃)₄ : ¹²⁰Sn, et non la masse moyenne de l'étain
+/// let bs = parse("{[]C[Sn](C)(C)C[]}").unwrap();
+/// let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1))
+///     .homopolymer()
+///     .unwrap();
+/// let m = try_monoisotopic_mass(&chain).unwrap();
+/// assert!((m - 179.9961).abs() < 0.001, "got {m}");
+/// ```
+pub fn try_monoisotopic_mass(chain: &PolymerChain) -> Result<f64, PolySimError> {
+    try_monoisotopic_mass_with(chain, &MassTable::default())
+}
+
+/// Comme [`monoisotopic_mass_with`], mais échoue au lieu de renvoyer `NaN`
+/// (voir [`try_monoisotopic_mass`]).
+pub fn try_monoisotopic_mass_with(
+    chain: &PolymerChain,
+    table: &MassTable,
+) -> Result<f64, PolySimError> {
     let mol = parse_smiles(&chain.smiles).expect("chain SMILES must be valid SMILES");
-    mol.nodes().iter().fold(0.0, |acc, node| {
+    let h_mass = table.monoisotopic(1).unwrap_or(H_MONO_MASS);
+    mol.nodes().iter().try_fold(0.0, |acc, node| {
         let atom = node.atom();
         let heavy_mass = if atom.isotope().is_some() {
             // Isotope explicitement spécifié → respecter (ex. [13C])
             atom.mass()
         } else {
-            most_abundant_isotope_mass(atom.element())
+            let z = atom.element().atomic_number();
+            table
+                .monoisotopic(z)
+                .or_else(|| most_abundant_isotope_mass(z))
+                .ok_or_else(|| PolySimError::UnknownIsotopeMass {
+                    element: element_symbol(z).to_string(),
+                })?
         };
-        acc + heavy_mass + node.hydrogens() as f64 * H_MONO_MASS
+        Ok(acc + heavy_mass + node.hydrogens() as f64 * h_mass)
     })
 }
 
-/// Retourne la masse du nucléide le plus abondant pour chaque élément.
-///
-/// Pour les éléments organiques courants en chimie des polymères, les valeurs exactes
-/// sont codées en dur. Pour les éléments rares, la masse standard IUPAC est utilisée
-/// comme approximation.
-fn most_abundant_isotope_mass(element: &AtomSymbol) -> f64 {
-    match element.atomic_number() {
-        0 => 0.0,                     // Wildcard (*)
-        1 => H_MONO_MASS,             // ¹H (99.985 %)
-        5 => 11.0093054,              // ¹¹B (80.1 %)
-        6 => 12.0,                    // ¹²C (98.89 %)
-        7 => 14.0030740048,           // ¹⁴N (99.63 %)
-        8 => 15.9949146221,           // ¹⁶O (99.76 %)
-        9 => 18.9984032,              // ¹⁹F (100 %)
-        14 => 27.9769265325,          // ²⁸Si (92.23 %)
-        15 => 30.97376163,            // ³¹P (100 %)
-        16 => 31.97207100,            // ³²S (95.02 %)
-        17 => 34.96885268,            // ³⁵Cl (75.77 %)
-        35 => 78.9183371,             // ⁷⁹Br (50.69 %)
-        53 => 126.904468,             // ¹²⁷I (100 %)
-        _ => element.standard_mass(), // fallback : masse IUPAC pour éléments rares
-    }
+/// Masse (g/mol) du nucléide le plus abondant de l'élément de numéro atomique
+/// `atomic_number`, ou `None` si l'élément n'est pas dans la table.
+///
+/// La table couvre les éléments organiques, les contre-ions, les métaux des
+/// catalyseurs et stabilisants courants (Ti, Zn, Sn, Sb…) et les gaz rares ;
+/// les masses sont celles de l'AME 2020. Le joker `*` vaut 0.
+///
+/// # Exemple
+///
+/// ```rust
+/// use polysim_core::properties::molecular_weight::most_abundant_isotope_mass;
+///
+/// assert_eq!(most_abundant_isotope_mass(6), Some(12.0)); // ¹²C
+/// assert_eq!(most_abundant_isotope_mass(43), None); // Tc : aucun isotope stable
+/// ```
+pub fn most_abundant_isotope_mass(atomic_number: u8) -> Option<f64> {
+    let mass = match atomic_number {
+        0 => 0.0,             // Joker (*)
+        1 => H_MONO_MASS,     // ¹H (99.985 %)
+        2 => 4.00260325413,   // ⁴He (99.9998 %)
+        3 => 7.01600344,      // ⁷Li (92.41 %)
+        4 => 9.0121831,       // ⁹Be (100 %)
+        5 => 11.00930536,     // ¹¹B (80.1 %)
+        6 => 12.0,            // ¹²C (98.89 %)
+        7 => 14.00307400443,  // ¹⁴N (99.63 %)
+        8 => 15.99491461957,  // ¹⁶O (99.76 %)
+        9 => 18.99840316273,  // ¹⁹F (100 %)
+        10 => 19.9924401762,  // ²⁰Ne (90.48 %)
+        11 => 22.9897692820,  // ²³Na (100 %)
+        12 => 23.985041697,   // ²⁴Mg (78.99 %)
+        13 => 26.98153853,    // ²⁷Al (100 %)
+        14 => 27.97692653465, // ²⁸Si (92.23 %)
+        15 => 30.97376199842, // ³¹P (100 %)
+        16 => 31.9720711744,  // ³²S (95.02 %)
+        17 => 34.968852682,   // ³⁵Cl (75.77 %)
+        18 => 39.9623831237,  // ⁴⁰Ar (99.60 %)
+        19 => 38.9637064864,  // ³⁹K (93.26 %)
+        20 => 39.962590863,   // ⁴⁰Ca (96.94 %)
+        22 => 47.94794198,    // ⁴⁸Ti (73.72 %)
+        23 => 50.94395704,    // ⁵¹V (99.75 %)
+        24 => 51.94050623,    // ⁵²Cr (83.79 %)
+        25 => 54.93804391,    // ⁵⁵Mn (100 %)
+        26 => 55.93493633,    // ⁵⁶Fe (91.75 %)
+        27 => 58.93319429,    // ⁵⁹Co (100 %)
+        28 => 57.93534241,    // ⁵⁸Ni (68.08 %)
+        29 => 62.92959772,    // ⁶³Cu (69.15 %)
+        30 => 63.92914201,    // ⁶⁴Zn (49.17 %)
+        31 => 68.9255735,     // ⁶⁹Ga (60.11 %)
+        32 => 73.921177761,   // ⁷⁴Ge (36.5 %)
+        33 => 74.92159457,    // ⁷⁵As (100 %)
+        34 => 79.9165218,     // ⁸⁰Se (49.61 %)
+        35 => 78.9183376,     // ⁷⁹Br (50.69 %)
+        37 => 84.9117897379,  // ⁸⁵Rb (72.17 %)
+        38 => 87.9056125,     // ⁸⁸Sr (82.58 %)
+        40 => 89.9046977,     // ⁹⁰Zr (51.45 %)
+        42 => 97.90540482,    // ⁹⁸Mo (24.39 %)
+        44 => 101.9043441,    // ¹⁰²Ru (31.55 %)
+        45 => 102.905498,     // ¹⁰³Rh (100 %)
+        46 => 105.9034804,    // ¹⁰⁶Pd (27.33 %)
+        47 => 106.9050916,    // ¹⁰⁷Ag (51.84 %)
+        48 => 113.90336509,   // ¹¹⁴Cd (28.73 %)
+        49 => 114.903878776,  // ¹¹⁵In (95.71 %)
+        50 => 119.90220163,   // ¹²⁰Sn (32.58 %)
+        51 => 120.903812,     // ¹²¹Sb (57.21 %)
+        52 => 129.906222748,  // ¹³⁰Te (34.08 %)
+        53 => 126.9044719,    // ¹²⁷I (100 %)
+        55 => 132.905451961,  // ¹³³Cs (100 %)
+        56 => 137.905247,     // ¹³⁸Ba (71.70 %)
+        74 => 183.95093092,   // ¹⁸⁴W (30.64 %)
+        78 => 194.9647917,    // ¹⁹⁵Pt (33.8 %)
+        79 => 196.96656879,   // ¹⁹⁷Au (100 %)
+        80 => 201.9706434,    // ²⁰²Hg (29.86 %)
+        82 => 207.9766525,    // ²⁰⁸Pb (52.4 %)
+        83 => 208.9803991,    // ²⁰⁹Bi (100 %)
+        _ => return None,
+    };
+    Some(mass)
 }
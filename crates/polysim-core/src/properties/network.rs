@@ -0,0 +1,83 @@
+//! Gelation and branching of polymer networks.
+//!
+//! Flory-Stockmayer mean-field theory for step-growth networks, and a simple
+//! branching density for the non-linear architectures built by
+//! [`BranchedBuilder`](crate::builder::branched::BranchedBuilder).
+
+use crate::polymer::{Architecture, PolymerChain};
+
+/// Critical conversion p_c at which a network of f-functional monomers gels.
+///
+/// Flory-Stockmayer theory for the homopolymerisation of an f-functional
+/// monomer gives p_c = 1/(f − 1). Monomers with f ≤ 1 never form a network and
+/// return `f64::INFINITY`; bifunctional monomers (f = 2) only gel at full
+/// conversion.
+///
+/// # Reference
+///
+/// Flory, P. J. (1941). Molecular Size Distribution in Three Dimensional
+/// Polymers. *J. Am. Chem. Soc.*, 63(11), 3083–3090.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::properties::network::gel_point_conversion;
+///
+/// assert_eq!(gel_point_conversion(3.0), 0.5); // e.g. glycerol
+/// ```
+pub fn gel_point_conversion(functionality: f64) -> f64 {
+    if functionality <= 1.0 {
+        return f64::INFINITY;
+    }
+    1.0 / (functionality - 1.0)
+}
+
+/// Fraction of repeat units that are branch points (dimensionless).
+///
+/// Branch points are taken from the architecture recorded by the builder:
+/// one per grafted branch for comb and graft polymers, the hub of a star, and
+/// every non-terminal node of a dendrimer. Linear, cyclic and gradient chains
+/// return 0.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{branched::BranchedBuilder, BuildStrategy}};
+/// use polysim_core::properties::network::degree_of_crosslinking;
+///
+/// let backbone = parse("{[]CC[]}").unwrap();
+/// let branch = parse("{[]CCCC[]}").unwrap();
+/// let comb = BranchedBuilder::new(backbone, branch, BuildStrategy::ByRepeatCount(20))
+///     .comb_polymer(4)
+///     .unwrap();
+/// // 5 branches over 20 backbone + 5 branch units
+/// assert!((degree_of_crosslinking(&comb) - 5.0 / 25.0).abs() < 1e-12);
+/// ```
+pub fn degree_of_crosslinking(chain: &PolymerChain) -> f64 {
+    let n = chain.repeat_count;
+    if n == 0 {
+        return 0.0;
+    }
+    let branch_points = match chain.architecture {
+        Architecture::Comb { .. } | Architecture::Graft { .. } => chain
+            .composition
+            .get(1)
+            .map_or(0.0, |branch| branch.fraction * n as f64),
+        Architecture::Star { .. } => 1.0,
+        Architecture::Dendrimer { generation } => dendrimer_branch_points(n, generation),
+        Architecture::Linear | Architecture::Cyclic | Architecture::Gradient => 0.0,
+    };
+    branch_points / n as f64
+}
+
+/// Non-terminal nodes of a dendrimer with `units` nodes over `generation` generations.
+///
+/// The branching factor b is recovered from units = 1 + b + … + b^generation;
+/// every non-terminal node then carries b children, so there are (units − 1)/b.
+fn dendrimer_branch_points(units: usize, generation: usize) -> f64 {
+    let total = |b: usize| (0..=generation as u32).map(|g| b.pow(g)).sum::<usize>();
+    match (2..=units).find(|&b| total(b) >= units) {
+        Some(b) if total(b) == units => (units - 1) as f64 / b as f64,
+        _ => 0.0,
+    }
+}
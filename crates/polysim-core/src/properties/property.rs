@@ -0,0 +1,153 @@
+//! A common interface over property calculators.
+//!
+//! [`PolymerProperty`] lets built-in and user-defined properties be computed
+//! the same way, e.g. from a list of boxed calculators sharing an output type.
+
+use crate::{error::PolySimError, polymer::PolymerChain};
+
+use super::{formula, molecular_weight, rheology, solubility, thermal, volume};
+
+/// A property computed from a single chain.
+///
+/// Implement it to plug a custom calculator next to the built-in ones.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::{PolySimError, PolymerChain, PolymerProperty};
+/// use polysim_core::properties::property::{AverageMass, MonoisotopicMass};
+///
+/// /// Mass per repeat unit (g/mol).
+/// struct MassPerUnit;
+///
+/// impl PolymerProperty for MassPerUnit {
+///     type Output = f64;
+///
+///     fn compute(&self, chain: &PolymerChain) -> Result<f64, PolySimError> {
+///         Ok(chain.mn / chain.repeat_count as f64)
+///     }
+/// }
+///
+/// let bs = parse("{[]CC[]}").unwrap();
+/// let pe = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(10))
+///     .homopolymer()
+///     .unwrap();
+///
+/// let properties: Vec<Box<dyn PolymerProperty<Output = f64>>> =
+///     vec![Box::new(AverageMass), Box::new(MonoisotopicMass), Box::new(MassPerUnit)];
+/// let values: Vec<f64> = properties
+///     .iter()
+///     .map(|p| p.compute(&pe).unwrap())
+///     .collect();
+/// assert!((values[2] - 28.255).abs() < 0.01);
+/// ```
+pub trait PolymerProperty {
+    /// The computed value.
+    type Output;
+
+    /// Computes the property of `chain`.
+    fn compute(&self, chain: &PolymerChain) -> Result<Self::Output, PolySimError>;
+}
+
+/// Average molecular mass (g/mol), see [`molecular_weight::average_mass`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AverageMass;
+
+impl PolymerProperty for AverageMass {
+    type Output = f64;
+
+    fn compute(&self, chain: &PolymerChain) -> Result<f64, PolySimError> {
+        Ok(molecular_weight::average_mass(chain))
+    }
+}
+
+/// Monoisotopic mass (g/mol), see [`molecular_weight::try_monoisotopic_mass`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MonoisotopicMass;
+
+impl PolymerProperty for MonoisotopicMass {
+    type Output = f64;
+
+    fn compute(&self, chain: &PolymerChain) -> Result<f64, PolySimError> {
+        molecular_weight::try_monoisotopic_mass(chain)
+    }
+}
+
+/// Hill-notation molecular formula, see [`formula::molecular_formula`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MolecularFormula;
+
+impl PolymerProperty for MolecularFormula {
+    type Output = String;
+
+    fn compute(&self, chain: &PolymerChain) -> Result<String, PolySimError> {
+        Ok(formula::molecular_formula(chain))
+    }
+}
+
+/// Total number of atoms, hydrogens included, see [`formula::total_atom_count`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TotalAtomCount;
+
+impl PolymerProperty for TotalAtomCount {
+    type Output = usize;
+
+    fn compute(&self, chain: &PolymerChain) -> Result<usize, PolySimError> {
+        Ok(formula::total_atom_count(chain))
+    }
+}
+
+/// Glass transition temperature Tg (K), see [`thermal::tg_van_krevelen`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlassTransition;
+
+impl PolymerProperty for GlassTransition {
+    type Output = f64;
+
+    fn compute(&self, chain: &PolymerChain) -> Result<f64, PolySimError> {
+        Ok(thermal::tg_van_krevelen(chain))
+    }
+}
+
+/// Amorphous density (g/cm³) at `temperature_k`, see [`volume::density`].
+#[derive(Debug, Clone, Copy)]
+pub struct Density {
+    pub temperature_k: f64,
+}
+
+impl PolymerProperty for Density {
+    type Output = f64;
+
+    fn compute(&self, chain: &PolymerChain) -> Result<f64, PolySimError> {
+        Ok(volume::density(chain, self.temperature_k))
+    }
+}
+
+/// Hildebrand solubility parameter (MPa^½) at `temperature_k`, see
+/// [`solubility::solubility_parameter`].
+#[derive(Debug, Clone, Copy)]
+pub struct SolubilityParameter {
+    pub temperature_k: f64,
+}
+
+impl PolymerProperty for SolubilityParameter {
+    type Output = f64;
+
+    fn compute(&self, chain: &PolymerChain) -> Result<f64, PolySimError> {
+        Ok(solubility::solubility_parameter(chain, self.temperature_k))
+    }
+}
+
+/// Molecular weight between entanglements Me (g/mol), see
+/// [`rheology::entanglement_molecular_weight`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EntanglementMolecularWeight;
+
+impl PolymerProperty for EntanglementMolecularWeight {
+    type Output = f64;
+
+    fn compute(&self, chain: &PolymerChain) -> Result<f64, PolySimError> {
+        Ok(rheology::entanglement_molecular_weight(chain))
+    }
+}
@@ -0,0 +1,123 @@
+//! Literature properties of common polymers.
+
+/// Literature properties of a common polymer, for comparison with the
+/// estimates of [`crate::properties`].
+///
+/// Values are for the atactic, amorphous, high-molar-mass polymer at 25 °C.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferenceProperties {
+    /// Glass transition temperature (K), `None` when not well defined.
+    pub tg: Option<f64>,
+    /// Amorphous density (g/cm³).
+    pub density: f64,
+    /// Refractive index n_D.
+    pub refractive_index: f64,
+}
+
+/// A common polymer and its repeat unit (SMILES, bonding descriptors stripped).
+pub(crate) struct KnownPolymer {
+    pub(crate) name: &'static str,
+    pub(crate) abbreviation: &'static str,
+    pub(crate) repeat_unit: &'static str,
+    /// Resin identification code (1–7) used for recycling.
+    pub(crate) resin_code: u8,
+    pub(crate) reference: ReferenceProperties,
+}
+
+const fn reference(tg: Option<f64>, density: f64, refractive_index: f64) -> ReferenceProperties {
+    ReferenceProperties {
+        tg,
+        density,
+        refractive_index,
+    }
+}
+
+pub(crate) const KNOWN_POLYMERS: &[KnownPolymer] = &[
+    KnownPolymer {
+        name: "Polyethylene",
+        abbreviation: "PE",
+        repeat_unit: "CC",
+        resin_code: 2,
+        reference: reference(Some(150.0), 0.855, 1.49),
+    },
+    KnownPolymer {
+        name: "Polypropylene",
+        abbreviation: "PP",
+        repeat_unit: "CC(C)",
+        resin_code: 5,
+        reference: reference(Some(260.0), 0.85, 1.49),
+    },
+    KnownPolymer {
+        name: "Polystyrene",
+        abbreviation: "PS",
+        repeat_unit: "CC(c1ccccc1)",
+        resin_code: 6,
+        reference: reference(Some(373.0), 1.05, 1.59),
+    },
+    KnownPolymer {
+        name: "Poly(methyl methacrylate)",
+        abbreviation: "PMMA",
+        repeat_unit: "CC(C)(C(=O)OC)",
+        resin_code: 7,
+        reference: reference(Some(378.0), 1.17, 1.49),
+    },
+    KnownPolymer {
+        name: "Poly(vinyl chloride)",
+        abbreviation: "PVC",
+        repeat_unit: "CC(Cl)",
+        resin_code: 3,
+        reference: reference(Some(354.0), 1.385, 1.54),
+    },
+    KnownPolymer {
+        name: "Poly(ethylene terephthalate)",
+        abbreviation: "PET",
+        repeat_unit: "OCCOC(=O)c1ccc(cc1)C(=O)",
+        resin_code: 1,
+        reference: reference(Some(343.0), 1.335, 1.57),
+    },
+    KnownPolymer {
+        name: "Nylon-6",
+        abbreviation: "PA6",
+        repeat_unit: "NCCCCCC(=O)",
+        resin_code: 7,
+        reference: reference(Some(323.0), 1.084, 1.53),
+    },
+    KnownPolymer {
+        name: "Polytetrafluoroethylene",
+        abbreviation: "PTFE",
+        repeat_unit: "C(F)(F)C(F)(F)",
+        resin_code: 7,
+        reference: reference(None, 2.00, 1.35),
+    },
+];
+
+/// Literature properties of a known polymer.
+///
+/// `name` is either the full name reported by [`closest_known`](crate::builder::identify::closest_known) (e.g.
+/// `"Polystyrene"`) or the usual abbreviation (e.g. `"PS"`), case-insensitive.
+/// Returns `None` for a polymer outside the built-in table.
+///
+/// # Reference
+///
+/// Brandrup, J., Immergut, E. H. & Grulke, E. A. (1999).
+/// *Polymer Handbook*, 4th ed., Wiley.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::properties::reference::reference_properties;
+///
+/// let ps = reference_properties("PS").unwrap();
+/// assert_eq!(ps.tg, Some(373.0));
+/// assert_eq!(ps.refractive_index, 1.59);
+/// assert_eq!(reference_properties("Polyunobtainium"), None);
+/// ```
+pub fn reference_properties(name: &str) -> Option<ReferenceProperties> {
+    let name = name.trim();
+    KNOWN_POLYMERS
+        .iter()
+        .find(|known| {
+            known.name.eq_ignore_ascii_case(name) || known.abbreviation.eq_ignore_ascii_case(name)
+        })
+        .map(|known| known.reference)
+}
@@ -0,0 +1,60 @@
+//! Melt rheology: entanglement of long chains.
+
+use crate::polymer::PolymerChain;
+
+use super::graph::MolGraph;
+use super::molecular_weight::average_mass;
+use super::thermal::backbone_atoms;
+
+/// Characteristic ratio C∞ of a chain without pendant groups (polyethylene).
+const BASE_CHARACTERISTIC_RATIO: f64 = 6.7;
+
+/// Relative increase of C∞ per pendant heavy atom per backbone atom.
+const PENDANT_STIFFENING: f64 = 0.15;
+
+/// Estimates the molecular weight between entanglements Me (g/mol).
+///
+/// Follows Wu's correlation Me = 3·C∞²·M_v, with M_v the mass per backbone
+/// bond. The characteristic ratio is estimated from the side-group bulk as
+/// C∞ = 6.7 × (1 + 0.15 × pendant heavy atoms per backbone atom), so bulky
+/// pendants stiffen the chain and push Me up. Backbone rings count as backbone.
+///
+/// Expect the right order of magnitude only: the C∞ estimate ignores
+/// conformational details, and rigid aromatic backbones (polycarbonate) come
+/// out too high.
+///
+/// # Reference
+///
+/// Wu, S. (1989). Chain structure and entanglement.
+/// *J. Polym. Sci. B Polym. Phys.*, 27(4), 723–741.
+///
+/// # Panics
+///
+/// Panics if `chain.smiles` is not valid SMILES (never the case for chains
+/// produced by the builders).
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::rheology::entanglement_molecular_weight;
+///
+/// let bs = parse("{[]CC[]}").unwrap();
+/// let pe = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1000))
+///     .homopolymer()
+///     .unwrap();
+/// let me = entanglement_molecular_weight(&pe);
+/// assert!(me > 1_000.0 && me < 2_500.0); // PE ≈ 1250 g/mol
+/// ```
+pub fn entanglement_molecular_weight(chain: &PolymerChain) -> f64 {
+    let graph = MolGraph::parse(&chain.smiles).expect("chain SMILES must be valid SMILES");
+    let (backbone, _) = backbone_atoms(&graph);
+    let heavy_atoms = graph.atoms.iter().filter(|a| a.atomic_number > 1).count();
+    let backbone_count = backbone.iter().filter(|&&b| b).count().max(1);
+    let pendant_atoms = heavy_atoms.saturating_sub(backbone_count);
+
+    let bulk = pendant_atoms as f64 / backbone_count as f64;
+    let c_inf = BASE_CHARACTERISTIC_RATIO * (1.0 + PENDANT_STIFFENING * bulk);
+    let mass_per_bond = average_mass(chain) / backbone_count as f64;
+    3.0 * c_inf * c_inf * mass_per_bond
+}
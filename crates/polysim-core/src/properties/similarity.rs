@@ -0,0 +1,162 @@
+//! Structural similarity between chains.
+
+use std::collections::BTreeMap;
+
+use crate::polymer::PolymerChain;
+
+use super::graph::{element_symbol, BondOrder, MolGraph};
+
+/// Longest path, in bonds, recorded in a fingerprint.
+const MAX_PATH_BONDS: usize = 4;
+
+/// Tanimoto similarity of the repeat units of two chains, in `[0, 1]`.
+///
+/// Each repeat unit is fingerprinted by counting its linear paths of up to four
+/// bonds, written as element and bond symbols (`C-C`, `c:c:c`, `C=O`…). The
+/// counts of a copolymer are weighted by the molar fraction of each unit, so
+/// the score does not depend on chain length. Chains built without composition
+/// data are fingerprinted on their full SMILES, divided by the repeat count.
+///
+/// The coefficient is Σ min / Σ max over the path counts: 1.0 for identical
+/// repeat units, 0.0 when no path is shared.
+///
+/// # Panics
+///
+/// Panics if a repeat unit or chain SMILES is not valid SMILES (never the case
+/// for chains produced by the builders).
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::similarity::tanimoto;
+///
+/// let build = |s: &str, n| {
+///     LinearBuilder::new(parse(s).unwrap(), BuildStrategy::ByRepeatCount(n))
+///         .homopolymer()
+///         .unwrap()
+/// };
+/// let (pe, long_pe, ps) = (build("{[]CC[]}", 10), build("{[]CC[]}", 500), build("{[]CC(c1ccccc1)[]}", 10));
+/// assert_eq!(tanimoto(&pe, &long_pe), 1.0);
+/// assert!(tanimoto(&pe, &ps) < 0.2);
+/// ```
+pub fn tanimoto(a: &PolymerChain, b: &PolymerChain) -> f64 {
+    weighted_tanimoto(&fingerprint(a), &fingerprint(b))
+}
+
+/// Tanimoto coefficient of two weighted multisets: Σ min / Σ max.
+///
+/// Returns 0.0 when both multisets are empty.
+pub(crate) fn weighted_tanimoto<K: Ord>(a: &BTreeMap<K, f64>, b: &BTreeMap<K, f64>) -> f64 {
+    let (mut shared, mut total) = (0.0, 0.0);
+    for key in a.keys().chain(b.keys().filter(|k| !a.contains_key(k))) {
+        let (x, y) = (
+            a.get(key).copied().unwrap_or(0.0),
+            b.get(key).copied().unwrap_or(0.0),
+        );
+        shared += x.min(y);
+        total += x.max(y);
+    }
+    if total == 0.0 {
+        0.0
+    } else {
+        shared / total
+    }
+}
+
+/// Path counts of the repeat units of `chain`, weighted by molar fraction.
+fn fingerprint(chain: &PolymerChain) -> BTreeMap<String, f64> {
+    let mut counts = BTreeMap::new();
+    if chain.composition.is_empty() {
+        let weight = 1.0 / chain.repeat_count.max(1) as f64;
+        add_paths(&mut counts, &chain.smiles, weight);
+    } else {
+        for unit in &chain.composition {
+            add_paths(&mut counts, &unit.smiles, unit.fraction);
+        }
+    }
+    counts
+}
+
+/// Adds every linear path of `smiles` to `counts` with the given weight.
+///
+/// Each path is recorded once, in the lexicographically smaller of its two
+/// spellings.
+fn add_paths(counts: &mut BTreeMap<String, f64>, smiles: &str, weight: f64) {
+    let graph = MolGraph::parse(smiles).expect("repeat unit SMILES must be valid SMILES");
+    let label = |i: usize| {
+        let atom = &graph.atoms[i];
+        let symbol = element_symbol(atom.atomic_number);
+        if atom.aromatic {
+            symbol.to_lowercase()
+        } else {
+            symbol.to_owned()
+        }
+    };
+
+    let mut path = Vec::with_capacity(MAX_PATH_BONDS + 1);
+    let mut bonds = Vec::with_capacity(MAX_PATH_BONDS);
+    for start in 0..graph.atoms.len() {
+        path.push(start);
+        walk(&graph, &mut path, &mut bonds, &mut |path, bonds| {
+            // Single atoms once, longer paths from their lower-index end only
+            if path.len() > 1 && path[0] > path[path.len() - 1] {
+                return;
+            }
+            let forward = spell(path.iter().copied(), bonds.iter().copied(), &label);
+            let backward = spell(
+                path.iter().rev().copied(),
+                bonds.iter().rev().copied(),
+                &label,
+            );
+            *counts.entry(forward.min(backward)).or_insert(0.0) += weight;
+        });
+        path.pop();
+    }
+}
+
+/// Depth-first enumeration of the simple paths extending `path`.
+fn walk(
+    graph: &MolGraph,
+    path: &mut Vec<usize>,
+    bonds: &mut Vec<BondOrder>,
+    visit: &mut impl FnMut(&[usize], &[BondOrder]),
+) {
+    visit(path, bonds);
+    if bonds.len() == MAX_PATH_BONDS {
+        return;
+    }
+    let last = path[path.len() - 1];
+    for &(next, bond) in graph.neighbors(last) {
+        if path.contains(&next) {
+            continue;
+        }
+        path.push(next);
+        bonds.push(graph.bonds[bond].order);
+        walk(graph, path, bonds, visit);
+        bonds.pop();
+        path.pop();
+    }
+}
+
+fn spell(
+    atoms: impl Iterator<Item = usize>,
+    bonds: impl Iterator<Item = BondOrder>,
+    label: &impl Fn(usize) -> String,
+) -> String {
+    let mut out = String::new();
+    let mut bonds = bonds;
+    for atom in atoms {
+        out.push_str(&label(atom));
+        if let Some(order) = bonds.next() {
+            out.push(match order {
+                BondOrder::Single => '-',
+                BondOrder::Double => '=',
+                BondOrder::Triple => '#',
+                BondOrder::Quadruple => '$',
+                BondOrder::Aromatic => ':',
+            });
+        }
+    }
+    out
+}
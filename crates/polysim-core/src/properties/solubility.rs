@@ -0,0 +1,140 @@
+//! Solubility parameter, polymer–polymer miscibility and water uptake.
+//!
+//! The estimates sum group increments over the whole chain, end groups
+//! included, so polar chain ends weigh in noticeably on short chains.
+
+use crate::polymer::PolymerChain;
+
+use super::groups::{self, GroupName};
+use super::molecular_weight::average_mass;
+use super::volume::molar_volume;
+
+/// Molar mass of water (g/mol).
+const WATER_MOLAR_MASS: f64 = 18.015;
+
+/// Molar gas constant (J/(mol·K)).
+const GAS_CONSTANT: f64 = 8.314_462_618;
+
+/// Estimates the Hildebrand solubility parameter δ (MPa^½) at `temperature_k`.
+///
+/// δ = (Ecoh / V)^½, with the cohesive energy Ecoh summed from Fedors group
+/// increments and V the [`molar_volume`], both per repeat unit.
+///
+/// # Reference
+///
+/// Fedors, R. F. (1974). A method for estimating both the solubility parameters
+/// and molar volumes of liquids. *Polym. Eng. Sci.*, 14(2), 147–154.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::solubility::solubility_parameter;
+///
+/// let bs = parse("{[]CC[]}").unwrap();
+/// let pe = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(500))
+///     .homopolymer()
+///     .unwrap();
+/// let delta = solubility_parameter(&pe, 298.15);
+/// assert!((delta - 17.0).abs() < 1.0); // PE ≈ 16–17.5 MPa^½
+/// ```
+pub fn solubility_parameter(chain: &PolymerChain, temperature_k: f64) -> f64 {
+    let ecoh =
+        groups::sum_increments(chain, GroupName::cohesive_energy) / chain.repeat_count as f64;
+    (ecoh / molar_volume(chain, temperature_k)).sqrt()
+}
+
+/// Estimates the Flory-Huggins interaction parameter χ between two polymers.
+///
+/// χ = V_ref·(δa − δb)² / RT, from the [`solubility_parameter`] of each chain,
+/// with the reference volume V_ref the geometric mean of the two repeat-unit
+/// [`molar_volume`]s. Being a squared difference, this Hildebrand estimate is
+/// never negative: it cannot predict miscibility driven by specific
+/// interactions such as hydrogen bonding.
+///
+/// # Reference
+///
+/// Flory, P. J. (1953). *Principles of Polymer Chemistry*. Cornell University
+/// Press. Chapter 12.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::solubility::flory_huggins_chi;
+///
+/// let build = |bigsmiles| {
+///     LinearBuilder::new(parse(bigsmiles).unwrap(), BuildStrategy::ByRepeatCount(500))
+///         .homopolymer()
+///         .unwrap()
+/// };
+/// let (pe, pvc) = (build("{[]CC[]}"), build("{[]CC(Cl)[]}"));
+/// assert!(flory_huggins_chi(&pe, &pvc, 298.15) > 0.0);
+/// assert_eq!(flory_huggins_chi(&pe, &pe, 298.15), 0.0);
+/// ```
+pub fn flory_huggins_chi(a: &PolymerChain, b: &PolymerChain, temperature_k: f64) -> f64 {
+    let delta_gap = solubility_parameter(a, temperature_k) - solubility_parameter(b, temperature_k);
+    reference_volume(a, b, temperature_k) * delta_gap * delta_gap / (GAS_CONSTANT * temperature_k)
+}
+
+/// Critical Flory-Huggins parameter χc above which a blend of `a` and `b` phase-separates.
+///
+/// χc = ½·(1/√Na + 1/√Nb)², where each Ni is the chain volume in units of the
+/// reference volume of [`flory_huggins_chi`]. Long chains leave almost no room
+/// for unfavourable interactions, which is why most polymer pairs are immiscible.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::solubility::critical_chi;
+///
+/// let build = |n| {
+///     LinearBuilder::new(parse("{[]CC[]}").unwrap(), BuildStrategy::ByRepeatCount(n))
+///         .homopolymer()
+///         .unwrap()
+/// };
+/// // Two equal chains of N units: χc = 2/N
+/// let chi_c = critical_chi(&build(100), &build(100), 298.15);
+/// assert!((chi_c - 0.02).abs() < 1e-9);
+/// ```
+pub fn critical_chi(a: &PolymerChain, b: &PolymerChain, temperature_k: f64) -> f64 {
+    let v_ref = reference_volume(a, b, temperature_k);
+    let segments = |chain: &PolymerChain| {
+        chain.repeat_count as f64 * molar_volume(chain, temperature_k) / v_ref
+    };
+    let sum = 1.0 / segments(a).sqrt() + 1.0 / segments(b).sqrt();
+    0.5 * sum * sum
+}
+
+/// Lattice reference volume (cm³/mol): geometric mean of the repeat-unit volumes.
+fn reference_volume(a: &PolymerChain, b: &PolymerChain, temperature_k: f64) -> f64 {
+    (molar_volume(a, temperature_k) * molar_volume(b, temperature_k)).sqrt()
+}
+
+/// Estimates the equilibrium water uptake (g water per g polymer) at 50 % RH.
+///
+/// The molar hydration Hw of every polar group is summed over the chain and
+/// converted to a mass of water per mass of polymer.
+///
+/// # Reference
+///
+/// Van Krevelen, D. W. & te Nijenhuis, K. (2009).
+/// *Properties of Polymers*, 4th ed., Elsevier. Chapter 18.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::solubility::water_uptake;
+///
+/// let bs = parse("{[]CC(O)[]}").unwrap(); // poly(vinyl alcohol)
+/// let pva = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(100))
+///     .homopolymer()
+///     .unwrap();
+/// assert!(water_uptake(&pva) > 0.1);
+/// ```
+pub fn water_uptake(chain: &PolymerChain) -> f64 {
+    let hw = groups::sum_increments(chain, GroupName::molar_hydration);
+    hw * WATER_MOLAR_MASS / average_mass(chain)
+}
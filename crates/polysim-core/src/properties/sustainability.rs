@@ -0,0 +1,90 @@
+//! Sustainability reporting: recycling codes of common polymers.
+
+use crate::builder::identify::closest_resin_code;
+use crate::polymer::PolymerChain;
+
+/// Minimum similarity to a known polymer for its recycling code to apply.
+///
+/// Higher than [`MIN_SIMILARITY`](crate::builder::identify::MIN_SIMILARITY):
+/// a resembling polymer is not necessarily accepted in the same stream, so
+/// only the known polymer with different end groups or length qualifies.
+pub const MIN_RECYCLING_SIMILARITY: f64 = 0.9;
+
+/// Resin identification code of a polymer (ASTM D7611).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecyclabilityClass {
+    /// 1 — poly(ethylene terephthalate).
+    Pet,
+    /// 2 — high-density polyethylene.
+    Hdpe,
+    /// 3 — poly(vinyl chloride).
+    Pvc,
+    /// 4 — low-density polyethylene.
+    Ldpe,
+    /// 5 — polypropylene.
+    Pp,
+    /// 6 — polystyrene.
+    Ps,
+    /// 7 — other identified plastics (PMMA, nylons, PTFE…).
+    Other,
+    /// Not in the table of known polymers.
+    Unknown,
+}
+
+impl RecyclabilityClass {
+    /// Numeric resin code (1–7), or `None` for [`RecyclabilityClass::Unknown`].
+    pub fn code(self) -> Option<u8> {
+        match self {
+            Self::Pet => Some(1),
+            Self::Hdpe => Some(2),
+            Self::Pvc => Some(3),
+            Self::Ldpe => Some(4),
+            Self::Pp => Some(5),
+            Self::Ps => Some(6),
+            Self::Other => Some(7),
+            Self::Unknown => None,
+        }
+    }
+
+    fn from_code(code: u8) -> Self {
+        match code {
+            1 => Self::Pet,
+            2 => Self::Hdpe,
+            3 => Self::Pvc,
+            4 => Self::Ldpe,
+            5 => Self::Pp,
+            6 => Self::Ps,
+            7 => Self::Other,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Recycling code of `chain`, from the known-polymer table.
+///
+/// Uses the matching of [`closest_known`](crate::builder::identify::closest_known)
+/// and requires a score of at least [`MIN_RECYCLING_SIMILARITY`]. Polyethylene
+/// is reported as [`RecyclabilityClass::Hdpe`]: a linear chain says nothing
+/// about the branching that makes LDPE.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::sustainability::{recyclability, RecyclabilityClass};
+///
+/// let bs = parse("{[]OCCOC(=O)c1ccc(cc1)C(=O)[]}").unwrap();
+/// let pet = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(10))
+///     .homopolymer()
+///     .unwrap();
+/// assert_eq!(recyclability(&pet), RecyclabilityClass::Pet);
+/// assert_eq!(recyclability(&pet).code(), Some(1));
+/// ```
+pub fn recyclability(chain: &PolymerChain) -> RecyclabilityClass {
+    match closest_resin_code(chain) {
+        Some((code, score)) if score >= MIN_RECYCLING_SIMILARITY => {
+            RecyclabilityClass::from_code(code)
+        }
+        _ => RecyclabilityClass::Unknown,
+    }
+}
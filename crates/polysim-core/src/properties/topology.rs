@@ -0,0 +1,193 @@
+//! Topological descriptors: where the atoms of a chain sit.
+
+use crate::polymer::PolymerChain;
+
+use super::graph::{BondOrder, MolGraph};
+
+/// Carbon atoms of a chain, classified by position.
+///
+/// Every carbon falls in exactly one category: aromatic carbons are counted
+/// as [`aromatic`](Self::aromatic) wherever they are, the other carbons as
+/// backbone or side chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CarbonCounts {
+    /// Non-aromatic carbons on the main chain.
+    pub backbone: usize,
+    /// Non-aromatic carbons in pendant groups.
+    pub side_chain: usize,
+    /// Aromatic carbons, on the main chain or in pendant groups.
+    pub aromatic: usize,
+}
+
+/// Classifies every carbon of `chain` as backbone, side-chain or aromatic.
+///
+/// The backbone is the main chain of the SMILES string, i.e. the atoms written
+/// outside branch parentheses; builders in this crate always write pendant
+/// groups as branches, so this is the polymer backbone.
+///
+/// # Panics
+///
+/// Panics if `chain.smiles` is not valid SMILES (never the case for chains
+/// produced by the builders).
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::topology::carbon_classification;
+///
+/// let bs = parse("{[]CC(C)[]}").unwrap();
+/// let pp = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(10))
+///     .homopolymer()
+///     .unwrap();
+/// let counts = carbon_classification(&pp);
+/// assert_eq!((counts.backbone, counts.side_chain, counts.aromatic), (20, 10, 0));
+/// ```
+pub fn carbon_classification(chain: &PolymerChain) -> CarbonCounts {
+    let graph = MolGraph::parse(&chain.smiles).expect("chain SMILES must be valid SMILES");
+    let mut counts = CarbonCounts::default();
+    for (atom, &on_main_chain) in graph.atoms.iter().zip(&graph.main_chain) {
+        if atom.atomic_number != 6 {
+            continue;
+        }
+        if atom.aromatic {
+            counts.aromatic += 1;
+        } else if on_main_chain {
+            counts.backbone += 1;
+        } else {
+            counts.side_chain += 1;
+        }
+    }
+    counts
+}
+
+/// Counts the heavy atoms on the backbone of `chain`.
+///
+/// Uses the same backbone as [`carbon_classification`]: every non-hydrogen
+/// atom of the main chain, aromatic or not, heteroatoms included. Wildcard
+/// atoms (`*`) are not counted.
+///
+/// # Panics
+///
+/// Panics if `chain.smiles` is not valid SMILES (never the case for chains
+/// produced by the builders).
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::topology::backbone_atom_count;
+///
+/// let bs = parse("{[]CCO[]}").unwrap();
+/// let peo = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(5))
+///     .homopolymer()
+///     .unwrap();
+/// assert_eq!(backbone_atom_count(&peo), 15);
+/// ```
+pub fn backbone_atom_count(chain: &PolymerChain) -> usize {
+    let graph = MolGraph::parse(&chain.smiles).expect("chain SMILES must be valid SMILES");
+    graph
+        .atoms
+        .iter()
+        .zip(&graph.main_chain)
+        .filter(|(atom, &on_main_chain)| on_main_chain && !matches!(atom.atomic_number, 0 | 1))
+        .count()
+}
+
+/// Counts the rotatable bonds of `chain`.
+///
+/// A bond is rotatable when it is a single bond outside any ring and both of
+/// its atoms carry at least one other heavy atom; bonds to terminal atoms
+/// (methyl, hydroxyl, halogen…) do not change the conformation. More
+/// rotatable bonds mean a more flexible chain and, as a rule, a lower Tg.
+///
+/// # Panics
+///
+/// Panics if `chain.smiles` is not valid SMILES (never the case for chains
+/// produced by the builders).
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::topology::rotatable_bonds;
+///
+/// let bs = parse("{[]CC[]}").unwrap();
+/// let pe = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(10))
+///     .homopolymer()
+///     .unwrap();
+/// // C20 backbone: 19 C–C bonds, minus the two terminal ones
+/// assert_eq!(rotatable_bonds(&pe), 17);
+/// ```
+pub fn rotatable_bonds(chain: &PolymerChain) -> usize {
+    let graph = MolGraph::parse(&chain.smiles).expect("chain SMILES must be valid SMILES");
+    graph
+        .bonds
+        .iter()
+        .filter(|bond| {
+            bond.order == BondOrder::Single
+                && !bond.in_ring
+                && graph.degree(bond.a) > 1
+                && graph.degree(bond.b) > 1
+        })
+        .count()
+}
+
+/// Counts the aromatic rings of `chain`.
+///
+/// Rings are counted on the subgraph of aromatic ring bonds as its cyclomatic
+/// number (bonds − atoms + connected components), so fused systems count one
+/// ring per cycle: naphthalene has 2 rings, biphenyl 2, benzene 1.
+///
+/// # Panics
+///
+/// Panics if `chain.smiles` is not valid SMILES (never the case for chains
+/// produced by the builders).
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::topology::aromatic_ring_count;
+///
+/// let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+/// let ps = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3))
+///     .homopolymer()
+///     .unwrap();
+/// assert_eq!(aromatic_ring_count(&ps), 3);
+/// ```
+pub fn aromatic_ring_count(chain: &PolymerChain) -> usize {
+    let graph = MolGraph::parse(&chain.smiles).expect("chain SMILES must be valid SMILES");
+    let ring_bonds: Vec<_> = graph
+        .bonds
+        .iter()
+        .filter(|bond| bond.in_ring && graph.atoms[bond.a].aromatic && graph.atoms[bond.b].aromatic)
+        .collect();
+
+    // Union-find over the atoms of the aromatic ring subgraph
+    let mut parent: Vec<usize> = (0..graph.atoms.len()).collect();
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    let mut atoms = vec![false; graph.atoms.len()];
+    let mut components = 0usize;
+    for bond in &ring_bonds {
+        for i in [bond.a, bond.b] {
+            if !atoms[i] {
+                atoms[i] = true;
+                components += 1;
+            }
+        }
+        let (ra, rb) = (find(&mut parent, bond.a), find(&mut parent, bond.b));
+        if ra != rb {
+            parent[ra] = rb;
+            components -= 1;
+        }
+    }
+    let atom_count = atoms.iter().filter(|&&a| a).count();
+    ring_bonds.len() + components - atom_count
+}
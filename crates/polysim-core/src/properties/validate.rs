@@ -0,0 +1,140 @@
+//! Self-consistency checks between property calculators.
+
+use crate::polymer::PolymerChain;
+
+use super::formula::molecular_formula;
+use super::graph::{element_symbol, MolGraph};
+use super::molecular_weight::average_mass;
+
+/// IUPAC standard atomic weights (g/mol) of the elements written by
+/// [`molecular_formula`].
+const STANDARD_WEIGHTS: &[(&str, f64)] = &[
+    ("H", 1.008),
+    ("Li", 6.94),
+    ("B", 10.81),
+    ("C", 12.011),
+    ("N", 14.007),
+    ("O", 15.999),
+    ("F", 18.998),
+    ("Na", 22.990),
+    ("Mg", 24.305),
+    ("Si", 28.085),
+    ("P", 30.974),
+    ("S", 32.06),
+    ("Cl", 35.45),
+    ("K", 39.098),
+    ("Ca", 40.078),
+    ("Br", 79.904),
+    ("I", 126.90),
+];
+
+/// Allowed gap between the two masses, per atom of the formula (g/mol).
+///
+/// Tables of standard atomic weights differ by well under 1 mg/mol per atom,
+/// while a miscounted hydrogen shifts the mass by ~1 g/mol.
+const TOLERANCE_PER_ATOM: f64 = 1e-3;
+
+/// Average molar mass (g/mol) of a molecular formula such as `"C20H42"`.
+///
+/// Returns `None` if the formula is malformed or contains an element without
+/// a standard atomic weight in the table.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::properties::validate::formula_mass;
+///
+/// let m = formula_mass("C2H6").unwrap();
+/// assert!((m - 30.070).abs() < 1e-9);
+/// assert_eq!(formula_mass("C2Xx6"), None);
+/// ```
+pub fn formula_mass(formula: &str) -> Option<f64> {
+    formula_counts(formula)?
+        .into_iter()
+        .map(|(symbol, count)| {
+            STANDARD_WEIGHTS
+                .iter()
+                .find(|&&(sym, _)| sym == symbol)
+                .map(|&(_, weight)| weight * count as f64)
+        })
+        .sum()
+}
+
+/// Checks that [`average_mass`] agrees with the mass recomputed from
+/// [`molecular_formula`].
+///
+/// Both are derived from the chain SMILES through different code paths, so a
+/// disagreement points at a parsing discrepancy such as double-counted
+/// hydrogens. Chains with explicit isotopes (`[13C]`, `[2H]`) or elements
+/// absent from the formula table cannot match and return `false`.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::validate::mass_matches_formula;
+///
+/// let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+/// let ps = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(10))
+///     .homopolymer()
+///     .unwrap();
+/// assert!(mass_matches_formula(&ps));
+/// ```
+pub fn mass_matches_formula(chain: &PolymerChain) -> bool {
+    mass_matches(&molecular_formula(chain), average_mass(chain))
+}
+
+/// `true` if `formula` accounts for `mass` within the per-atom tolerance.
+pub(crate) fn mass_matches(formula: &str, mass: f64) -> bool {
+    let (Some(counts), Some(expected)) = (formula_counts(formula), formula_mass(formula)) else {
+        return false;
+    };
+    let atoms: usize = counts.iter().map(|&(_, count)| count).sum();
+    (expected - mass).abs() <= TOLERANCE_PER_ATOM * atoms.max(1) as f64
+}
+
+/// `true` if every atom of `chain` has a standard atomic weight: no explicit
+/// isotope and an element known to the formula table.
+///
+/// Only such chains are expected to pass [`mass_matches_formula`].
+pub(crate) fn has_standard_composition(chain: &PolymerChain) -> bool {
+    let Ok(graph) = MolGraph::parse(&chain.smiles) else {
+        return false;
+    };
+    graph.atoms.iter().all(|atom| {
+        atom.atomic_number == 0
+            || (atom.isotope == 0
+                && STANDARD_WEIGHTS
+                    .iter()
+                    .any(|&(sym, _)| sym == element_symbol(atom.atomic_number)))
+    })
+}
+
+/// Splits a formula into `(symbol, count)` pairs; `None` if it is malformed.
+fn formula_counts(formula: &str) -> Option<Vec<(&str, usize)>> {
+    let mut counts = Vec::new();
+    let mut rest = formula;
+    while !rest.is_empty() {
+        let symbol_len = rest
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| !c.is_ascii_lowercase())
+            .map_or(rest.len(), |(i, _)| i);
+        let (symbol, tail) = rest.split_at(symbol_len);
+        if !symbol.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return None;
+        }
+        let digits_len = tail
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let (digits, tail) = tail.split_at(digits_len);
+        let count = if digits.is_empty() {
+            1
+        } else {
+            digits.parse().ok()?
+        };
+        counts.push((symbol, count));
+        rest = tail;
+    }
+    Some(counts)
+}
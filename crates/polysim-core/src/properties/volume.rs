@@ -0,0 +1,181 @@
+//! Molar volume and density.
+//!
+//! Volumes are derived from the Van der Waals volume Vw of the chain, summed over
+//! structural groups, and scaled to the amorphous state at the requested
+//! temperature.
+
+use crate::polymer::PolymerChain;
+
+use super::groups::{self, GroupName};
+use super::molecular_weight::average_mass;
+use super::thermal::tg_van_krevelen;
+
+/// Ratio of the glassy to the rubbery thermal expansion coefficient.
+const GLASSY_EXPANSION_RATIO: f64 = 0.35;
+
+/// Van der Waals volume Vw (cm³/mol per repeat unit) from Bondi group increments.
+///
+/// End groups are spread over the repeat units, as in [`molar_volume`].
+///
+/// # Reference
+///
+/// Bondi, A. (1964). van der Waals Volumes and Radii.
+/// *J. Phys. Chem.*, 68(3), 441–451.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::volume::van_der_waals_volume;
+///
+/// let bs = parse("{[]CC[]}").unwrap();
+/// let pe = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(500))
+///     .homopolymer()
+///     .unwrap();
+/// let vw = van_der_waals_volume(&pe);
+/// assert!((vw - 20.46).abs() < 0.1); // 2 × CH₂ = 20.46 cm³/mol
+/// ```
+pub fn van_der_waals_volume(chain: &PolymerChain) -> f64 {
+    groups::sum_increments(chain, GroupName::van_der_waals_volume) / chain.repeat_count as f64
+}
+
+/// Estimates the amorphous molar volume (cm³/mol per repeat unit) at `temperature_k`.
+///
+/// Above Tg the rubbery correlation V = Vw·(1.30 + 1.0×10⁻³·T) is used. Below Tg
+/// the volume follows the rubbery line down to Tg, then expands with the smaller
+/// glassy coefficient. Tg is estimated with [`tg_van_krevelen`]. End groups are
+/// spread over the repeat units.
+///
+/// # Reference
+///
+/// Van Krevelen, D. W. & te Nijenhuis, K. (2009).
+/// *Properties of Polymers*, 4th ed., Elsevier. Chapter 4.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::volume::molar_volume;
+///
+/// let bs = parse("{[]CC[]}").unwrap();
+/// let pe = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(500))
+///     .homopolymer()
+///     .unwrap();
+/// let v = molar_volume(&pe, 298.15);
+/// assert!((v - 32.7).abs() < 1.0); // amorphous PE ≈ 33 cm³/mol
+/// ```
+pub fn molar_volume(chain: &PolymerChain, temperature_k: f64) -> f64 {
+    molar_volume_with_tg(chain, temperature_k, tg_van_krevelen(chain))
+}
+
+/// Molar volume at `temperature_k` for a glass transition at `tg` (K).
+fn molar_volume_with_tg(chain: &PolymerChain, temperature_k: f64, tg: f64) -> f64 {
+    let vw = van_der_waals_volume(chain);
+    let rubbery = |t: f64| vw * (1.30 + 1.0e-3 * t);
+    if temperature_k >= tg {
+        rubbery(temperature_k)
+    } else {
+        rubbery(tg) - vw * 1.0e-3 * GLASSY_EXPANSION_RATIO * (tg - temperature_k)
+    }
+}
+
+/// Estimates the amorphous density (g/cm³) at `temperature_k`.
+///
+/// ρ = M₀ / V, with M₀ the mass per repeat unit and V the
+/// [`molar_volume`] at the same temperature.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::volume::density;
+///
+/// let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+/// let ps = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(100))
+///     .homopolymer()
+///     .unwrap();
+/// let rho = density(&ps, 298.15);
+/// assert!((rho - 1.05).abs() < 0.06); // atactic PS ≈ 1.05 g/cm³
+/// ```
+pub fn density(chain: &PolymerChain, temperature_k: f64) -> f64 {
+    let repeat_mass = average_mass(chain) / chain.repeat_count as f64;
+    repeat_mass / molar_volume(chain, temperature_k)
+}
+
+/// Estimates the amorphous specific volume (cm³/g) at `temperature_k`.
+///
+/// v = V / M₀, the inverse of the [`density`]. Like [`molar_volume`] it grows
+/// with the rubbery expansion coefficient above Tg and with the smaller glassy
+/// one below, which gives the kink of a PVT curve at atmospheric pressure.
+/// Tg is estimated with [`tg_van_krevelen`]; use [`specific_volume_with_tg`]
+/// to supply another one.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::volume::specific_volume;
+///
+/// let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+/// let ps = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(100))
+///     .homopolymer()
+///     .unwrap();
+/// let v = specific_volume(&ps, 298.15);
+/// assert!((v - 0.95).abs() < 0.06); // atactic PS ≈ 0.95 cm³/g
+/// ```
+pub fn specific_volume(chain: &PolymerChain, temperature_k: f64) -> f64 {
+    specific_volume_with_tg(chain, temperature_k, tg_van_krevelen(chain))
+}
+
+/// Estimates the amorphous specific volume (cm³/g) at `temperature_k` for a
+/// glass transition at `tg` (K).
+///
+/// Same model as [`specific_volume`], with the glassy/rubbery break placed at
+/// `tg`: for instance a blend or copolymer Tg from
+/// [`tg_fox`](super::thermal::tg_fox), or a short-chain Tg from
+/// [`tg_fox_flory`](super::thermal::tg_fox_flory).
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::{thermal::tg_fox_flory, volume::specific_volume_with_tg};
+///
+/// let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+/// let ps = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(20))
+///     .homopolymer()
+///     .unwrap();
+/// // Oligomer: Tg lowered from 373 K by the chain ends
+/// let tg = tg_fox_flory(373.0, ps.mn, 1.0e5);
+/// let glassy = specific_volume_with_tg(&ps, tg - 10.0, tg);
+/// let rubbery = specific_volume_with_tg(&ps, tg + 10.0, tg);
+/// assert!(rubbery > glassy);
+/// ```
+pub fn specific_volume_with_tg(chain: &PolymerChain, temperature_k: f64, tg: f64) -> f64 {
+    let repeat_mass = average_mass(chain) / chain.repeat_count as f64;
+    molar_volume_with_tg(chain, temperature_k, tg) / repeat_mass
+}
+
+/// Fractional free volume at `temperature_k`.
+///
+/// FFV = (V − 1.3·Vw) / V, with V the [`molar_volume`] and Vw the
+/// [`van_der_waals_volume`]. The factor 1.3 converts Vw into the occupied
+/// volume of the chain (Bondi). FFV correlates with gas permeability.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::{parse, builder::{linear::LinearBuilder, BuildStrategy}};
+/// use polysim_core::properties::volume::fractional_free_volume;
+///
+/// let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+/// let ps = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(100))
+///     .homopolymer()
+///     .unwrap();
+/// let ffv = fractional_free_volume(&ps, 298.15);
+/// assert!((ffv - 0.18).abs() < 0.04); // atactic PS ≈ 0.18
+/// ```
+pub fn fractional_free_volume(chain: &PolymerChain, temperature_k: f64) -> f64 {
+    let v = molar_volume(chain, temperature_k);
+    (v - 1.3 * van_der_waals_volume(chain)) / v
+}
@@ -0,0 +1,86 @@
+//! Strict BigSMILES validation.
+//!
+//! [`parse`](crate::parse) is lenient and accepts inputs that cannot produce a
+//! chain. [`parse_strict`] rejects them up front with a descriptive error.
+
+use bigsmiles::{BigSmiles, BigSmilesSegment};
+
+use crate::error::PolySimError;
+
+/// Parses a BigSMILES string, rejecting ambiguous or unusable input.
+///
+/// On top of the checks done by [`parse`](crate::parse), the string is rejected if:
+/// - its `{` / `}` braces are unbalanced;
+/// - a stochastic object is empty (`{}`) or holds no repeat unit;
+/// - a repeat unit has no atoms (e.g. `{[][]}`).
+///
+/// # Errors
+///
+/// Returns [`PolySimError::StrictValidation`] for the cases above, or
+/// [`PolySimError::Parse`] if the lenient parser itself fails.
+///
+/// # Example
+///
+/// ```rust
+/// use polysim_core::parse_strict;
+///
+/// assert!(parse_strict("{[]CC[]}").is_ok());
+/// assert!(parse_strict("{}").is_err());
+/// ```
+pub fn parse_strict(s: &str) -> Result<BigSmiles, PolySimError> {
+    check_braces(s)?;
+    let bigsmiles = bigsmiles::parse(s)?;
+
+    let objects = bigsmiles.segments.iter().filter_map(|seg| match seg {
+        BigSmilesSegment::Stochastic(obj) => Some(obj),
+        BigSmilesSegment::Smiles(_) => None,
+    });
+    for (k, obj) in objects.enumerate() {
+        if obj.repeat_units.is_empty() {
+            return Err(PolySimError::StrictValidation(format!(
+                "stochastic object #{} has no repeat units",
+                k + 1
+            )));
+        }
+        if let Some(i) = obj.repeat_units.iter().position(|unit| {
+            !unit
+                .smiles_raw
+                .chars()
+                .any(|c| c.is_ascii_alphabetic() || c == '*')
+        }) {
+            return Err(PolySimError::StrictValidation(format!(
+                "repeat unit #{} of stochastic object #{} has no atoms",
+                i + 1,
+                k + 1
+            )));
+        }
+    }
+    Ok(bigsmiles)
+}
+
+/// Rejects unbalanced braces and empty `{}` stochastic objects.
+fn check_braces(s: &str) -> Result<(), PolySimError> {
+    let mut open: Vec<usize> = Vec::new();
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' => open.push(i),
+            '}' => {
+                let start = open.pop().ok_or_else(|| {
+                    PolySimError::StrictValidation(format!("unmatched '}}' at position {i}"))
+                })?;
+                if s[start + 1..i].trim().is_empty() {
+                    return Err(PolySimError::StrictValidation(format!(
+                        "empty stochastic object at position {start}"
+                    )));
+                }
+            }
+            _ => {}
+        }
+    }
+    match open.pop() {
+        Some(start) => Err(PolySimError::StrictValidation(format!(
+            "unclosed '{{' at position {start}"
+        ))),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,118 @@
+use polysim_core::{
+    analyze_bigsmiles,
+    builder::{linear::LinearBuilder, BuildStrategy},
+    parse,
+    properties::{
+        formula::molecular_formula, molecular_weight::monoisotopic_mass, thermal::tg_infinite_chain,
+    },
+    PolySimError, PropertySummary,
+};
+
+// ── analyze_bigsmiles ────────────────────────────────────────────────────────
+
+#[test]
+fn analyze_matches_individual_calculators() {
+    let summary =
+        analyze_bigsmiles("{[]CC(c1ccccc1)[]}", BuildStrategy::ByRepeatCount(50)).unwrap();
+    let chain = LinearBuilder::new(
+        parse("{[]CC(c1ccccc1)[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(50),
+    )
+    .homopolymer()
+    .unwrap();
+
+    assert_eq!(summary.chain, chain);
+    assert_eq!(summary.mn, chain.mn);
+    assert_eq!(summary.monoisotopic_mass, monoisotopic_mass(&chain));
+    assert_eq!(summary.formula, molecular_formula(&chain));
+    assert_eq!(summary.tg_infinity, tg_infinite_chain(&chain));
+    // Fox-Flory : chaîne finie → Tg < Tg∞
+    assert!(summary.tg < summary.tg_infinity);
+    assert!(
+        (summary.density - 1.05).abs() < 0.06,
+        "ρ = {}",
+        summary.density
+    );
+}
+
+#[test]
+fn analyze_by_target_mn() {
+    let summary = analyze_bigsmiles("{[]CC(C)[]}", BuildStrategy::ByTargetMn(10_000.0)).unwrap();
+    assert!((summary.mn - 10_000.0).abs() < 50.0, "Mn = {}", summary.mn);
+}
+
+#[test]
+fn analyze_invalid_bigsmiles_is_parse_error() {
+    let result = analyze_bigsmiles("{[]CC[", BuildStrategy::ByRepeatCount(5));
+    assert!(
+        matches!(result, Err(PolySimError::Parse(_))),
+        "got: {result:?}"
+    );
+}
+
+#[test]
+fn analyze_copolymer_is_error() {
+    let result = analyze_bigsmiles("{[$]CC[$],[$]CC(C)[$]}", BuildStrategy::ByRepeatCount(5));
+    assert!(
+        matches!(result, Err(PolySimError::RepeatUnitCount { .. })),
+        "got: {result:?}"
+    );
+}
+
+// ── Normalisation par atome du squelette ─────────────────────────────────────
+
+#[test]
+fn per_backbone_carbon_mass_pe_and_pp() {
+    // PE n = 100 : C₂₀₀H₄₀₂, 200 carbones de squelette
+    let pe = analyze_bigsmiles("{[]CC[]}", BuildStrategy::ByRepeatCount(100)).unwrap();
+    assert_eq!(pe.backbone_atoms, 200);
+    let per_atom = pe.per_backbone_atom();
+    assert!((per_atom.mass - pe.mn / 200.0).abs() < 1e-12);
+    assert!((per_atom.mass - 14.04).abs() < 0.01, "{}", per_atom.mass);
+
+    // PP n = 100 : le méthyle pendant n'est pas dans le squelette → ~21 g/mol
+    let pp = analyze_bigsmiles("{[]CC(C)[]}", BuildStrategy::ByRepeatCount(100)).unwrap();
+    assert_eq!(pp.backbone_atoms, 200);
+    let per_atom = pp.per_backbone_atom();
+    assert!((per_atom.mass - 21.05).abs() < 0.01, "{}", per_atom.mass);
+    assert!(
+        (per_atom.volume - pp.mn / pp.density / 200.0).abs() < 1e-9,
+        "{}",
+        per_atom.volume
+    );
+}
+
+// ── Sérialisation ────────────────────────────────────────────────────────────
+
+#[test]
+fn serialized_summary_has_every_documented_field() {
+    let summary = analyze_bigsmiles("{[]CC[]}", BuildStrategy::ByRepeatCount(10)).unwrap();
+    let json = serde_json::to_value(&summary).unwrap();
+    let object = json.as_object().unwrap();
+
+    let keys: Vec<&str> = object.keys().map(String::as_str).collect();
+    let mut expected: Vec<&str> = PropertySummary::FIELDS.iter().map(|f| f.name).collect();
+    expected.sort_unstable();
+    assert_eq!(keys, expected);
+
+    assert_eq!(object["formula"], "C20H42");
+    assert_eq!(object["chain"]["repeat_count"], 10);
+    assert_eq!(object["chain"]["architecture"], "Linear");
+}
+
+#[test]
+fn summary_field_types_match_serialized_values() {
+    let summary = analyze_bigsmiles("{[]CC(C)[]}", BuildStrategy::ByRepeatCount(5)).unwrap();
+    let json = serde_json::to_value(&summary).unwrap();
+    for field in PropertySummary::FIELDS {
+        let value = &json[field.name];
+        let matches = match field.json_type {
+            "number" => value.is_f64(),
+            "integer" => value.is_u64(),
+            "string" => value.is_string(),
+            "object" => value.is_object(),
+            other => panic!("unexpected JSON type {other}"),
+        };
+        assert!(matches, "{} = {value}", field.name);
+    }
+}
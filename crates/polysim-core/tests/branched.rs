@@ -34,6 +34,53 @@ fn comb_pe_n4_branch_every2() {
     ));
 }
 
+#[test]
+fn comb_with_polymeric_branches_counts_all_units() {
+    // 30 PE backbone units, one 5-unit PP branch every 10 backbone units
+    let backbone = parse("{[]CC[]}").unwrap();
+    let branch = parse("{[]CC(C)[]}").unwrap();
+    let chain = BranchedBuilder::new(backbone, branch, BuildStrategy::ByRepeatCount(30))
+        .branch_length(5)
+        .comb_polymer(10)
+        .unwrap();
+
+    assert_eq!(chain.repeat_count, 30 + 3 * 5);
+    assert_eq!(
+        chain.smiles.matches("(CC(C)CC(C)CC(C)CC(C)CC(C))").count(),
+        3
+    );
+    let fractions: Vec<f64> = chain.composition.iter().map(|m| m.fraction).collect();
+    assert_eq!(fractions, [30.0 / 45.0, 15.0 / 45.0]);
+}
+
+#[test]
+fn graft_with_polymeric_branches_counts_all_units() {
+    let backbone = parse("{[]CC[]}").unwrap();
+    let branch = parse("{[]CC(C)[]}").unwrap();
+    let chain = BranchedBuilder::new(backbone, branch, BuildStrategy::ByRepeatCount(50))
+        .seed(673)
+        .branch_length(3)
+        .graft_copolymer(0.2, None)
+        .unwrap();
+
+    let branch_count = chain.smiles.matches("(CC(C)CC(C)CC(C))").count();
+    assert!(branch_count > 0);
+    assert_eq!(chain.repeat_count, 50 + 3 * branch_count);
+}
+
+#[test]
+fn zero_branch_length_is_error() {
+    let backbone = parse("{[]CC[]}").unwrap();
+    let branch = parse("{[]CC(C)[]}").unwrap();
+    let result = BranchedBuilder::new(backbone, branch, BuildStrategy::ByRepeatCount(10))
+        .branch_length(0)
+        .comb_polymer(2);
+    assert!(matches!(
+        result,
+        Err(polysim_core::PolySimError::BuildStrategy(_))
+    ));
+}
+
 // --- Graft ---
 
 #[test]
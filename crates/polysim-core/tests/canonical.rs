@@ -0,0 +1,210 @@
+use std::collections::HashSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use bigsmiles::parse;
+use polysim_core::{
+    builder::{canonical_repeat_unit, linear::LinearBuilder, repeat_units, BuildStrategy},
+    PolySimError, PolymerChain,
+};
+
+fn chain(smiles: &str, n: usize) -> PolymerChain {
+    PolymerChain::new(smiles.to_string(), n, 0.0)
+}
+
+fn hash_of(chain: &PolymerChain) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    chain.canonical_key().hash(&mut hasher);
+    hasher.finish()
+}
+
+// ── canonical_smiles ─────────────────────────────────────────────────────────
+
+#[test]
+fn canonical_smiles_ignores_atom_order() {
+    let variants = ["CC(C)CCC", "CCCC(C)C", "C(C)(C)CCC", "C(CCC)(C)C"];
+    let canon: HashSet<String> = variants
+        .iter()
+        .map(|s| chain(s, 1).canonical_smiles())
+        .collect();
+    assert_eq!(canon.len(), 1, "{canon:?}");
+}
+
+#[test]
+fn canonical_smiles_ignores_ring_numbering_and_start_atom() {
+    let a = chain("CCc1ccccc1", 1).canonical_smiles();
+    let b = chain("c1ccc(CC)cc1", 1).canonical_smiles();
+    let c = chain("c2ccccc2CC", 1).canonical_smiles();
+    assert_eq!(a, b);
+    assert_eq!(a, c);
+}
+
+#[test]
+fn canonical_smiles_is_idempotent() {
+    for smiles in [
+        "CC(c1ccccc1)CC(c1ccccc1)",
+        "CC(C)(C(=O)OC)CC(C)(C(=O)OC)",
+        "C1CCCCC1",
+    ] {
+        let once = chain(smiles, 1).canonical_smiles();
+        let twice = chain(&once, 1).canonical_smiles();
+        assert_eq!(once, twice, "{smiles}");
+    }
+}
+
+#[test]
+fn canonical_smiles_distinguishes_isomers() {
+    let butane = chain("CCCC", 1).canonical_smiles();
+    let isobutane = chain("CC(C)C", 1).canonical_smiles();
+    assert_ne!(butane, isobutane);
+}
+
+#[test]
+fn canonical_smiles_keeps_charges_and_isotopes() {
+    let a = chain("CC(=O)[O-].[Na+]", 1).canonical_smiles();
+    let b = chain("[Na+].[O-]C(C)=O", 1).canonical_smiles();
+    assert_eq!(a, b);
+    assert!(a.contains("[O-]") && a.contains("[Na+]"), "{a}");
+    assert_ne!(
+        chain("[13CH3]C", 1).canonical_smiles(),
+        chain("CC", 1).canonical_smiles()
+    );
+}
+
+// ── canonical_repeat_unit ────────────────────────────────────────────────────
+
+#[test]
+fn canonical_repeat_unit_ps_is_stable_across_spellings() {
+    // Ordre des atomes, numéros de cycle et style de descripteur différents
+    let variants = [
+        "{[]CC(c1ccccc1)[]}",
+        "{[]C(c1ccccc1)C[]}",
+        "{[]CC(c2ccccc2)[]}",
+        "{[$]CC(c1ccccc1)[$]}",
+        "{[<]CC(c1ccccc1)[>]}",
+    ];
+    let canon: HashSet<String> = variants
+        .iter()
+        .map(|s| canonical_repeat_unit(&parse(s).unwrap(), 0).unwrap())
+        .collect();
+    assert_eq!(canon.len(), 1, "{canon:?}");
+}
+
+#[test]
+fn canonical_repeat_unit_matches_single_unit_chain() {
+    // Le motif coiffé d'hydrogènes = chaîne n = 1 sans groupes terminaux
+    let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+    let built = LinearBuilder::new(bs.clone(), BuildStrategy::ByRepeatCount(1))
+        .homopolymer()
+        .unwrap();
+    assert_eq!(
+        canonical_repeat_unit(&bs, 0).unwrap(),
+        built.canonical_smiles()
+    );
+}
+
+#[test]
+fn canonical_repeat_unit_selects_by_index() {
+    let bs = parse("{[$]CC[$],[$]CC(C)[$]}").unwrap();
+    let pe = canonical_repeat_unit(&bs, 0).unwrap();
+    let pp = canonical_repeat_unit(&bs, 1).unwrap();
+    assert_ne!(pe, pp);
+    assert_eq!(pp, chain("CCC", 1).canonical_smiles());
+}
+
+#[test]
+fn canonical_repeat_unit_out_of_range_fails() {
+    let bs = parse("{[]CC[]}").unwrap();
+    let err = canonical_repeat_unit(&bs, 1).unwrap_err();
+    assert!(matches!(err, PolySimError::InvalidRepeatUnit(_)), "{err}");
+}
+
+#[test]
+fn canonical_repeat_unit_without_stochastic_object_fails() {
+    let bs = parse("CCO").unwrap();
+    assert!(matches!(
+        canonical_repeat_unit(&bs, 0),
+        Err(PolySimError::NoStochasticObject)
+    ));
+}
+
+// ── canonical_eq / canonical_key ─────────────────────────────────────────────
+
+#[test]
+fn equivalent_chains_are_equal_and_hash_equal() {
+    let a = chain("CC(C)CC(C)", 2);
+    let b = chain("C(C)(C)CCC", 2);
+    assert!(a.canonical_eq(&b));
+    assert_eq!(a.canonical_key(), b.canonical_key());
+    assert_eq!(hash_of(&a), hash_of(&b));
+
+    let set: HashSet<_> = [&a, &b].map(PolymerChain::canonical_key).into();
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn built_chain_equals_hand_written_equivalent() {
+    let bs = parse("{[]CC(C)[]}").unwrap();
+    let built = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(2))
+        .homopolymer()
+        .unwrap();
+    assert!(built.canonical_eq(&chain("CCCC(C)C", 2)));
+}
+
+#[test]
+fn chains_with_different_repeat_count_are_distinct() {
+    assert!(!chain("CCCC", 2).canonical_eq(&chain("CCCC", 4)));
+    assert_ne!(
+        chain("CCCC", 2).canonical_key(),
+        chain("CCCC", 4).canonical_key()
+    );
+}
+
+#[test]
+fn structural_equality_keeps_the_written_smiles() {
+    // `==` compare les champs tels qu'écrits ; l'espèce passe par canonical_eq
+    assert_ne!(chain("CC(C)CC(C)", 2), chain("C(C)(C)CCC", 2));
+    assert_eq!(chain("CCCC", 2), chain("CCCC", 2));
+}
+
+#[test]
+fn hashset_counts_unique_species() {
+    let chains = [
+        chain("CCCC", 2),
+        chain("C(C)CC", 2),
+        chain("CC(C)C", 2),
+        chain("C(C)(C)C", 2),
+        chain("CCCCCC", 3),
+    ];
+    let unique: HashSet<_> = chains.iter().map(PolymerChain::canonical_key).collect();
+    assert_eq!(unique.len(), 3);
+}
+
+// ── repeat_units ─────────────────────────────────────────────────────────────
+
+#[test]
+fn repeat_units_of_polyethylene() {
+    assert_eq!(repeat_units(&parse("{[]CC[]}").unwrap()).unwrap(), ["CC"]);
+}
+
+#[test]
+fn repeat_units_of_styrene_maleic_anhydride() {
+    // SMA : styrène et anhydride maléique, descripteurs retirés
+    let units =
+        repeat_units(&parse("{[$]CC(c1ccccc1)[$],[$]C1C(=O)OC(=O)C1[$]}").unwrap()).unwrap();
+    assert_eq!(units, ["CC(c1ccccc1)", "C1C(=O)OC(=O)C1"]);
+}
+
+#[test]
+fn repeat_units_span_every_stochastic_object() {
+    // Copolymère à blocs écrit avec deux objets stochastiques
+    let units = repeat_units(&parse("{[$]CC[$]}{[$]CC(C)[$]}").unwrap()).unwrap();
+    assert_eq!(units, ["CC", "CC(C)"]);
+}
+
+#[test]
+fn repeat_units_without_stochastic_object_is_error() {
+    assert!(matches!(
+        repeat_units(&parse("CCO").unwrap()),
+        Err(PolySimError::NoStochasticObject)
+    ));
+}
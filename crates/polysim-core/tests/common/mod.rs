@@ -0,0 +1,13 @@
+//! Helpers shared by the integration tests.
+
+use polysim_core::{
+    builder::{linear::LinearBuilder, BuildStrategy},
+    parse, PolymerChain,
+};
+
+/// Homopolymer of `n` repeat units of `bigsmiles`.
+pub fn homopolymer(bigsmiles: &str, n: usize) -> PolymerChain {
+    LinearBuilder::new(parse(bigsmiles).unwrap(), BuildStrategy::ByRepeatCount(n))
+        .homopolymer()
+        .unwrap()
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use bigsmiles::parse;
+use common::homopolymer;
+use polysim_core::{
+    builder::{linear::LinearBuilder, BuildStrategy, RoundingMode},
+    properties::conformation::{contour_length, radius_of_gyration},
+    PolySimError,
+};
+
+const CC_BOND_NM: f64 = 0.154;
+
+// ── Longueur de contour ──────────────────────────────────────────────────────
+
+#[test]
+fn polyethylene_contour_length_scales_linearly_with_n() {
+    // Chaque unité ajoute 2 liaisons C–C projetées : pas constant
+    let l = |n| contour_length(&homopolymer("{[]CC[]}", n), CC_BOND_NM);
+    let step = l(20) - l(10);
+    assert!((l(30) - l(20) - step).abs() < 1e-9);
+    assert!((l(110) - l(10) - 10.0 * step).abs() < 1e-9);
+    // 2 × 0.154 × sin(109.5°/2) ≈ 0.2515 nm par unité (zigzag tout-trans)
+    assert!((step / 10.0 - 0.2515).abs() < 1e-3, "pas = {}", step / 10.0);
+}
+
+#[test]
+fn polyethylene_contour_length_value() {
+    // C₁₀ : 9 liaisons projetées
+    let length = contour_length(&homopolymer("{[]CC[]}", 5), CC_BOND_NM);
+    let expected = 9.0 * CC_BOND_NM * (109.5_f64.to_radians() / 2.0).sin();
+    assert!((length - expected).abs() < 1e-9, "L = {length}");
+}
+
+#[test]
+fn contour_length_is_below_fully_stretched_bonds() {
+    let pe = homopolymer("{[]CC[]}", 50);
+    assert!(contour_length(&pe, CC_BOND_NM) < 99.0 * CC_BOND_NM);
+}
+
+#[test]
+fn pendant_groups_do_not_lengthen_the_backbone() {
+    // PS et PE de même n : même squelette carboné
+    let pe = contour_length(&homopolymer("{[]CC[]}", 20), CC_BOND_NM);
+    let ps = contour_length(&homopolymer("{[]CC(c1ccccc1)[]}", 20), CC_BOND_NM);
+    assert!((pe - ps).abs() < 1e-9, "PE = {pe}, PS = {ps}");
+}
+
+#[test]
+fn sp2_backbone_projects_longer_than_sp3() {
+    // Polyacétylène : liaisons à 120°, moins raccourcies qu'à 109,5°
+    let pa = contour_length(&homopolymer("{[]C=C[]}", 20), CC_BOND_NM);
+    let pe = contour_length(&homopolymer("{[]CC[]}", 20), CC_BOND_NM);
+    assert!(pa > pe, "PA = {pa}, PE = {pe}");
+}
+
+#[test]
+fn contour_length_is_proportional_to_bond_length() {
+    let pe = homopolymer("{[]CC[]}", 10);
+    let ratio = contour_length(&pe, 0.308) / contour_length(&pe, CC_BOND_NM);
+    assert!((ratio - 2.0).abs() < 1e-12);
+}
+
+// ── Rayon de giration ────────────────────────────────────────────────────────
+
+const PE_CHAR_RATIO: f64 = 6.7;
+
+fn pe_builder() -> LinearBuilder {
+    LinearBuilder::new(parse("{[]CC[]}").unwrap(), BuildStrategy::ByRepeatCount(1))
+}
+
+#[test]
+fn polyethylene_radius_of_gyration_value() {
+    // C₂₀ : 19 liaisons, Rg² = C∞·n·l²/6
+    let rg = radius_of_gyration(&homopolymer("{[]CC[]}", 10), PE_CHAR_RATIO, CC_BOND_NM);
+    let expected = (PE_CHAR_RATIO * 19.0 * CC_BOND_NM * CC_BOND_NM / 6.0).sqrt();
+    assert!((rg - expected).abs() < 1e-12, "Rg = {rg}");
+}
+
+#[test]
+fn radius_of_gyration_grows_as_square_root_of_bond_count() {
+    // n liaisons = 2·DP − 1 : Rg(1000)/Rg(250) = √(1999/499), presque 2
+    let rg = |n| radius_of_gyration(&homopolymer("{[]CC[]}", n), PE_CHAR_RATIO, CC_BOND_NM);
+    let ratio = rg(1000) / rg(250);
+    assert!((ratio - (1999.0_f64 / 499.0).sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn build_for_rg_hits_polyethylene_target() {
+    for target in [2.0, 5.0, 10.0, 20.0] {
+        let chain = pe_builder()
+            .build_for_rg(target, PE_CHAR_RATIO, CC_BOND_NM)
+            .unwrap();
+        let rg = radius_of_gyration(&chain, PE_CHAR_RATIO, CC_BOND_NM);
+        // Une unité de plus ou de moins déplace Rg de moins de 1 %
+        assert!(
+            (rg - target).abs() / target < 0.01,
+            "cible {target}, Rg = {rg}"
+        );
+    }
+}
+
+#[test]
+fn build_for_rg_picks_closest_repeat_count() {
+    // 5 nm tombe entre n = 472 (4,9973 nm) et n = 473 (5,0026 nm)
+    let chain = pe_builder()
+        .build_for_rg(5.0, PE_CHAR_RATIO, CC_BOND_NM)
+        .unwrap();
+    assert_eq!(chain.repeat_count, 473);
+    let floor = pe_builder()
+        .rounding(RoundingMode::Floor)
+        .build_for_rg(5.0, PE_CHAR_RATIO, CC_BOND_NM)
+        .unwrap();
+    assert_eq!(floor.repeat_count, 472);
+    assert!(radius_of_gyration(&floor, PE_CHAR_RATIO, CC_BOND_NM) <= 5.0);
+}
+
+#[test]
+fn build_for_rg_ignores_builder_strategy_and_keeps_end_groups() {
+    let chain = LinearBuilder::new(parse("{[]CC[]}").unwrap(), BuildStrategy::ByTargetMn(1e5))
+        .with_end_groups("O", "O")
+        .build_for_rg(3.0, PE_CHAR_RATIO, CC_BOND_NM)
+        .unwrap();
+    assert!(chain.smiles.starts_with('O') && chain.smiles.ends_with('O'));
+    let rg = radius_of_gyration(&chain, PE_CHAR_RATIO, CC_BOND_NM);
+    assert!((rg - 3.0).abs() < 0.03, "Rg = {rg}");
+}
+
+#[test]
+fn build_for_rg_below_one_unit_is_an_error() {
+    // Rg(1) ≈ 0,16 nm : l'arrondi par défaut d'une cible plus petite donne n = 0
+    let err = pe_builder()
+        .rounding(RoundingMode::Floor)
+        .build_for_rg(0.1, PE_CHAR_RATIO, CC_BOND_NM)
+        .unwrap_err();
+    assert!(matches!(err, PolySimError::BuildStrategy(_)), "{err}");
+    assert!(err.to_string().contains("minimum achievable Rg"), "{err}");
+}
+
+#[test]
+fn build_for_rg_rejects_non_positive_arguments() {
+    for (rg, c, l) in [
+        (0.0, PE_CHAR_RATIO, CC_BOND_NM),
+        (5.0, -1.0, CC_BOND_NM),
+        (5.0, PE_CHAR_RATIO, f64::NAN),
+    ] {
+        let err = pe_builder().build_for_rg(rg, c, l).unwrap_err();
+        assert!(matches!(err, PolySimError::BuildStrategy(_)), "{err}");
+    }
+}
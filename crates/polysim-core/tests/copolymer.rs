@@ -58,6 +58,34 @@ fn alternating_by_target_mn() {
     );
 }
 
+#[test]
+fn alternating_reports_composition() {
+    let bs = parse("{[$]CC[$],[$]CC(C)[$]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(6))
+        .alternating_copolymer()
+        .unwrap();
+    let fractions: Vec<f64> = chain.composition.iter().map(|m| m.fraction).collect();
+    assert_eq!(fractions, [0.5, 0.5]);
+}
+
+#[test]
+fn alternating_sequence_string() {
+    let bs = parse("{[$]CC[$],[$]CC(C)[$]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(6))
+        .alternating_copolymer()
+        .unwrap();
+    assert_eq!(chain.sequence_string(), "ABABAB");
+}
+
+#[test]
+fn alternating_3_units_sequence_string() {
+    let bs = parse("{[$]CC[$],[$]CC(C)[$],[$]CCO[$]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(7))
+        .alternating_copolymer()
+        .unwrap();
+    assert_eq!(chain.sequence_string(), "ABCABCA");
+}
+
 // ═══ Block copolymer ════════════════════════════════════════════════════════
 
 #[test]
@@ -72,6 +100,34 @@ fn block_3_3() {
     assert!(chain.mn > 0.0);
 }
 
+#[test]
+fn block_reports_composition() {
+    let bs = parse("{[$]CC[$],[$]CC(C)[$]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(4))
+        .block_copolymer(&[3, 1])
+        .unwrap();
+    let fractions: Vec<f64> = chain.composition.iter().map(|m| m.fraction).collect();
+    assert_eq!(fractions, [0.75, 0.25]);
+}
+
+#[test]
+fn block_sequence_string() {
+    let bs = parse("{[$]CC[$],[$]CC(C)[$]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(4))
+        .block_copolymer(&[3, 2])
+        .unwrap();
+    assert_eq!(chain.sequence_string(), "AAABB");
+}
+
+#[test]
+fn block_species_count() {
+    let bs = parse("{[$]CC[$],[$]CC(C)[$]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(6))
+        .block_copolymer(&[3, 3])
+        .unwrap();
+    assert_eq!(chain.species_count(), 2);
+}
+
 #[test]
 fn block_wrong_count() {
     let bs = parse("{[$]CC[$],[$]CC(C)[$]}").unwrap();
@@ -119,6 +175,23 @@ fn random_n10_seeded() {
         .unwrap();
     assert_eq!(chain.repeat_count, 10);
     assert!(chain.mn > 0.0);
+    let total: f64 = chain.composition.iter().map(|m| m.fraction).sum();
+    assert_eq!(chain.composition.len(), 2);
+    assert!((total - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn random_sequence_string_matches_composition() {
+    let bs = parse("{[$]CC[$],[$]CC(C)[$]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(40))
+        .seed(7)
+        .random_copolymer(&[0.3, 0.7])
+        .unwrap();
+    let seq = chain.sequence_string();
+    assert_eq!(seq.len(), 40);
+    assert!(seq.chars().all(|c| c == 'A' || c == 'B'), "{seq}");
+    let frac_a = seq.matches('A').count() as f64 / 40.0;
+    assert!((frac_a - chain.composition[0].fraction).abs() < 1e-12);
 }
 
 #[test]
@@ -167,6 +240,45 @@ fn random_by_target_mn() {
     );
 }
 
+// ═══ Sequence-controlled copolymer ═════════════════════════════════════════
+
+#[test]
+fn from_sequence_abba() {
+    let bs = parse("{[$]CC[$],[$]CC(C)[$]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1))
+        .from_sequence("ABBA")
+        .unwrap();
+    assert_eq!(chain.repeat_count, 4);
+    // Pattern: CC CC(C) CC(C) CC
+    assert_eq!(chain.smiles, "CCCC(C)CC(C)CC");
+    assert_eq!(chain.sequence_string(), "ABBA");
+    assert!(chain.mn > 0.0);
+}
+
+#[test]
+fn from_sequence_ring_numbers_stay_disjoint() {
+    let bs = parse("{[$]CC(c1ccccc1)[$],[$]CC[$]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1))
+        .from_sequence("AABA")
+        .unwrap();
+    // Décalage par position : la 3ᵉ copie (éthylène) n'utilise pas son numéro 3
+    assert_eq!(chain.smiles, "CC(c1ccccc1)CC(c2ccccc2)CCCC(c4ccccc4)");
+    assert!(opensmiles::parse(&chain.smiles).is_ok(), "{}", chain.smiles);
+}
+
+#[test]
+fn from_sequence_letter_beyond_units_is_error() {
+    let bs = parse("{[$]CC[$],[$]CC(C)[$]}").unwrap();
+    let builder = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1));
+    for pattern in ["ABC", "AbA", "A-B", ""] {
+        let result = builder.from_sequence(pattern);
+        assert!(
+            matches!(result, Err(PolySimError::BuildStrategy(_))),
+            "{pattern:?}: {result:?}"
+        );
+    }
+}
+
 // ═══ Validation: homopolymer rejects >1 unit ════════════════════════════════
 
 #[test]
@@ -216,3 +328,50 @@ fn block_ensemble_basic() {
         .unwrap();
     assert_eq!(ensemble.len(), 50);
 }
+
+// ═══ Bonding descriptor pairing ([<] / [>]) ═════════════════════════════════
+
+#[test]
+fn aa_bb_alternating_nylon66_connects() {
+    // Adipoyl (AA, [<] ends) and hexamethylenediamine (BB, [>] ends)
+    let bs = parse("{[][<]C(=O)CCCCC(=O)[<],[>]NCCCCCCN[>][]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(4))
+        .alternating_copolymer()
+        .unwrap();
+    assert_eq!(chain.smiles, "C(=O)CCCCC(=O)NCCCCCCNC(=O)CCCCC(=O)NCCCCCCN");
+}
+
+#[test]
+fn ab_homopolymer_with_complementary_ends_connects() {
+    // Nylon-6 written as an AB unit: [<] head, [>] tail
+    let bs = parse("{[][<]NCCCCCC(=O)[>][]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3))
+        .homopolymer()
+        .unwrap();
+    assert_eq!(chain.smiles, "NCCCCCC(=O)NCCCCCC(=O)NCCCCCC(=O)");
+}
+
+#[test]
+fn aa_homopolymer_is_invalid_repeat_unit() {
+    let bs = parse("{[][<]C(=O)CCCCC(=O)[<][]}").unwrap();
+    let result = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3)).homopolymer();
+    assert!(
+        matches!(result, Err(PolySimError::InvalidRepeatUnit(_))),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn aa_bb_block_copolymer_is_invalid_repeat_unit() {
+    // Two AA units in a row cannot bond
+    let bs = parse("{[][<]C(=O)CCCCC(=O)[<],[>]NCCCCCCN[>][]}").unwrap();
+    let result = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(4)).block_copolymer(&[2, 2]);
+    assert!(matches!(result, Err(PolySimError::InvalidRepeatUnit(_))));
+}
+
+#[test]
+fn mismatched_descriptor_indices_do_not_pair() {
+    let bs = parse("{[][<1]CC[>2][]}").unwrap();
+    let result = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(2)).homopolymer();
+    assert!(matches!(result, Err(PolySimError::InvalidRepeatUnit(_))));
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use bigsmiles::parse;
+use common::homopolymer;
+use polysim_core::{
+    builder::{linear::LinearBuilder, BuildStrategy},
+    properties::{
+        conformation::contour_length,
+        electrical::{
+            dielectric_constant, dipole_moment, linear_charge_density, net_charge, refractive_index,
+        },
+    },
+};
+
+// ── dipole_moment ────────────────────────────────────────────────────────────
+
+#[test]
+fn dipole_moment_polyethylene_is_zero() {
+    let mu = dipole_moment(&homopolymer("{[]CC[]}", 100));
+    assert_eq!(mu, 0.0);
+}
+
+#[test]
+fn dipole_moment_pvc_is_c_cl_bond() {
+    // Une liaison C–Cl par unité : μ ≈ 1.9 D
+    let mu = dipole_moment(&homopolymer("{[]CC(Cl)[]}", 100));
+    assert!((mu - 1.9).abs() < 0.1, "μ(PVC) = {mu}");
+}
+
+#[test]
+fn dipole_moment_pmma_above_pvc() {
+    // Ester : C=O + -O- → μ plus élevé que le C–Cl seul
+    let pmma = dipole_moment(&homopolymer("{[]CC(C)(C(=O)OC)[]}", 100));
+    let pvc = dipole_moment(&homopolymer("{[]CC(Cl)[]}", 100));
+    assert!(pmma > pvc, "PMMA {pmma} vs PVC {pvc}");
+}
+
+// ── dielectric_constant ──────────────────────────────────────────────────────
+
+#[test]
+fn dielectric_constant_polyethylene() {
+    // PE amorphe : ε ≈ 2.3
+    let eps = dielectric_constant(&homopolymer("{[]CC[]}", 500), 1.0e3);
+    assert!((eps - 2.3).abs() < 0.15, "ε(PE) = {eps}");
+}
+
+#[test]
+fn dielectric_constant_pvc_above_polyethylene() {
+    // PVC : ε ≈ 2.8–3.1 à basse fréquence
+    let pvc = dielectric_constant(&homopolymer("{[]CC(Cl)[]}", 100), 1.0e3);
+    let pe = dielectric_constant(&homopolymer("{[]CC[]}", 500), 1.0e3);
+    assert!(pvc > pe, "PVC {pvc} vs PE {pe}");
+    assert!((pvc - 2.95).abs() < 0.25, "ε(PVC) = {pvc}");
+}
+
+#[test]
+fn dielectric_constant_nonpolar_is_frequency_independent() {
+    let pe = homopolymer("{[]CC[]}", 500);
+    let low = dielectric_constant(&pe, 1.0e3);
+    let high = dielectric_constant(&pe, 1.0e14);
+    assert!((low - high).abs() < 1e-12, "{low} vs {high}");
+}
+
+#[test]
+fn dielectric_constant_polar_drops_at_high_frequency() {
+    // Les dipôles ne suivent plus le champ : ε → n² (PVC : n ≈ 1.54)
+    let pvc = homopolymer("{[]CC(Cl)[]}", 100);
+    let low = dielectric_constant(&pvc, 1.0e3);
+    let optical = dielectric_constant(&pvc, 1.0e14);
+    assert!(optical < low, "{optical} vs {low}");
+    assert!(
+        (optical - 1.54_f64.powi(2)).abs() < 0.2,
+        "ε∞(PVC) = {optical}"
+    );
+}
+
+// ── refractive_index ─────────────────────────────────────────────────────────
+
+#[test]
+fn refractive_index_common_polymers() {
+    // Valeurs de la littérature à 25 °C (n_D)
+    for (bigsmiles, expected) in [
+        ("{[]CC(c1ccccc1)[]}", 1.59),
+        ("{[]CC(C)(C(=O)OC)[]}", 1.49),
+        ("{[]CC(Cl)[]}", 1.54),
+    ] {
+        let n = refractive_index(&homopolymer(bigsmiles, 100));
+        assert!((n - expected).abs() < 0.03, "{bigsmiles}: n = {n}");
+    }
+}
+
+#[test]
+fn refractive_index_squared_is_optical_dielectric_constant() {
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 100);
+    let n = refractive_index(&ps);
+    let eps_optical = dielectric_constant(&ps, 1.0e15);
+    assert!(
+        (n * n - eps_optical).abs() < 1e-3,
+        "n² = {}, ε∞ = {eps_optical}",
+        n * n
+    );
+}
+
+// ── net_charge / linear_charge_density ───────────────────────────────────────
+
+const PSS: &str = "{[]CC(c1ccc(cc1)S(=O)(=O)[O-])[]}";
+
+#[test]
+fn net_charge_of_neutral_chain_is_zero() {
+    assert_eq!(net_charge(&homopolymer("{[]CC(C)(C(=O)OC)[]}", 50)), 0);
+}
+
+#[test]
+fn net_charge_sulfonated_polystyrene_is_one_per_unit() {
+    assert_eq!(net_charge(&homopolymer(PSS, 80)), -80);
+}
+
+#[test]
+fn net_charge_is_zero_with_counterions() {
+    let chain = LinearBuilder::new(parse(PSS).unwrap(), BuildStrategy::ByRepeatCount(20))
+        .counterions(true)
+        .homopolymer()
+        .unwrap();
+    assert_eq!(net_charge(&chain), 0);
+}
+
+#[test]
+fn linear_charge_density_sulfonated_polystyrene() {
+    // PSS entièrement sulfoné : une charge par unité, soit deux liaisons C–C
+    // du squelette → b = 2 × 0.154 nm × sin(54.75°) ≈ 0.2516 nm, λ ≈ −1/b
+    let n = 200;
+    let chain = homopolymer(PSS, n);
+    let length = contour_length(&chain, 0.154);
+    let density = linear_charge_density(&chain, length);
+    let spacing = length / n as f64;
+    assert!((spacing - 0.2516).abs() < 0.005, "b = {spacing} nm");
+    assert!((density + 1.0 / 0.2516).abs() < 0.05, "λ = {density} e/nm");
+}
+
+#[test]
+fn linear_charge_density_of_neutral_chain_is_zero() {
+    let chain = homopolymer("{[]CC[]}", 100);
+    assert_eq!(
+        linear_charge_density(&chain, contour_length(&chain, 0.154)),
+        0.0
+    );
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::homopolymer;
+use polysim_core::{
+    builder::{elemental::REPRESENTATIVE_DP, match_elemental_analysis, ElementalComposition},
+    properties::formula::mass_fractions,
+    PolymerChain,
+};
+
+const PE: &str = "{[]CC[]}";
+const PP: &str = "{[]CC(C)[]}";
+const PS: &str = "{[]CC(c1ccccc1)[]}";
+const PMMA: &str = "{[]CC(C)(C(=O)OC)[]}";
+const PAN: &str = "{[]CC(C#N)[]}";
+
+// ── mass_fractions ───────────────────────────────────────────────────────────
+
+#[test]
+fn mass_fractions_sum_to_one() {
+    for bigsmiles in [PE, PS, PMMA, PAN, "{[]CC(Cl)[]}"] {
+        let total: f64 = mass_fractions(&homopolymer(bigsmiles, 20)).values().sum();
+        assert!((total - 1.0).abs() < 1e-12, "{bigsmiles}: {total}");
+    }
+}
+
+#[test]
+fn mass_fractions_polyacrylonitrile() {
+    // C₃H₃N : C 67.91 %, H 5.70 %, N 26.40 %
+    let fractions = mass_fractions(&homopolymer(PAN, REPRESENTATIVE_DP));
+    assert!((fractions["C"] - 0.6791).abs() < 1e-3, "{fractions:?}");
+    assert!((fractions["H"] - 0.0570).abs() < 1e-3, "{fractions:?}");
+    assert!((fractions["N"] - 0.2640).abs() < 1e-3, "{fractions:?}");
+}
+
+#[test]
+fn mass_fractions_ignore_wildcards() {
+    let with = mass_fractions(&PolymerChain::new("*CC*".to_string(), 1, 0.0));
+    let without = mass_fractions(&PolymerChain::new("[CH2][CH2]".to_string(), 1, 0.0));
+    assert_eq!(with.len(), 2);
+    assert!((with["C"] - without["C"]).abs() < 1e-12);
+}
+
+// ── match_elemental_analysis ─────────────────────────────────────────────────
+
+#[test]
+fn polystyrene_percentages_match_polystyrene() {
+    // Analyse mesurée d'un PS : C 92.1 %, H 7.8 % (théorie C₈H₈ : 92.26 / 7.74)
+    let measured = ElementalComposition::new().with("C", 92.1).with("H", 7.8);
+    let matches = match_elemental_analysis(&[PE, PS, PMMA, PAN], &measured, 0.3);
+    assert_eq!(matches.len(), 1, "{matches:?}");
+    assert_eq!(matches[0].bigsmiles, PS);
+    assert_eq!(matches[0].repeat_count, REPRESENTATIVE_DP);
+    assert!(matches[0].max_deviation < 0.3);
+    assert!((matches[0].percentages["C"] - 92.24).abs() < 0.05);
+}
+
+#[test]
+fn nitrogen_selects_polyacrylonitrile() {
+    let measured = ElementalComposition::new()
+        .with("C", 67.8)
+        .with("H", 5.8)
+        .with("N", 26.2);
+    let matches = match_elemental_analysis(&[PE, PS, PMMA, PAN], &measured, 0.5);
+    assert_eq!(matches.len(), 1, "{matches:?}");
+    assert_eq!(matches[0].bigsmiles, PAN);
+}
+
+#[test]
+fn same_empirical_formula_all_match_best_first() {
+    // PE et PP : (CH₂)ₙ, seuls les bouts de chaîne diffèrent
+    let measured = ElementalComposition::new().with("C", 85.6).with("H", 14.4);
+    let matches = match_elemental_analysis(&[PS, PP, PE], &measured, 0.2);
+    assert_eq!(matches.len(), 2, "{matches:?}");
+    assert!(matches[0].max_deviation <= matches[1].max_deviation);
+    let names: Vec<&str> = matches.iter().map(|m| m.bigsmiles.as_str()).collect();
+    assert!(names.contains(&PE) && names.contains(&PP), "{names:?}");
+}
+
+#[test]
+fn missing_element_counts_as_zero_percent() {
+    // N mesuré à 5 % : aucun hydrocarbure ne convient
+    let measured = ElementalComposition::new().with("C", 85.6).with("N", 5.0);
+    assert!(match_elemental_analysis(&[PE, PP], &measured, 1.0).is_empty());
+}
+
+#[test]
+fn invalid_candidates_are_skipped() {
+    let measured = ElementalComposition::new().with("C", 92.1).with("H", 7.8);
+    let matches = match_elemental_analysis(&["not bigsmiles {", "CCO", PS], &measured, 0.3);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].bigsmiles, PS);
+}
@@ -1,3 +1,6 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use polysim_core::{
     builder::EnsembleBuilder,
     distribution::{Flory, SchulzZimm},
@@ -22,6 +25,21 @@ fn ensemble_mn_mw_pdi_manual() {
     assert!((ensemble.pdi() - 1.25).abs() < 0.01);
 }
 
+#[test]
+fn ensemble_xn_xw_match_mn_mw_ratio() {
+    // Même motif (100 g/mol), sans groupes terminaux : Mi = 100·xi
+    // Xn = (1 + 3 + 6) / 3, Xw = (1² + 3² + 6²) / (1 + 3 + 6) = 4.6
+    let chains = vec![
+        PolymerChain::new("CC".to_string(), 1, 100.0),
+        PolymerChain::new("CCCCCC".to_string(), 3, 300.0),
+        PolymerChain::new("CCCCCCCCCCCC".to_string(), 6, 600.0),
+    ];
+    let ensemble = PolymerEnsemble::new(chains).unwrap();
+    assert!((ensemble.xn() - 10.0 / 3.0).abs() < 1e-9);
+    assert!((ensemble.xw() - 4.6).abs() < 1e-9);
+    assert!((ensemble.xw() / ensemble.xn() - ensemble.pdi()).abs() < 1e-9);
+}
+
 #[test]
 fn ensemble_len() {
     let chains = vec![
@@ -39,6 +57,127 @@ fn ensemble_empty_returns_error() {
     assert!(result.is_err());
 }
 
+#[test]
+fn ensemble_merge_concatenates_chains() {
+    let a = PolymerEnsemble::new(vec![PolymerChain::new("CC".to_string(), 1, 100.0)]).unwrap();
+    let b = PolymerEnsemble::new(vec![
+        PolymerChain::new("CCCC".to_string(), 2, 200.0),
+        PolymerChain::new("CCCCCC".to_string(), 3, 300.0),
+    ])
+    .unwrap();
+    let merged = a.merge(b);
+    let mns: Vec<f64> = merged.chains().iter().map(|c| c.mn).collect();
+    assert_eq!(mns, [100.0, 200.0, 300.0]);
+}
+
+#[test]
+fn ensemble_merge_bimodal_blend_broadens_distribution() {
+    // Deux lots étroits (PDI ≈ 1.05) à 2 000 et 20 000 g/mol
+    let batch = |mn: f64, seed: u64| {
+        EnsembleBuilder::new(parse("{[]CC[]}").unwrap(), SchulzZimm, mn, 1.05)
+            .num_chains(200)
+            .seed(seed)
+            .homopolymer_ensemble()
+            .unwrap()
+    };
+    let low = batch(2_000.0, 1);
+    let high = batch(20_000.0, 2);
+    let (low_pdi, high_pdi) = (low.pdi(), high.pdi());
+    let (low_mn, high_mn) = (low.mn(), high.mn());
+
+    let blend = low.merge(high);
+    assert_eq!(blend.len(), 400);
+    // Mélange équimolaire : Mn = moyenne des Mn
+    assert!((blend.mn() - (low_mn + high_mn) / 2.0).abs() < 1e-6);
+    assert!(blend.mn() > low_mn && blend.mn() < high_mn);
+    // Mw dominé par le lot lourd → PDI nettement plus large (≈ 1.7)
+    assert!(
+        blend.pdi() > low_pdi.max(high_pdi) + 0.5,
+        "PDI = {}",
+        blend.pdi()
+    );
+}
+
+#[test]
+fn ensemble_distribution_csv_bins_and_weight_fractions() {
+    let bs = parse("{[]CC[]}").unwrap();
+    let ensemble = EnsembleBuilder::new(bs, SchulzZimm, 5_000.0, 1.5)
+        .num_chains(300)
+        .seed(605)
+        .homopolymer_ensemble()
+        .unwrap();
+    let mut csv = Vec::new();
+    ensemble.write_distribution_csv(&mut csv, 500.0).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("bin_center,count,weight_fraction"));
+    let rows: Vec<(f64, usize, f64)> = lines
+        .map(|line| {
+            let cols: Vec<&str> = line.split(',').collect();
+            (
+                cols[0].parse().unwrap(),
+                cols[1].parse().unwrap(),
+                cols[2].parse().unwrap(),
+            )
+        })
+        .collect();
+
+    // Une ligne par intervalle de 500 g/mol entre la chaîne la plus légère et la plus lourde
+    let stats = EnsembleStats::from_ensemble(&ensemble);
+    let expected_bins =
+        (stats.mn_max / 500.0).floor() as usize - (stats.mn_min / 500.0).floor() as usize + 1;
+    assert_eq!(rows.len(), expected_bins);
+    assert_eq!(rows.iter().map(|r| r.1).sum::<usize>(), 300);
+    let total: f64 = rows.iter().map(|r| r.2).sum();
+    assert!((total - 1.0).abs() < 1e-4, "Σ w = {total}");
+    // Centres espacés d'une largeur d'intervalle
+    assert!(rows
+        .windows(2)
+        .all(|w| (w[1].0 - w[0].0 - 500.0).abs() < 1e-6));
+}
+
+#[test]
+fn ensemble_distribution_csv_rejects_non_positive_bin_width() {
+    let chains = vec![PolymerChain::new("CC".to_string(), 1, 100.0)];
+    let ensemble = PolymerEnsemble::new(chains).unwrap();
+    let err = ensemble
+        .write_distribution_csv(Vec::new(), 0.0)
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn ensemble_distribution_svg_has_one_rect_per_bin() {
+    let bs = parse("{[]CC[]}").unwrap();
+    let ensemble = EnsembleBuilder::new(bs, SchulzZimm, 5_000.0, 1.5)
+        .num_chains(300)
+        .seed(652)
+        .homopolymer_ensemble()
+        .unwrap();
+    let mut svg = Vec::new();
+    ensemble.write_distribution_svg(&mut svg, 500.0).unwrap();
+    let svg = String::from_utf8(svg).unwrap();
+
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    // Même découpage que l'export CSV
+    let mut csv = Vec::new();
+    ensemble.write_distribution_csv(&mut csv, 500.0).unwrap();
+    let bins = String::from_utf8(csv).unwrap().lines().count() - 1;
+    assert_eq!(svg.matches("<rect").count(), bins);
+}
+
+#[test]
+fn ensemble_distribution_svg_rejects_non_positive_bin_width() {
+    let chains = vec![PolymerChain::new("CC".to_string(), 1, 100.0)];
+    let ensemble = PolymerEnsemble::new(chains).unwrap();
+    let err = ensemble
+        .write_distribution_svg(Vec::new(), -1.0)
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
 #[test]
 fn ensemble_stats_display() {
     let chains = vec![
@@ -100,6 +239,26 @@ fn ensemble_builder_polyethylene_schulz_zimm() {
     assert!((pdi - 1.5).abs() < 0.5, "PDI = {pdi:.3}, expected ~1.5");
 }
 
+#[test]
+fn ensemble_builder_exact_mn_hits_target() {
+    // Sans correction, Mn fluctue de plusieurs % avec 100 chaînes
+    let build = |exact| {
+        EnsembleBuilder::new(parse("{[]CC[]}").unwrap(), SchulzZimm, 50_000.0, 1.8)
+            .seed(662)
+            .exact_mn(exact)
+            .homopolymer_ensemble()
+            .unwrap()
+    };
+    let exact = build(true);
+    assert!(
+        (exact.mn() - 50_000.0).abs() / 50_000.0 < 0.001,
+        "Mn = {:.1}",
+        exact.mn()
+    );
+    // Facteur d'échelle commun : Đ inchangée aux arrondis près
+    assert!((exact.pdi() - build(false).pdi()).abs() < 0.01);
+}
+
 #[test]
 fn ensemble_builder_seed_reproducibility() {
     let bs1 = parse("{[]CC[]}").unwrap();
@@ -121,6 +280,22 @@ fn ensemble_builder_seed_reproducibility() {
     assert_eq!(mns1, mns2, "Same seed should produce identical ensembles");
 }
 
+#[test]
+fn ensemble_builder_reports_progress_per_chain() {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let sink = Rc::clone(&calls);
+    let ensemble = EnsembleBuilder::new(parse("{[]CC[]}").unwrap(), Flory, 2805.0, 2.0)
+        .num_chains(20)
+        .seed(7)
+        .on_progress(move |done, total| sink.borrow_mut().push((done, total)))
+        .homopolymer_ensemble()
+        .unwrap();
+
+    let expected: Vec<(usize, usize)> = (1..=20).map(|i| (i, 20)).collect();
+    assert_eq!(*calls.borrow(), expected);
+    assert_eq!(ensemble.len(), 20);
+}
+
 #[test]
 fn ensemble_builder_no_stochastic_object() {
     let bs = parse("CC").unwrap();
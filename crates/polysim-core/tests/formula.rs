@@ -1,7 +1,11 @@
 use bigsmiles::parse;
 use polysim_core::{
     builder::{linear::LinearBuilder, BuildStrategy},
-    properties::formula::{molecular_formula, total_atom_count},
+    properties::formula::{
+        formula_diff, molecular_formula, molecular_formula_of_smiles, molecular_formula_with_order,
+        repeat_unit_formula, total_atom_count, try_molecular_formula, FormulaOrder,
+    },
+    PolySimError,
 };
 
 // ─── Helpers ────────────────────────────────────────────────────────────────
@@ -104,6 +108,44 @@ fn formula_hill_hydrogen_is_second() {
     );
 }
 
+// ─── Ordre des éléments — FormulaOrder ──────────────────────────────────────
+
+#[test]
+fn formula_order_default_is_hill() {
+    let chain = build("{[]CC(B(O)O)[]}", 2);
+    assert_eq!(FormulaOrder::default(), FormulaOrder::Hill);
+    assert_eq!(
+        molecular_formula_with_order(&chain, &FormulaOrder::Hill),
+        molecular_formula(&chain)
+    );
+}
+
+#[test]
+fn formula_order_alphabetical_puts_b_before_c() {
+    // Poly(acide vinylboronique) n=2 : C₄H₁₂B₂O₄ en Hill
+    let chain = build("{[]CC(B(O)O)[]}", 2);
+    assert_eq!(molecular_formula(&chain), "C4H12B2O4");
+    assert_eq!(
+        molecular_formula_with_order(&chain, &FormulaOrder::Alphabetical),
+        "B2C4H12O4"
+    );
+}
+
+#[test]
+fn formula_order_custom_respects_given_order() {
+    // Symboles listés d'abord, le reste par ordre alphabétique
+    let chain = build("{[]CC(Cl)[]}", 3);
+    let order = FormulaOrder::Custom(vec!["H", "Cl"]);
+    assert_eq!(molecular_formula_with_order(&chain, &order), "H11Cl3C6");
+}
+
+#[test]
+fn formula_order_custom_ignores_absent_and_repeated_symbols() {
+    let chain = build_pe(10);
+    let order = FormulaOrder::Custom(vec!["N", "H", "H"]);
+    assert_eq!(molecular_formula_with_order(&chain, &order), "H42C20");
+}
+
 // ─── Cohérence formule ↔ masse ───────────────────────────────────────────────
 
 #[test]
@@ -128,6 +170,70 @@ fn formula_pp_matches_mass_rule() {
     }
 }
 
+// ─── Repli sur la composition ───────────────────────────────────────────────
+
+#[test]
+fn molecular_formula_falls_back_to_composition_when_smiles_fails_to_parse() {
+    // PS n=4 → C32H34, même avec un SMILES de chaîne non analysable
+    let mut chain = build_ps(4);
+    chain.smiles.push('(');
+    assert_eq!(molecular_formula(&chain), "C32H34");
+}
+
+#[test]
+fn molecular_formula_fallback_handles_bracket_atoms_and_copolymers() {
+    let block = LinearBuilder::new(
+        parse("{[$]CC(C#N)[$],[$][Si](C)(C)O[$]}").unwrap(),
+        BuildStrategy::ByRepeatCount(6),
+    )
+    .block_copolymer(&[2, 4])
+    .unwrap();
+    for chain in [build("{[]C[C@@H](c1cc[nH]c1)[]}", 5), block] {
+        let expected = try_molecular_formula(&chain).unwrap();
+        let mut broken = chain.clone();
+        broken.smiles.push('(');
+        assert!(try_molecular_formula(&broken).is_err());
+        assert_eq!(molecular_formula(&broken), expected, "{}", chain.smiles);
+    }
+}
+
+// ─── formula_diff ───────────────────────────────────────────────────────────
+
+#[test]
+fn formula_diff_pe_n10_minus_n9_is_one_repeat_unit() {
+    // C20H42 − C18H38 = C2H4
+    let diff = formula_diff(&build_pe(10), &build_pe(9));
+    assert_eq!(diff.into_iter().collect::<Vec<_>>(), [("C", 2), ("H", 4)]);
+}
+
+#[test]
+fn formula_diff_is_signed_and_antisymmetric() {
+    let diff = formula_diff(&build_pe(9), &build_pe(10));
+    assert_eq!(diff.into_iter().collect::<Vec<_>>(), [("C", -2), ("H", -4)]);
+}
+
+#[test]
+fn formula_diff_of_identical_formulas_is_empty() {
+    // PE n=3 et PP n=2 : C6H14 tous les deux
+    assert!(formula_diff(&build_pe(3), &build_pp(2)).is_empty());
+}
+
+// ─── molecular_formula_of_smiles — fragments ────────────────────────────────
+
+#[test]
+fn molecular_formula_of_begin_block() {
+    // Bloc de début « CC » lu seul : éthane
+    assert_eq!(molecular_formula_of_smiles("CC").unwrap(), "C2H6");
+}
+
+#[test]
+fn molecular_formula_of_invalid_smiles_is_error() {
+    assert!(matches!(
+        molecular_formula_of_smiles("C("),
+        Err(PolySimError::InvalidRepeatUnit(_))
+    ));
+}
+
 // ─── total_atom_count — polyéthylène ────────────────────────────────────────
 
 #[test]
@@ -223,3 +329,36 @@ fn atom_count_equals_sum_of_formula_counts_pe() {
         );
     }
 }
+
+// ─── repeat_unit_formula — motif sans extrémités ────────────────────────────
+
+fn unit_formula(bigsmiles: &str) -> Result<String, PolySimError> {
+    repeat_unit_formula(&parse(bigsmiles).unwrap())
+}
+
+#[test]
+fn repeat_unit_formula_pe_pp_ps() {
+    assert_eq!(unit_formula("{[]CC[]}").unwrap(), "C2H4");
+    assert_eq!(unit_formula("{[]CC(C)[]}").unwrap(), "C3H6");
+    assert_eq!(unit_formula("{[]CC(c1ccccc1)[]}").unwrap(), "C8H8");
+}
+
+#[test]
+fn repeat_unit_formula_excludes_end_hydrogens() {
+    // La chaîne n = 1 porte deux H de bout de chaîne en plus : C2H6
+    assert_eq!(molecular_formula(&build_pe(1)), "C2H6");
+    assert_eq!(unit_formula("{[]CC[]}").unwrap(), "C2H4");
+}
+
+#[test]
+fn repeat_unit_formula_pmma_hill_order() {
+    assert_eq!(unit_formula("{[]CC(C)(C(=O)OC)[]}").unwrap(), "C5H8O2");
+}
+
+#[test]
+fn repeat_unit_formula_rejects_copolymers() {
+    assert!(matches!(
+        unit_formula("{[$]CC[$],[$]CC(C)[$]}"),
+        Err(PolySimError::RepeatUnitCount { got: 2, .. })
+    ));
+}
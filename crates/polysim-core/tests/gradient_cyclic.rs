@@ -1,6 +1,8 @@
 use polysim_core::{
     builder::{linear::LinearBuilder, BuildStrategy, GradientProfile},
-    parse, Architecture, PolySimError,
+    parse,
+    properties::formula::molecular_formula,
+    Architecture, PolySimError,
 };
 
 // ═══ Gradient copolymer ═════════════════════════════════════════════════════
@@ -106,6 +108,93 @@ fn cyclic_needs_exactly_1_unit() {
     assert!(matches!(result, Err(PolySimError::RepeatUnitCount { .. })));
 }
 
+#[test]
+fn cyclic_pe_n10_has_no_end_hydrogens() {
+    let ring = LinearBuilder::new(parse("{[]CC[]}").unwrap(), BuildStrategy::ByRepeatCount(10))
+        .cyclic()
+        .unwrap();
+    let linear = LinearBuilder::new(parse("{[]CC[]}").unwrap(), BuildStrategy::ByRepeatCount(10))
+        .homopolymer()
+        .unwrap();
+    assert_eq!(ring.smiles, format!("C1{}C1", "C".repeat(18)));
+    assert_eq!(molecular_formula(&ring), "C20H40");
+    assert_eq!(molecular_formula(&linear), "C20H42");
+    assert!((linear.mn - ring.mn - 2.0 * 1.008).abs() < 1e-3);
+}
+
+#[test]
+fn cyclic_closure_does_not_reuse_repeat_unit_ring_numbers() {
+    // Each phenyl ring gets its own number; 1 is kept for the macrocycle
+    let ring = LinearBuilder::new(
+        parse("{[]CC(c1ccccc1)[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(3),
+    )
+    .cyclic()
+    .unwrap();
+    assert_eq!(ring.smiles, "C1C(c2ccccc2)CC(c3ccccc3)CC1(c4ccccc4)");
+    assert_eq!(molecular_formula(&ring), "C24H24");
+}
+
+#[test]
+fn cyclic_closure_follows_ring_offset() {
+    let ring = LinearBuilder::new(parse("{[]CC[]}").unwrap(), BuildStrategy::ByRepeatCount(3))
+        .ring_offset(9)
+        .cyclic()
+        .unwrap();
+    assert_eq!(ring.smiles, "C%10CCCCC%10");
+}
+
+#[test]
+fn cyclic_rejects_rings_below_three_backbone_atoms() {
+    // n = 1 of CC would give C1C1 (closure duplicating the chain bond), and a
+    // one-atom unit would give C11 (atom bonded to itself)
+    for (bigsmiles, n) in [("{[]CC[]}", 1), ("{[]C[]}", 1), ("{[]C[]}", 2)] {
+        let result =
+            LinearBuilder::new(parse(bigsmiles).unwrap(), BuildStrategy::ByRepeatCount(n)).cyclic();
+        assert!(
+            matches!(result, Err(PolySimError::BuildStrategy(_))),
+            "{bigsmiles} n={n}: {result:?}"
+        );
+    }
+    // Three backbone atoms: cyclopropane
+    let ring = LinearBuilder::new(parse("{[]C[]}").unwrap(), BuildStrategy::ByRepeatCount(3))
+        .cyclic()
+        .unwrap();
+    assert_eq!(ring.smiles, "C1CC1");
+}
+
+#[test]
+fn cyclic_single_unit_checks_its_own_descriptors() {
+    // n = 1 : la fermeture relie la queue de l'unité à sa propre tête
+    let result = LinearBuilder::new(
+        parse("{[][<]CCC[<][]}").unwrap(),
+        BuildStrategy::ByRepeatCount(1),
+    )
+    .cyclic();
+    assert!(result.is_err(), "{result:?}");
+
+    let ring = LinearBuilder::new(
+        parse("{[$]=CCC=[$]}").unwrap(),
+        BuildStrategy::ByRepeatCount(1),
+    )
+    .cyclic()
+    .unwrap();
+    assert_eq!(ring.smiles, "C1CC=1");
+    assert_eq!(molecular_formula(&ring), "C3H4");
+}
+
+#[test]
+fn cyclic_homopolymer_matches_cyclic() {
+    let builder = LinearBuilder::new(
+        parse("{[]CC(C)[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(5),
+    );
+    assert_eq!(
+        builder.cyclic_homopolymer().unwrap(),
+        builder.cyclic().unwrap()
+    );
+}
+
 // ═══ Cyclic with two-letter atoms ════════════════════════════════════════════
 
 #[test]
@@ -0,0 +1,53 @@
+mod common;
+
+use common::homopolymer;
+use polysim_core::{
+    properties::groups::{decompose, GroupName},
+    PolymerChain,
+};
+
+fn count(groups: &[(GroupName, usize)], name: GroupName) -> usize {
+    groups
+        .iter()
+        .find(|&&(group, _)| group == name)
+        .map_or(0, |&(_, n)| n)
+}
+
+// ── decompose ────────────────────────────────────────────────────────────────
+
+#[test]
+fn decompose_pmma_ester_methyl_backbone() {
+    // Motif –CH2–C(CH3)(C(=O)OCH3)– : 1 CH2, 1 C quaternaire, 2 CH3, 1 C=O, 1 –O–
+    // Extrémités : le premier CH2 devient CH3, le dernier C quaternaire un CH
+    let n = 10;
+    let groups = decompose(&homopolymer("{[]CC(C)(C(=O)OC)[]}", n));
+    assert_eq!(count(&groups, GroupName::Carbonyl), n);
+    assert_eq!(count(&groups, GroupName::Ether), n);
+    assert_eq!(count(&groups, GroupName::Methyl), 2 * n + 1);
+    assert_eq!(count(&groups, GroupName::Methylene), n - 1);
+    assert_eq!(count(&groups, GroupName::QuaternaryCarbon), n - 1);
+    assert_eq!(count(&groups, GroupName::Methine), 1);
+    assert_eq!(groups.len(), 6, "{groups:?}");
+}
+
+#[test]
+fn decompose_is_sorted_and_skips_empty_groups() {
+    let groups = decompose(&homopolymer("{[]CC(c1ccccc1)[]}", 5));
+    assert!(groups.windows(2).all(|w| w[0].0 < w[1].0), "{groups:?}");
+    assert!(groups.iter().all(|&(_, n)| n > 0));
+    assert_eq!(count(&groups, GroupName::AromaticCH), 25);
+    assert_eq!(count(&groups, GroupName::AromaticC), 5);
+}
+
+#[test]
+fn decompose_ignores_wildcards() {
+    let chain = PolymerChain::new("*CC*".to_string(), 1, 0.0);
+    assert_eq!(decompose(&chain), vec![(GroupName::Methylene, 2)]);
+}
+
+#[test]
+fn group_labels() {
+    assert_eq!(GroupName::Methyl.label(), "-CH3");
+    assert_eq!(GroupName::Carbonyl.label(), ">C=O");
+    assert_eq!(GroupName::Ether.label(), "-O-");
+}
@@ -1,8 +1,15 @@
-use bigsmiles::parse;
+use bigsmiles::{parse, BigSmiles, BigSmilesSegment};
 use polysim_core::{
-    builder::{linear::LinearBuilder, BuildStrategy},
+    builder::{
+        linear::LinearBuilder, max_ring_number_for_chain, BuildStrategy, DoubleBondGeometry,
+    },
     error::PolySimError,
+    properties::{
+        formula::molecular_formula, groups::decompose, molecular_weight::average_mass,
+        thermal::tg_van_krevelen,
+    },
 };
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 // ── ByRepeatCount — nominal cases ────────────────────────────────────────────
 
@@ -36,6 +43,24 @@ fn polypropylene_n2() {
     assert_eq!(chain.repeat_count, 2);
 }
 
+#[test]
+fn homopolymer_sequence_string_is_all_a() {
+    let bs = parse("{[]CC[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(4))
+        .homopolymer()
+        .unwrap();
+    assert_eq!(chain.sequence_string(), "AAAA");
+}
+
+#[test]
+fn homopolymer_has_one_species() {
+    let bs = parse("{[]CC[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(4))
+        .homopolymer()
+        .unwrap();
+    assert_eq!(chain.species_count(), 1);
+}
+
 // ── Ring renumbering ─────────────────────────────────────────────────────────
 
 #[test]
@@ -69,6 +94,41 @@ fn bracket_atom_digits_not_renumbered() {
     assert_eq!(chain.smiles, "[13C][13C][13C][13C]");
 }
 
+#[test]
+fn bond_prefixed_ring_closures_renumbered_as_pair() {
+    // Le symbole de liaison précède le chiffre : '=' puis '1' à l'ouverture et à la fermeture
+    let bs = parse("{[]C=1CCCCC=1[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3))
+        .homopolymer()
+        .unwrap();
+    assert_eq!(chain.smiles, "C=1CCCCC=1C=2CCCCC=2C=3CCCCC=3");
+    assert!(opensmiles::parse(&chain.smiles).is_ok(), "{}", chain.smiles);
+    assert_eq!(molecular_formula(&chain), "C18H26");
+}
+
+#[test]
+fn bond_prefixed_two_digit_ring_closures_renumbered_as_pair() {
+    let unit = "CC(C-%10CCC-%10)";
+    let bs = parse(&format!("{{[]{unit}[]}}")).unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(12))
+        .homopolymer()
+        .unwrap();
+    assert!(chain.smiles.starts_with("CC(C-%10CCC-%10)CC(C-%20CCC-%20)"));
+    assert_eq!(chain.smiles, reference_linear_smiles(unit, 12));
+    assert!(opensmiles::parse(&chain.smiles).is_ok(), "{}", chain.smiles);
+}
+
+#[test]
+fn mixed_bond_prefixed_and_plain_ring_closures() {
+    // Anneau aromatique explicite + cycle à liaison '-' explicite dans la même unité
+    let bs = parse("{[]CC(c1ccccc1)C-2CC-2[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(2))
+        .homopolymer()
+        .unwrap();
+    assert_eq!(chain.smiles, "CC(c1ccccc1)C-2CC-2CC(c3ccccc3)C-4CC-4");
+    assert!(opensmiles::parse(&chain.smiles).is_ok(), "{}", chain.smiles);
+}
+
 // ── Ring number cycling (SMILES allows reuse of a closed ring number) ────────
 
 #[test]
@@ -89,6 +149,733 @@ fn polystyrene_ring_cycling_n100() {
     );
 }
 
+/// Straightforward per-copy renumbering, used as the reference for the builder.
+fn reference_linear_smiles(unit: &str, n: usize) -> String {
+    let ring_numbers = |s: &str| -> Vec<u32> {
+        let mut numbers = Vec::new();
+        let mut in_bracket = false;
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '[' => in_bracket = true,
+                ']' => in_bracket = false,
+                _ if in_bracket => {}
+                '%' => {
+                    let d: String = chars.by_ref().take(2).collect();
+                    numbers.push(d.parse().unwrap());
+                }
+                c if c.is_ascii_digit() => numbers.push(c.to_digit(10).unwrap()),
+                _ => {}
+            }
+        }
+        numbers
+    };
+    let max_ring = ring_numbers(unit).into_iter().max().unwrap_or(0);
+    let mut out = String::new();
+    for i in 0..n {
+        let offset = if max_ring == 0 {
+            0
+        } else {
+            (i % (99 / max_ring as usize)) as u32 * max_ring
+        };
+        let mut in_bracket = false;
+        let mut chars = unit.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '[' | ']' => {
+                    in_bracket = c == '[';
+                    out.push(c);
+                }
+                _ if in_bracket => out.push(c),
+                '%' if offset == 0 => out.push(c),
+                '%' => {
+                    let d: String = chars.by_ref().take(2).collect();
+                    out.push_str(&format!("%{:02}", d.parse::<u32>().unwrap() + offset));
+                }
+                c if c.is_ascii_digit() => match c.to_digit(10).unwrap() + offset {
+                    r if r <= 9 => out.push_str(&r.to_string()),
+                    r => out.push_str(&format!("%{r:02}")),
+                },
+                _ => out.push(c),
+            }
+        }
+    }
+    out
+}
+
+/// Random repeat unit made of acyclic fragments and small rings numbered 1..=33.
+fn random_ring_unit(rng: &mut StdRng) -> String {
+    const ACYCLIC: [&str; 4] = ["C", "CC(C)", "C(=O)O", "[13CH2]"];
+    let mut unit = String::new();
+    for _ in 0..rng.random_range(1..=4) {
+        if rng.random_bool(0.5) {
+            unit.push_str(ACYCLIC[rng.random_range(0..ACYCLIC.len())]);
+        } else {
+            let r: u32 = rng.random_range(1..=33);
+            let label = if r <= 9 && rng.random_bool(0.7) {
+                r.to_string()
+            } else {
+                format!("%{r:02}")
+            };
+            unit.push_str(&format!("C{label}CCC{label}"));
+        }
+    }
+    unit
+}
+
+#[test]
+fn ring_renumbering_matches_reference_on_random_units() {
+    let mut rng = StdRng::seed_from_u64(589);
+    for _ in 0..200 {
+        let unit = random_ring_unit(&mut rng);
+        let n = rng.random_range(1..=250);
+        let bs = parse(&format!("{{[]{unit}[]}}")).unwrap();
+        let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(n))
+            .homopolymer()
+            .unwrap();
+        assert_eq!(
+            chain.smiles,
+            reference_linear_smiles(&unit, n),
+            "unit={unit}, n={n}"
+        );
+    }
+}
+
+#[test]
+fn max_ring_number_for_chain_predicts_recycling() {
+    // PS n = 100 : les numéros 1..=99 sont tous utilisés puis recyclés
+    let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+    assert_eq!(max_ring_number_for_chain(&bs, 100), 99);
+    assert_eq!(max_ring_number_for_chain(&bs, 12), 12);
+    // Motif à deux cycles : 49 copies tiennent dans 1..=98
+    let bs = parse("{[]C1CC1C2CC2[]}").unwrap();
+    assert_eq!(max_ring_number_for_chain(&bs, 100), 98);
+    assert_eq!(
+        max_ring_number_for_chain(&parse("{[]CC[]}").unwrap(), 100),
+        0
+    );
+}
+
+#[test]
+fn max_ring_number_for_chain_includes_end_segments() {
+    // Le groupe terminal est décalé après le corps : c4ccccc4 pour n = 3
+    let bs = parse("c1ccccc1{[]CC(c1ccccc1)[]}").unwrap();
+    assert_eq!(max_ring_number_for_chain(&bs, 3), 4);
+    let chain = LinearBuilder::new(bs.clone(), BuildStrategy::ByRepeatCount(3))
+        .homopolymer()
+        .unwrap();
+    assert!(chain.smiles.starts_with("c4ccccc4"), "{}", chain.smiles);
+    // Corps saturé : le groupe garde son propre numéro
+    assert_eq!(max_ring_number_for_chain(&bs, 100), 99);
+}
+
+#[test]
+fn max_ring_number_for_chain_matches_reference_on_random_units() {
+    let mut rng = StdRng::seed_from_u64(638);
+    for _ in 0..100 {
+        let unit = random_ring_unit(&mut rng);
+        let n = rng.random_range(1..=250);
+        let reference = reference_linear_smiles(&unit, n);
+        let used = (1..=99u32)
+            .rev()
+            .find(|r| {
+                let label = if *r <= 9 {
+                    r.to_string()
+                } else {
+                    format!("%{r:02}")
+                };
+                reference.contains(&format!("C{label}C"))
+            })
+            .unwrap_or(0);
+        let bs = parse(&format!("{{[]{unit}[]}}")).unwrap();
+        assert_eq!(
+            max_ring_number_for_chain(&bs, n),
+            used,
+            "unit={unit}, n={n}"
+        );
+    }
+}
+
+/// Ladder repeat unit holding `rings` ring closures open at the same time:
+/// atoms a1..ak open rings 1..k, a spacer CH2, then bk..b1 close them.
+fn ladder_unit(rings: u32) -> String {
+    let label = |r: u32| {
+        if r <= 9 {
+            r.to_string()
+        } else {
+            format!("%{r:02}")
+        }
+    };
+    let opens: String = (1..=rings).map(|r| format!("C{}", label(r))).collect();
+    let closes: String = (1..=rings)
+        .rev()
+        .map(|r| format!("C{}", label(r)))
+        .collect();
+    format!("{opens}C{closes}")
+}
+
+#[test]
+fn fifty_ring_unit_reuses_numbers_in_every_copy() {
+    // 50 cycles ouverts simultanément : cycle_length = 99 / 50 = 1, chaque copie
+    // referme ses cycles avant la suivante et réutilise donc 1..=50
+    let unit = ladder_unit(50);
+    let bs = parse(&format!("{{[]{unit}[]}}")).unwrap();
+    let chain = LinearBuilder::new(bs.clone(), BuildStrategy::ByRepeatCount(3))
+        .homopolymer()
+        .unwrap();
+    assert_eq!(chain.smiles, unit.repeat(3));
+    assert!(!chain.smiles.contains("%51"));
+    assert_eq!(max_ring_number_for_chain(&bs, 3), 50);
+    assert!(opensmiles::parse(&chain.smiles).is_ok());
+    // 101 C par motif : a1..a50, l'espaceur CH2, b50..b1 (un H chacun)
+    assert_eq!(molecular_formula(&chain), "C303H308");
+}
+
+#[test]
+fn fifty_ring_unit_in_copolymer_reuses_numbers() {
+    let unit = ladder_unit(50);
+    let bs = parse(&format!("{{[$]{unit}[$],[$]CC(c1ccccc1)[$]}}")).unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(4))
+        .alternating_copolymer()
+        .unwrap();
+    assert!(!chain.smiles.contains("%51"));
+    assert!(opensmiles::parse(&chain.smiles).is_ok(), "{}", chain.smiles);
+}
+
+#[test]
+fn fifty_ring_unit_with_ring_offset_overflows() {
+    // 50 cycles au-dessus de 60 numéros réservés : 39 disponibles seulement
+    let bs = parse(&format!("{{[]{}[]}}", ladder_unit(50))).unwrap();
+    let result = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3))
+        .ring_offset(60)
+        .homopolymer();
+    assert!(
+        matches!(
+            result,
+            Err(PolySimError::RingNumberOverflow {
+                max_ring: 50,
+                max_supported: 39
+            })
+        ),
+        "got: {result:?}"
+    );
+}
+
+// ── Dangling bonds capped with hydrogen ──────────────────────────────────────
+
+#[test]
+fn nondirectional_descriptors_give_h_capped_chain() {
+    for n in 1..=4 {
+        let bs = parse("{[$]CC[$]}").unwrap();
+        let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(n))
+            .homopolymer()
+            .unwrap();
+        assert_eq!(chain.smiles, "CC".repeat(n));
+        assert!(opensmiles::parse(&chain.smiles).is_ok(), "{}", chain.smiles);
+        // Saturated alkane CnH2n+2: no open valence left
+        assert_eq!(
+            molecular_formula(&chain),
+            format!("C{}H{}", 2 * n, 4 * n + 2)
+        );
+    }
+}
+
+#[test]
+fn directional_descriptors_give_h_capped_chain() {
+    let bs = parse("{[<]CC(C)[>]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3))
+        .homopolymer()
+        .unwrap();
+    assert!(opensmiles::parse(&chain.smiles).is_ok(), "{}", chain.smiles);
+    assert_eq!(molecular_formula(&chain), "C9H20");
+}
+
+#[test]
+fn inter_unit_bond_order_is_kept_between_copies() {
+    // Seuls la tête de la première unité et la queue de la dernière sont coiffées
+    let builder = |n| {
+        LinearBuilder::new(
+            parse("{[$]=CC=[$]}").unwrap(),
+            BuildStrategy::ByRepeatCount(n),
+        )
+    };
+    let chain = builder(3).homopolymer().unwrap();
+    assert_eq!(chain.smiles, "CC=CC=CC");
+    assert_eq!(molecular_formula(&chain), "C6H10");
+    assert_eq!(builder(1).homopolymer().unwrap().smiles, "CC");
+
+    let ring = builder(3).cyclic().unwrap();
+    assert!(opensmiles::parse(&ring.smiles).is_ok(), "{}", ring.smiles);
+    assert_eq!(molecular_formula(&ring), "C6H6");
+}
+
+#[test]
+fn mismatched_inter_unit_bonds_are_rejected() {
+    let err = LinearBuilder::new(
+        parse("{[$]=CC#[$]}").unwrap(),
+        BuildStrategy::ByRepeatCount(2),
+    )
+    .homopolymer()
+    .unwrap_err();
+    assert!(matches!(err, PolySimError::InvalidRepeatUnit(_)), "{err}");
+}
+
+#[test]
+fn dangling_bond_inside_repeat_unit_is_rejected() {
+    // Descripteur dans une branche : la liaison ne peut pas être coiffée en silence
+    let err = LinearBuilder::new(
+        parse("{[$]CC([$])C[$]}").unwrap(),
+        BuildStrategy::ByRepeatCount(2),
+    )
+    .homopolymer()
+    .unwrap_err();
+    assert!(matches!(err, PolySimError::InvalidRepeatUnit(_)), "{err}");
+}
+
+// ── Explicit end groups ──────────────────────────────────────────────────────
+
+#[test]
+fn sec_butyl_head_shifts_mass_and_formula() {
+    // Anionic PS: sec-BuLi initiator adds C4H8 (C4H9 replaces one terminal H)
+    let plain = LinearBuilder::new(
+        parse("{[]CC(c1ccccc1)[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(10),
+    )
+    .homopolymer()
+    .unwrap();
+    let capped = LinearBuilder::new(
+        parse("{[]CC(c1ccccc1)[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(10),
+    )
+    .with_end_groups("CCC(C)", "")
+    .homopolymer()
+    .unwrap();
+
+    assert!(capped.smiles.starts_with("CCC(C)CC(c1ccccc1)"));
+    assert_eq!(capped.repeat_count, 10);
+    assert_eq!(molecular_formula(&plain), "C80H82");
+    assert_eq!(molecular_formula(&capped), "C84H90");
+    let c4h8 = 4.0 * 12.011 + 8.0 * 1.008;
+    assert!((capped.mn - plain.mn - c4h8).abs() < 0.01);
+}
+
+#[test]
+fn explicit_end_groups_replace_bigsmiles_segments() {
+    let bs = parse("CC{[]CC[]}CC").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(2))
+        .with_end_groups("O", "N")
+        .homopolymer()
+        .unwrap();
+    assert_eq!(chain.smiles, "OCCCCN");
+}
+
+#[test]
+fn end_group_ring_numbers_do_not_collide_with_body() {
+    let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(2))
+        .with_end_groups("c1ccccc1", "C1CC1")
+        .homopolymer()
+        .unwrap();
+    assert_eq!(chain.smiles, "c3ccccc3CC(c1ccccc1)CC(c2ccccc2)C3CC3");
+}
+
+#[test]
+fn ring_offset_keeps_low_numbers_for_end_groups() {
+    // Amorceur pyrène (4 cycles) : les motifs PS commencent au-delà de 4
+    let pyrene = "c1cc2ccc3cccc4ccc(c2c34)c1";
+    let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3))
+        .with_end_groups(pyrene, "")
+        .ring_offset(4)
+        .homopolymer()
+        .unwrap();
+    assert_eq!(
+        chain.smiles,
+        format!("{pyrene}CC(c5ccccc5)CC(c6ccccc6)CC(c7ccccc7)")
+    );
+    assert!(opensmiles::parse(&chain.smiles).is_ok());
+    // C16H9 + 3 × C8H8 + H
+    assert_eq!(molecular_formula(&chain), "C40H34");
+}
+
+#[test]
+fn ring_offset_applies_to_copolymers() {
+    let bs = parse("{[]CC(c1ccccc1)[],[]CC(C)[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(4))
+        .with_end_groups("C1CC1", "")
+        .ring_offset(1)
+        .alternating_copolymer()
+        .unwrap();
+    assert_eq!(chain.smiles, "C1CC1CC(c2ccccc2)CC(C)CC(c4ccccc4)CC(C)");
+}
+
+#[test]
+fn ring_offset_cycles_above_reserved_numbers() {
+    // 97 numéros disponibles (3..=99) : le motif 98 réutilise le numéro 3
+    let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(98))
+        .ring_offset(2)
+        .homopolymer()
+        .unwrap();
+    assert!(chain.smiles.starts_with("CC(c3ccccc3)"));
+    assert!(chain.smiles.ends_with("CC(c%99ccccc%99)CC(c3ccccc3)"));
+    assert!(opensmiles::parse(&chain.smiles).is_ok());
+}
+
+#[test]
+fn ring_offset_too_large_is_error() {
+    let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+    let result = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(2))
+        .ring_offset(99)
+        .homopolymer();
+    assert!(
+        matches!(
+            result,
+            Err(PolySimError::RingNumberOverflow {
+                max_ring: 1,
+                max_supported: 0
+            })
+        ),
+        "got: {result:?}"
+    );
+}
+
+#[test]
+fn invalid_end_group_is_error() {
+    let bs = parse("{[]CC[]}").unwrap();
+    let result = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3))
+        .with_end_groups("C(", "")
+        .homopolymer();
+    assert!(
+        matches!(result, Err(PolySimError::BuildStrategy(_))),
+        "got: {result:?}"
+    );
+}
+
+#[test]
+fn target_mn_accounts_for_end_group_mass() {
+    // HO–(CH₂CH₂)₅–OH : 5 × 28.054 + 34.015 ≈ 174.28 g/mol
+    let target = 174.28;
+    let capped = LinearBuilder::new(
+        parse("{[]CC[]}").unwrap(),
+        BuildStrategy::ByTargetMn(target),
+    )
+    .with_end_groups("O", "O")
+    .homopolymer()
+    .unwrap();
+    assert_eq!(capped.repeat_count, 5);
+    assert!((capped.mn - target).abs() < 0.05, "Mn = {}", capped.mn);
+
+    // Sans groupes terminaux, la même cible donne une unité de plus
+    let plain = LinearBuilder::new(
+        parse("{[]CC[]}").unwrap(),
+        BuildStrategy::ByTargetMn(target),
+    )
+    .homopolymer()
+    .unwrap();
+    assert_eq!(plain.repeat_count, 6);
+}
+
+#[test]
+fn target_mn_accounts_for_bigsmiles_end_segments() {
+    // Segments CC…CC : 56.1 g/mol de plus qu'un PE nu de même n
+    let chain = LinearBuilder::new(
+        parse("CC{[]CC[]}CC").unwrap(),
+        BuildStrategy::ByTargetMn(28.054 * 10.0 + 58.12),
+    )
+    .homopolymer()
+    .unwrap();
+    assert_eq!(chain.repeat_count, 10);
+}
+
+// ── Counter-ions ─────────────────────────────────────────────────────────────
+
+const PSS: &str = "{[]CC(c1ccc(cc1)S(=O)(=O)[O-])[]}";
+
+#[test]
+fn sodium_counterions_add_one_sodium_mass_per_unit() {
+    // PSS : un Na⁺ par groupe sulfonate, soit 22,990 g/mol par unité
+    let build = |counterions| {
+        LinearBuilder::new(parse(PSS).unwrap(), BuildStrategy::ByRepeatCount(10))
+            .counterions(counterions)
+            .homopolymer()
+            .unwrap()
+    };
+    let (bare, salt) = (build(false), build(true));
+    assert_eq!(bare.counterion_count, 0);
+    assert_eq!(salt.counterion_count, 10);
+    assert!((salt.mn - bare.mn - 10.0 * 22.990).abs() < 0.01);
+    assert_eq!(molecular_formula(&bare), "C80H72O30S10");
+    assert_eq!(molecular_formula(&salt), "C80H72Na10O30S10");
+    assert!(opensmiles::parse(&salt.smiles).is_ok());
+}
+
+#[test]
+fn target_mn_includes_counterions() {
+    // Poly(acrylate de sodium) : n est calibré sur la masse du sel, Na⁺ compris
+    let target = 2000.0;
+    let chain = LinearBuilder::new(
+        parse("{[]CC(C(=O)[O-])[]}").unwrap(),
+        BuildStrategy::ByTargetMn(target),
+    )
+    .counterions(true)
+    .homopolymer()
+    .unwrap();
+    let unit = 71.055 + 22.990;
+    assert!((chain.mn - target).abs() <= unit / 2.0, "Mn = {}", chain.mn);
+    assert_eq!(chain.counterion_count, chain.repeat_count);
+}
+
+#[test]
+fn cationic_chain_gets_chloride_counterions() {
+    // Polyvinylpyridinium (N-méthylé) : un Cl⁻ par unité
+    let chain = LinearBuilder::new(
+        parse("{[]CC(c1cc[n+](C)cc1)[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(4),
+    )
+    .counterions(true)
+    .homopolymer()
+    .unwrap();
+    assert_eq!(chain.counterion_count, 4);
+    assert!(chain.smiles.ends_with(".[Cl-].[Cl-].[Cl-].[Cl-]"));
+}
+
+#[test]
+fn neutral_chain_gets_no_counterions() {
+    let chain = LinearBuilder::new(
+        parse("{[]CC(c1ccccc1)[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(5),
+    )
+    .counterions(true)
+    .homopolymer()
+    .unwrap();
+    assert_eq!(chain.counterion_count, 0);
+    assert!(!chain.smiles.contains('.'));
+}
+
+#[test]
+fn counterions_follow_copolymer_charge() {
+    // Alternance styrène / styrène sulfonate : un Na⁺ pour deux unités
+    let chain = LinearBuilder::new(
+        parse("{[]CC(c1ccccc1)[],[]CC(c1ccc(cc1)S(=O)(=O)[O-])[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(6),
+    )
+    .counterions(true)
+    .alternating_copolymer()
+    .unwrap();
+    assert_eq!(chain.counterion_count, 3);
+}
+
+// ── Kekulé rings written as aromatic ─────────────────────────────────────────
+
+fn build_n(bigsmiles: &str, n: usize) -> polysim_core::PolymerChain {
+    LinearBuilder::new(parse(bigsmiles).unwrap(), BuildStrategy::ByRepeatCount(n))
+        .homopolymer()
+        .unwrap()
+}
+
+#[test]
+fn kekule_and_aromatic_benzene_pendants_are_identical() {
+    let kekule = build_n("{[]CC(C1=CC=CC=C1)[]}", 5);
+    let aromatic = build_n("{[]CC(c1ccccc1)[]}", 5);
+    assert_eq!(kekule.smiles, aromatic.smiles);
+    assert_eq!(molecular_formula(&kekule), molecular_formula(&aromatic));
+    assert_eq!(average_mass(&kekule), average_mass(&aromatic));
+    // Les contributions de groupes voient des CH aromatiques, pas des C=C
+    assert_eq!(decompose(&kekule), decompose(&aromatic));
+    assert_eq!(tg_van_krevelen(&kekule), tg_van_krevelen(&aromatic));
+}
+
+#[test]
+fn kekule_rings_of_various_units_are_aromatized() {
+    for (kekule, aromatic) in [
+        // Pyridine
+        ("{[]CC(C1=CC=NC=C1)[]}", "{[]CC(c1ccncc1)[]}"),
+        // Naphtalène (cycles fusionnés)
+        ("{[]CC(C1=CC=C2C=CC=CC2=C1)[]}", "{[]CC(c1ccc2ccccc2c1)[]}"),
+        // Biphényle : la liaison entre cycles reste simple
+        (
+            "{[]CC(C1=CC=C(C=C1)C2=CC=CC=C2)[]}",
+            "{[]CC(c1ccc(cc1)-c2ccccc2)[]}",
+        ),
+        // Cycle dans le squelette (PET)
+        (
+            "{[]OCCOC(=O)C1=CC=C(C=C1)C(=O)[]}",
+            "{[]OCCOC(=O)c1ccc(cc1)C(=O)[]}",
+        ),
+    ] {
+        let (a, b) = (build_n(kekule, 3), build_n(aromatic, 3));
+        assert_eq!(a.canonical_smiles(), b.canonical_smiles(), "{kekule}");
+        assert_eq!(decompose(&a), decompose(&b), "{kekule}");
+    }
+}
+
+#[test]
+fn biphenyl_link_is_written_as_single_bond() {
+    let chain = build_n("{[]CC(C1=CC=C(C=C1)C2=CC=CC=C2)[]}", 1);
+    assert_eq!(chain.smiles, "CC(c1ccc(cc1)-c2ccccc2)");
+}
+
+#[test]
+fn non_aromatic_rings_are_unchanged() {
+    // Cyclohexène, quinone (C=O exocycliques), cycle à 5 chaînons
+    for unit in ["C1CC=CCC1", "C1=CC(=O)C=CC1=O", "C1=CC=CC1"] {
+        let chain = build_n(&format!("{{[]CC({unit})[]}}"), 1);
+        assert_eq!(chain.smiles, format!("CC({unit})"));
+    }
+}
+
+#[test]
+fn kekule_copolymer_composition_is_aromatized() {
+    let bs = parse("{[$]CC[$],[$]CC(C1=CC=CC=C1)[$]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(4))
+        .alternating_copolymer()
+        .unwrap();
+    assert_eq!(chain.composition[1].smiles, "CC(c1ccccc1)");
+    assert!(!chain.smiles.contains('='), "{}", chain.smiles);
+}
+
+// ── Vinyl-terminated chains ──────────────────────────────────────────────────
+
+#[test]
+fn vinyl_terminated_chain_ends_in_double_bond() {
+    let bs = parse("{[]CC[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(5))
+        .vinyl_terminated()
+        .homopolymer()
+        .unwrap();
+    assert!(chain.smiles.ends_with("=C"), "{}", chain.smiles);
+    // C₁₁H₂₂ : une insaturation, deux H de moins que l'undécane C₁₁H₂₄
+    assert_eq!(molecular_formula(&chain), "C11H22");
+}
+
+#[test]
+fn vinyl_end_replaces_tail_group() {
+    let bs = parse("{[]CC(C)[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(2))
+        .with_end_groups("", "CCO")
+        .vinyl_terminated()
+        .homopolymer()
+        .unwrap();
+    // Bout vinylidène du polypropylène, head inchangé
+    assert_eq!(chain.smiles, "CC(C)CC(C)=C");
+    assert_eq!(molecular_formula(&chain), "C7H14");
+}
+
+#[test]
+fn vinyl_terminated_target_mn_includes_vinyl_group() {
+    let bs = parse("{[]CC[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByTargetMn(2819.4))
+        .vinyl_terminated()
+        .homopolymer()
+        .unwrap();
+    // C₂₀₁H₄₀₂ = 100 motifs + =CH₂
+    assert_eq!(chain.repeat_count, 100);
+    assert!((chain.mn - 2819.4).abs() < 0.1, "Mn = {}", chain.mn);
+}
+
+#[test]
+fn vinyl_end_on_aromatic_atom_is_rejected() {
+    // Poly(p-phénylène) : le dernier atome du squelette est aromatique
+    let bs = parse("{[]c1ccc(cc1)[]}").unwrap();
+    let err = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3))
+        .vinyl_terminated()
+        .homopolymer()
+        .unwrap_err();
+    assert!(matches!(err, PolySimError::BuildStrategy(_)), "{err}");
+}
+
+// ── Wildcard atoms ───────────────────────────────────────────────────────────
+
+#[test]
+fn wildcard_end_groups_are_detected() {
+    let bs = parse("*{[]CC[]}*").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3))
+        .homopolymer()
+        .unwrap();
+    assert!(chain.has_wildcards());
+}
+
+#[test]
+fn plain_chain_has_no_wildcards() {
+    let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3))
+        .homopolymer()
+        .unwrap();
+    assert!(!chain.has_wildcards());
+}
+
+// ── Cis/trans geometry of backbone double bonds ──────────────────────────────
+
+fn polybutadiene(bigsmiles: &str, n: usize, geometry: DoubleBondGeometry, seed: u64) -> String {
+    LinearBuilder::new(parse(bigsmiles).unwrap(), BuildStrategy::ByRepeatCount(n))
+        .homopolymer_with_geometry(geometry, seed)
+        .unwrap()
+        .smiles
+}
+
+#[test]
+fn trans_polybutadiene_has_directional_bonds() {
+    let smiles = polybutadiene("{[]CC=CC[]}", 3, DoubleBondGeometry::Trans, 0);
+    assert_eq!(smiles, "C/C=C/CC/C=C/CC/C=C/C");
+    assert!(opensmiles::parse(&smiles).is_ok(), "{smiles}");
+}
+
+#[test]
+fn cis_polybutadiene_has_directional_bonds() {
+    let smiles = polybutadiene("{[]CC=CC[]}", 2, DoubleBondGeometry::Cis, 0);
+    assert_eq!(smiles, "C/C=C\\CC/C=C\\C");
+    assert!(opensmiles::parse(&smiles).is_ok(), "{smiles}");
+}
+
+#[test]
+fn directional_repeat_unit_is_rewritten_to_requested_geometry() {
+    // Motif écrit trans : la géométrie demandée l'emporte
+    let smiles = polybutadiene("{[]C/C=C/C[]}", 2, DoubleBondGeometry::Cis, 0);
+    assert_eq!(smiles, "C/C=C\\CC/C=C\\C");
+}
+
+#[test]
+fn substituted_double_bond_keeps_its_branch() {
+    // Polyisoprène 1,4 : le méthyle reste en branche
+    let smiles = polybutadiene("{[]CC(C)=CC[]}", 2, DoubleBondGeometry::Cis, 0);
+    assert_eq!(smiles, "C/C(C)=C\\CC/C(C)=C\\C");
+}
+
+#[test]
+fn random_geometry_is_reproducible_and_mixed() {
+    let a = polybutadiene("{[]CC=CC[]}", 40, DoubleBondGeometry::Random, 667);
+    let b = polybutadiene("{[]CC=CC[]}", 40, DoubleBondGeometry::Random, 667);
+    assert_eq!(a, b);
+    assert!(a.contains("C=C/") && a.contains("C=C\\"), "{a}");
+}
+
+#[test]
+fn geometry_does_not_change_mass_or_formula() {
+    let bs = parse("{[]CC=CC[]}").unwrap();
+    let builder = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(10));
+    let plain = builder.homopolymer().unwrap();
+    let cis = builder
+        .homopolymer_with_geometry(DoubleBondGeometry::Cis, 0)
+        .unwrap();
+    assert_eq!(molecular_formula(&cis), molecular_formula(&plain));
+    assert!((cis.mn - plain.mn).abs() < 1e-9);
+    assert_eq!(cis.composition, plain.composition);
+}
+
+#[test]
+fn geometry_without_backbone_double_bond_is_error() {
+    // Polyéthylène : aucune double liaison ; polystyrène : seulement aromatique
+    for bigsmiles in ["{[]CC[]}", "{[]CC(c1ccccc1)[]}"] {
+        let result = LinearBuilder::new(parse(bigsmiles).unwrap(), BuildStrategy::ByRepeatCount(5))
+            .homopolymer_with_geometry(DoubleBondGeometry::Trans, 0);
+        assert!(
+            matches!(result, Err(PolySimError::InvalidRepeatUnit(_))),
+            "{bigsmiles}: {result:?}"
+        );
+    }
+}
+
 // ── Error cases ───────────────────────────────────────────────────────────────
 
 #[test]
@@ -122,3 +909,118 @@ fn multiple_repeat_units_is_error() {
         "got: {result:?}"
     );
 }
+
+#[test]
+fn empty_stochastic_object_is_error() {
+    // `{[][]}` se parse mais ne contient aucun motif : erreur, pas de panique
+    for strategy in [
+        BuildStrategy::ByRepeatCount(3),
+        BuildStrategy::ByTargetMn(1_000.0),
+    ] {
+        let bs = parse("{[][]}").unwrap();
+        let result = LinearBuilder::new(bs, strategy).homopolymer();
+        assert!(
+            matches!(
+                result,
+                Err(PolySimError::RepeatUnitCount {
+                    got: 0,
+                    need_min: 1,
+                    ..
+                })
+            ),
+            "got: {result:?}"
+        );
+    }
+}
+
+/// Remplace le motif de répétition d'un BigSMILES déjà parsé, pour simuler un
+/// fragment que le parseur aurait laissé passer.
+fn with_repeat_unit(bigsmiles: &str, unit: &str) -> BigSmiles {
+    let mut bs = parse(bigsmiles).unwrap();
+    for segment in &mut bs.segments {
+        if let BigSmilesSegment::Stochastic(object) = segment {
+            object.repeat_units[0].smiles_raw = unit.to_string();
+        }
+    }
+    bs
+}
+
+#[test]
+fn malformed_repeat_unit_fails_at_build_time() {
+    let bs = with_repeat_unit("{[]CC[]}", "[]CC(C[]");
+    let result = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(3)).homopolymer();
+    match result {
+        Err(PolySimError::InvalidRepeatUnit(msg)) => {
+            assert!(msg.contains("CC(C"), "message: {msg}");
+            assert!(msg.contains("not valid SMILES"), "message: {msg}");
+        }
+        other => panic!("expected InvalidRepeatUnit, got: {other:?}"),
+    }
+}
+
+#[test]
+fn malformed_repeat_unit_fails_for_copolymers() {
+    let bs = with_repeat_unit("{[$]CC[$],[$]CC(C)[$]}", "[$]C1CC[$]");
+    let result = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(4)).alternating_copolymer();
+    assert!(
+        matches!(result, Err(PolySimError::InvalidRepeatUnit(_))),
+        "got: {result:?}"
+    );
+}
+
+// ── write_smiles — streaming ─────────────────────────────────────────────────
+
+fn streamed(builder: &LinearBuilder) -> (String, usize) {
+    let mut out = Vec::new();
+    let n = builder.write_smiles(&mut out).unwrap();
+    (String::from_utf8(out).unwrap(), n)
+}
+
+#[test]
+fn write_smiles_matches_homopolymer() {
+    for (bigsmiles, n) in [("{[]CC[]}", 50), ("{[]CC(c1ccccc1)[]}", 7)] {
+        let builder =
+            LinearBuilder::new(parse(bigsmiles).unwrap(), BuildStrategy::ByRepeatCount(n));
+        let chain = builder.homopolymer().unwrap();
+        assert_eq!(streamed(&builder), (chain.smiles, n), "{bigsmiles}");
+    }
+    // Les contre-ions suivent le corps de chaîne, comme dans homopolymer()
+    let builder =
+        LinearBuilder::new(parse(PSS).unwrap(), BuildStrategy::ByRepeatCount(5)).counterions(true);
+    let chain = builder.homopolymer().unwrap();
+    assert!(chain.smiles.ends_with(".[Na+]"));
+    assert_eq!(streamed(&builder), (chain.smiles, 5));
+}
+
+#[test]
+fn write_smiles_cycles_ring_numbers_like_homopolymer() {
+    // n > 99 : les numéros de cycle sont réutilisés
+    let builder = LinearBuilder::new(
+        parse("{[]CC(c1ccccc1)[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(250),
+    );
+    assert_eq!(streamed(&builder).0, builder.homopolymer().unwrap().smiles);
+}
+
+#[test]
+fn write_smiles_includes_end_groups() {
+    // Les groupes terminaux sont décalés après les numéros de cycle du corps
+    let builder = LinearBuilder::new(
+        parse("{[]CC(c1ccccc1)[]}").unwrap(),
+        BuildStrategy::ByTargetMn(2000.0),
+    )
+    .with_end_groups("c1ccccc1", "O");
+    let chain = builder.homopolymer().unwrap();
+    assert_eq!(streamed(&builder), (chain.smiles, chain.repeat_count));
+}
+
+#[test]
+fn write_smiles_propagates_build_errors() {
+    let bs = parse("{[]CC[]}").unwrap();
+    let result =
+        LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(0)).write_smiles(&mut Vec::new());
+    assert!(
+        matches!(result, Err(PolySimError::BuildStrategy(_))),
+        "got: {result:?}"
+    );
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use bigsmiles::parse;
+use common::homopolymer;
+use polysim_core::{
+    builder::{identify::closest_known, linear::LinearBuilder, BuildStrategy},
+    properties::reference::reference_properties,
+};
+
+#[test]
+fn polystyrene_matches_with_score_one() {
+    let chain = homopolymer("{[]CC(c1ccccc1)[]}", 20);
+    assert_eq!(closest_known(&chain), Some(("Polystyrene", 1.0)));
+}
+
+#[test]
+fn equivalent_writing_still_matches_exactly() {
+    // Même unité répétée, atomes écrits dans un autre ordre
+    let chain = homopolymer("{[]C(C)C[]}", 5);
+    let (name, score) = closest_known(&chain).unwrap();
+    assert_eq!(name, "Polypropylene");
+    assert_eq!(score, 1.0);
+}
+
+#[test]
+fn every_table_entry_matches_itself() {
+    for (bigsmiles, expected) in [
+        ("{[]CC[]}", "Polyethylene"),
+        ("{[]CC(C)(C(=O)OC)[]}", "Poly(methyl methacrylate)"),
+        ("{[]CC(Cl)[]}", "Poly(vinyl chloride)"),
+        (
+            "{[]OCCOC(=O)c1ccc(cc1)C(=O)[]}",
+            "Poly(ethylene terephthalate)",
+        ),
+        ("{[]NCCCCCC(=O)[]}", "Nylon-6"),
+        ("{[]C(F)(F)C(F)(F)[]}", "Polytetrafluoroethylene"),
+    ] {
+        let chain = homopolymer(bigsmiles, 10);
+        assert_eq!(closest_known(&chain), Some((expected, 1.0)), "{bigsmiles}");
+    }
+}
+
+#[test]
+fn close_analogue_scores_below_one() {
+    // Poly(4-méthylstyrène) : proche du PS sans être identique
+    let chain = homopolymer("{[]CC(c1ccc(C)cc1)[]}", 10);
+    let (name, score) = closest_known(&chain).unwrap();
+    assert_eq!(name, "Polystyrene");
+    assert!(score > 0.5 && score < 1.0, "score = {score}");
+}
+
+#[test]
+fn unrelated_chain_has_no_match() {
+    // Polydiméthylsiloxane : absent de la table
+    let chain = homopolymer("{[][Si](C)(C)O[]}", 10);
+    assert_eq!(closest_known(&chain), None);
+}
+
+#[test]
+fn end_groups_and_length_do_not_change_the_match() {
+    // Seul le motif compte : dimère à groupes terminaux explicites compris
+    for chain in [
+        homopolymer("CCCC{[]CC(c1ccccc1)[]}O", 2),
+        homopolymer("{[<]CC(c1ccccc1)[>]}", 1),
+    ] {
+        assert_eq!(
+            closest_known(&chain),
+            Some(("Polystyrene", 1.0)),
+            "{}",
+            chain.smiles
+        );
+    }
+}
+
+#[test]
+fn attachment_points_distinguish_same_atoms() {
+    // Poly(triméthylène) : mêmes atomes que le motif PP, autres points d'attache
+    let chain = homopolymer("{[]CCC[]}", 10);
+    let score = closest_known(&chain).map_or(0.0, |(_, score)| score);
+    assert!(score < 1.0, "{:?}", closest_known(&chain));
+}
+
+#[test]
+fn copolymer_has_no_match() {
+    let chain = LinearBuilder::new(
+        parse("{[$]CC[$],[$]CC(c1ccccc1)[$]}").unwrap(),
+        BuildStrategy::ByRepeatCount(10),
+    )
+    .alternating_copolymer()
+    .unwrap();
+    assert_eq!(closest_known(&chain), None);
+}
+
+// ── Valeurs de référence ─────────────────────────────────────────────────────
+
+#[test]
+fn reference_properties_by_name_or_abbreviation() {
+    let by_name = reference_properties("Poly(methyl methacrylate)").unwrap();
+    assert_eq!(reference_properties("pmma"), Some(by_name));
+    assert_eq!(by_name.tg, Some(378.0));
+    assert_eq!(by_name.density, 1.17);
+}
+
+#[test]
+fn every_known_polymer_has_reference_properties() {
+    for bigsmiles in [
+        "{[]CC[]}",
+        "{[]CC(C)[]}",
+        "{[]CC(c1ccccc1)[]}",
+        "{[]CC(C)(C(=O)OC)[]}",
+        "{[]CC(Cl)[]}",
+        "{[]OCCOC(=O)c1ccc(cc1)C(=O)[]}",
+        "{[]NCCCCCC(=O)[]}",
+        "{[]C(F)(F)C(F)(F)[]}",
+    ] {
+        let (name, _) = closest_known(&homopolymer(bigsmiles, 10)).unwrap();
+        let reference = reference_properties(name).unwrap();
+        assert!(reference.density > 0.8 && reference.density < 2.2, "{name}");
+        assert!(reference.refractive_index > 1.3, "{name}");
+    }
+}
+
+#[test]
+fn ptfe_has_no_reference_tg() {
+    // Tg du PTFE trop controversée pour être tabulée
+    assert_eq!(reference_properties("PTFE").unwrap().tg, None);
+}
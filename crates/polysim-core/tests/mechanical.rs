@@ -0,0 +1,174 @@
+mod common;
+
+use bigsmiles::parse;
+use common::homopolymer;
+use polysim_core::{
+    builder::{linear::LinearBuilder, BuildStrategy},
+    properties::{
+        mechanical::{ductility_class, youngs_modulus, DuctilityClass},
+        thermal::{crystallization_tendency, percent_crystallinity, CrystallizationTendency},
+    },
+};
+
+// ── Tendance à cristalliser ──────────────────────────────────────────────────
+
+#[test]
+fn regular_chains_crystallize_readily() {
+    // PE, POM, PTFE : aucun groupe pendant encombrant, pas de stéréocentre
+    for bigsmiles in ["{[]CC[]}", "{[]CO[]}", "{[]C(F)(F)C(F)(F)[]}"] {
+        assert_eq!(
+            crystallization_tendency(&homopolymer(bigsmiles, 100)),
+            CrystallizationTendency::High,
+            "{bigsmiles}"
+        );
+    }
+}
+
+#[test]
+fn backbone_rings_lower_crystallization_tendency() {
+    // PET : cycle aromatique dans le squelette
+    let pet = homopolymer("{[]OCCOC(=O)c1ccc(cc1)C(=O)[]}", 20);
+    assert_eq!(
+        crystallization_tendency(&pet),
+        CrystallizationTendency::Medium
+    );
+}
+
+#[test]
+fn stereocentres_without_tacticity_give_low_tendency() {
+    // PP et PVC sans stéréochimie : chaîne irrégulière
+    for bigsmiles in ["{[]CC(C)[]}", "{[]CC(Cl)[]}"] {
+        assert_eq!(
+            crystallization_tendency(&homopolymer(bigsmiles, 100)),
+            CrystallizationTendency::Low,
+            "{bigsmiles}"
+        );
+    }
+}
+
+#[test]
+fn isotactic_polypropylene_crystallizes_readily() {
+    let ipp = homopolymer("{[]C[C@@H](C)[]}", 100);
+    assert_eq!(
+        crystallization_tendency(&ipp),
+        CrystallizationTendency::High
+    );
+}
+
+#[test]
+fn syndiotactic_polypropylene_has_medium_tendency() {
+    // Configurations alternées : chaque motif porte une dyade r
+    let spp = homopolymer("{[]C[C@@H](C)C[C@H](C)[]}", 50);
+    assert_eq!(
+        crystallization_tendency(&spp),
+        CrystallizationTendency::Medium
+    );
+}
+
+#[test]
+fn atactic_polypropylene_is_amorphous() {
+    // Configurations aléatoires : f_m ≈ 0,5
+    let app = LinearBuilder::new(
+        parse("{[]C[C@@H](C)[],[]C[C@H](C)[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(1000),
+    )
+    .seed(594)
+    .random_copolymer(&[0.5, 0.5])
+    .unwrap();
+    assert_eq!(
+        crystallization_tendency(&app),
+        CrystallizationTendency::Amorphous
+    );
+    assert_eq!(percent_crystallinity(&app), None);
+}
+
+#[test]
+fn bulky_pendants_are_amorphous() {
+    // PS (phényle) et PMMA (ester)
+    for bigsmiles in ["{[]CC(c1ccccc1)[]}", "{[]CC(C)(C(=O)OC)[]}"] {
+        assert_eq!(
+            crystallization_tendency(&homopolymer(bigsmiles, 100)),
+            CrystallizationTendency::Amorphous,
+            "{bigsmiles}"
+        );
+    }
+}
+
+// ── Taux de cristallinité ────────────────────────────────────────────────────
+
+#[test]
+fn linear_polyethylene_has_high_crystallinity() {
+    // PEHD : 60–80 %
+    let xc = percent_crystallinity(&homopolymer("{[]CC[]}", 1000)).unwrap();
+    assert!(xc > 60.0 && xc < 80.0, "Xc = {xc} %");
+}
+
+#[test]
+fn atactic_polystyrene_has_no_crystallinity() {
+    assert_eq!(
+        percent_crystallinity(&homopolymer("{[]CC(c1ccccc1)[]}", 100)),
+        None
+    );
+}
+
+#[test]
+fn isotactic_polypropylene_crystallizes_more_than_unspecified() {
+    let isotactic = percent_crystallinity(&homopolymer("{[]C[C@@H](C)[]}", 100)).unwrap();
+    let unspecified = percent_crystallinity(&homopolymer("{[]CC(C)[]}", 100)).unwrap();
+    assert!(isotactic > 40.0, "iPP Xc = {isotactic} %");
+    assert!(unspecified < isotactic / 2.0, "PP Xc = {unspecified} %");
+}
+
+#[test]
+fn backbone_rings_lower_crystallinity() {
+    // PET : 30–40 % typique
+    let xc = percent_crystallinity(&homopolymer("{[]OCCOC(=O)c1ccc(cc1)C(=O)[]}", 50)).unwrap();
+    assert!(xc > 25.0 && xc < 50.0, "PET Xc = {xc} %");
+}
+
+// ── Classe de ductilité ──────────────────────────────────────────────────────
+
+#[test]
+fn polystyrene_is_brittle_at_room_temperature() {
+    // Tg ≈ 373 K, amorphe
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 500);
+    assert_eq!(ductility_class(&ps, 298.0), DuctilityClass::Brittle);
+}
+
+#[test]
+fn polyethylene_is_ductile_at_room_temperature() {
+    // Tg ≈ 192 K, semi-cristallin
+    let pe = homopolymer("{[]CC[]}", 1000);
+    assert_eq!(ductility_class(&pe, 298.0), DuctilityClass::Ductile);
+}
+
+#[test]
+fn amorphous_chain_above_tg_is_rubbery() {
+    // 1,4-polyisoprène : Tg ≈ 200 K, géométrie cis/trans non précisée
+    let pi = homopolymer("{[]CC(C)=CC[]}", 500);
+    assert_eq!(ductility_class(&pi, 298.0), DuctilityClass::Rubbery);
+}
+
+// ── Module d'Young ───────────────────────────────────────────────────────────
+
+#[test]
+fn polystyrene_glassy_modulus_is_about_3_gpa() {
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 500);
+    let e = youngs_modulus(&ps, 298.0);
+    assert!((e - 3.0).abs() < 0.6, "E = {e} GPa");
+}
+
+#[test]
+fn polystyrene_rubbery_modulus_above_tg() {
+    // Plateau caoutchoutique : quelques MPa, trois ordres de grandeur sous le verre
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 500);
+    let e = youngs_modulus(&ps, 450.0);
+    assert!(e > 0.5e-3 && e < 5.0e-3, "E = {e} GPa");
+}
+
+#[test]
+fn unentangled_chain_above_tg_has_no_modulus() {
+    // PE n = 10 : 20 atomes de squelette, bien en dessous de Me
+    let pe = homopolymer("{[]CC[]}", 10);
+    assert_eq!(youngs_modulus(&pe, 298.0), 0.0);
+}
@@ -1,7 +1,15 @@
 use bigsmiles::parse;
 use polysim_core::{
-    builder::{linear::LinearBuilder, BuildStrategy},
-    properties::molecular_weight::{average_mass, monoisotopic_mass},
+    builder::{
+        conversion_from_dp, dp_from_mn, linear::LinearBuilder, BuildStrategy, RoundingMode,
+        StepOrChain,
+    },
+    error::PolySimError,
+    properties::molecular_weight::{
+        average_mass, average_mass_of_smiles, average_mass_with, monoisotopic_mass,
+        monoisotopic_mass_with, most_abundant_isotope_mass, try_average_mass,
+        try_monoisotopic_mass, MassTable,
+    },
 };
 
 // ─── Helpers ────────────────────────────────────────────────────────────────
@@ -91,6 +99,99 @@ fn average_mass_is_linear_in_n() {
     assert_close(delta12, delta23, 0.001, "linéarité PE");
 }
 
+// ─── average_mass — repli sur la composition ─────────────────────────────────
+
+#[test]
+fn average_mass_falls_back_to_composition_when_smiles_fails_to_parse() {
+    // SMILES volontairement non analysable (parenthèse non fermée) : la masse est
+    // reconstruite à partir du motif × n + 2 H terminaux, sans paniquer
+    for chain in [build_pp(7), build_ps(4)] {
+        let expected = average_mass(&chain);
+        let mut broken = chain.clone();
+        broken.smiles.push('(');
+        assert_close(average_mass(&broken), expected, 1e-6, &chain.smiles);
+    }
+}
+
+#[test]
+fn builder_mn_survives_a_chain_smiles_the_parser_rejects() {
+    // Motifs qui sollicitent l'analyseur (isotope, stéréo, [nH], charge, cycles) :
+    // la masse repliée sur le cache de motifs retrouve le Mn du builder
+    let builder = |bigsmiles: &str| {
+        LinearBuilder::new(parse(bigsmiles).unwrap(), BuildStrategy::ByRepeatCount(6))
+    };
+    let chains = [
+        builder("{[]C[C@@H](c1cc[nH]c1)[]}").homopolymer().unwrap(),
+        builder("{[][13CH2]C(C)(C(=O)[O-])[]}")
+            .homopolymer()
+            .unwrap(),
+        builder("{[$]CC[$],[$]CC(c1ccc2ccccc2c1)[$]}")
+            .alternating_copolymer()
+            .unwrap(),
+        builder("{[$]CC(C#N)[$],[$][Si](C)(C)O[$]}")
+            .block_copolymer(&[2, 4])
+            .unwrap(),
+    ];
+    for chain in chains {
+        assert_close(
+            try_average_mass(&chain).unwrap(),
+            chain.mn,
+            1e-6,
+            &chain.smiles,
+        );
+        let mut broken = chain.clone();
+        broken.smiles.push('(');
+        assert!(try_average_mass(&broken).is_err(), "{}", broken.smiles);
+        assert_close(average_mass(&broken), chain.mn, 1e-6, &chain.smiles);
+    }
+}
+
+#[test]
+fn average_mass_fallback_ignores_explicit_end_groups() {
+    // Les groupes terminaux ne sont pas dans la composition : l'estimation
+    // retombe sur la chaîne terminée par des H
+    let build = |bigsmiles: &str| {
+        LinearBuilder::new(parse(bigsmiles).unwrap(), BuildStrategy::ByRepeatCount(5))
+            .homopolymer()
+            .unwrap()
+    };
+    let capped = build("CC{[]CC(C(=O)[O-])[]}O");
+    let bare = build("{[]CC(C(=O)[O-])[]}");
+    let mut broken = capped.clone();
+    broken.smiles.push('(');
+    assert_close(average_mass(&broken), bare.mn, 1e-6, &capped.smiles);
+}
+
+#[test]
+fn average_mass_fallback_is_nan_without_composition() {
+    let chain = polysim_core::PolymerChain::new("CC(C".to_string(), 1, 0.0);
+    assert!(average_mass(&chain).is_nan());
+}
+
+// ─── average_mass_of_smiles — fragments ─────────────────────────────────────
+
+#[test]
+fn average_mass_of_smiles_matches_single_molecule_chain() {
+    // Un fragment isolé pèse autant que la « chaîne » qui ne contient que lui
+    for smiles in ["CC", "O", "c1ccccc1", "CCC(C)"] {
+        let chain = polysim_core::PolymerChain::new(smiles.to_string(), 1, 0.0);
+        assert_close(
+            average_mass_of_smiles(smiles).unwrap(),
+            average_mass(&chain),
+            1e-9,
+            smiles,
+        );
+    }
+}
+
+#[test]
+fn average_mass_of_invalid_smiles_is_error() {
+    assert!(matches!(
+        average_mass_of_smiles("C("),
+        Err(PolySimError::InvalidRepeatUnit(_))
+    ));
+}
+
 // ─── monoisotopic_mass ───────────────────────────────────────────────────────
 
 #[test]
@@ -120,6 +221,102 @@ fn monoisotopic_mass_less_than_average() {
     );
 }
 
+#[test]
+fn tin_uses_sn120_not_average_mass() {
+    // Sn(CH₃)₄ : la contribution de l'étain est ¹²⁰Sn (119.902), pas 118.71
+    let bs = parse("{[]C[Sn](C)(C)C[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1))
+        .homopolymer()
+        .unwrap();
+    let tin = try_monoisotopic_mass(&chain).unwrap() - (4.0 * 12.0 + 12.0 * 1.00782503207);
+    assert_close(tin, 119.9022, 1e-3, "¹²⁰Sn");
+    assert_eq!(
+        monoisotopic_mass(&chain),
+        try_monoisotopic_mass(&chain).unwrap()
+    );
+}
+
+#[test]
+fn most_abundant_isotope_covers_counter_ions_and_metals() {
+    for (z, mass) in [
+        (11, 22.98977),  // ²³Na
+        (19, 38.96371),  // ³⁹K
+        (20, 39.96259),  // ⁴⁰Ca
+        (30, 63.92914),  // ⁶⁴Zn
+        (50, 119.90220), // ¹²⁰Sn
+    ] {
+        assert_close(
+            most_abundant_isotope_mass(z).unwrap(),
+            mass,
+            1e-4,
+            &format!("Z = {z}"),
+        );
+    }
+}
+
+#[test]
+fn element_without_stable_isotope_has_no_monoisotopic_mass() {
+    // Tc et Pm : aucun isotope stable, pas de repli sur la masse moyenne
+    assert_eq!(most_abundant_isotope_mass(43), None);
+    assert_eq!(most_abundant_isotope_mass(61), None);
+}
+
+// ─── MassTable ──────────────────────────────────────────────────────────────
+
+#[test]
+fn default_mass_table_matches_builtin_masses() {
+    let chain = build_ps(10);
+    let table = MassTable::default();
+    assert_eq!(average_mass_with(&chain, &table), average_mass(&chain));
+    assert_eq!(
+        monoisotopic_mass_with(&chain, &table),
+        monoisotopic_mass(&chain)
+    );
+}
+
+#[test]
+fn custom_carbon_mass_shifts_average_mass_per_carbon() {
+    // PE n=10 : C₂₀H₄₂ → +0.01 g/mol par carbone = +0.20 g/mol
+    let chain = build_pe(10);
+    let table = MassTable::default().with_average_mass(6, 12.011 + 0.01);
+    let shift = average_mass_with(&chain, &table) - average_mass(&chain);
+    assert_close(shift, 20.0 * 0.01, 1e-9, "décalage C");
+}
+
+#[test]
+fn custom_hydrogen_mass_shifts_average_mass_per_hydrogen() {
+    // Deutérium partout : 42 H × (2.014 − 1.008)
+    let chain = build_pe(10);
+    let table = MassTable::default().with_average_mass(1, 2.014);
+    let shift = average_mass_with(&chain, &table) - average_mass(&chain);
+    assert_close(shift, 42.0 * (2.014 - 1.008), 1e-9, "décalage H");
+}
+
+#[test]
+fn custom_monoisotopic_mass_leaves_average_unchanged() {
+    let chain = build_pe(10);
+    let table = MassTable::default().with_monoisotopic_mass(6, 12.5);
+    assert_close(
+        monoisotopic_mass_with(&chain, &table) - monoisotopic_mass(&chain),
+        20.0 * 0.5,
+        1e-9,
+        "décalage mono C",
+    );
+    assert_eq!(average_mass_with(&chain, &table), average_mass(&chain));
+}
+
+#[test]
+fn explicit_isotope_ignores_mass_table() {
+    // [13C] garde la masse de l'isotope ; seul le carbone naturel est surchargé
+    let bs = parse("{[][13CH2]C[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1))
+        .homopolymer()
+        .unwrap();
+    let table = MassTable::default().with_average_mass(6, 13.0);
+    let shift = average_mass_with(&chain, &table) - average_mass(&chain);
+    assert_close(shift, 13.0 - 12.011, 1e-9, "seul C naturel décalé");
+}
+
 // ─── chain.mn renseigné à la construction ───────────────────────────────────
 
 #[test]
@@ -194,6 +391,251 @@ fn by_target_mn_rounds_to_nearest() {
     assert_eq!(chain2.repeat_count, 2);
 }
 
+#[test]
+fn by_target_mn_below_one_unit_is_error() {
+    // 10 g/mol < éthane (30.070 g/mol) : n arrondi vaudrait 0
+    let bs = parse("{[]CC[]}").unwrap();
+    let result = LinearBuilder::new(bs, BuildStrategy::ByTargetMn(10.0)).homopolymer();
+    match result {
+        Err(PolySimError::BuildStrategy(msg)) => {
+            assert!(msg.contains("30.070 g/mol"), "message : {msg}");
+        }
+        other => panic!("BuildStrategy attendu, obtenu : {other:?}"),
+    }
+}
+
+#[test]
+fn by_target_mn_minimum_includes_end_groups() {
+    // HO–CH₂CH₂–OH : 62.068 g/mol
+    let bs = parse("{[]CC[]}").unwrap();
+    let result = LinearBuilder::new(bs, BuildStrategy::ByTargetMn(20.0))
+        .with_end_groups("O", "O")
+        .homopolymer();
+    match result {
+        Err(PolySimError::BuildStrategy(msg)) => {
+            assert!(msg.contains("62.06"), "message : {msg}");
+        }
+        other => panic!("BuildStrategy attendu, obtenu : {other:?}"),
+    }
+}
+
+// ─── RoundingMode ───────────────────────────────────────────────────────────
+
+fn pe_target_mn(target: f64, rounding: RoundingMode) -> polysim_core::PolymerChain {
+    let bs = parse("{[]CC[]}").unwrap();
+    LinearBuilder::new(bs, BuildStrategy::ByTargetMn(target))
+        .rounding(rounding)
+        .homopolymer()
+        .unwrap()
+}
+
+#[test]
+fn rounding_modes_differ_between_two_units() {
+    // PE : MW(10) = 282.554, MW(11) = 310.606 ; 300 g/mol est plus proche de n=11
+    assert_eq!(pe_target_mn(300.0, RoundingMode::Nearest).repeat_count, 11);
+    assert_eq!(pe_target_mn(300.0, RoundingMode::Floor).repeat_count, 10);
+    assert_eq!(pe_target_mn(300.0, RoundingMode::Ceil).repeat_count, 11);
+    // 290 g/mol est plus proche de n=10
+    assert_eq!(pe_target_mn(290.0, RoundingMode::Nearest).repeat_count, 10);
+    assert_eq!(pe_target_mn(290.0, RoundingMode::Floor).repeat_count, 10);
+    assert_eq!(pe_target_mn(290.0, RoundingMode::Ceil).repeat_count, 11);
+}
+
+#[test]
+fn rounding_floor_never_exceeds_ceil_never_below_target() {
+    for target in [100.0, 1_234.5, 5_000.0, 28_000.0] {
+        let floor = pe_target_mn(target, RoundingMode::Floor);
+        let ceil = pe_target_mn(target, RoundingMode::Ceil);
+        assert!(floor.mn <= target, "floor {target} → {}", floor.mn);
+        assert!(ceil.mn >= target, "ceil {target} → {}", ceil.mn);
+        assert!(ceil.repeat_count - floor.repeat_count <= 1);
+    }
+}
+
+#[test]
+fn rounding_keeps_exact_target() {
+    // Cible = MW exact de n=10 : les trois modes donnent n=10
+    let target = build_pe(10).mn;
+    for mode in [
+        RoundingMode::Nearest,
+        RoundingMode::Floor,
+        RoundingMode::Ceil,
+    ] {
+        assert_eq!(pe_target_mn(target, mode).repeat_count, 10, "{mode:?}");
+    }
+}
+
+#[test]
+fn rounding_default_is_nearest() {
+    assert_eq!(RoundingMode::default(), RoundingMode::Nearest);
+}
+
+#[test]
+fn rounding_floor_below_one_unit_is_error() {
+    // 50 g/mol < butane : l'arrondi inférieur donne n=1, 25 g/mol donne n=0
+    assert_eq!(pe_target_mn(50.0, RoundingMode::Floor).repeat_count, 1);
+    let bs = parse("{[]CC[]}").unwrap();
+    let result = LinearBuilder::new(bs, BuildStrategy::ByTargetMn(25.0))
+        .rounding(RoundingMode::Floor)
+        .homopolymer();
+    assert!(matches!(result, Err(PolySimError::BuildStrategy(_))));
+}
+
+// ─── dp_from_mn ─────────────────────────────────────────────────────────────
+
+#[test]
+fn dp_from_mn_rounds_to_nearest() {
+    // Mêmes attentes que by_target_mn_rounds_to_nearest (midpoint ≈ 44.1)
+    let bs = parse("{[]CC[]}").unwrap();
+    assert_eq!(dp_from_mn(&bs, 35.0).unwrap(), 1);
+    assert_eq!(dp_from_mn(&bs, 50.0).unwrap(), 2);
+}
+
+#[test]
+fn dp_from_mn_matches_by_target_mn() {
+    let bs = parse("{[]CC(C)[]}").unwrap();
+    for mn in [212.421, 1_000.0, 25_000.0] {
+        let chain = LinearBuilder::new(bs.clone(), BuildStrategy::ByTargetMn(mn))
+            .homopolymer()
+            .unwrap();
+        assert_eq!(dp_from_mn(&bs, mn).unwrap(), chain.repeat_count);
+    }
+}
+
+#[test]
+fn dp_from_mn_rejects_non_positive_mn() {
+    let bs = parse("{[]CC[]}").unwrap();
+    assert!(matches!(
+        dp_from_mn(&bs, 0.0),
+        Err(PolySimError::BuildStrategy(_))
+    ));
+    assert!(matches!(
+        dp_from_mn(&bs, f64::NAN),
+        Err(PolySimError::BuildStrategy(_))
+    ));
+}
+
+#[test]
+fn dp_from_mn_requires_single_repeat_unit() {
+    let bs = parse("{[]CC[],[]CC(C)[]}").unwrap();
+    assert!(matches!(
+        dp_from_mn(&bs, 1_000.0),
+        Err(PolySimError::RepeatUnitCount { got: 2, .. })
+    ));
+}
+
+// ─── LinearBuilder::mn_series ───────────────────────────────────────────────
+
+#[test]
+fn mn_series_is_strictly_increasing_and_linear() {
+    let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+    let series = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1)).mn_series(50);
+    assert_eq!(series.len(), 50);
+    assert!(series.iter().zip(1..).all(|(&(n, _), k)| n == k));
+    assert!(series.windows(2).all(|w| w[1].1 > w[0].1));
+    // Incrément constant = masse de l'unité répétée (C8H8 ≈ 104.15 g/mol)
+    let steps: Vec<f64> = series.windows(2).map(|w| w[1].1 - w[0].1).collect();
+    assert!(
+        steps.iter().all(|s| (s - steps[0]).abs() < 1e-9),
+        "{steps:?}"
+    );
+    assert!((steps[0] - 104.15).abs() < 0.01, "M0 = {}", steps[0]);
+}
+
+#[test]
+fn mn_series_matches_built_chains() {
+    let bs = parse("{[]CC(C)[]}").unwrap();
+    let series = LinearBuilder::new(bs.clone(), BuildStrategy::ByRepeatCount(1))
+        .with_end_groups("CCC(C)", "O")
+        .mn_series(20);
+    for (n, mn) in series {
+        let chain = LinearBuilder::new(bs.clone(), BuildStrategy::ByRepeatCount(n))
+            .with_end_groups("CCC(C)", "O")
+            .homopolymer()
+            .unwrap();
+        assert!((chain.mn - mn).abs() < 1e-6, "n={n}: {} vs {mn}", chain.mn);
+    }
+}
+
+#[test]
+fn mn_series_empty_for_copolymer() {
+    let bs = parse("{[]CC[],[]CC(C)[]}").unwrap();
+    let series = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1)).mn_series(10);
+    assert!(series.is_empty());
+}
+
+// ─── LinearBuilder::build_closest_to_mn ─────────────────────────────────────
+
+#[test]
+fn build_closest_to_mn_delta_matches_chain_mn() {
+    let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+    for target in [500.0, 10_000.0, 123_456.0] {
+        let (chain, delta) = LinearBuilder::new(bs.clone(), BuildStrategy::ByRepeatCount(1))
+            .build_closest_to_mn(target)
+            .unwrap();
+        assert_eq!(delta, chain.mn - target);
+        // Erreur bornée par une demi-unité répétée (104.15 / 2)
+        assert!(delta.abs() <= 52.08, "target = {target}, Δ = {delta}");
+    }
+}
+
+#[test]
+fn build_closest_to_mn_matches_by_target_mn_strategy() {
+    let bs = parse("{[]CC(C)[]}").unwrap();
+    let (chain, _) = LinearBuilder::new(bs.clone(), BuildStrategy::ByRepeatCount(3))
+        .with_end_groups("CCC(C)", "O")
+        .build_closest_to_mn(4200.0)
+        .unwrap();
+    let reference = LinearBuilder::new(bs, BuildStrategy::ByTargetMn(4200.0))
+        .with_end_groups("CCC(C)", "O")
+        .homopolymer()
+        .unwrap();
+    assert_eq!(chain.repeat_count, reference.repeat_count);
+    assert_eq!(chain.smiles, reference.smiles);
+}
+
+#[test]
+fn build_closest_to_mn_below_one_unit_fails() {
+    let bs = parse("{[]CC[]}").unwrap();
+    let result = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(1)).build_closest_to_mn(10.0);
+    assert!(matches!(result, Err(PolySimError::BuildStrategy(_))));
+}
+
+// ─── conversion_from_dp ─────────────────────────────────────────────────────
+
+#[test]
+fn conversion_step_growth_carothers() {
+    // DP = 20 → p = 1 − 1/20 = 0.95
+    let p = conversion_from_dp(20.0, StepOrChain::StepGrowth);
+    assert!((p - 0.95).abs() < 1e-12, "p = {p}");
+    assert_eq!(conversion_from_dp(1.0, StepOrChain::StepGrowth), 0.0);
+}
+
+#[test]
+fn conversion_living_chain_growth() {
+    // [M]₀/[I]₀ = 100 : DP = 50 atteint à mi-conversion
+    let living = StepOrChain::ChainGrowth {
+        monomer_to_initiator: 100.0,
+    };
+    assert!((conversion_from_dp(50.0, living) - 0.5).abs() < 1e-12);
+    assert!(conversion_from_dp(150.0, living).is_nan());
+}
+
+#[test]
+fn conversion_from_dp_from_measured_mn() {
+    // dp_from_mn puis conversion_from_dp : Mn = 2807 g/mol → DP = 100 → p = 0.99
+    let bs = parse("{[]CC[]}").unwrap();
+    let dp = dp_from_mn(&bs, 2807.0).unwrap();
+    let p = conversion_from_dp(dp as f64, StepOrChain::StepGrowth);
+    assert!((p - 0.99).abs() < 1e-12, "p = {p}");
+}
+
+#[test]
+fn conversion_rejects_dp_below_one() {
+    assert!(conversion_from_dp(0.5, StepOrChain::StepGrowth).is_nan());
+    assert!(conversion_from_dp(f64::NAN, StepOrChain::StepGrowth).is_nan());
+}
+
 // ─── BuildStrategy::ByExactMass ─────────────────────────────────────────────
 
 #[test]
@@ -215,3 +657,35 @@ fn by_exact_mass_pe_n1() {
         .unwrap();
     assert_eq!(chain.repeat_count, 1);
 }
+
+// ─── BuildStrategy::ByAtomCount ─────────────────────────────────────────────
+
+#[test]
+fn by_atom_count_pe_n10() {
+    // C₂₀H₄₂ = 62 atomes
+    let bs = parse("{[]CC[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByAtomCount(62))
+        .homopolymer()
+        .unwrap();
+    assert_eq!(chain.repeat_count, 10);
+}
+
+#[test]
+fn by_atom_count_rounds_to_nearest() {
+    // PS : 16 atomes par unité + 2 H terminaux ; 500 atomes → n = 31 (498 atomes)
+    let bs = parse("{[]CC(c1ccccc1)[]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByAtomCount(500))
+        .homopolymer()
+        .unwrap();
+    assert_eq!(chain.repeat_count, 31);
+}
+
+#[test]
+fn by_atom_count_alternating_copolymer() {
+    // PE/PP alterné : 6 + 9 atomes par paire + 2 H terminaux
+    let bs = parse("{[$]CC[$],[$]CC(C)[$]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByAtomCount(152))
+        .alternating_copolymer()
+        .unwrap();
+    assert_eq!(chain.repeat_count, 20);
+}
@@ -0,0 +1,72 @@
+use polysim_core::{
+    builder::{branched::BranchedBuilder, linear::LinearBuilder, BuildStrategy},
+    parse,
+    properties::network::{degree_of_crosslinking, gel_point_conversion},
+};
+
+// ── gel_point_conversion ─────────────────────────────────────────────────────
+
+#[test]
+fn gel_point_trifunctional() {
+    assert_eq!(gel_point_conversion(3.0), 0.5);
+}
+
+#[test]
+fn gel_point_tetrafunctional() {
+    assert!((gel_point_conversion(4.0) - 1.0 / 3.0).abs() < 1e-12);
+}
+
+#[test]
+fn gel_point_bifunctional_only_at_full_conversion() {
+    assert_eq!(gel_point_conversion(2.0), 1.0);
+}
+
+#[test]
+fn gel_point_monofunctional_never_gels() {
+    assert_eq!(gel_point_conversion(1.0), f64::INFINITY);
+}
+
+// ── degree_of_crosslinking ───────────────────────────────────────────────────
+
+fn branched(n: usize) -> BranchedBuilder {
+    BranchedBuilder::new(
+        parse("{[]CC[]}").unwrap(),
+        parse("{[]CCCC[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(n),
+    )
+}
+
+#[test]
+fn crosslinking_linear_is_zero() {
+    let pe = LinearBuilder::new(parse("{[]CC[]}").unwrap(), BuildStrategy::ByRepeatCount(50))
+        .homopolymer()
+        .unwrap();
+    assert_eq!(degree_of_crosslinking(&pe), 0.0);
+}
+
+#[test]
+fn crosslinking_comb_counts_branches() {
+    // 30 unités de squelette, une branche toutes les 3 → 10 branches / 40 unités
+    let comb = branched(30).comb_polymer(3).unwrap();
+    assert!((degree_of_crosslinking(&comb) - 10.0 / 40.0).abs() < 1e-12);
+}
+
+#[test]
+fn crosslinking_denser_graft_is_higher() {
+    let sparse = branched(100).graft_copolymer(0.1, Some(1)).unwrap();
+    let dense = branched(100).graft_copolymer(0.5, Some(1)).unwrap();
+    assert!(degree_of_crosslinking(&dense) > degree_of_crosslinking(&sparse));
+}
+
+#[test]
+fn crosslinking_star_has_single_hub() {
+    let star = branched(10).star_polymer(4).unwrap();
+    assert!((degree_of_crosslinking(&star) - 1.0 / 40.0).abs() < 1e-12);
+}
+
+#[test]
+fn crosslinking_dendrimer_counts_inner_nodes() {
+    // g = 2, b = 2 : 1 + 2 + 4 = 7 nœuds dont 3 internes
+    let dendrimer = branched(1).dendrimer(2, 2).unwrap();
+    assert!((degree_of_crosslinking(&dendrimer) - 3.0 / 7.0).abs() < 1e-12);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::homopolymer;
+use polysim_core::{
+    properties::{
+        formula::molecular_formula,
+        molecular_weight::{average_mass, monoisotopic_mass},
+        property::{AverageMass, Density, MolecularFormula, MonoisotopicMass, TotalAtomCount},
+        volume::density,
+    },
+    PolySimError, PolymerChain, PolymerProperty,
+};
+
+/// Propriété utilisateur : nombre d'atomes de carbone de la chaîne.
+struct CarbonCount;
+
+impl PolymerProperty for CarbonCount {
+    type Output = usize;
+
+    fn compute(&self, chain: &PolymerChain) -> Result<usize, PolySimError> {
+        Ok(chain
+            .smiles
+            .chars()
+            .filter(|c| matches!(c, 'C' | 'c'))
+            .count())
+    }
+}
+
+/// Propriété utilisateur qui échoue sur les chaînes trop courtes.
+struct MinLength(usize);
+
+impl PolymerProperty for MinLength {
+    type Output = usize;
+
+    fn compute(&self, chain: &PolymerChain) -> Result<usize, PolySimError> {
+        if chain.repeat_count < self.0 {
+            return Err(PolySimError::BuildStrategy(format!(
+                "need at least {} repeat units",
+                self.0
+            )));
+        }
+        Ok(chain.repeat_count)
+    }
+}
+
+// ── Propriétés intégrées ─────────────────────────────────────────────────────
+
+#[test]
+fn builtin_properties_match_free_functions() {
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 20);
+    assert_eq!(AverageMass.compute(&ps).unwrap(), average_mass(&ps));
+    assert_eq!(
+        MonoisotopicMass.compute(&ps).unwrap(),
+        monoisotopic_mass(&ps)
+    );
+    assert_eq!(
+        MolecularFormula.compute(&ps).unwrap(),
+        molecular_formula(&ps)
+    );
+    assert_eq!(TotalAtomCount.compute(&ps).unwrap(), 20 * 16 + 2);
+    let rho = Density {
+        temperature_k: 298.15,
+    };
+    assert_eq!(rho.compute(&ps).unwrap(), density(&ps, 298.15));
+}
+
+// ── Propriétés utilisateur ───────────────────────────────────────────────────
+
+#[test]
+fn custom_property_computes() {
+    let pe = homopolymer("{[]CC[]}", 10);
+    assert_eq!(CarbonCount.compute(&pe).unwrap(), 20);
+}
+
+#[test]
+fn custom_property_error_is_propagated() {
+    let pe = homopolymer("{[]CC[]}", 3);
+    assert!(matches!(
+        MinLength(5).compute(&pe),
+        Err(PolySimError::BuildStrategy(_))
+    ));
+    assert_eq!(MinLength(2).compute(&pe).unwrap(), 3);
+}
+
+#[test]
+fn boxed_properties_mix_builtin_and_custom() {
+    let pe = homopolymer("{[]CC[]}", 10);
+    let properties: Vec<Box<dyn PolymerProperty<Output = usize>>> =
+        vec![Box::new(TotalAtomCount), Box::new(CarbonCount)];
+    let values: Vec<usize> = properties.iter().map(|p| p.compute(&pe).unwrap()).collect();
+    assert_eq!(values, vec![62, 20]);
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use bigsmiles::parse;
+use common::homopolymer;
+use polysim_core::{
+    builder::{linear::LinearBuilder, BuildStrategy, EnsembleBuilder, RepeatUnitCache},
+    distribution::SchulzZimm,
+    properties::{
+        formula::{molecular_formula, total_atom_count},
+        molecular_weight::{average_mass, monoisotopic_mass},
+    },
+    PolySimError,
+};
+
+fn cache(bigsmiles: &str) -> RepeatUnitCache {
+    RepeatUnitCache::new(&parse(bigsmiles).unwrap()).unwrap()
+}
+
+// ── Homopolymères : identique au calcul sur la chaîne complète ───────────────
+
+#[test]
+fn homopolymer_properties_match_chain_parse() {
+    // PE, PS (cycle renuméroté), PMMA (ester), PVC (halogène)
+    for bigsmiles in [
+        "{[]CC[]}",
+        "{[]CC(c1ccccc1)[]}",
+        "{[]CC(C)(C(=O)OC)[]}",
+        "{[]CC(Cl)[]}",
+    ] {
+        let cache = cache(bigsmiles);
+        for n in [1, 2, 7, 150] {
+            let chain = homopolymer(bigsmiles, n);
+            let label = format!("{bigsmiles} n={n}");
+            assert!(
+                (cache.average_mass(&[n], 0, 0) - average_mass(&chain)).abs() < 1e-6,
+                "{label}"
+            );
+            assert!(
+                (cache.monoisotopic_mass(&[n], 0, 0) - monoisotopic_mass(&chain)).abs() < 1e-6,
+                "{label}"
+            );
+            assert_eq!(
+                cache.atom_count(&[n], 0, 0),
+                total_atom_count(&chain),
+                "{label}"
+            );
+            assert_eq!(
+                cache.molecular_formula(&[n], 0, 0),
+                molecular_formula(&chain),
+                "{label}"
+            );
+        }
+    }
+}
+
+#[test]
+fn unit_and_end_contributions_of_polyethylene() {
+    let cache = cache("{[]CC[]}");
+    // Motif C₂H₄, extrémités : deux H
+    assert_eq!(cache.units().len(), 1);
+    assert_eq!(cache.units()[0].atom_count, 6);
+    assert_eq!(cache.units()[0].elements["C"], 2);
+    assert_eq!(cache.units()[0].elements["H"], 4);
+    assert_eq!(cache.ends(0, 0).atom_count, 2);
+    assert!((cache.ends(0, 0).average_mass - 2.016).abs() < 1e-9);
+}
+
+// ── Copolymères ──────────────────────────────────────────────────────────────
+
+#[test]
+fn random_copolymer_mass_matches_chain_parse() {
+    let bigsmiles = "{[]CC[],[]CC(c1ccccc1)[]}";
+    let chain = LinearBuilder::new(parse(bigsmiles).unwrap(), BuildStrategy::ByRepeatCount(60))
+        .seed(650)
+        .random_copolymer(&[0.5, 0.5])
+        .unwrap();
+    let mut counts = [0; 2];
+    for &i in &chain.sequence {
+        counts[i] += 1;
+    }
+    let (head, tail) = (chain.sequence[0], chain.sequence[59]);
+    let cache = cache(bigsmiles);
+    assert!((cache.average_mass(&counts, head, tail) - average_mass(&chain)).abs() < 1e-6);
+    assert_eq!(
+        cache.molecular_formula(&counts, head, tail),
+        molecular_formula(&chain)
+    );
+}
+
+#[test]
+fn chain_ends_follow_the_end_units() {
+    // [Si] en bout de chaîne ne prend pas d'hydrogène : les extrémités dépendent
+    // du premier et du dernier motif, pas seulement du premier
+    let bigsmiles = "{[$]CC[$],[$][Si](C)(C)O[$]}";
+    let cache = cache(bigsmiles);
+    assert_eq!(cache.ends(0, 0).atom_count, 2);
+    assert_eq!(cache.ends(1, 1).atom_count, 1);
+    assert_eq!(cache.ends(0, 1).atom_count, 2);
+    assert_eq!(cache.ends(1, 0).atom_count, 1);
+    for pattern in ["AB", "BA", "ABBA", "BAAB", "BBB"] {
+        let chain = LinearBuilder::new(parse(bigsmiles).unwrap(), BuildStrategy::ByRepeatCount(1))
+            .from_sequence(pattern)
+            .unwrap();
+        let mut counts = [0; 2];
+        for &i in &chain.sequence {
+            counts[i] += 1;
+        }
+        let (head, tail) = (chain.sequence[0], *chain.sequence.last().unwrap());
+        assert!(
+            (cache.average_mass(&counts, head, tail) - average_mass(&chain)).abs() < 1e-6,
+            "{pattern}"
+        );
+        assert_eq!(
+            cache.molecular_formula(&counts, head, tail),
+            molecular_formula(&chain),
+            "{pattern}"
+        );
+    }
+}
+
+#[test]
+fn ensemble_chain_masses_match_chain_parse() {
+    let ensemble = EnsembleBuilder::new(parse("{[]CC(C)[]}").unwrap(), SchulzZimm, 5_000.0, 1.5)
+        .num_chains(50)
+        .seed(650)
+        .homopolymer_ensemble()
+        .unwrap();
+    for chain in ensemble.chains() {
+        assert!(
+            (chain.mn - average_mass(chain)).abs() < 1e-6,
+            "n={}: {} vs {}",
+            chain.repeat_count,
+            chain.mn,
+            average_mass(chain)
+        );
+    }
+}
+
+// ── Cas d'erreur ─────────────────────────────────────────────────────────────
+
+#[test]
+fn no_stochastic_object_is_error() {
+    assert!(matches!(
+        RepeatUnitCache::new(&parse("CCO").unwrap()),
+        Err(PolySimError::NoStochasticObject)
+    ));
+}
+
+#[test]
+fn empty_stochastic_object_is_error() {
+    assert!(matches!(
+        RepeatUnitCache::new(&parse("{[][]}").unwrap()),
+        Err(PolySimError::RepeatUnitCount { got: 0, .. })
+    ));
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use common::homopolymer;
+use polysim_core::properties::rheology::entanglement_molecular_weight;
+
+// ── Masse entre enchevêtrements ──────────────────────────────────────────────
+
+#[test]
+fn polyethylene_entanglement_mass() {
+    // Expérimental ≈ 1250 g/mol ; tolérance d'un ordre de grandeur
+    let me = entanglement_molecular_weight(&homopolymer("{[]CC[]}", 1000));
+    assert!(me > 1250.0 / 3.0 && me < 1250.0 * 3.0, "Me = {me}");
+}
+
+#[test]
+fn polystyrene_entanglement_mass() {
+    // Expérimental ≈ 13 000–18 000 g/mol
+    let me = entanglement_molecular_weight(&homopolymer("{[]CC(c1ccccc1)[]}", 500));
+    assert!(me > 18_000.0 / 3.0 && me < 18_000.0 * 3.0, "Me = {me}");
+}
+
+#[test]
+fn bulky_pendants_raise_entanglement_mass() {
+    let pe = entanglement_molecular_weight(&homopolymer("{[]CC[]}", 500));
+    let pp = entanglement_molecular_weight(&homopolymer("{[]CC(C)[]}", 500));
+    let ps = entanglement_molecular_weight(&homopolymer("{[]CC(c1ccccc1)[]}", 500));
+    assert!(pe < pp && pp < ps, "PE {pe}, PP {pp}, PS {ps}");
+}
+
+#[test]
+fn entanglement_mass_independent_of_chain_length() {
+    let short = entanglement_molecular_weight(&homopolymer("{[]CC(C)[]}", 200));
+    let long = entanglement_molecular_weight(&homopolymer("{[]CC(C)[]}", 2000));
+    assert!((short - long).abs() / long < 0.01, "{short} vs {long}");
+}
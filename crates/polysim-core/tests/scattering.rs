@@ -0,0 +1,33 @@
+mod common;
+
+use common::homopolymer;
+use polysim_core::properties::scattering::neutron_sld;
+
+// ── Densité de longueur de diffusion ─────────────────────────────────────────
+
+#[test]
+fn protonated_polystyrene_sld() {
+    // h-PS : ≈ 1.41 × 10⁻⁶ Å⁻² à 1.05 g/cm³
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 200);
+    let sld = neutron_sld(&ps, 1.05);
+    assert!((sld - 1.41).abs() < 0.03, "SLD = {sld}");
+}
+
+#[test]
+fn deuterated_polyethylene_much_higher_than_protonated() {
+    // d-PE : CD₂, b = 19.99 fm pour 16.04 g/mol → ≈ 7.9 × 10⁻⁶ Å⁻² à
+    // 1.05 g/cm³ ; h-PE ≈ −0.33 × 10⁻⁶ Å⁻²
+    let h_pe = homopolymer("{[]CC[]}", 200);
+    let d_pe = homopolymer("{[]C([2H])([2H])C([2H])([2H])[]}", 200);
+    let h_sld = neutron_sld(&h_pe, 0.92);
+    let d_sld = neutron_sld(&d_pe, 1.05);
+    assert!(h_sld < 0.0, "h-PE SLD = {h_sld}");
+    assert!((d_sld - 7.9).abs() < 0.1, "d-PE SLD = {d_sld}");
+}
+
+#[test]
+fn sld_scales_with_density() {
+    let pmma = homopolymer("{[]CC(C)(C(=O)OC)[]}", 100);
+    let ratio = neutron_sld(&pmma, 2.36) / neutron_sld(&pmma, 1.18);
+    assert!((ratio - 2.0).abs() < 1e-12);
+}
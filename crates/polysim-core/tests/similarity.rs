@@ -0,0 +1,61 @@
+mod common;
+
+use bigsmiles::parse;
+use common::homopolymer;
+use polysim_core::{
+    builder::{linear::LinearBuilder, BuildStrategy},
+    properties::similarity::tanimoto,
+};
+
+// ── Tanimoto ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn identical_repeat_units_score_one() {
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 10);
+    assert_eq!(tanimoto(&ps, &ps), 1.0);
+    // Indépendant de la longueur de chaîne
+    assert_eq!(tanimoto(&ps, &homopolymer("{[]CC(c1ccccc1)[]}", 200)), 1.0);
+}
+
+#[test]
+fn polyethylene_vs_polystyrene_is_low() {
+    let score = tanimoto(
+        &homopolymer("{[]CC[]}", 20),
+        &homopolymer("{[]CC(c1ccccc1)[]}", 20),
+    );
+    assert!(score < 0.2, "PE/PS = {score}");
+}
+
+#[test]
+fn related_structures_score_higher_than_unrelated() {
+    // PP ressemble plus au PE qu'au PS
+    let pe = homopolymer("{[]CC[]}", 20);
+    let pp = homopolymer("{[]CC(C)[]}", 20);
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 20);
+    assert!(tanimoto(&pe, &pp) > tanimoto(&pe, &ps));
+    assert!(tanimoto(&pp, &pe) > tanimoto(&pp, &ps));
+}
+
+#[test]
+fn tanimoto_is_symmetric_and_bounded() {
+    let pmma = homopolymer("{[]CC(C)(C(=O)OC)[]}", 10);
+    let pvc = homopolymer("{[]CC(Cl)[]}", 10);
+    let (ab, ba) = (tanimoto(&pmma, &pvc), tanimoto(&pvc, &pmma));
+    assert_eq!(ab, ba);
+    assert!((0.0..=1.0).contains(&ab));
+}
+
+#[test]
+fn copolymer_composition_is_weighted() {
+    // Alterné PE/PS : entre les deux homopolymères
+    let copolymer = LinearBuilder::new(
+        parse("{[]CC[],[]CC(c1ccccc1)[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(10),
+    )
+    .alternating_copolymer()
+    .unwrap();
+    let pe = homopolymer("{[]CC[]}", 10);
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 10);
+    let to_ps = tanimoto(&copolymer, &ps);
+    assert!(to_ps > tanimoto(&pe, &ps) && to_ps < 1.0, "{to_ps}");
+}
@@ -0,0 +1,105 @@
+use polysim_core::{
+    analyze_bigsmiles,
+    builder::{linear::LinearBuilder, BuildStrategy},
+    parse,
+    properties::solubility::{critical_chi, flory_huggins_chi, solubility_parameter, water_uptake},
+    PolymerChain,
+};
+
+fn build(bigsmiles: &str, n: usize) -> PolymerChain {
+    LinearBuilder::new(parse(bigsmiles).unwrap(), BuildStrategy::ByRepeatCount(n))
+        .homopolymer()
+        .unwrap()
+}
+
+// ── Paramètre de solubilité ──────────────────────────────────────────────────
+
+#[test]
+fn solubility_parameter_pe_matches_literature() {
+    // PE : δ ≈ 16–17.5 MPa^½ (Van Krevelen, Table 7.5)
+    let delta = solubility_parameter(&build("{[]CC[]}", 500), 298.15);
+    assert!((delta - 17.0).abs() < 1.0, "δ = {delta}");
+}
+
+#[test]
+fn solubility_parameter_pvc_above_pe() {
+    let pe = solubility_parameter(&build("{[]CC[]}", 200), 298.15);
+    let pvc = solubility_parameter(&build("{[]CC(Cl)[]}", 200), 298.15);
+    assert!(pvc > pe, "PVC δ = {pvc}, PE δ = {pe}");
+}
+
+// ── Paramètre d'interaction de Flory-Huggins ─────────────────────────────────
+
+#[test]
+fn chi_is_symmetric_and_zero_for_identical_polymers() {
+    let ps = build("{[]CC(c1ccccc1)[]}", 200);
+    let pmma = build("{[]CC(C)(C(=O)OC)[]}", 200);
+    let ab = flory_huggins_chi(&ps, &pmma, 298.15);
+    let ba = flory_huggins_chi(&pmma, &ps, 298.15);
+    assert!((ab - ba).abs() < 1e-12);
+    assert_eq!(flory_huggins_chi(&ps, &ps, 298.15), 0.0);
+}
+
+#[test]
+fn chi_ps_pmma_is_small_but_above_critical() {
+    // PS/PMMA : χ ≈ 0,01–0,05 expérimentalement, non miscible à haute masse
+    let ps = build("{[]CC(c1ccccc1)[]}", 1000);
+    let pmma = build("{[]CC(C)(C(=O)OC)[]}", 1000);
+    let chi = flory_huggins_chi(&ps, &pmma, 298.15);
+    assert!(chi > 0.0 && chi < 0.5, "χ = {chi}");
+    assert!(chi > critical_chi(&ps, &pmma, 298.15));
+}
+
+#[test]
+fn critical_chi_decreases_with_chain_length() {
+    let pe = |n| build("{[]CC[]}", n);
+    let pp = |n| build("{[]CC(C)[]}", n);
+    let short = critical_chi(&pe(20), &pp(20), 298.15);
+    let long = critical_chi(&pe(2000), &pp(2000), 298.15);
+    assert!(long < short / 50.0, "χc(20) = {short}, χc(2000) = {long}");
+}
+
+// ── Reprise en eau ───────────────────────────────────────────────────────────
+
+#[test]
+fn water_uptake_pe_is_zero() {
+    assert_eq!(water_uptake(&build("{[]CC[]}", 100)), 0.0);
+}
+
+#[test]
+fn water_uptake_pva_above_pe() {
+    let pva = water_uptake(&build("{[]CC(O)[]}", 100));
+    assert!(pva > 0.1, "PVA uptake = {pva}");
+}
+
+// ── Groupes terminaux ────────────────────────────────────────────────────────
+
+#[test]
+fn short_hydroxyl_terminated_pe_more_hydrophilic() {
+    let short = build("O{[]CC[]}O", 5);
+    let long = build("O{[]CC[]}O", 500);
+
+    assert_eq!(short.end_group_atoms(), 2);
+    assert_eq!(long.end_group_atoms(), 2);
+    // Les deux OH pèsent beaucoup plus sur une chaîne courte
+    assert!(water_uptake(&short) > 10.0 * water_uptake(&long));
+    assert!(solubility_parameter(&short, 298.15) > solubility_parameter(&long, 298.15));
+}
+
+#[test]
+fn end_group_atoms_zero_without_end_groups() {
+    assert_eq!(build("{[]CC(C)[]}", 20).end_group_atoms(), 0);
+}
+
+#[test]
+fn summary_reports_end_groups() {
+    let summary = analyze_bigsmiles("O{[]CC[]}O", BuildStrategy::ByRepeatCount(5)).unwrap();
+    assert_eq!(summary.end_group_atoms, 2);
+    // 2 × OH = 34.01 g/mol
+    assert!(
+        (summary.end_group_mass - 34.015).abs() < 0.01,
+        "M_end = {}",
+        summary.end_group_mass
+    );
+    assert!(summary.water_uptake > 0.0);
+}
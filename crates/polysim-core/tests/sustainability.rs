@@ -0,0 +1,58 @@
+mod common;
+
+use bigsmiles::parse;
+use common::homopolymer;
+use polysim_core::{
+    builder::{linear::LinearBuilder, BuildStrategy},
+    properties::sustainability::{recyclability, RecyclabilityClass},
+};
+
+// ── Codes de recyclage ───────────────────────────────────────────────────────
+
+#[test]
+fn pet_is_code_one() {
+    let pet = homopolymer("{[]OCCOC(=O)c1ccc(cc1)C(=O)[]}", 10);
+    assert_eq!(recyclability(&pet), RecyclabilityClass::Pet);
+    assert_eq!(recyclability(&pet).code(), Some(1));
+}
+
+#[test]
+fn commodity_polymers_get_their_codes() {
+    for (bigsmiles, expected) in [
+        ("{[]CC[]}", RecyclabilityClass::Hdpe),
+        ("{[]CC(Cl)[]}", RecyclabilityClass::Pvc),
+        ("{[]CC(C)[]}", RecyclabilityClass::Pp),
+        ("{[]CC(c1ccccc1)[]}", RecyclabilityClass::Ps),
+        ("{[]CC(C)(C(=O)OC)[]}", RecyclabilityClass::Other),
+        ("{[]NCCCCCC(=O)[]}", RecyclabilityClass::Other),
+    ] {
+        assert_eq!(
+            recyclability(&homopolymer(bigsmiles, 20)),
+            expected,
+            "{bigsmiles}"
+        );
+    }
+}
+
+#[test]
+fn end_groups_do_not_change_the_code() {
+    // PS amorcé au sec-butyllithium : même flux de recyclage
+    let ps = LinearBuilder::new(
+        parse("{[]CC(c1ccccc1)[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(50),
+    )
+    .with_end_groups("CCC(C)", "")
+    .homopolymer()
+    .unwrap();
+    assert_eq!(recyclability(&ps), RecyclabilityClass::Ps);
+}
+
+#[test]
+fn unlisted_polymer_is_unknown() {
+    // Polyisoprène et poly(oxyde d'éthylène) : absents de la table
+    for bigsmiles in ["{[]CC=C(C)C[]}", "{[]CCO[]}"] {
+        let chain = homopolymer(bigsmiles, 20);
+        assert_eq!(recyclability(&chain), RecyclabilityClass::Unknown);
+        assert_eq!(recyclability(&chain).code(), None);
+    }
+}
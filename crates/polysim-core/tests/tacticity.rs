@@ -0,0 +1,143 @@
+use bigsmiles::parse;
+use polysim_core::{
+    builder::{linear::LinearBuilder, BuildStrategy, DoubleBondGeometry},
+    properties::{
+        formula::{molecular_formula, total_atom_count},
+        molecular_weight::{average_mass, monoisotopic_mass},
+    },
+    PolymerChain,
+};
+
+fn builder(bigsmiles: &str, n: usize) -> LinearBuilder {
+    LinearBuilder::new(parse(bigsmiles).unwrap(), BuildStrategy::ByRepeatCount(n))
+}
+
+// ── Generated modes ──────────────────────────────────────────────────────────
+
+#[test]
+fn isotactic_polypropylene_is_all_meso() {
+    let chain = builder("{[]C[C@@H](C)[]}", 50).homopolymer().unwrap();
+    assert_eq!(chain.tacticity_fraction(), 1.0);
+}
+
+#[test]
+fn syndiotactic_polypropylene_is_all_racemo() {
+    // Alternating configurations: the repeat unit spans one r dyad.
+    let chain = builder("{[]C[C@@H](C)C[C@H](C)[]}", 25)
+        .homopolymer()
+        .unwrap();
+    assert_eq!(chain.tacticity_fraction(), 0.0);
+}
+
+#[test]
+fn atactic_polypropylene_is_about_half_meso() {
+    let chain = builder("{[]C[C@@H](C)[],[]C[C@H](C)[]}", 1000)
+        .seed(594)
+        .random_copolymer(&[0.5, 0.5])
+        .unwrap();
+    let m = chain.tacticity_fraction();
+    assert!((m - 0.5).abs() < 0.05, "m = {m}");
+}
+
+#[test]
+fn isotactic_polystyrene_is_all_meso() {
+    let chain = builder("{[]C[C@H](c1ccccc1)[]}", 30).homopolymer().unwrap();
+    assert_eq!(chain.tacticity_fraction(), 1.0);
+}
+
+// ── Unspecified stereochemistry ──────────────────────────────────────────────
+
+#[test]
+fn chain_without_stereocentres_is_unspecified() {
+    let chain = builder("{[]CC(C)[]}", 20).homopolymer().unwrap();
+    assert_eq!(chain.tacticity_fraction(), 0.5);
+}
+
+#[test]
+fn single_stereocentre_is_unspecified() {
+    let chain = PolymerChain::new("CC[C@H](C)CC".to_string(), 1, 0.0);
+    assert_eq!(chain.tacticity_fraction(), 0.5);
+}
+
+// ── Stereo descriptors are mass-neutral ──────────────────────────────────────
+
+/// Asserts that two chains have the same masses, formula and atom count.
+fn assert_same_composition(a: &PolymerChain, b: &PolymerChain) {
+    let label = format!("{} vs {}", a.smiles, b.smiles);
+    assert!((average_mass(a) - average_mass(b)).abs() < 1e-9, "{label}");
+    assert!(
+        (monoisotopic_mass(a) - monoisotopic_mass(b)).abs() < 1e-9,
+        "{label}"
+    );
+    assert_eq!(molecular_formula(a), molecular_formula(b), "{label}");
+    assert_eq!(total_atom_count(a), total_atom_count(b), "{label}");
+}
+
+#[test]
+fn isotactic_and_atactic_polypropylene_have_same_mass_and_formula() {
+    // [C@@H] porte son hydrogène explicitement : en bout de chaîne il doit
+    // recevoir celui qui remplace la liaison vers le motif suivant
+    let plain = builder("{[]CC(C)[]}", 50).homopolymer().unwrap();
+    let isotactic = builder("{[]C[C@@H](C)[]}", 50).homopolymer().unwrap();
+    let atactic = builder("{[]C[C@@H](C)[],[]C[C@H](C)[]}", 50)
+        .seed(678)
+        .random_copolymer(&[0.5, 0.5])
+        .unwrap();
+    assert_eq!(molecular_formula(&plain), "C150H302");
+    assert_same_composition(&isotactic, &plain);
+    assert_same_composition(&atactic, &plain);
+}
+
+#[test]
+fn stereocentre_at_a_free_chain_end_is_saturated() {
+    let tail = builder("{[]C[C@@H](C)[]}", 3).homopolymer().unwrap();
+    assert_eq!(tail.smiles, "C[C@@H](C)C[C@@H](C)C[CH2](C)");
+
+    let head = builder("{[][C@@H](C)C[]}", 3).homopolymer().unwrap();
+    assert_eq!(head.smiles, "[CH2](C)C[C@@H](C)C[C@@H](C)C");
+    assert_same_composition(&head, &tail);
+}
+
+#[test]
+fn stereocentre_next_to_an_end_group_keeps_its_chirality() {
+    let chain = builder("{[]C[C@@H](C)[]}", 2)
+        .with_end_groups("C", "O")
+        .homopolymer()
+        .unwrap();
+    assert_eq!(chain.smiles, "CC[C@@H](C)C[C@@H](C)O");
+}
+
+#[test]
+fn streamed_isotactic_chain_matches_built_chain() {
+    let builder = builder("{[]C[C@@H](C)[]}", 4);
+    let mut out = Vec::new();
+    builder.write_smiles(&mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        builder.homopolymer().unwrap().smiles
+    );
+}
+
+#[test]
+fn syndiotactic_polystyrene_has_same_mass_and_formula() {
+    let plain = builder("{[]CC(c1ccccc1)[]}", 20).homopolymer().unwrap();
+    let syndiotactic = builder("{[]C[C@@H](c1ccccc1)C[C@H](c1ccccc1)[]}", 10)
+        .homopolymer()
+        .unwrap();
+    assert_same_composition(&syndiotactic, &plain);
+}
+
+#[test]
+fn directional_bonds_are_mass_neutral() {
+    let plain = builder("{[]CC=CC[]}", 30).homopolymer().unwrap();
+    for geometry in [
+        DoubleBondGeometry::Cis,
+        DoubleBondGeometry::Trans,
+        DoubleBondGeometry::Random,
+    ] {
+        let configured = builder("{[]CC=CC[]}", 30)
+            .homopolymer_with_geometry(geometry, 678)
+            .unwrap();
+        assert_same_composition(&configured, &plain);
+    }
+}
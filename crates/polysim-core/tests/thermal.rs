@@ -0,0 +1,278 @@
+mod common;
+
+use bigsmiles::parse;
+use common::homopolymer;
+use polysim_core::{
+    builder::identify::closest_known,
+    builder::{linear::LinearBuilder, BuildStrategy},
+    properties::thermal::{
+        homopolymer_tg, lamellar_thickness, stiffness_correction, tg_copolymer, tg_fox,
+        tg_fox_flory, tg_infinite_chain, tg_van_krevelen, tg_van_krevelen_with,
+        DEFAULT_FOX_FLORY_K,
+    },
+    PolySimError, PolymerChain,
+};
+
+// ── Fox ──────────────────────────────────────────────────────────────────────
+
+#[test]
+fn tg_fox_single_component_is_identity() {
+    assert!((tg_fox(&[(1.0, 373.0)]) - 373.0).abs() < 1e-9);
+}
+
+// ── Fox sur la composition réalisée ──────────────────────────────────────────
+
+fn styrene_mma_tgs() -> Vec<(String, f64)> {
+    vec![
+        ("CC(c1ccccc1)".to_string(), 373.0),
+        ("CC(C)(C(=O)OC)".to_string(), 378.0),
+    ]
+}
+
+#[test]
+fn tg_copolymer_random_styrene_mma_follows_realized_composition() {
+    let bs = parse("{[$]CC(c1ccccc1)[$],[$]CC(C)(C(=O)OC)[$]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(200))
+        .seed(676)
+        .random_copolymer(&[0.5, 0.5])
+        .unwrap();
+    let tg = tg_copolymer(&chain, &styrene_mma_tgs()).unwrap();
+
+    // Fractions molaires réalisées → fractions massiques (104.15 / 100.12 g/mol)
+    let x_s = chain.composition[0].fraction;
+    let (m_s, m_mma) = (x_s * 104.152, (1.0 - x_s) * 100.117);
+    let expected = tg_fox(&[(m_s / (m_s + m_mma), 373.0), (m_mma / (m_s + m_mma), 378.0)]);
+    assert!(
+        (tg - expected).abs() < 0.01,
+        "Tg = {tg}, attendu {expected}"
+    );
+    assert!(tg > 373.0 && tg < 378.0);
+}
+
+#[test]
+fn tg_copolymer_matches_units_whatever_their_spelling() {
+    let bs = parse("{[$]CC(c1ccccc1)[$],[$]CC(C)(C(=O)OC)[$]}").unwrap();
+    let chain = LinearBuilder::new(bs, BuildStrategy::ByRepeatCount(10))
+        .alternating_copolymer()
+        .unwrap();
+    let respelled = vec![
+        ("[$]C(c1ccccc1)C[$]".to_string(), 373.0),
+        ("COC(=O)C(C)C".to_string(), 378.0),
+    ];
+    let a = tg_copolymer(&chain, &styrene_mma_tgs()).unwrap();
+    let b = tg_copolymer(&chain, &respelled).unwrap();
+    assert!((a - b).abs() < 1e-9);
+}
+
+#[test]
+fn tg_copolymer_homopolymer_returns_its_tg() {
+    let tg = tg_copolymer(&homopolymer("{[]CC(c1ccccc1)[]}", 50), &styrene_mma_tgs()).unwrap();
+    assert!((tg - 373.0).abs() < 1e-9);
+}
+
+#[test]
+fn tg_copolymer_missing_unit_is_error() {
+    let result = tg_copolymer(&homopolymer("{[]CC(C)[]}", 10), &styrene_mma_tgs());
+    assert!(
+        matches!(
+            result,
+            Err(PolySimError::MissingUnitProperty { property: "Tg", .. })
+        ),
+        "got: {result:?}"
+    );
+}
+
+#[test]
+fn tg_copolymer_without_composition_is_error() {
+    let chain = PolymerChain::new("CCCC".to_string(), 2, 58.1);
+    assert!(matches!(
+        tg_copolymer(&chain, &styrene_mma_tgs()),
+        Err(PolySimError::RepeatUnitCount { got: 0, .. })
+    ));
+}
+
+// ── Tg tabulées ──────────────────────────────────────────────────────────────
+
+#[test]
+fn homopolymer_tg_polystyrene() {
+    let tg = homopolymer_tg("Polystyrene").unwrap();
+    assert!((tg - 373.0).abs() < 1.0, "Tg(PS) = {tg}");
+    assert_eq!(homopolymer_tg("PS"), Some(tg));
+    assert_eq!(homopolymer_tg("polystyrene"), Some(tg));
+}
+
+#[test]
+fn homopolymer_tg_unknown_polymer_is_none() {
+    assert_eq!(homopolymer_tg("Polyunobtainium"), None);
+    assert_eq!(homopolymer_tg(""), None);
+}
+
+#[test]
+fn homopolymer_tg_resolves_identified_polymer() {
+    // Le nom renvoyé par closest_known sert directement de clé
+    let (name, _) = closest_known(&homopolymer("{[]CC(C)(C(=O)OC)[]}", 20)).unwrap();
+    let tg = homopolymer_tg(name).unwrap();
+    assert!((tg - 378.0).abs() < 1.0, "Tg({name}) = {tg}");
+}
+
+// ── Fox-Flory ────────────────────────────────────────────────────────────────
+
+#[test]
+fn tg_fox_flory_rises_toward_tg_infinity() {
+    let tg_inf = 373.0;
+    let tgs: Vec<f64> = [2_000.0, 10_000.0, 100_000.0, 1_000_000.0]
+        .iter()
+        .map(|&mn| tg_fox_flory(tg_inf, mn, DEFAULT_FOX_FLORY_K))
+        .collect();
+    assert!(
+        tgs.windows(2).all(|w| w[0] < w[1]),
+        "Tg must rise with Mn: {tgs:?}"
+    );
+    assert!(tgs.iter().all(|&tg| tg < tg_inf));
+    assert!(tg_inf - tgs[3] < 0.2);
+}
+
+#[test]
+fn tg_fox_flory_known_value() {
+    // PS, Mn = 5000 : 373 − 1e5 / 5000 = 353 K
+    let tg = tg_fox_flory(373.0, 5_000.0, 1.0e5);
+    assert!((tg - 353.0).abs() < 1e-9);
+}
+
+#[test]
+fn tg_fox_flory_clamped_at_zero_for_oligomers() {
+    assert_eq!(tg_fox_flory(200.0, 100.0, 1.0e5), 0.0);
+}
+
+#[test]
+fn tg_infinite_chain_ignores_end_groups() {
+    // Tg∞ ne dépend que du motif : ni la longueur ni les groupes terminaux
+    let long = tg_infinite_chain(&homopolymer("{[]CC(c1ccccc1)[]}", 300));
+    for chain in [
+        homopolymer("{[]CC(c1ccccc1)[]}", 3),
+        homopolymer("CCCC{[]CC(c1ccccc1)[]}O", 5),
+    ] {
+        let tg = tg_infinite_chain(&chain);
+        assert!((tg - long).abs() < 1e-9, "{}: {tg} vs {long}", chain.smiles);
+    }
+    // … et c'est la limite de Van Krevelen sur la chaîne entière
+    let vk = tg_van_krevelen(&homopolymer("{[]CC(c1ccccc1)[]}", 2000));
+    assert!((vk - long).abs() < 0.5, "{vk} vs {long}");
+}
+
+#[test]
+fn tg_infinite_chain_of_copolymer_lies_between_homopolymers() {
+    let s = tg_infinite_chain(&homopolymer("{[]CC(c1ccccc1)[]}", 10));
+    let m = tg_infinite_chain(&homopolymer("{[]CC(C)(C(=O)OC)[]}", 10));
+    let chain = LinearBuilder::new(
+        parse("{[$]CC(c1ccccc1)[$],[$]CC(C)(C(=O)OC)[$]}").unwrap(),
+        BuildStrategy::ByRepeatCount(10),
+    )
+    .alternating_copolymer()
+    .unwrap();
+    let tg = tg_infinite_chain(&chain);
+    assert!(tg > s.min(m) && tg < s.max(m), "{tg} not in [{s}, {m}]");
+}
+
+#[test]
+fn tg_infinite_chain_without_composition_is_van_krevelen() {
+    let chain = PolymerChain::new("CCCC(c1ccccc1)".to_string(), 1, 0.0);
+    assert_eq!(tg_infinite_chain(&chain), tg_van_krevelen(&chain));
+}
+
+// ── Van Krevelen ─────────────────────────────────────────────────────────────
+
+#[test]
+fn tg_van_krevelen_polyethylene() {
+    // Van Krevelen: Yg(CH2) / M(CH2) = 2700 / 14.03 ≈ 192 K
+    let tg = tg_van_krevelen(&homopolymer("{[]CC[]}", 200));
+    assert!((tg - 192.0).abs() < 5.0, "PE Tg = {tg}");
+}
+
+#[test]
+fn tg_van_krevelen_polystyrene() {
+    // Experimental ≈ 373 K
+    let tg = tg_van_krevelen(&homopolymer("{[]CC(c1ccccc1)[]}", 100));
+    assert!((tg - 373.0).abs() < 15.0, "PS Tg = {tg}");
+}
+
+#[test]
+fn tg_van_krevelen_pvc() {
+    // Experimental ≈ 354 K
+    let tg = tg_van_krevelen(&homopolymer("{[]CC(Cl)[]}", 100));
+    assert!((tg - 354.0).abs() < 15.0, "PVC Tg = {tg}");
+}
+
+// ── Correction de rigidité ───────────────────────────────────────────────────
+
+#[test]
+fn stiffness_correction_raises_bulky_pendant_tg() {
+    // Poly(4-tert-butylstyrène) : Tg mesurée ≈ 400 K, l'additivité seule la
+    // sous-estime nettement
+    let ptbs = homopolymer("{[]CC(c1ccc(cc1)C(C)(C)C)[]}", 100);
+    let plain = tg_van_krevelen_with(&ptbs, false);
+    let corrected = tg_van_krevelen_with(&ptbs, true);
+    assert!(corrected > plain + 30.0, "{plain} → {corrected}");
+    assert!((corrected - 400.0).abs() < (plain - 400.0).abs());
+}
+
+#[test]
+fn stiffness_correction_backbone_rings() {
+    // Polycarbonate BPA (Tg ≈ 423 K) et polysulfone (Tg ≈ 458 K)
+    for (bigsmiles, tg_exp) in [
+        ("{[]Oc1ccc(cc1)C(C)(C)c1ccc(cc1)OC(=O)[]}", 423.0),
+        (
+            "{[]Oc1ccc(cc1)C(C)(C)c1ccc(cc1)Oc1ccc(cc1)S(=O)(=O)c1ccc(cc1)[]}",
+            458.0,
+        ),
+    ] {
+        let chain = homopolymer(bigsmiles, 50);
+        let corrected = tg_van_krevelen_with(&chain, true);
+        assert!(
+            (corrected - tg_exp).abs() < 40.0,
+            "{bigsmiles}: Tg = {corrected} K"
+        );
+        assert!(corrected > tg_van_krevelen(&chain));
+    }
+}
+
+#[test]
+fn stiffness_correction_zero_for_flexible_chains() {
+    // Ni cycle dans le squelette ni pendant volumineux : PE, PVC, PS (phényle seul)
+    for bigsmiles in ["{[]CC[]}", "{[]CC(Cl)[]}", "{[]CC(c1ccccc1)[]}"] {
+        let chain = homopolymer(bigsmiles, 50);
+        assert_eq!(stiffness_correction(&chain), 0.0, "{bigsmiles}");
+        assert_eq!(tg_van_krevelen_with(&chain, true), tg_van_krevelen(&chain));
+    }
+}
+
+// ── Épaisseur lamellaire (Gibbs-Thomson) ─────────────────────────────────────
+
+#[test]
+fn lamellar_thickness_polyethylene_is_plausible() {
+    // PE cristallisé vers 380 K : lamelles de 10–20 nm
+    let pe = homopolymer("{[]CC[]}", 1000);
+    let l = lamellar_thickness(&pe, 380.0).unwrap();
+    assert!(l > 5.0 && l < 30.0, "l = {l} nm");
+}
+
+#[test]
+fn lamellar_thickness_grows_as_supercooling_shrinks() {
+    let pe = homopolymer("{[]CC[]}", 1000);
+    let deep = lamellar_thickness(&pe, 340.0).unwrap();
+    let shallow = lamellar_thickness(&pe, 395.0).unwrap();
+    assert!(shallow > 2.0 * deep, "{shallow} vs {deep}");
+}
+
+#[test]
+fn lamellar_thickness_none_above_melting() {
+    let pe = homopolymer("{[]CC[]}", 1000);
+    assert_eq!(lamellar_thickness(&pe, 450.0), None);
+}
+
+#[test]
+fn lamellar_thickness_none_for_amorphous_chain() {
+    // PS atactique : ne cristallise pas
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 100);
+    assert_eq!(lamellar_thickness(&ps, 400.0), None);
+}
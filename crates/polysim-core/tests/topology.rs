@@ -0,0 +1,133 @@
+mod common;
+
+use common::homopolymer;
+use polysim_core::properties::topology::{
+    aromatic_ring_count, backbone_atom_count, carbon_classification, rotatable_bonds, CarbonCounts,
+};
+
+fn counts(backbone: usize, side_chain: usize, aromatic: usize) -> CarbonCounts {
+    CarbonCounts {
+        backbone,
+        side_chain,
+        aromatic,
+    }
+}
+
+#[test]
+fn polyethylene_is_all_backbone() {
+    let pe = homopolymer("{[]CC[]}", 50);
+    assert_eq!(carbon_classification(&pe), counts(100, 0, 0));
+}
+
+#[test]
+fn polystyrene_phenyl_rings_are_aromatic() {
+    // CH₂–CH backbone, pendant phenyl: 6 aromatic carbons per unit
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 20);
+    assert_eq!(carbon_classification(&ps), counts(40, 0, 120));
+}
+
+#[test]
+fn pmma_has_side_chain_carbons() {
+    // α-methyl, ester carbonyl and methoxy carbons hang off the backbone
+    let pmma = homopolymer("{[]CC(C)(C(=O)OC)[]}", 10);
+    assert_eq!(carbon_classification(&pmma), counts(20, 30, 0));
+}
+
+#[test]
+fn pet_backbone_runs_through_the_ring() {
+    // Backbone: 2 × CH₂ + 2 × C=O; the para-phenylene ring is aromatic
+    let pet = homopolymer("{[]OCCOC(=O)c1ccc(cc1)C(=O)[]}", 5);
+    assert_eq!(carbon_classification(&pet), counts(20, 0, 30));
+}
+
+#[test]
+fn classification_covers_every_carbon() {
+    let pp = homopolymer("{[]CC(C)[]}", 7);
+    let c = carbon_classification(&pp);
+    assert_eq!(c.backbone + c.side_chain + c.aromatic, 21);
+}
+
+// ── Liaisons rotatives ───────────────────────────────────────────────────────
+
+#[test]
+fn polyethylene_rotatable_bonds_scale_with_n() {
+    // 2n carbones → 2n − 1 liaisons C–C, dont 2 terminales
+    for n in [5, 10, 50] {
+        let pe = homopolymer("{[]CC[]}", n);
+        assert_eq!(rotatable_bonds(&pe), 2 * n - 3, "n = {n}");
+    }
+}
+
+#[test]
+fn aromatic_pendant_adds_no_ring_bonds() {
+    // PS n = 10 : 19 liaisons squelette dont 1 terminale (le dernier CH porte
+    // le phényle), plus 10 liaisons squelette–phényle ; les 60 liaisons
+    // aromatiques du cycle ne comptent pas
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 10);
+    assert_eq!(rotatable_bonds(&ps), 18 + 10);
+}
+
+#[test]
+fn methyl_pendant_is_not_rotatable() {
+    // PP n = 10 : 19 liaisons squelette dont 1 terminale ; les liaisons vers
+    // les méthyles terminaux ne comptent pas
+    let pp = homopolymer("{[]CC(C)[]}", 10);
+    assert_eq!(rotatable_bonds(&pp), 18);
+}
+
+#[test]
+fn double_bonds_are_not_rotatable() {
+    // Polybutadiène 1,4 : C–C=C–C, la double liaison est exclue
+    let pb = homopolymer("{[]CC=CC[]}", 5);
+    assert_eq!(rotatable_bonds(&pb), 19 - 5 - 2);
+}
+
+// ── Cycles aromatiques ───────────────────────────────────────────────────────
+
+#[test]
+fn polystyrene_has_one_ring_per_unit() {
+    assert_eq!(
+        aromatic_ring_count(&homopolymer("{[]CC(c1ccccc1)[]}", 3)),
+        3
+    );
+}
+
+#[test]
+fn polyethylene_has_no_aromatic_ring() {
+    assert_eq!(aromatic_ring_count(&homopolymer("{[]CC[]}", 20)), 0);
+}
+
+#[test]
+fn aliphatic_ring_is_not_aromatic() {
+    // Poly(vinylcyclohexane) : cycle saturé
+    assert_eq!(
+        aromatic_ring_count(&homopolymer("{[]CC(C1CCCCC1)[]}", 4)),
+        0
+    );
+}
+
+#[test]
+fn fused_and_linked_rings_count_each_cycle() {
+    // Naphtyle : 2 cycles fusionnés ; biphényle : 2 cycles liés par une
+    // liaison simple hors cycle
+    assert_eq!(
+        aromatic_ring_count(&homopolymer("{[]CC(c1ccc2ccccc2c1)[]}", 2)),
+        4
+    );
+    assert_eq!(
+        aromatic_ring_count(&homopolymer("{[]CC(c1ccc(cc1)c1ccccc1)[]}", 2)),
+        4
+    );
+}
+
+// ── Atomes du squelette ──────────────────────────────────────────────────────
+
+#[test]
+fn backbone_atoms_include_heteroatoms_and_skip_pendants() {
+    // PEO : C–C–O par unité ; PS : le phényle est pendant
+    assert_eq!(backbone_atom_count(&homopolymer("{[]CCO[]}", 4)), 12);
+    assert_eq!(
+        backbone_atom_count(&homopolymer("{[]CC(c1ccccc1)[]}", 4)),
+        8
+    );
+}
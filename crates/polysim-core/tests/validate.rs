@@ -0,0 +1,151 @@
+mod common;
+
+use common::homopolymer;
+use polysim_core::{
+    builder::{linear::LinearBuilder, BuildStrategy},
+    parse,
+    properties::{
+        molecular_weight::average_mass,
+        validate::{formula_mass, mass_matches_formula},
+    },
+    PolySimError, PolymerChain,
+};
+
+// ── Cohérence formule ↔ masse moyenne ────────────────────────────────────────
+
+#[test]
+fn mass_matches_formula_for_common_polymers() {
+    for bigsmiles in ["{[]CC[]}", "{[]CC(C)[]}", "{[]CC(c1ccccc1)[]}"] {
+        for n in [1, 10, 200] {
+            let chain = homopolymer(bigsmiles, n);
+            assert!(mass_matches_formula(&chain), "{bigsmiles} n = {n}");
+        }
+    }
+}
+
+#[test]
+fn mass_matches_formula_with_heteroatoms() {
+    for bigsmiles in ["{[]CC(Cl)[]}", "{[]CC(C)(C(=O)OC)[]}", "{[]CC(C#N)[]}"] {
+        assert!(
+            mass_matches_formula(&homopolymer(bigsmiles, 20)),
+            "{bigsmiles}"
+        );
+    }
+}
+
+#[test]
+fn mass_matches_formula_with_sodium_counterions() {
+    let pss = LinearBuilder::new(
+        parse("{[]CC(c1ccc(cc1)S(=O)(=O)[O-])[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(10),
+    )
+    .counterions(true)
+    .homopolymer()
+    .unwrap();
+    assert!(mass_matches_formula(&pss), "{}", pss.smiles);
+}
+
+#[test]
+fn mismatched_manual_formula_is_detected() {
+    // PE n=10 : C₂₀H₄₂ ; un hydrogène manquant doit être repéré
+    let pe = homopolymer("{[]CC[]}", 10);
+    let mass = average_mass(&pe);
+    assert!((formula_mass("C20H42").unwrap() - mass).abs() < 1e-6);
+    assert!((formula_mass("C20H41").unwrap() - mass).abs() > 1.0);
+}
+
+#[test]
+fn explicit_isotope_does_not_match() {
+    // [13C] : la formule ne porte pas l'isotope
+    let chain = homopolymer("{[][13CH2]C[]}", 5);
+    assert!(!mass_matches_formula(&chain));
+}
+
+#[test]
+fn formula_mass_rejects_malformed_formula() {
+    assert_eq!(formula_mass("c2H6"), None);
+    assert_eq!(formula_mass("C2Xx6"), None);
+    assert_eq!(formula_mass(""), Some(0.0));
+}
+
+// ── Valences ─────────────────────────────────────────────────────────────────
+
+fn chain(smiles: &str) -> PolymerChain {
+    PolymerChain::new(smiles.to_string(), 1, 0.0)
+}
+
+#[test]
+fn built_chains_have_valid_valences() {
+    for bigsmiles in [
+        "{[]CC[]}",
+        "{[]CC(c1ccccc1)[]}",
+        "{[]CC(C)(C(=O)OC)[]}",
+        "{[]CC(C#N)[]}",
+        "{[]NCCCCCC(=O)[]}",
+        "{[]OCCOC(=O)c1ccc(cc1)C(=O)[]}",
+        "{[]C(F)(F)C(F)(F)[]}",
+        "{[]c1ccc(s1)[]}",
+    ] {
+        let chain = homopolymer(bigsmiles, 10);
+        assert!(chain.validate().is_ok(), "{bigsmiles}: {}", chain.smiles);
+    }
+}
+
+#[test]
+fn charged_chain_with_counterions_is_valid() {
+    let pss = LinearBuilder::new(
+        parse("{[]CC(c1ccc(cc1)S(=O)(=O)[O-])[]}").unwrap(),
+        BuildStrategy::ByRepeatCount(10),
+    )
+    .counterions(true)
+    .homopolymer()
+    .unwrap();
+    assert!(pss.validate().is_ok(), "{}", pss.smiles);
+}
+
+#[test]
+fn charged_atoms_shift_the_valence() {
+    // Ammonium et oxonium portent une liaison de plus, un carbanion une de moins
+    assert!(chain("[NH4+]").validate().is_ok());
+    assert!(chain("C[O+](C)C").validate().is_ok());
+    assert!(chain("C[O-]").validate().is_ok());
+    assert!(chain("C[CH2-]").validate().is_ok());
+    assert!(chain("C[CH3-]").validate().is_err());
+}
+
+#[test]
+fn five_bonded_carbon_is_invalid() {
+    match chain("CC(C)(C)(C)C").validate() {
+        Err(PolySimError::InvalidValence {
+            atom_index,
+            element,
+        }) => {
+            assert_eq!(atom_index, 1);
+            assert_eq!(element, "C");
+        }
+        other => panic!("InvalidValence attendu, obtenu : {other:?}"),
+    }
+}
+
+#[test]
+fn over_bonded_atoms_are_invalid() {
+    // Halogène divalent, carbone à deux doubles liaisons + deux simples,
+    // hydrogènes explicites en trop
+    for (smiles, index) in [("CFC", 1), ("CC(=O)(=O)C", 1), ("C[CH5]", 1), ("O=O=O", 1)] {
+        assert!(
+            matches!(
+                chain(smiles).validate(),
+                Err(PolySimError::InvalidValence { atom_index, .. }) if atom_index == index
+            ),
+            "{smiles}"
+        );
+    }
+}
+
+#[test]
+fn validate_rejects_unparsable_smiles() {
+    assert!(matches!(
+        chain("CC(C").validate(),
+        Err(PolySimError::InvalidRepeatUnit(_))
+    ));
+}
@@ -0,0 +1,46 @@
+use polysim_core::{parse_strict, PolySimError};
+
+fn strict_error(s: &str) -> String {
+    match parse_strict(s) {
+        Err(PolySimError::StrictValidation(msg)) => msg,
+        other => panic!("expected a strict validation error for {s:?}, got {other:?}"),
+    }
+}
+
+#[test]
+fn valid_polyethylene_passes() {
+    let bs = parse_strict("{[]CC[]}").unwrap();
+    let units = &bs.first_stochastic().unwrap().repeat_units;
+    assert_eq!(units.len(), 1);
+}
+
+#[test]
+fn valid_copolymer_with_end_groups_passes() {
+    assert!(parse_strict("CC{[$]CC[$],[$]CC(C)[$]}CC").is_ok());
+}
+
+#[test]
+fn empty_stochastic_object_is_rejected() {
+    assert!(strict_error("{}").contains("empty stochastic object"));
+    assert!(strict_error("CC{ }CC").contains("position 2"));
+}
+
+#[test]
+fn stochastic_object_without_atoms_is_rejected() {
+    let msg = match parse_strict("{[][]}") {
+        Err(PolySimError::StrictValidation(msg)) => msg,
+        // The lenient parser may already refuse it; either way it is an error.
+        Err(PolySimError::Parse(_)) => return,
+        other => panic!("unexpected result {other:?}"),
+    };
+    assert!(
+        msg.contains("no repeat units") || msg.contains("no atoms"),
+        "{msg}"
+    );
+}
+
+#[test]
+fn unbalanced_braces_are_rejected() {
+    assert!(strict_error("{[]CC[]").contains("unclosed '{'"));
+    assert!(strict_error("[]CC[]}").contains("unmatched '}'"));
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use common::homopolymer;
+use polysim_core::properties::{
+    molecular_weight::average_mass,
+    thermal::tg_fox,
+    volume::{
+        density, fractional_free_volume, molar_volume, specific_volume, specific_volume_with_tg,
+        van_der_waals_volume,
+    },
+};
+
+// ── molar_volume ─────────────────────────────────────────────────────────────
+
+#[test]
+fn molar_volume_polyethylene() {
+    // PE amorphe à 298 K : V ≈ 33 cm³/mol (Van Krevelen)
+    let v = molar_volume(&homopolymer("{[]CC[]}", 500), 298.15);
+    assert!((v - 33.0).abs() < 1.5, "V(PE) = {v}");
+}
+
+#[test]
+fn molar_volume_polystyrene() {
+    // PS atactique à 298 K : V ≈ 99 cm³/mol (ρ ≈ 1.05 g/cm³)
+    let v = molar_volume(&homopolymer("{[]CC(c1ccccc1)[]}", 100), 298.15);
+    assert!((v - 99.0).abs() < 5.0, "V(PS) = {v}");
+}
+
+#[test]
+fn molar_volume_increases_with_temperature() {
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 100);
+    let volumes: Vec<f64> = [200.0, 298.15, 373.0, 450.0]
+        .iter()
+        .map(|&t| molar_volume(&ps, t))
+        .collect();
+    assert!(volumes.windows(2).all(|w| w[0] < w[1]), "{volumes:?}");
+}
+
+// ── density ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn density_times_molar_volume_is_repeat_mass() {
+    for (bigsmiles, n) in [("{[]CC[]}", 200), ("{[]CC(c1ccccc1)[]}", 50)] {
+        let chain = homopolymer(bigsmiles, n);
+        let repeat_mass = average_mass(&chain) / n as f64;
+        let product = density(&chain, 298.15) * molar_volume(&chain, 298.15);
+        assert!((product - repeat_mass).abs() < 1e-9, "{bigsmiles}");
+    }
+}
+
+#[test]
+fn density_polyethylene_amorphous() {
+    // PE amorphe : ρ ≈ 0.855 g/cm³
+    let rho = density(&homopolymer("{[]CC[]}", 500), 298.15);
+    assert!((rho - 0.855).abs() < 0.04, "ρ(PE) = {rho}");
+}
+
+// ── specific_volume ──────────────────────────────────────────────────────────
+
+#[test]
+fn specific_volume_is_inverse_density() {
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 100);
+    for t in [250.0, 298.15, 450.0] {
+        assert!((specific_volume(&ps, t) * density(&ps, t) - 1.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn specific_volume_expands_faster_above_tg() {
+    // PS : Tg ≈ 373 K ; pente dv/dT vitreuse < pente caoutchoutique
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 100);
+    let glassy = specific_volume(&ps, 310.0) - specific_volume(&ps, 300.0);
+    let rubbery = specific_volume(&ps, 460.0) - specific_volume(&ps, 450.0);
+    assert!(glassy > 0.0, "glassy slope {glassy}");
+    assert!(
+        rubbery > 2.0 * glassy,
+        "rubbery {rubbery} vs glassy {glassy}"
+    );
+}
+
+#[test]
+fn specific_volume_with_fox_tg_moves_the_kink() {
+    // Tg de Fox d'un mélange 50/50 PS (373 K) / PMMA (378 K) vs Tg abaissée à 300 K
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 100);
+    let tg = tg_fox(&[(0.5, 373.0), (0.5, 378.0)]);
+    let slope = |tg: f64, t: f64| {
+        specific_volume_with_tg(&ps, t + 5.0, tg) - specific_volume_with_tg(&ps, t, tg)
+    };
+    // À 330 K : vitreux avec la Tg de Fox, caoutchoutique avec Tg = 300 K
+    assert!(slope(300.0, 330.0) > 2.0 * slope(tg, 330.0));
+}
+
+// ── van_der_waals_volume ─────────────────────────────────────────────────────
+
+#[test]
+fn van_der_waals_volume_polyethylene() {
+    // Bondi : CH₂ = 10.23 cm³/mol → 20.46 par unité C₂H₄
+    let vw = van_der_waals_volume(&homopolymer("{[]CC[]}", 500));
+    assert!((vw - 20.46).abs() < 0.2, "Vw(PE) = {vw}");
+}
+
+#[test]
+fn van_der_waals_volume_polystyrene() {
+    // Van Krevelen, table 4.6 : Vw(PS) ≈ 62.8 cm³/mol
+    let vw = van_der_waals_volume(&homopolymer("{[]CC(c1ccccc1)[]}", 100));
+    assert!((vw - 62.8).abs() < 2.0, "Vw(PS) = {vw}");
+}
+
+// ── fractional_free_volume ───────────────────────────────────────────────────
+
+#[test]
+fn fractional_free_volume_polyethylene() {
+    // PE amorphe à 298 K : FFV ≈ 0.18 (Park & Paul, 1997)
+    let ffv = fractional_free_volume(&homopolymer("{[]CC[]}", 500), 298.15);
+    assert!((ffv - 0.18).abs() < 0.03, "FFV(PE) = {ffv}");
+}
+
+#[test]
+fn fractional_free_volume_polystyrene() {
+    // PS à 298 K : FFV ≈ 0.18 (méthode de Bondi, ρ = 1.05 g/cm³, Vw = 62.8 cm³/mol).
+    // Le volume vitreux estimé étant un peu surévalué, la tolérance est plus large.
+    let ffv = fractional_free_volume(&homopolymer("{[]CC(c1ccccc1)[]}", 100), 298.15);
+    assert!((ffv - 0.18).abs() < 0.04, "FFV(PS) = {ffv}");
+}
+
+#[test]
+fn fractional_free_volume_increases_with_temperature() {
+    let ps = homopolymer("{[]CC(c1ccccc1)[]}", 100);
+    assert!(fractional_free_volume(&ps, 450.0) > fractional_free_volume(&ps, 298.15));
+}